@@ -5,6 +5,19 @@ use std::path::Path;
 use anyhow::Result;
 
 use super::*;
+use crate::claude::archive_rules;
+use crate::claude::branches::parse_branches;
+use crate::claude::details::ConversationDetails;
+use crate::claude::export::{self, ExportFormat, ExportOptions};
+use crate::claude::git_log;
+use crate::claude::metadata::ConversationMeta;
+use crate::claude::tool_stats::{self, ToolProjectRow, ToolStats};
+use crate::claude::transcript::parse_transcript;
+use crate::claude::usage::{UsageDimension, UsageReport};
+use crate::ui::modal::{ExportDestination, FileTouchEntry, TouchedFile};
+
+/// How long the toast offering to undo a delete stays up.
+const UNDO_TOAST_DURATION: std::time::Duration = std::time::Duration::from_secs(8);
 
 impl App {
     /// Archive the currently selected conversation
@@ -69,6 +82,10 @@ impl App {
             Some(SidebarItem::EphemeralSession { .. }) => {
                 self.toast_warning("Cannot archive unsaved conversation");
             }
+            // Archiving a whole group or project is previewed first
+            Some(SidebarItem::GroupHeader { .. } | SidebarItem::ProjectHeader { .. }) => {
+                self.open_archive_group_modal();
+            }
             _ => {
                 self.toast_warning("Select a conversation to archive");
             }
@@ -148,44 +165,281 @@ impl App {
         self.toast_info(format!("Showing {} conversations", mode));
     }
 
-    /// Check for conversations that should be auto-archived
-    /// Archives Idle conversations older than the configured threshold
-    pub fn check_auto_archive(&mut self) {
-        // Collect sessions that need to be archived
-        let mut to_archive: Vec<String> = Vec::new();
+    /// Star or unstar the selected conversation. Starred conversations are
+    /// pinned in their own section at the top of the sidebar; the selection
+    /// follows the conversation there and back.
+    pub fn toggle_pin_selected_conversation(&mut self) {
+        let items = self.sidebar_items();
+        let selected = self.sidebar_state.list_state.selected().unwrap_or(0);
+        let (group_key, index) = match items.get(selected) {
+            Some(
+                SidebarItem::Conversation { group_key, index }
+                | SidebarItem::Subagent {
+                    group_key, index, ..
+                },
+            ) => (group_key.clone(), *index),
+            Some(SidebarItem::EphemeralSession { .. }) => {
+                self.toast_warning("Cannot pin unsaved conversation");
+                return;
+            }
+            _ => {
+                self.toast_warning("Select a conversation to pin");
+                return;
+            }
+        };
+        let Some(session_id) = self
+            .conversation_at(&group_key, index)
+            .map(|conv| conv.session_id.clone())
+        else {
+            return;
+        };
 
-        for group in &self.groups {
-            for conv in group.conversations() {
-                // Skip if already archived or if running
-                if conv.is_archived {
-                    continue;
-                }
-                if self.running_session_ids().contains(&conv.session_id) {
-                    continue;
-                }
+        let pinned = self.archive_manager.toggle_pinned(&session_id);
+        let pinned_at = self.archive_manager.get_pinned_at(&session_id);
+        if let Some(conv) = self
+            .groups
+            .iter_mut()
+            .find(|g| g.key() == group_key)
+            .and_then(|g| g.conversations_mut().get_mut(index))
+        {
+            conv.pinned_at = pinned_at;
+        }
+        if let Err(e) = self.archive_manager.save() {
+            self.toast_error(format!("Failed to save archive: {}", e));
+            return;
+        }
 
-                // Check if conversation should be auto-archived based on age
-                if self.archive_manager.should_auto_archive(conv.timestamp) {
-                    to_archive.push(conv.session_id.clone());
-                }
+        let moved = self.sidebar_items().iter().position(|item| {
+            matches!(item, SidebarItem::Conversation { group_key: gk, index: i } if *gk == group_key && *i == index)
+        });
+        if let Some(moved) = moved {
+            self.sidebar_state.list_state.select(Some(moved));
+        }
+        self.update_selected_conversation();
+        self.toast_success(if pinned {
+            "Conversation pinned"
+        } else {
+            "Conversation unpinned"
+        });
+    }
+
+    /// Open the `number`th (1-based) conversation in the Pinned section.
+    pub fn open_pinned_conversation(&mut self, number: usize) -> Result<()> {
+        let items = self.sidebar_items();
+        let position = items
+            .iter()
+            .enumerate()
+            .take_while(|(_, item)| !matches!(item, SidebarItem::WorkspaceSectionHeader))
+            .filter(|(_, item)| matches!(item, SidebarItem::Conversation { .. }))
+            .nth(number.saturating_sub(1))
+            .map(|(position, _)| position);
+        let Some(position) = position else {
+            self.toast_warning(format!("No pinned conversation {}", number));
+            return Ok(());
+        };
+        self.sidebar_state.list_state.select(Some(position));
+        self.update_selected_conversation();
+        self.open_selected()
+    }
+
+    /// Move the selected conversation to the trash. Running conversations
+    /// can't be deleted.
+    pub fn trash_selected_conversation(&mut self) {
+        let items = self.sidebar_items();
+        let selected = self.sidebar_state.list_state.selected().unwrap_or(0);
+
+        let conv = match items.get(selected) {
+            Some(SidebarItem::Conversation { group_key, index }) => {
+                self.conversation_at(group_key, *index).cloned()
+            }
+            Some(SidebarItem::EphemeralSession { .. }) => {
+                self.toast_warning("Cannot delete unsaved conversation");
+                return;
+            }
+            _ => None,
+        };
+        let Some(conv) = conv else {
+            self.toast_warning("Select a conversation to delete");
+            return;
+        };
+        if self.is_conversation_running(&conv.session_id) {
+            self.toast_warning("Cannot delete active conversation");
+            return;
+        }
+        self.trash_conversations(&[conv]);
+    }
+
+    /// Preview moving every archived conversation that isn't running to the
+    /// trash, asking for confirmation.
+    pub fn trash_archived_conversations(&mut self) {
+        let running = self.running_session_ids();
+        let archived: Vec<Conversation> = self
+            .groups
+            .iter()
+            .flat_map(ConversationGroup::conversations)
+            .filter(|c| c.is_archived && !running.contains(&c.session_id))
+            .cloned()
+            .collect();
+        if archived.is_empty() {
+            self.toast_info("No archived conversations to delete");
+            return;
+        }
+        let candidates = archived
+            .iter()
+            .map(|conv| archive_rules::ArchiveCandidate::new(conv, None))
+            .collect();
+        let state = ArchiveModalState::trash("Delete archived conversations", candidates);
+        self.modal_state = ModalState::Archive(Box::new(state));
+        self.input_mode = InputMode::Insert;
+    }
+
+    /// Move the confirmed conversations to the trash (from the preview).
+    pub fn trash_sessions(&mut self, session_ids: &[String]) {
+        self.close_modal();
+        let running = self.running_session_ids();
+        let conversations: Vec<Conversation> = self
+            .groups
+            .iter()
+            .flat_map(ConversationGroup::conversations)
+            .filter(|c| session_ids.contains(&c.session_id) && !running.contains(&c.session_id))
+            .cloned()
+            .collect();
+        self.trash_conversations(&conversations);
+    }
+
+    fn trash_conversations(&mut self, conversations: &[Conversation]) {
+        let mut trashed = Vec::new();
+        let mut failure = None;
+        for conv in conversations {
+            match self.trash_manager.trash(conv) {
+                Ok(id) => trashed.push(id),
+                Err(e) => failure = Some(e),
             }
         }
+        if let Err(e) = self.trash_manager.save() {
+            self.toast_error(format!("Failed to save trash: {}", e));
+        }
 
-        // Archive the conversations
-        for session_id in &to_archive {
-            self.archive_manager.archive(session_id, true);
+        if !trashed.is_empty() {
+            let message = match trashed.len() {
+                1 => "Conversation moved to trash · U to undo".to_string(),
+                n => format!("{} conversations moved to trash · U to undo", n),
+            };
+            self.last_trashed = trashed;
+            self.reload_after_trash_change();
+            self.toast_manager
+                .push_with_duration(message, ToastType::Success, UNDO_TOAST_DURATION);
+        }
+        if let Some(e) = failure {
+            self.toast_error(format!("Failed to delete conversation: {:#}", e));
+        }
+    }
 
-            // Update the conversation's is_archived flag in groups
-            for group in &mut self.groups {
-                for conv in group.conversations_mut() {
-                    if &conv.session_id == session_id {
-                        conv.is_archived = true;
-                        conv.archived_at = Some(chrono::Utc::now());
-                        break;
-                    }
+    /// Restore the conversations moved to the trash by the last delete.
+    pub fn undo_trash(&mut self) {
+        if self.last_trashed.is_empty() {
+            self.toast_info("Nothing to undo");
+            return;
+        }
+        let ids = std::mem::take(&mut self.last_trashed);
+        let mut restored = 0;
+        let mut failure = None;
+        for id in &ids {
+            match self.trash_manager.restore(id) {
+                Ok(_) => restored += 1,
+                Err(e) => failure = Some(e),
+            }
+        }
+        let _ = self.trash_manager.save();
+        self.reload_after_trash_change();
+
+        if let Some(e) = failure {
+            self.toast_error(format!("Failed to restore conversation: {:#}", e));
+        } else if restored == 1 {
+            self.toast_success("Conversation restored");
+        } else {
+            self.toast_success(format!("{} conversations restored", restored));
+        }
+    }
+
+    /// Open the trash browser.
+    pub fn open_trash_modal(&mut self) {
+        let state = TrashModalState::new(
+            self.trash_manager.entries().to_vec(),
+            self.trash_manager.purge_after_days(),
+        );
+        self.modal_state = ModalState::Trash(Box::new(state));
+        self.input_mode = InputMode::Insert;
+    }
+
+    /// Restore a conversation from the trash browser, by trash entry ID.
+    pub fn restore_from_trash(&mut self, id: &str) {
+        match self.trash_manager.restore(id) {
+            Ok(_) => {
+                let _ = self.trash_manager.save();
+                self.last_trashed.retain(|trashed| trashed != id);
+                self.reload_after_trash_change();
+                self.toast_success("Conversation restored");
+            }
+            Err(e) => self.toast_error(format!("Failed to restore: {:#}", e)),
+        }
+    }
+
+    /// Permanently delete a conversation from the trash browser, by trash
+    /// entry ID. Its archive, pin and metadata entries go too unless another
+    /// copy of the session is left.
+    pub fn purge_from_trash(&mut self, id: &str) {
+        let purged = self.trash_manager.purge(id);
+        match purged.and_then(|entry| self.trash_manager.save().map(|()| entry)) {
+            Ok(entry) => {
+                self.last_trashed.retain(|trashed| trashed != id);
+                if let Some(entry) = entry.filter(|e| self.trash_manager.is_gone(e)) {
+                    self.archive_manager.remove(&entry.session_id);
+                    self.metadata_manager.remove(&entry.session_id);
+                    let _ = self.archive_manager.save();
+                    let _ = self.metadata_manager.save();
                 }
+                self.refresh_trash_modal();
+                self.toast_success("Deleted permanently");
             }
+            Err(e) => self.toast_error(format!("Failed to delete: {:#}", e)),
+        }
+    }
+
+    fn refresh_trash_modal(&mut self) {
+        if let ModalState::Trash(ref mut state) = self.modal_state {
+            state.set_entries(self.trash_manager.entries().to_vec());
+        }
+    }
+
+    /// Reload conversations after files moved in or out of the trash.
+    fn reload_after_trash_change(&mut self) {
+        let trashed = |id: &str| self.trash_manager.contains(id);
+        if self
+            .transcript_view
+            .as_ref()
+            .is_some_and(|v| trashed(&v.conversation.session_id))
+        {
+            self.clear_preview();
         }
+        let _ = self.with_sidebar_selection_preserved(Self::load_conversations_preserve_order);
+        self.update_selected_conversation();
+        self.refresh_trash_modal();
+    }
+
+    /// Check for conversations that should be auto-archived
+    /// Archives Idle conversations older than the configured threshold
+    pub fn check_auto_archive(&mut self) {
+        let running = self.running_session_ids();
+        let to_archive: Vec<String> = self
+            .groups
+            .iter()
+            .flat_map(ConversationGroup::conversations)
+            .filter(|conv| !conv.is_archived && !running.contains(&conv.session_id))
+            .filter(|conv| self.archive_manager.should_auto_archive(conv.timestamp))
+            .map(|conv| conv.session_id.clone())
+            .collect();
+        self.mark_archived(&to_archive, true);
 
         // Save if we archived anything
         if !to_archive.is_empty() {
@@ -196,6 +450,115 @@ impl App {
         }
     }
 
+    /// Archive sessions and flag their conversations as archived, without saving.
+    fn mark_archived(&mut self, session_ids: &[String], auto_archived: bool) {
+        let ids: HashSet<&str> = session_ids.iter().map(String::as_str).collect();
+        for session_id in &ids {
+            self.archive_manager.archive(session_id, auto_archived);
+        }
+        let now = chrono::Utc::now();
+        for conv in self
+            .groups
+            .iter_mut()
+            .flat_map(ConversationGroup::conversations_mut)
+            .filter(|conv| ids.contains(conv.session_id.as_str()))
+        {
+            conv.is_archived = true;
+            conv.archived_at = Some(now);
+        }
+    }
+
+    /// Archive the conversations confirmed in the archive preview.
+    pub fn archive_sessions(&mut self, session_ids: &[String], by_rule: bool) {
+        self.close_modal();
+        self.mark_archived(session_ids, by_rule);
+        if let Err(e) = self.archive_manager.save() {
+            self.toast_error(format!("Failed to save archive: {}", e));
+            return;
+        }
+        self.update_selected_conversation();
+        self.toast_success(match session_ids.len() {
+            1 => "Conversation archived".to_string(),
+            n => format!("{} conversations archived", n),
+        });
+    }
+
+    /// Preview archiving every conversation in the selected group, or in
+    /// every group of the selected project header.
+    pub fn open_archive_group_modal(&mut self) {
+        let items = self.sidebar_items();
+        let selected = self.sidebar_state.list_state.selected().unwrap_or(0);
+        let groups: Vec<&ConversationGroup> = match items.get(selected) {
+            Some(SidebarItem::ProjectHeader { project_key, .. }) => self
+                .groups
+                .iter()
+                .filter(|g| g.project_key() == *project_key)
+                .collect(),
+            _ => match Self::get_group_key_for_index(&items, selected) {
+                Some(key) => self.groups.iter().filter(|g| g.key() == key).collect(),
+                None => Vec::new(),
+            },
+        };
+        let Some(first) = groups.first() else {
+            self.toast_warning("Select a group or project to archive");
+            return;
+        };
+        let title = if groups.len() > 1 {
+            format!("Archive project {}", first.project_display_name())
+        } else {
+            format!("Archive {}", first.display_name())
+        };
+        let candidates = archive_rules::bulk_candidates(
+            groups.iter().flat_map(|g| g.conversations()),
+            &self.running_session_ids(),
+        );
+        self.open_archive_modal(ArchiveModalState::new(title, candidates, false));
+    }
+
+    /// Preview archiving every conversation idle since before a cutoff typed
+    /// in the modal.
+    pub fn open_archive_older_than_modal(&mut self) {
+        let candidates = archive_rules::bulk_candidates(
+            self.groups
+                .iter()
+                .flat_map(ConversationGroup::conversations),
+            &self.running_session_ids(),
+        );
+        let days = self.archive_manager.get_auto_archive_days().unwrap_or(30);
+        let cutoff = format!("{}d", days);
+        self.open_archive_modal(ArchiveModalState::older_than(candidates, &cutoff));
+    }
+
+    /// Preview what the configured archive rules would archive.
+    ///
+    /// Sessions are re-read so conversations whose directory is gone, which
+    /// the sidebar leaves out, are included.
+    pub fn open_archive_rules_modal(&mut self) {
+        if self.config.archive_rules.is_empty() {
+            self.toast_info("No archive rules configured (archive_rules in config.json)");
+            return;
+        }
+        let conversations = match self.parse_sessions() {
+            Ok(sessions) => self.sessions_to_conversations(sessions),
+            Err(e) => {
+                self.toast_error(format!("Failed to read conversations: {}", e));
+                return;
+            }
+        };
+        let candidates = archive_rules::rule_candidates(
+            &self.config.archive_rules,
+            &conversations,
+            &self.running_session_ids(),
+            chrono::Utc::now().timestamp_millis(),
+        );
+        self.open_archive_modal(ArchiveModalState::new("Archive rules", candidates, true));
+    }
+
+    fn open_archive_modal(&mut self, state: ArchiveModalState) {
+        self.modal_state = ModalState::Archive(Box::new(state));
+        self.input_mode = InputMode::Insert;
+    }
+
     // =========================================================================
     // Clipboard Methods
     // =========================================================================
@@ -366,11 +729,480 @@ impl App {
     }
 
     /// Open the search modal dialog
+    ///
+    /// Starts a content index refresh in the background; results are re-run
+    /// once newly written messages are indexed.
     pub fn open_search_modal(&mut self) {
+        self.search_engine.start_index_refresh();
         self.modal_state = ModalState::Search(Box::default());
         self.input_mode = InputMode::Insert;
     }
 
+    /// Open the usage stats modal with totals for every breakdown.
+    pub fn open_stats_modal(&mut self) {
+        let state = StatsModalState::new(self.usage_reports());
+        self.modal_state = ModalState::Stats(Box::new(state));
+        self.input_mode = InputMode::Insert;
+    }
+
+    fn usage_reports(&self) -> Vec<(UsageDimension, UsageReport)> {
+        UsageDimension::ALL
+            .into_iter()
+            .map(|dimension| {
+                let report = UsageReport::build(
+                    self.log_stats.stats(),
+                    &self.groups,
+                    &self.price_table,
+                    &self.config.profiles,
+                    dimension,
+                );
+                (dimension, report)
+            })
+            .collect()
+    }
+
+    /// Open the tool stats of the selected conversation (or the transcript's)
+    /// and of every project.
+    pub fn open_tools_modal(&mut self) {
+        let (conversation, projects) = self.tool_stats();
+        let state = ToolsModalState::new(conversation, projects);
+        self.modal_state = ModalState::Tools(Box::new(state));
+        self.input_mode = InputMode::Insert;
+    }
+
+    fn tool_stats(&self) -> (Option<(String, ToolStats)>, Vec<ToolProjectRow>) {
+        let target = match (&self.focus, &self.transcript_view) {
+            (Focus::Transcript, Some(view)) => Some(view.root_conversation()),
+            _ => self.selected_conversation.as_ref(),
+        };
+        let stats = self.log_stats.stats();
+        let conversation = target.map(|conv| {
+            let conv_stats = stats.tools(&conv.session_id).cloned().unwrap_or_default();
+            (conv.display.clone(), conv_stats)
+        });
+        (conversation, tool_stats::project_rows(stats, &self.groups))
+    }
+
+    /// Show the stats of a finished log stats refresh in whatever displays
+    /// them: the stats and tools modals and the detail panel.
+    pub(crate) fn show_log_stats(&mut self) {
+        match self.modal_state {
+            ModalState::Stats(_) => {
+                let reports = self.usage_reports();
+                if let ModalState::Stats(ref mut state) = self.modal_state {
+                    state.set_reports(reports);
+                }
+            }
+            ModalState::Tools(_) => {
+                let (conversation, projects) = self.tool_stats();
+                if let ModalState::Tools(ref mut state) = self.modal_state {
+                    state.set_stats(conversation, projects);
+                }
+            }
+            _ => {}
+        }
+        if let Some(ref mut state) = self.detail_panel {
+            let cost = self
+                .session_costs
+                .get(&state.conversation.session_id)
+                .copied();
+            state.set_log_stats(self.log_stats.stats(), cost);
+        }
+    }
+
+    /// Show or hide the detail panel of the selected conversation.
+    pub fn toggle_detail_panel(&mut self) {
+        self.detail_panel_open = !self.detail_panel_open;
+        self.detail_panel = None;
+        self.update_detail_panel();
+    }
+
+    /// Rebuild the detail panel when the conversation it should show
+    /// changed: another one was selected, or the selected one was updated
+    /// (at most every couple of seconds while it keeps growing). Only that
+    /// conversation's log is re-read. Does nothing while the panel is hidden.
+    pub fn update_detail_panel(&mut self) {
+        if !self.detail_panel_open {
+            return;
+        }
+        let target = match (&self.focus, &self.transcript_view) {
+            (Focus::Transcript, Some(view)) => Some(view.root_conversation()),
+            _ => self.selected_conversation.as_ref(),
+        };
+        let Some(conv) = target else {
+            self.detail_panel = None;
+            return;
+        };
+        let running = self.is_conversation_running(&conv.session_id);
+        let shown = self.detail_panel.as_ref();
+        if shown.is_some_and(|state| !state.is_stale(conv, running)) {
+            return;
+        }
+        let grew = shown.is_some_and(|state| {
+            state.conversation.session_id == conv.session_id
+                && state.conversation.timestamp != conv.timestamp
+        });
+        if grew
+            && self.detail_panel_built_at.elapsed()
+                < std::time::Duration::from_millis(DETAIL_PANEL_REFRESH_MS)
+        {
+            return;
+        }
+        let conv = conv.clone();
+        // On error the files touched stay as last indexed
+        let _ = self
+            .search_engine
+            .refresh_index_file(&conv.session_id, &conv.file_path);
+
+        let mut state = DetailPanelState {
+            running,
+            details: ConversationDetails::read(&conv.file_path),
+            messages: conv.message_count.or_else(|| {
+                self.search_engine
+                    .content_index()
+                    .message_count(&conv.session_id)
+            }),
+            models: Vec::new(),
+            tokens: None,
+            cost: None,
+            tools: None,
+            files: self
+                .search_engine
+                .content_index()
+                .session_files(&conv.session_id),
+            conversation: conv,
+        };
+        let cost = self
+            .session_costs
+            .get(&state.conversation.session_id)
+            .copied();
+        state.set_log_stats(self.log_stats.stats(), cost);
+        self.detail_panel = Some(state);
+        self.detail_panel_built_at = Instant::now();
+    }
+
+    /// Open the picker of files touched by conversations, scoped to the
+    /// project of the selected group.
+    ///
+    /// Lists what the content index knows now (it follows session file
+    /// changes in the background) and starts a refresh for the next opening.
+    pub fn open_files_modal(&mut self) {
+        self.search_engine.start_index_refresh();
+
+        let titles: HashMap<&str, &str> = self
+            .groups
+            .iter()
+            .flat_map(ConversationGroup::conversations)
+            .map(|c| (c.session_id.as_str(), c.display.as_str()))
+            .collect();
+        let files = self
+            .search_engine
+            .content_index()
+            .file_touches()
+            .into_iter()
+            .filter_map(|(path, touchers)| {
+                // Only conversations still listed in the sidebar can be opened
+                let touchers: Vec<FileTouchEntry> = touchers
+                    .into_iter()
+                    .filter_map(|t| {
+                        let title = titles.get(t.session_id.as_str())?.to_string();
+                        Some(FileTouchEntry {
+                            session_id: t.session_id,
+                            title,
+                            touch: t.touch,
+                        })
+                    })
+                    .collect();
+                (!touchers.is_empty()).then_some(TouchedFile { path, touchers })
+            })
+            .collect();
+
+        let items = self.sidebar_items();
+        let selected = self.sidebar_state.list_state.selected().unwrap_or(0);
+        let project = Self::get_group_key_for_index(&items, selected).and_then(|key| {
+            self.groups
+                .iter()
+                .find(|g| g.key() == key)
+                .and_then(ConversationGroup::project_path)
+        });
+
+        self.modal_state = ModalState::Files(Box::new(FilesModalState::new(files, project)));
+        self.input_mode = InputMode::Insert;
+    }
+
+    /// Open the export modal for the conversation in the transcript view, or
+    /// the one selected in the sidebar.
+    /// Open the editor for the selected conversation's title, tags and note.
+    pub fn open_metadata_modal(&mut self) {
+        let target = match (&self.focus, &self.transcript_view) {
+            (Focus::Transcript, Some(view)) => Some(view.root_conversation()),
+            _ => self.selected_conversation.as_ref(),
+        };
+        let Some(conv) = target else {
+            self.toast_error("Select a conversation to edit");
+            return;
+        };
+        let state = MetadataModalState::new(
+            conv.session_id.clone(),
+            conv.display.clone(),
+            self.metadata_manager.get(&conv.session_id),
+        );
+        self.modal_state = ModalState::Metadata(Box::new(state));
+        self.input_mode = InputMode::Insert;
+    }
+
+    /// Store edited metadata and rebuild the conversations so it shows everywhere.
+    pub fn save_conversation_metadata(&mut self, session_id: &str, meta: ConversationMeta) {
+        self.close_modal();
+        self.metadata_manager.set(session_id, meta);
+        if let Err(e) = self.metadata_manager.save() {
+            self.toast_error(format!("Failed to save metadata: {}", e));
+            return;
+        }
+        let _ = self.with_sidebar_selection_preserved(Self::load_conversations_preserve_order);
+        self.update_selected_conversation();
+        self.toast_success("Conversation updated");
+    }
+
+    pub fn open_export_modal(&mut self) {
+        let target = match (&self.focus, &self.transcript_view) {
+            (Focus::Transcript, Some(view)) => Some(&view.conversation),
+            _ => self.selected_conversation.as_ref(),
+        };
+        let Some(conv) = target else {
+            self.toast_error("Select a conversation to export");
+            return;
+        };
+        let state = ExportModalState::new(conv.session_id.clone(), conv.display.clone());
+        self.modal_state = ModalState::Export(Box::new(state));
+        self.input_mode = InputMode::Insert;
+    }
+
+    /// Render a conversation and save it to the downloads directory or copy it
+    /// to the clipboard.
+    pub fn confirm_export(
+        &mut self,
+        session_id: &str,
+        format: ExportFormat,
+        options: &ExportOptions,
+        destination: ExportDestination,
+    ) {
+        self.close_modal();
+
+        let Some(conv) = self.conversation_by_id(session_id) else {
+            self.toast_error("Conversation not found");
+            return;
+        };
+
+        let rendered = parse_transcript(&conv.file_path)
+            .and_then(|transcript| export::export(&conv, &transcript, format, options));
+        let content = match rendered {
+            Ok(content) => content,
+            Err(e) => {
+                self.toast_error(format!("Export failed: {}", e));
+                return;
+            }
+        };
+
+        match destination {
+            ExportDestination::Clipboard => {
+                let copied = arboard::Clipboard::new()
+                    .and_then(|mut clipboard| clipboard.set_text(content))
+                    .is_ok();
+                if copied {
+                    self.toast_success(format!("Copied {} to clipboard", format.name()));
+                } else {
+                    self.toast_error("Clipboard unavailable");
+                }
+            }
+            ExportDestination::File => {
+                let Some(dir) = dirs::download_dir().or_else(dirs::home_dir) else {
+                    self.toast_error("No downloads directory");
+                    return;
+                };
+                let path = dir.join(export::file_name(&conv, format));
+                match std::fs::write(&path, content) {
+                    Ok(()) => self.toast_success(format!("Exported to {}", path.display())),
+                    Err(e) => self.toast_error(format!("Export failed: {}", e)),
+                }
+            }
+        }
+    }
+
+    /// A loaded conversation (or the one in the transcript view) by session ID.
+    fn conversation_by_id(&self, session_id: &str) -> Option<Conversation> {
+        self.groups
+            .iter()
+            .flat_map(ConversationGroup::conversations)
+            .chain(self.transcript_view.as_ref().map(|v| &v.conversation))
+            .find(|c| c.session_id == session_id)
+            .cloned()
+    }
+
+    /// Open the branch tree of the conversation in the transcript view (the
+    /// parent, for a subagent), or the one selected in the sidebar.
+    pub fn open_branches_modal(&mut self) {
+        let target = match (&self.focus, &self.transcript_view) {
+            (Focus::Transcript, Some(view)) => Some(view.root_conversation()),
+            _ => self.selected_conversation.as_ref(),
+        };
+        let Some(conv) = target else {
+            self.toast_error("Select a conversation to show its branches");
+            return;
+        };
+        match parse_branches(&conv.file_path) {
+            Ok(tree) => {
+                let state =
+                    BranchesModalState::new(conv.session_id.clone(), conv.display.clone(), tree);
+                self.modal_state = ModalState::Branches(Box::new(state));
+                self.input_mode = InputMode::Insert;
+            }
+            Err(e) => self.toast_error(format!("Failed to read conversation: {}", e)),
+        }
+    }
+
+    /// The conversation linked to `session_id` by a plan: the one that
+    /// planned it, else the latest implementation of its plan.
+    fn plan_link_of(&self, session_id: &str) -> Option<Conversation> {
+        let conversations = || {
+            self.groups
+                .iter()
+                .flat_map(ConversationGroup::conversations)
+        };
+        let parent = conversations()
+            .find(|c| c.session_id == session_id)?
+            .plan_parent
+            .clone();
+        match parent {
+            Some(parent) => conversations().find(|c| c.session_id == parent),
+            None => conversations()
+                .filter(|c| c.plan_parent.as_deref() == Some(session_id))
+                .max_by_key(|c| c.timestamp),
+        }
+        .cloned()
+    }
+
+    /// Select the selected conversation's planning conversation, or the
+    /// implementation of its plan. One not listed in the sidebar opens in the
+    /// transcript view instead.
+    pub fn jump_to_plan_link(&mut self) {
+        let Some(conv) = self.selected_conversation.as_ref() else {
+            self.toast_warning("Select a conversation");
+            return;
+        };
+        let Some(target) = self.plan_link_of(&conv.session_id) else {
+            self.toast_info("No linked plan or implementation");
+            return;
+        };
+        let position = self.sidebar_items().iter().position(|item| {
+            matches!(item, SidebarItem::Conversation { group_key, index }
+                if self.conversation_at(group_key, *index)
+                    .is_some_and(|c| c.session_id == target.session_id))
+        });
+        match position {
+            Some(position) => self.navigate_to_index(position),
+            None => {
+                self.clear_preview();
+                self.open_transcript(target, None);
+                if self.transcript_view.is_some() {
+                    self.focus = Focus::Transcript;
+                }
+            }
+        }
+    }
+
+    /// Show the transcript of the planning conversation of the one in the
+    /// transcript view, or of the implementation of its plan.
+    pub fn open_plan_link_transcript(&mut self) {
+        let Some(view) = self.transcript_view.as_ref() else {
+            return;
+        };
+        let session_id = view.root_conversation().session_id.clone();
+        let Some(target) = self.plan_link_of(&session_id) else {
+            self.toast_info("No linked plan or implementation");
+            return;
+        };
+        self.open_transcript(target, None);
+    }
+
+    /// Open the git commits made while the conversation in the transcript
+    /// view (the parent, for a subagent) or the selected one was active.
+    pub fn open_commits_modal(&mut self) {
+        let target = match (&self.focus, &self.transcript_view) {
+            (Focus::Transcript, Some(view)) => Some(view.root_conversation()),
+            _ => self.selected_conversation.as_ref(),
+        };
+        let Some(conv) = target else {
+            self.toast_error("Select a conversation to show its commits");
+            return;
+        };
+        let Some(span) = conv.active_span() else {
+            self.toast_error("Conversation has no timestamps");
+            return;
+        };
+        match git_log::session_commits(conv) {
+            Ok(commits) => {
+                let state = CommitsModalState::new(conv.display.clone(), span, commits);
+                self.modal_state = ModalState::Commits(Box::new(state));
+                self.input_mode = InputMode::Insert;
+            }
+            Err(e) => self.toast_error(format!("Failed to read commits: {}", e)),
+        }
+    }
+
+    /// Copy a commit hash from the commits modal to the clipboard.
+    pub fn copy_commit_hash(&mut self, hash: &str) {
+        match arboard::Clipboard::new() {
+            Ok(mut clipboard) => {
+                if clipboard.set_text(hash).is_ok() {
+                    self.toast_success(format!("Copied {}", &hash[..hash.len().min(7)]));
+                } else {
+                    self.toast_error("Failed to copy");
+                }
+            }
+            Err(_) => self.toast_error("Clipboard unavailable"),
+        }
+    }
+
+    /// Show the transcript of a conversation with the cursor on a branch tip.
+    pub fn open_branch_tip(&mut self, session_id: &str, tip: &str) {
+        self.close_modal();
+        let Some(conv) = self.conversation_by_id(session_id) else {
+            self.toast_error("Conversation not found");
+            return;
+        };
+        self.clear_preview();
+        self.open_transcript(conv, None);
+        if let Some(view) = self.transcript_view.as_mut() {
+            if let Some(index) = view.message_index(tip) {
+                view.jump_to_message(index);
+            }
+            self.focus = Focus::Transcript;
+        }
+    }
+
+    /// Resume a conversation, forking it at `fork_at` when a branch other
+    /// than the latest one was chosen.
+    pub fn resume_branch(&mut self, session_id: &str, fork_at: Option<&str>) -> Result<()> {
+        self.close_modal();
+        let Some(conv) = self.conversation_by_id(session_id) else {
+            self.toast_error("Conversation not found");
+            return Ok(());
+        };
+        self.clear_preview();
+        let Some(uuid) = fork_at else {
+            return self.resume_conversation(conv);
+        };
+        if !conv.project_path.exists() {
+            self.toast_error("Project directory no longer exists");
+            return Ok(());
+        }
+        self.fork_session(&conv.project_path, &conv.session_id, uuid)?;
+        self.focus = Focus::Terminal(self.tiling.focused());
+        self.enter_insert_mode();
+        Ok(())
+    }
+
     /// Close any open modal dialog
     pub fn close_modal(&mut self) {
         self.modal_state = ModalState::None;
@@ -380,15 +1212,24 @@ impl App {
 
     /// Perform a search with the current query in the search modal
     pub fn perform_search(&mut self) {
+        let running = self.running_session_ids();
         if let ModalState::Search(ref mut state) = self.modal_state {
-            let query = state.search_query();
+            let mut query = state.search_query();
+            self.search_engine.resolve_query(&mut query, &running);
             let results = self.search_engine.search(&query, &self.groups);
             state.set_results(results);
         }
     }
 
-    /// Navigate to a conversation by session_id (from search results)
-    pub fn navigate_to_conversation(&mut self, session_id: &str) -> Result<bool> {
+    /// Navigate to a conversation by session_id (from search results).
+    ///
+    /// With a `message_index` (content hit) the transcript opens at that
+    /// message; otherwise the conversation is resumed.
+    pub fn navigate_to_conversation(
+        &mut self,
+        session_id: &str,
+        message_index: Option<usize>,
+    ) -> Result<bool> {
         // Close search modal
         self.modal_state = ModalState::None;
 
@@ -443,28 +1284,15 @@ impl App {
         }
         self.update_selected_conversation();
 
-        // Open the conversation directly (don't rely on open_selected which
-        // depends on the sidebar item being visible/selected)
-        let project_path = conv.project_path.clone();
-        if !project_path.exists() {
-            self.selected_conversation = Some(conv);
+        if let Some(message) = message_index {
+            self.open_transcript(conv, Some(message));
+            self.focus_transcript();
             return Ok(true);
         }
 
-        let existing_session = self
-            .session_to_claude_id
-            .iter()
-            .find(|(_, v)| **v == Some(session_id.to_string()))
-            .map(|(k, _)| k.clone());
-
-        if let Some(sid) = existing_session {
-            self.active_session_id = Some(sid);
-        } else {
-            self.start_session(&project_path, Some(session_id))?;
-        }
-        self.selected_conversation = Some(conv);
-        self.focus = Focus::Terminal(TerminalPaneId::Primary);
-        self.enter_insert_mode();
+        // Open the conversation directly (don't rely on open_selected which
+        // depends on the sidebar item being visible/selected)
+        self.resume_conversation(conv)?;
         Ok(true)
     }
 
@@ -481,7 +1309,7 @@ impl App {
         // Start a new session in the selected directory
         self.start_session(path, None)?;
         self.selected_conversation = None;
-        self.focus = Focus::Terminal(TerminalPaneId::Primary);
+        self.focus = Focus::Terminal(self.tiling.focused());
         self.enter_insert_mode();
 
         Ok(())
@@ -566,7 +1394,7 @@ impl App {
                 // Start a new conversation in the worktree
                 self.start_session(&worktree_path, None)?;
                 self.selected_conversation = None;
-                self.focus = Focus::Terminal(TerminalPaneId::Primary);
+                self.focus = Focus::Terminal(self.tiling.focused());
                 self.enter_insert_mode();
             }
             Err(e) => {
@@ -641,7 +1469,7 @@ impl App {
                 self.toast_success(format!("Worktree '{}' created", branch_name));
                 self.start_session(&worktree_path, None)?;
                 self.selected_conversation = None;
-                self.focus = Focus::Terminal(TerminalPaneId::Primary);
+                self.focus = Focus::Terminal(self.tiling.focused());
                 self.enter_insert_mode();
             }
             Err(e) => {
@@ -678,7 +1506,12 @@ impl App {
 
     /// Open the profile management modal.
     pub fn open_profile_modal(&mut self) {
-        let profile_names: Vec<String> = self.config.profiles.iter().map(|p| p.name.clone()).collect();
+        let profile_names: Vec<String> = self
+            .config
+            .profiles
+            .iter()
+            .map(|p| p.name.clone())
+            .collect();
         let state = crate::ui::modal::ProfileModalState::new(profile_names, self.active_profile);
         self.modal_state = ModalState::Profile(Box::new(state));
         self.input_mode = InputMode::Insert;
@@ -956,7 +1789,7 @@ impl App {
     }
 
     /// Save config silently (don't show error toast, just log)
-    fn save_config_silent(&self) {
+    pub(crate) fn save_config_silent(&self) {
         if let Err(e) = self.config.save() {
             eprintln!("Failed to save config: {}", e);
         }
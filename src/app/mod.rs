@@ -6,7 +6,7 @@ mod panes;
 mod sessions;
 mod state;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::time::Instant;
 
@@ -16,31 +16,45 @@ use crossterm::event::KeyEvent;
 use ratatui::layout::Rect;
 
 use crate::claude::archive::ArchiveManager;
+use crate::claude::budget::{BudgetAlerts, UsageWindows};
 use crate::claude::conversation::Conversation;
+use crate::claude::data_dir::resolve_claude_dirs;
 use crate::claude::grouping::{
     group_conversations, group_conversations_unordered, order_groups_by_keys,
     retain_existing_groups, ConversationGroup,
 };
-use crate::claude::sessions::{parse_all_sessions, SessionEntry};
-use crate::claude::SessionsWatcher;
+use crate::claude::log_stats::BackgroundLogStats;
+use crate::claude::session_cache::SessionCache;
+use crate::claude::sessions::{parse_all_sessions_cached, parse_project_sessions, SessionEntry};
+use crate::claude::subagents::{load_subagents, Subagent};
+use crate::claude::usage::PriceTable;
+use crate::claude::{
+    ClaudeDir, HealthTracker, MetadataManager, PlanLinker, SessionsWatcher, TrashManager,
+};
 use crate::config::{Config, SidebarPosition};
 use crate::input::which_key::WhichKeyConfig;
 use crate::input::{InputMode, LeaderState};
-use crate::search::SearchEngine;
+use crate::search::{ContentIndex, SearchEngine, SearchFilterType, SearchQuery};
 use crate::session::{ScreenState, SessionManager, SessionState};
+use crate::ui::detail_panel::DetailPanelState;
 use crate::ui::modal::{
-    Modal, NewProjectModalState, ProfileModalState, SearchModalState, WorkspaceModalState,
+    ArchiveModalState, BranchesModalState, CommitsModalState, ExportModalState, FilesModalState,
+    LayoutModalState, MetadataModalState, Modal, NewProjectModalState, ProfileModalState,
+    SearchModalState, StatsModalState, ToolsModalState, TrashModalState, WorkspaceModalState,
     WorktreeModalState, WorktreeSearchModalState,
 };
 use crate::ui::sidebar::{
-    build_sidebar_items, group_has_active_content, SidebarContext, SidebarItem, SidebarState,
+    build_sidebar_items, group_has_active_content, ArchiveFilter, SidebarContext, SidebarItem,
+    SidebarState,
 };
+use crate::ui::tiling::{PaneId, SplitHandle, TilingLayout};
 use crate::ui::toast::{ToastManager, ToastType};
+use crate::ui::transcript::TranscriptViewState;
 
 // Re-export all public types from submodules
 pub use state::{
-    ArchiveStatus, ClipboardStatus, EphemeralSession, PaneConfig, SplitMode, TerminalPaneId,
-    TerminalPosition, TextSelection,
+    ArchiveStatus, ClipboardStatus, EphemeralSession, PaneConfig, SplitMode, TerminalPosition,
+    TextSelection,
 };
 
 /// Modal dialog state
@@ -59,6 +73,26 @@ pub enum ModalState {
     Workspace(Box<WorkspaceModalState>),
     /// Profile management modal
     Profile(Box<ProfileModalState>),
+    /// Named layout management modal
+    Layout(Box<LayoutModalState>),
+    /// Token usage and cost stats
+    Stats(Box<StatsModalState>),
+    /// Tool calls, failures and turn durations
+    Tools(Box<ToolsModalState>),
+    /// Files touched by conversations
+    Files(Box<FilesModalState>),
+    /// Branch tree of a conversation
+    Branches(Box<BranchesModalState>),
+    /// Git commits made while a conversation was active
+    Commits(Box<CommitsModalState>),
+    /// Conversation export options
+    Export(Box<ExportModalState>),
+    /// Deleted conversations
+    Trash(Box<TrashModalState>),
+    /// Custom title, tags and note of a conversation
+    Metadata(Box<MetadataModalState>),
+    /// Preview of a bulk or rule-based archive
+    Archive(Box<ArchiveModalState>),
 }
 
 impl ModalState {
@@ -72,6 +106,16 @@ impl ModalState {
             ModalState::WorktreeSearch(state) => Some(state.as_mut()),
             ModalState::Workspace(state) => Some(state.as_mut()),
             ModalState::Profile(state) => Some(state.as_mut()),
+            ModalState::Layout(state) => Some(state.as_mut()),
+            ModalState::Stats(state) => Some(state.as_mut()),
+            ModalState::Tools(state) => Some(state.as_mut()),
+            ModalState::Files(state) => Some(state.as_mut()),
+            ModalState::Branches(state) => Some(state.as_mut()),
+            ModalState::Commits(state) => Some(state.as_mut()),
+            ModalState::Export(state) => Some(state.as_mut()),
+            ModalState::Trash(state) => Some(state.as_mut()),
+            ModalState::Metadata(state) => Some(state.as_mut()),
+            ModalState::Archive(state) => Some(state.as_mut()),
         }
     }
 }
@@ -83,9 +127,11 @@ pub enum Focus {
     #[default]
     Sidebar,
     /// A terminal pane has focus
-    Terminal(TerminalPaneId),
+    Terminal(PaneId),
     /// Mosaic grid view has focus
     Mosaic,
+    /// Read-only transcript view has focus
+    Transcript,
 }
 
 /// State for tracking jk/kj rapid-press escape sequence in insert mode
@@ -119,6 +165,9 @@ pub enum ChordState {
 /// Chord state timeout duration (500ms)
 const CHORD_TIMEOUT_MS: u64 = 500;
 
+/// Minimum time between detail panel rebuilds while the shown conversation grows
+const DETAIL_PANEL_REFRESH_MS: u64 = 2000;
+
 impl ChordState {
     /// Check if the chord has expired (timed out)
     pub fn is_expired(&self) -> bool {
@@ -151,14 +200,16 @@ pub enum ActiveSessionInfo {
     Conversation {
         index: usize,
         session_id: String,
-        conversation: Conversation,
+        conversation: Box<Conversation>,
     },
 }
 
 /// Application state
 pub struct App {
-    /// Path to ~/.claude
-    pub claude_dir: PathBuf,
+    /// Claude data directories shown in the sidebar, the primary one first
+    pub claude_dirs: Vec<ClaudeDir>,
+    /// What session discovery has already read from disk
+    session_cache: SessionCache,
     /// Conversation groups
     pub groups: Vec<ConversationGroup>,
     /// Sidebar state
@@ -167,20 +218,30 @@ pub struct App {
     pub focus: Focus,
     /// Session manager for PTY sessions (owned directly, no daemon)
     pub session_manager: SessionManager,
-    /// Currently active session ID (deprecated - use panes[].session_id)
+    /// Session ID shown in the focused pane (kept in sync with `panes`)
     pub active_session_id: Option<String>,
     /// Session ID being previewed (shown in terminal pane while sidebar keeps focus)
     pub preview_session_id: Option<String>,
-    /// Cached session state for active session (deprecated - use pane_state_caches)
+    /// Read-only transcript preview (shown in the focused pane instead of a PTY)
+    pub transcript_view: Option<TranscriptViewState>,
+    /// Cached session state for the focused pane (active or previewed session)
     pub session_state_cache: Option<SessionState>,
     /// Current split mode
     pub split_mode: SplitMode,
-    /// Pane configurations (indexed by TerminalPaneId)
-    pub panes: [PaneConfig; 2],
-    /// Which terminal pane has focus when in Terminal focus mode
-    pub active_pane: TerminalPaneId,
-    /// Cached session states for both panes
-    pub pane_state_caches: [Option<SessionState>; 2],
+    /// Tiling layout tree of the terminal area (tracks the focused pane)
+    pub tiling: TilingLayout,
+    /// Pane configurations keyed by pane ID
+    pub panes: HashMap<PaneId, PaneConfig>,
+    /// Cached session states for unfocused panes
+    pub pane_state_caches: HashMap<PaneId, SessionState>,
+    /// Pane rects from the last render (used for mouse hit-testing and PTY sizing)
+    pub pane_areas: Vec<(PaneId, Rect)>,
+    /// Area the tiling layout was rendered into (used for split dragging)
+    pub tiling_area: Option<Rect>,
+    /// Split boundary currently being dragged with the mouse
+    pub split_drag: Option<SplitHandle>,
+    /// Whether the focused pane (or selected mosaic tile) is temporarily maximized
+    pub pane_zoomed: bool,
     /// Mapping from session ID to Claude session ID (for resuming)
     /// When a session is created with --resume, we store the Claude session ID here
     pub session_to_claude_id: HashMap<String, Option<String>>,
@@ -220,8 +281,33 @@ pub struct App {
     pub archive_manager: ArchiveManager,
     /// Archive status for feedback display
     pub archive_status: ArchiveStatus,
+    /// Custom titles, tags and notes
+    pub metadata_manager: MetadataManager,
+    /// Links plan implementations to the conversations that planned them
+    pub plan_linker: PlanLinker,
+    /// Flags conversations whose last turn was interrupted or failed
+    pub health_tracker: HealthTracker,
+    /// When conversation health was last judged
+    health_checked_at: Instant,
+    /// Deleted conversations, kept recoverable for a while
+    pub trash_manager: TrashManager,
+    /// Trash entry IDs created by the last delete, for undo
+    pub last_trashed: Vec<String>,
     /// Search engine for finding conversations
     pub search_engine: SearchEngine,
+    /// Per-session token usage, tool calls and turns, read from the
+    /// conversation logs in the background
+    pub log_stats: BackgroundLogStats,
+    /// Token prices (built-in table plus `config.prices`)
+    pub price_table: PriceTable,
+    /// Cost of each session in USD, refreshed with the conversations
+    pub session_costs: HashMap<String, f64>,
+    /// Subagents of the conversations expanded in the sidebar, by session ID
+    pub subagents: HashMap<String, Vec<Subagent>>,
+    /// Usage in the current 5-hour block and today, shown in the status bar
+    pub usage_windows: UsageWindows,
+    /// Budget thresholds already reported for the current windows
+    pub budget_alerts: BudgetAlerts,
     /// Application configuration (layout, etc.)
     pub config: Config,
     /// Current input mode (Normal, Insert, Leader)
@@ -236,6 +322,12 @@ pub struct App {
     pub terminal_inner_area: Option<Rect>,
     /// Whether the help menu overlay is open (toggled by '?')
     pub help_menu_open: bool,
+    /// Whether the conversation detail panel is shown
+    pub detail_panel_open: bool,
+    /// What the detail panel shows, rebuilt when the selection changes
+    pub detail_panel: Option<DetailPanelState>,
+    /// When `detail_panel` was last rebuilt
+    detail_panel_built_at: Instant,
     /// Index of the selected pane in mosaic grid view
     pub mosaic_selected: usize,
     /// Cached session states for mosaic rendering (session_id, display_name, state)
@@ -246,38 +338,76 @@ pub struct App {
 
 impl App {
     /// Create a new application instance
-    pub fn new() -> Result<Self> {
-        let claude_dir = dirs::home_dir()
-            .context("Could not find home directory")?
-            .join(".claude");
+    ///
+    /// `claude_dir_flags` are the `--claude-dir` arguments, which replace the
+    /// default and configured data directories.
+    pub fn new(claude_dir_flags: &[PathBuf]) -> Result<Self> {
+        // Load configuration (use defaults if not found or invalid)
+        let mut config = Config::load().unwrap_or_default();
+        config.layout.validate();
+
+        let claude_dirs = resolve_claude_dirs(claude_dir_flags, &config.claude_dirs)?;
 
         // Create sessions watcher (optional - app works without it)
-        let sessions_watcher = SessionsWatcher::new(&claude_dir).ok();
+        let sessions_watcher = SessionsWatcher::new(&claude_dirs).ok();
+
+        // Archive state lives in the primary data directory
+        let mut archive_manager = ArchiveManager::new(&claude_dirs[0].path)
+            .context("Failed to create archive manager")?;
+
+        // As do custom titles, tags and notes
+        let mut metadata_manager = MetadataManager::new(&claude_dirs[0].path)
+            .context("Failed to load conversation metadata")?;
+
+        // And so does the trash; conversations deleted long enough ago go for good
+        let mut trash_manager =
+            TrashManager::new(&claude_dirs[0].path).context("Failed to open trash")?;
+        // (an entry that can't be deleted now is tried again next start)
+        let (purged, _) = trash_manager.purge_expired(chrono::Utc::now());
+        if !purged.is_empty() {
+            let _ = trash_manager.save();
+            for entry in purged.iter().filter(|e| trash_manager.is_gone(e)) {
+                archive_manager.remove(&entry.session_id);
+                metadata_manager.remove(&entry.session_id);
+            }
+            let _ = archive_manager.save();
+            let _ = metadata_manager.save();
+        }
 
-        // Create archive manager
-        let archive_manager =
-            ArchiveManager::new(&claude_dir).context("Failed to create archive manager")?;
+        let session_cache = SessionCache::default_path()
+            .map_or_else(SessionCache::new, |path| SessionCache::load(&path));
 
         // Create search engine
-        let search_engine = SearchEngine::new(claude_dir.clone());
-
-        // Load configuration (use defaults if not found or invalid)
-        let mut config = Config::load().unwrap_or_default();
-        config.layout.validate();
+        let content_index = ContentIndex::default_path()
+            .map_or_else(ContentIndex::new, |path| ContentIndex::load(&path));
+        let mut search_engine =
+            SearchEngine::new(claude_dirs.clone()).with_content_index(content_index);
+        // Catch up with conversations written since the index was saved
+        search_engine.start_index_refresh();
 
         let mut app = Self {
-            claude_dir,
+            claude_dirs,
+            session_cache,
             groups: Vec::new(),
             sidebar_state: SidebarState::new(),
             focus: Focus::Sidebar,
             session_manager: SessionManager::new(),
             active_session_id: None,
             preview_session_id: None,
+            transcript_view: None,
             session_state_cache: None,
-            split_mode: SplitMode::None,
-            panes: [PaneConfig::default(), PaneConfig::default()],
-            active_pane: TerminalPaneId::Primary,
-            pane_state_caches: [None, None],
+            split_mode: SplitMode::Tiled,
+            tiling: config
+                .layout
+                .panes
+                .clone()
+                .map_or_else(TilingLayout::new, TilingLayout::from_root),
+            panes: HashMap::new(),
+            pane_state_caches: HashMap::new(),
+            pane_areas: Vec::new(),
+            tiling_area: None,
+            split_drag: None,
+            pane_zoomed: false,
             session_to_claude_id: HashMap::new(),
             selected_conversation: None,
             should_quit: false,
@@ -295,8 +425,20 @@ impl App {
             modal_state: ModalState::None,
             toast_manager: ToastManager::new(),
             archive_manager,
+            metadata_manager,
+            plan_linker: PlanLinker::new(config.plan_prefixes.clone()),
+            health_tracker: HealthTracker::new(),
+            health_checked_at: Instant::now(),
+            trash_manager,
+            last_trashed: Vec::new(),
             archive_status: ArchiveStatus::None,
             search_engine,
+            log_stats: BackgroundLogStats::new(),
+            price_table: PriceTable::with_overrides(&config.prices),
+            session_costs: HashMap::new(),
+            subagents: HashMap::new(),
+            usage_windows: UsageWindows::default(),
+            budget_alerts: BudgetAlerts::new(),
             config,
             input_mode: InputMode::default(),
             which_key_config: WhichKeyConfig::new(),
@@ -304,6 +446,9 @@ impl App {
             text_selection: None,
             terminal_inner_area: None,
             help_menu_open: false,
+            detail_panel_open: false,
+            detail_panel: None,
+            detail_panel_built_at: Instant::now(),
             mosaic_selected: 0,
             mosaic_state_cache: Vec::new(),
             active_profile: None,
@@ -324,67 +469,171 @@ impl App {
     /// Load conversations with full re-sort (initial load and manual refresh).
     /// Groups are sorted by most recent activity.
     pub(crate) fn load_conversations_full(&mut self) -> Result<()> {
-        let sessions = parse_all_sessions(&self.claude_dir)?;
+        let sessions = self.parse_sessions()?;
         let conversations = self.sessions_to_conversations(sessions);
         let mut groups = group_conversations(conversations);
         retain_existing_groups(&mut groups);
         self.groups = groups;
         self.group_order = self.groups.iter().map(ConversationGroup::key).collect();
+        self.plan_linker.link(&mut self.groups);
+        self.refresh_health();
+        self.log_stats.start_refresh(&self.groups);
+        self.refresh_subagents();
         Ok(())
     }
 
     /// Load conversations preserving existing group order (auto-refresh).
     /// New groups appear at the front, existing groups maintain their position.
     pub(crate) fn load_conversations_preserve_order(&mut self) -> Result<()> {
-        let sessions = parse_all_sessions(&self.claude_dir)?;
+        let sessions = self.parse_sessions()?;
         let conversations = self.sessions_to_conversations(sessions);
         let mut groups = group_conversations_unordered(conversations);
         retain_existing_groups(&mut groups);
         let (ordered_groups, updated_order) = order_groups_by_keys(groups, &self.group_order);
         self.groups = ordered_groups;
         self.group_order = updated_order;
+        self.plan_linker.link(&mut self.groups);
+        self.refresh_health();
+        self.log_stats.start_refresh(&self.groups);
+        self.refresh_subagents();
         Ok(())
     }
 
+    /// Reload only the conversations stored in the given project directories
+    /// (auto-refresh). Groups holding none of them are left untouched, and
+    /// group order is preserved as in `load_conversations_preserve_order`.
+    pub(crate) fn reload_project_dirs(&mut self, project_dirs: &HashSet<PathBuf>) {
+        let in_dirs =
+            |conv: &Conversation| project_dirs.iter().any(|d| conv.file_path.starts_with(d));
+
+        let mut fresh: Vec<Conversation> = Vec::new();
+        for dir in project_dirs {
+            let sessions = parse_project_sessions(dir, &mut self.session_cache);
+            fresh.extend(self.sessions_to_conversations(sessions));
+        }
+        let _ = self.session_cache.save();
+
+        // Regroup the conversations of every group that had one in the dirs
+        let (affected, mut kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.groups)
+            .into_iter()
+            .partition(|g| g.conversations().iter().any(in_dirs));
+        fresh.extend(
+            affected
+                .into_iter()
+                .flat_map(|g| g.conversations().to_vec())
+                .filter(|c| !in_dirs(c)),
+        );
+        let mut regrouped = group_conversations_unordered(fresh);
+        retain_existing_groups(&mut regrouped);
+        for group in regrouped {
+            // A new conversation can land in a group that wasn't affected
+            match kept.iter_mut().find(|g| g.key() == group.key()) {
+                Some(existing) => {
+                    let conversations = existing.conversations_mut();
+                    conversations.extend(group.conversations().iter().cloned());
+                    conversations.sort_by_key(|c| std::cmp::Reverse(c.timestamp));
+                }
+                None => kept.push(group),
+            }
+        }
+
+        let (ordered_groups, updated_order) = order_groups_by_keys(kept, &self.group_order);
+        self.groups = ordered_groups;
+        self.group_order = updated_order;
+        self.plan_linker.link(&mut self.groups);
+        self.refresh_health();
+        self.log_stats.start_refresh(&self.groups);
+        self.refresh_subagents();
+    }
+
+    /// Discover sessions, reading only files that changed since the last
+    /// refresh, and persist what was learned.
+    fn parse_sessions(&mut self) -> Result<Vec<SessionEntry>> {
+        let sessions = parse_all_sessions_cached(&self.claude_dirs, &mut self.session_cache)?;
+        // A cache that can't be written only costs speed on the next start
+        let _ = self.session_cache.save();
+        Ok(sessions)
+    }
+
+    /// Reload the subagents of conversations expanded in the sidebar, dropping
+    /// conversations that are gone.
+    fn refresh_subagents(&mut self) {
+        if self.subagents.is_empty() {
+            return;
+        }
+        let expanded: Vec<String> = self.subagents.keys().cloned().collect();
+        self.subagents.clear();
+        for conv in self
+            .groups
+            .iter()
+            .flat_map(ConversationGroup::conversations)
+        {
+            if expanded.contains(&conv.session_id) {
+                let subagents = load_subagents(conv);
+                if !subagents.is_empty() {
+                    self.subagents.insert(conv.session_id.clone(), subagents);
+                }
+            }
+        }
+    }
+
+    /// Judge how each conversation's log ends, re-reading only changed tails.
+    pub(crate) fn refresh_health(&mut self) {
+        let running = self.running_session_ids();
+        self.health_tracker.refresh(&mut self.groups, &running);
+        self.health_checked_at = Instant::now();
+    }
+
+    /// Re-judge conversation health every minute, so turns that stopped
+    /// mid-way are flagged once their log has been quiet long enough, even
+    /// without a file change.
+    pub fn check_health_due(&mut self) {
+        if self.health_checked_at.elapsed() >= std::time::Duration::from_secs(60) {
+            self.refresh_health();
+        }
+    }
+
+    /// Apply a finished background refresh of the content index, re-running
+    /// an open search so new messages show up in its results.
+    pub fn check_index_refresh(&mut self) {
+        match self.search_engine.poll_index_refresh() {
+            Ok(true) if matches!(self.modal_state, ModalState::Search(_)) => self.perform_search(),
+            Ok(_) => {}
+            Err(e) => self.toast_warning(format!("Search index not saved: {}", e)),
+        }
+    }
+
+    /// Take token usage and tool stats read in the background: reprice,
+    /// raise alerts for newly crossed budgets, and update what shows them.
+    pub fn check_log_stats_refresh(&mut self) {
+        if !self.log_stats.poll_refresh() {
+            return;
+        }
+        let stats = self.log_stats.stats();
+        self.session_costs = stats.session_costs(&self.price_table);
+
+        let now = chrono::Utc::now().timestamp_millis();
+        self.usage_windows = UsageWindows::compute(stats.recent_events(), &self.price_table, now);
+        for alert in self
+            .budget_alerts
+            .check(&self.usage_windows, &self.config.budget, now)
+        {
+            self.toast_warning(alert);
+        }
+        self.show_log_stats();
+    }
+
     /// Convert SessionEntry list to Conversation list
     fn sessions_to_conversations(&self, sessions: Vec<SessionEntry>) -> Vec<Conversation> {
         sessions
             .into_iter()
             .map(|session| {
-                // Detect plan implementation conversations by checking first_prompt
-                let is_plan_implementation = session
-                    .first_prompt
-                    .starts_with("Implement the following plan:");
-
-                // Check archive status
-                let is_archived = self.archive_manager.is_archived(&session.session_id);
-                let archived_at = self.archive_manager.get_archived_at(&session.session_id);
-
-                Conversation {
-                    session_id: session.session_id,
-                    display: session.summary.clone().unwrap_or_else(|| {
-                        let stripped = session
-                            .first_prompt
-                            .strip_prefix("Implement the following plan:\n")
-                            .or_else(|| {
-                                session
-                                    .first_prompt
-                                    .strip_prefix("Implement the following plan:")
-                            })
-                            .unwrap_or(&session.first_prompt)
-                            .trim();
-                        stripped.strip_prefix("# ").unwrap_or(stripped).to_string()
-                    }),
-                    summary: session.summary,
-                    timestamp: session.file_mtime,
-                    modified: session.modified,
-                    project_path: PathBuf::from(&session.project_path),
-                    message_count: session.message_count,
-                    git_branch: session.git_branch,
-                    is_plan_implementation,
-                    is_archived,
-                    archived_at,
-                }
+                Conversation::from_session(
+                    session,
+                    &self.archive_manager,
+                    &self.metadata_manager,
+                    &self.config.plan_prefixes,
+                )
             })
             .collect()
     }
@@ -417,21 +666,45 @@ impl App {
             .map(|p| p.name.as_str())
     }
 
+    /// Parse the sidebar filter text, resolving `is:running` and `tool:` filters.
+    pub fn sidebar_filter(&self, running: &HashSet<String>) -> SearchQuery {
+        let mut query = SearchQuery::new(
+            self.sidebar_state.filter_query.as_str(),
+            SearchFilterType::All,
+        );
+        self.search_engine.resolve_query(&mut query, running);
+        query
+    }
+
+    /// Archive filter in effect: an `is:archived` filter query overrides the toggle.
+    pub fn effective_archive_filter(&self, filter: &SearchQuery) -> ArchiveFilter {
+        if filter.constrains_archived() {
+            ArchiveFilter::All
+        } else {
+            self.sidebar_state.archive_filter
+        }
+    }
+
     /// Get the flattened sidebar items for navigation
     pub fn sidebar_items(&self) -> Vec<SidebarItem> {
         let running = self.running_session_ids();
         let effective = self.effective_workspaces();
+        let filter = self.sidebar_filter(&running);
         let ctx = SidebarContext {
             groups: &self.groups,
             running_sessions: &running,
             ephemeral_sessions: &self.ephemeral_sessions,
             hide_inactive: self.sidebar_state.hide_inactive,
-            archive_filter: self.sidebar_state.archive_filter,
+            archive_filter: self.effective_archive_filter(&filter),
             filter_query: &self.sidebar_state.filter_query,
+            filter: &filter,
             filter_active: self.sidebar_state.filter_active,
             filter_cursor_pos: self.sidebar_state.filter_cursor_pos,
             workspaces: &effective,
             active_profile_name: self.active_profile_name(),
+            session_costs: &self.session_costs,
+            subagents: &self.subagents,
+            claude_dirs: &self.claude_dirs,
         };
         build_sidebar_items(
             &ctx,
@@ -513,9 +786,11 @@ impl App {
 
     /// Enter insert mode and focus the terminal pane
     pub fn enter_insert_mode(&mut self) {
+        // Typing goes to the live PTY, so drop any transcript preview covering it
+        self.transcript_view = None;
         self.input_mode = InputMode::Insert;
         self.escape_seq_state = EscapeSequenceState::None;
-        self.focus = Focus::Terminal(TerminalPaneId::Primary);
+        self.focus = Focus::Terminal(self.tiling.focused());
     }
 
     /// Exit insert mode, focus the sidebar, and sync sidebar cursor to active session
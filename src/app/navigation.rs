@@ -142,7 +142,7 @@ impl App {
                     return Some(ActiveSessionInfo::Conversation {
                         index: idx,
                         session_id: conv.session_id.clone(),
-                        conversation: conv.clone(),
+                        conversation: Box::new(conv.clone()),
                     });
                 }
             }
@@ -155,7 +155,8 @@ impl App {
     pub(crate) fn get_group_key_for_index(items: &[SidebarItem], index: usize) -> Option<String> {
         items.get(index).and_then(|item| match item {
             SidebarItem::GroupHeader { key, .. } => Some(key.clone()),
-            SidebarItem::Conversation { group_key, .. } => Some(group_key.clone()),
+            SidebarItem::Conversation { group_key, .. }
+            | SidebarItem::Subagent { group_key, .. } => Some(group_key.clone()),
             SidebarItem::EphemeralSession { group_key, .. } => Some(group_key.clone()),
             SidebarItem::SectionControl {
                 key,
@@ -164,6 +165,7 @@ impl App {
             } => Some(key.clone()),
             SidebarItem::SectionControl { .. }
             | SidebarItem::OtherHeader { .. }
+            | SidebarItem::PinnedHeader
             | SidebarItem::WorkspaceSectionHeader
             | SidebarItem::AddWorkspace
             | SidebarItem::ProjectHeader { .. } => None,
@@ -316,7 +318,7 @@ impl App {
                     // Switch to the already-running ephemeral session
                     self.active_session_id = Some(session_id);
                     self.selected_conversation = None;
-                    self.focus = Focus::Terminal(TerminalPaneId::Primary);
+                    self.focus = Focus::Terminal(self.tiling.focused());
                     self.enter_insert_mode();
                 }
                 ActiveSessionInfo::Conversation {
@@ -326,7 +328,7 @@ impl App {
                 } => {
                     // Select the conversation in sidebar
                     self.sidebar_state.list_state.select(Some(index));
-                    self.selected_conversation = Some(conversation.clone());
+                    self.selected_conversation = Some(*conversation.clone());
 
                     // Check if we already have a daemon session for this conversation
                     let existing_session = self
@@ -342,7 +344,7 @@ impl App {
                         // Start new session with --resume
                         self.start_session(&conversation.project_path, Some(&claude_session_id))?;
                     }
-                    self.focus = Focus::Terminal(TerminalPaneId::Primary);
+                    self.focus = Focus::Terminal(self.tiling.focused());
                     self.enter_insert_mode();
                 }
             }
@@ -419,6 +421,7 @@ impl App {
                     self.sidebar_state.toggle_group(key);
                 }
                 SidebarItem::Conversation { group_key, .. }
+                | SidebarItem::Subagent { group_key, .. }
                 | SidebarItem::EphemeralSession { group_key, .. } => {
                     self.sidebar_state.toggle_group(group_key);
                 }
@@ -451,9 +454,113 @@ impl App {
                 SidebarItem::ProjectHeader { project_key, .. } => {
                     self.sidebar_state.toggle_project(project_key);
                 }
-                SidebarItem::WorkspaceSectionHeader | SidebarItem::AddWorkspace => {}
+                SidebarItem::PinnedHeader
+                | SidebarItem::WorkspaceSectionHeader
+                | SidebarItem::AddWorkspace => {}
+            }
+        }
+    }
+
+    /// Run a reload, keeping the same sidebar row selected (at the same height
+    /// on screen) even when rows above it come or go.
+    pub(crate) fn with_sidebar_selection_preserved<T>(
+        &mut self,
+        reload: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let items = self.sidebar_items();
+        let index = self.sidebar_state.list_state.selected().unwrap_or(0);
+        let row = index.saturating_sub(self.sidebar_state.list_state.offset());
+        let item = items.get(index).cloned();
+        // Conversation rows are positional, so remember which session it was
+        let session = item.as_ref().and_then(|item| match item {
+            SidebarItem::Conversation { group_key, index } => self
+                .conversation_at(group_key, *index)
+                .map(|c| (c.session_id.clone(), None)),
+            SidebarItem::Subagent {
+                group_key,
+                index,
+                child,
+            } => self
+                .conversation_at(group_key, *index)
+                .map(|c| (c.session_id.clone(), Some(*child))),
+            _ => None,
+        });
+
+        let result = reload(self);
+
+        let items = self.sidebar_items();
+        let found = items
+            .iter()
+            .position(|candidate| match (candidate, &session) {
+                (SidebarItem::Conversation { group_key, index }, Some((session_id, None))) => self
+                    .conversation_at(group_key, *index)
+                    .is_some_and(|c| &c.session_id == session_id),
+                (
+                    SidebarItem::Subagent {
+                        group_key,
+                        index,
+                        child,
+                    },
+                    Some((session_id, Some(old_child))),
+                ) => {
+                    child == old_child
+                        && self
+                            .conversation_at(group_key, *index)
+                            .is_some_and(|c| &c.session_id == session_id)
+                }
+                (_, None) => item.as_ref() == Some(candidate),
+                _ => false,
+            });
+        let fallback = items.len().checked_sub(1).map(|last| index.min(last));
+        if let Some(new_index) = found.or(fallback) {
+            self.sidebar_state.list_state.select(Some(new_index));
+            *self.sidebar_state.list_state.offset_mut() = new_index.saturating_sub(row);
+        }
+        result
+    }
+
+    /// The conversation at `index` in the group with `group_key`.
+    pub(crate) fn conversation_at(&self, group_key: &str, index: usize) -> Option<&Conversation> {
+        self.groups
+            .iter()
+            .find(|g| g.key() == group_key)
+            .and_then(|g| g.conversations().get(index))
+    }
+
+    /// Expand or collapse the subagents listed under the selected conversation.
+    pub fn toggle_selected_subagents(&mut self) {
+        let items = self.sidebar_items();
+        let selected = self.sidebar_state.list_state.selected().unwrap_or(0);
+        let (group_key, index) = match items.get(selected) {
+            Some(
+                SidebarItem::Conversation { group_key, index }
+                | SidebarItem::Subagent {
+                    group_key, index, ..
+                },
+            ) => (group_key.clone(), *index),
+            _ => return,
+        };
+        let Some(conv) = self.conversation_at(&group_key, index).cloned() else {
+            return;
+        };
+
+        if self.subagents.remove(&conv.session_id).is_some() {
+            // Collapsing from a subagent row moves the selection to its parent
+            let parent = self.sidebar_items().iter().position(|item| {
+                matches!(item, SidebarItem::Conversation { group_key: gk, index: i } if *gk == group_key && *i == index)
+            });
+            if let Some(parent) = parent {
+                self.sidebar_state.list_state.select(Some(parent));
             }
+            return;
         }
+
+        let subagents = load_subagents(&conv);
+        if subagents.is_empty() {
+            self.toast_info("No subagents");
+            return;
+        }
+        self.subagents.insert(conv.session_id, subagents);
     }
 
     pub fn update_selected_conversation(&mut self) {
@@ -462,7 +569,11 @@ impl App {
 
         if let Some(item) = items.get(selected) {
             match item {
-                SidebarItem::Conversation { group_key, index } => {
+                // A subagent row selects its parent conversation
+                SidebarItem::Conversation { group_key, index }
+                | SidebarItem::Subagent {
+                    group_key, index, ..
+                } => {
                     // Find the conversation
                     for group in &self.groups {
                         if &group.key() == group_key {
@@ -478,6 +589,7 @@ impl App {
                 | SidebarItem::SectionControl { .. }
                 | SidebarItem::OtherHeader { .. }
                 | SidebarItem::ProjectHeader { .. }
+                | SidebarItem::PinnedHeader
                 | SidebarItem::WorkspaceSectionHeader
                 | SidebarItem::AddWorkspace => {
                     // No conversation selected for headers, ephemeral sessions, or section controls
@@ -562,6 +674,29 @@ impl App {
         self.update_selected_conversation();
     }
 
+    /// Filter the sidebar to conversations that need attention (`is:attention`),
+    /// or clear that filter when it is already set
+    pub fn toggle_attention_filter(&mut self) {
+        const ATTENTION_FILTER: &str = "is:attention";
+        if self.sidebar_state.filter_query == ATTENTION_FILTER {
+            self.clear_sidebar_filter();
+            return;
+        }
+        self.sidebar_state.set_filter(ATTENTION_FILTER);
+        self.sidebar_state.list_state.select(Some(1));
+        self.update_selected_conversation();
+
+        let count = self
+            .groups
+            .iter()
+            .flat_map(ConversationGroup::conversations)
+            .filter(|c| c.health.is_some() && !c.is_archived)
+            .count();
+        if count == 0 {
+            self.toast_info("No conversations need attention");
+        }
+    }
+
     /// Whether the sidebar filter input is currently active (accepting keystrokes)
     pub fn is_sidebar_filter_active(&self) -> bool {
         self.sidebar_state.filter_active
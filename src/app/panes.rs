@@ -1,17 +1,337 @@
-//! Split-pane management on App.
+//! Tiling pane management on App.
+
+use crate::config::NamedLayout;
+use crate::ui::tiling::{Direction, SplitDirection};
 
 use super::*;
 
 impl App {
-    /// Update the pane state caches from the session manager.
+    /// Update the state caches of unfocused panes from the session manager.
+    ///
+    /// Panes whose session has ended are emptied.
     pub fn update_pane_state_caches(&mut self) {
-        for pane_id in [TerminalPaneId::Primary, TerminalPaneId::Secondary] {
-            let idx = pane_id.index();
-            if let Some(ref session_id) = self.panes[idx].session_id {
-                self.pane_state_caches[idx] = self.session_manager.get_session_state(session_id);
-            } else {
-                self.pane_state_caches[idx] = None;
+        self.pane_state_caches.clear();
+        let focused = self.tiling.focused();
+        for pane_id in self.tiling.pane_ids() {
+            if pane_id == focused {
+                continue;
+            }
+            let Some(session_id) = self.pane_session_id(pane_id) else {
+                continue;
+            };
+            match self.session_manager.get_session_state(&session_id) {
+                Some(state) => {
+                    self.pane_state_caches.insert(pane_id, state);
+                }
+                None => {
+                    self.panes.entry(pane_id).or_default().session_id = None;
+                }
             }
         }
     }
+
+    /// Session ID assigned to a pane, if any.
+    pub fn pane_session_id(&self, pane_id: PaneId) -> Option<String> {
+        self.panes.get(&pane_id).and_then(|p| p.session_id.clone())
+    }
+
+    /// Whether the detail panel takes over the whole terminal area: a single
+    /// pane with nothing running or previewed in it.
+    pub fn detail_panel_fills_terminal_area(&self) -> bool {
+        self.split_mode != SplitMode::Mosaic
+            && self.tiling.pane_count() == 1
+            && self.transcript_view.is_none()
+            && self.get_session_state().is_none()
+    }
+
+    /// Find the pane currently showing a session.
+    fn pane_showing(&self, session_id: &str) -> Option<PaneId> {
+        self.tiling
+            .pane_ids()
+            .into_iter()
+            .find(|id| self.pane_session_id(*id).as_deref() == Some(session_id))
+    }
+
+    /// Write `active_session_id` into the focused pane.
+    ///
+    /// If the active session is already shown in another pane, focus moves to
+    /// that pane instead of showing the same PTY twice.
+    pub(crate) fn sync_focused_pane(&mut self) {
+        let focused = self.tiling.focused();
+        if let Some(ref session_id) = self.active_session_id {
+            if let Some(other) = self.pane_showing(session_id).filter(|id| *id != focused) {
+                self.tiling.focus(other);
+                if matches!(self.focus, Focus::Terminal(_)) {
+                    self.focus = Focus::Terminal(other);
+                }
+                return;
+            }
+        }
+        self.panes.entry(focused).or_default().session_id = self.active_session_id.clone();
+    }
+
+    /// Focus a pane and make its session the active one.
+    pub fn focus_pane(&mut self, pane_id: PaneId) {
+        self.sync_focused_pane();
+        if pane_id == self.tiling.focused() || !self.tiling.focus(pane_id) {
+            return;
+        }
+        self.active_session_id = self.pane_session_id(pane_id);
+        self.clear_preview();
+        self.text_selection = None;
+        if matches!(self.focus, Focus::Terminal(_)) {
+            self.focus = Focus::Terminal(pane_id);
+        }
+        self.select_sidebar_for_active_session();
+    }
+
+    /// Split the focused pane. The new, empty pane receives focus.
+    pub fn split_pane(&mut self, direction: SplitDirection) {
+        if self.split_mode == SplitMode::Mosaic {
+            self.toast_warning("Exit mosaic view to split panes");
+            return;
+        }
+        self.sync_focused_pane();
+        let Some(new_id) = self.tiling.split_focused(direction) else {
+            return;
+        };
+        self.pane_zoomed = false;
+        self.panes.insert(new_id, PaneConfig::default());
+        self.active_session_id = None;
+        self.clear_preview();
+        if matches!(self.focus, Focus::Terminal(_)) {
+            self.focus = Focus::Terminal(new_id);
+        }
+        self.persist_pane_layout();
+        self.toast_info(format!("{} panes", self.tiling.pane_count()));
+    }
+
+    /// Close the focused pane. Its session keeps running in the background.
+    pub fn close_pane(&mut self) {
+        self.sync_focused_pane();
+        self.pane_zoomed = false;
+        let Some(closed) = self.tiling.close_focused() else {
+            self.toast_warning("Cannot close the last pane");
+            return;
+        };
+        self.panes.remove(&closed);
+        self.pane_state_caches.remove(&closed);
+        let focused = self.tiling.focused();
+        self.active_session_id = self.pane_session_id(focused);
+        self.clear_preview();
+        self.text_selection = None;
+        if matches!(self.focus, Focus::Terminal(_)) {
+            self.focus = Focus::Terminal(focused);
+        }
+        self.persist_pane_layout();
+        self.toast_info(format!("{} panes", self.tiling.pane_count()));
+    }
+
+    /// Move focus to the neighboring pane in `direction`.
+    pub fn focus_pane_direction(&mut self, direction: Direction) {
+        if let Some(target) = self.tiling.neighbor(direction) {
+            self.pane_zoomed = false;
+            self.focus_pane(target);
+        }
+    }
+
+    /// Swap the focused pane with its neighbor in `direction`.
+    pub fn swap_pane(&mut self, direction: Direction) {
+        self.sync_focused_pane();
+        self.pane_zoomed = false;
+        if self.tiling.swap_direction(direction).is_some() {
+            self.persist_pane_layout();
+        } else {
+            self.toast_warning("No pane in that direction");
+        }
+    }
+
+    /// Move the split boundary next to the focused pane (keyboard resize).
+    pub fn resize_pane(&mut self, direction: Direction) {
+        const STEP: u16 = 5;
+
+        if self.split_mode == SplitMode::Mosaic || self.pane_zoomed {
+            return;
+        }
+        match self.tiling.resize_focused(direction, STEP) {
+            Some(ratio) => {
+                self.persist_pane_layout();
+                self.toast_info(format!("Split: {}/{}", ratio, 100 - ratio));
+            }
+            None => self.toast_warning("No split in that direction"),
+        }
+    }
+
+    /// Start dragging a split boundary if the click lands on one.
+    /// Returns true if a drag started.
+    pub fn begin_split_drag(&mut self, col: u16, row: u16) -> bool {
+        if self.split_mode == SplitMode::Mosaic || self.pane_zoomed {
+            return false;
+        }
+        let Some(area) = self.tiling_area else {
+            return false;
+        };
+        self.split_drag = self.tiling.handle_at(area, col, row);
+        self.split_drag.is_some()
+    }
+
+    /// Move the dragged split boundary to the mouse position.
+    pub fn update_split_drag(&mut self, col: u16, row: u16) {
+        if let Some(ref handle) = self.split_drag {
+            let ratio = handle.ratio_at(col, row);
+            let path = handle.path.clone();
+            self.tiling.set_ratio(&path, ratio);
+        }
+    }
+
+    /// Finish a split drag and persist the new ratio.
+    pub fn end_split_drag(&mut self) {
+        if self.split_drag.take().is_some() {
+            self.persist_pane_layout();
+        }
+    }
+
+    /// Toggle zoom: maximize the focused pane (or selected mosaic tile) and
+    /// restore the layout on the next toggle.
+    pub fn toggle_zoom(&mut self) {
+        let zoomable = match self.split_mode {
+            SplitMode::Mosaic => self.mosaic_state_cache.len() > 1,
+            SplitMode::Tiled => self.tiling.pane_count() > 1,
+        };
+        if !self.pane_zoomed && !zoomable {
+            self.toast_info("Nothing to zoom");
+            return;
+        }
+        self.pane_zoomed = !self.pane_zoomed;
+        self.text_selection = None;
+        if self.pane_zoomed {
+            self.toast_info("Zoomed");
+        } else {
+            self.toast_info("Layout restored");
+        }
+    }
+
+    /// Save the current tiling tree (with split ratios) to `LayoutConfig`.
+    fn persist_pane_layout(&mut self) {
+        self.config.layout.panes =
+            (self.tiling.pane_count() > 1).then(|| self.tiling.root().clone());
+        self.save_config_silent();
+    }
+
+    /// Open the named layout modal.
+    pub fn open_layout_modal(&mut self) {
+        let names = self.config.layouts.iter().map(|l| l.name.clone()).collect();
+        self.modal_state = ModalState::Layout(Box::new(LayoutModalState::new(names)));
+        self.input_mode = InputMode::Insert;
+    }
+
+    /// Save the current split tree under `name`, replacing a layout with the same name.
+    pub fn save_layout(&mut self, name: &str) {
+        let root = self.tiling.root().clone();
+        if let Some(existing) = self
+            .config
+            .layouts
+            .iter_mut()
+            .find(|l| l.name.eq_ignore_ascii_case(name))
+        {
+            existing.root = root;
+        } else {
+            self.config.layouts.push(NamedLayout {
+                name: name.to_string(),
+                root,
+            });
+        }
+        self.save_config_silent();
+
+        if let ModalState::Layout(ref mut state) = self.modal_state {
+            state.set_layouts(self.config.layouts.iter().map(|l| l.name.clone()).collect());
+        }
+        self.toast_success(format!("Layout saved: {}", name));
+    }
+
+    /// Replace the current tiling with a saved layout.
+    ///
+    /// Sessions from the current panes are carried over in reading order;
+    /// any that don't fit keep running in the background.
+    pub fn apply_layout(&mut self, index: usize) {
+        let Some(layout) = self.config.layouts.get(index).cloned() else {
+            return;
+        };
+        self.close_modal();
+        self.sync_focused_pane();
+
+        let sessions: Vec<String> = self
+            .tiling
+            .pane_ids()
+            .into_iter()
+            .filter_map(|id| self.pane_session_id(id))
+            .collect();
+
+        self.tiling = TilingLayout::from_root(layout.root);
+        self.pane_zoomed = false;
+        self.panes.clear();
+        self.pane_state_caches.clear();
+        let mut sessions = sessions.into_iter();
+        for pane_id in self.tiling.pane_ids() {
+            self.panes.insert(
+                pane_id,
+                PaneConfig {
+                    session_id: sessions.next(),
+                },
+            );
+        }
+
+        let focused = self.tiling.focused();
+        self.active_session_id = self.pane_session_id(focused);
+        self.clear_preview();
+        self.text_selection = None;
+        if self.split_mode == SplitMode::Mosaic {
+            self.split_mode = SplitMode::Tiled;
+            self.mosaic_state_cache.clear();
+            self.focus = Focus::Sidebar;
+        } else if matches!(self.focus, Focus::Terminal(_)) {
+            self.focus = Focus::Terminal(focused);
+        }
+        self.persist_pane_layout();
+        self.toast_success(format!("Layout: {}", layout.name));
+    }
+
+    /// Delete a saved layout by index.
+    pub fn delete_layout(&mut self, index: usize) {
+        if index >= self.config.layouts.len() {
+            return;
+        }
+        let removed = self.config.layouts.remove(index).name;
+        self.save_config_silent();
+
+        if let ModalState::Layout(ref mut state) = self.modal_state {
+            state.set_layouts(self.config.layouts.iter().map(|l| l.name.clone()).collect());
+        }
+        self.toast_success(format!("Layout deleted: {}", removed));
+    }
+
+    /// Resize the PTYs shown in panes to match their rendered size.
+    pub fn fit_sessions_to_panes(&mut self) {
+        let focused = self.tiling.focused();
+        let assignments: Vec<(String, Rect)> = self
+            .pane_areas
+            .iter()
+            .filter_map(|(pane_id, rect)| {
+                let session_id = if *pane_id == focused {
+                    self.display_session_id()
+                } else {
+                    self.pane_session_id(*pane_id)
+                }?;
+                Some((session_id, *rect))
+            })
+            .collect();
+
+        for (session_id, rect) in assignments {
+            self.session_manager.resize_session(
+                &session_id,
+                rect.height.saturating_sub(2),
+                rect.width.saturating_sub(2),
+            );
+        }
+    }
 }
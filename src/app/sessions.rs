@@ -1,11 +1,15 @@
 //! Session lifecycle methods on App.
 
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use anyhow::Result;
 
+use crate::claude::data_dir::source_of;
+use crate::claude::transcript::parse_transcript;
+use crate::session::SpawnOptions;
+
 use super::*;
 
 impl App {
@@ -23,47 +27,29 @@ impl App {
         match items.get(selected) {
             Some(SidebarItem::Conversation { group_key, index }) => {
                 // Resume existing conversation with session_id
-                let mut target: Option<(PathBuf, String, Conversation)> = None;
+                let mut target: Option<Conversation> = None;
                 for group in &self.groups {
                     if &group.key() == group_key {
                         if let Some(conv) = group.conversations().get(*index) {
-                            target = Some((
-                                conv.project_path.clone(),
-                                conv.session_id.clone(),
-                                conv.clone(),
-                            ));
+                            target = Some(conv.clone());
                             break;
                         }
                     }
                 }
 
-                if let Some((path, claude_session_id, conv)) = target {
-                    // Check if the working directory still exists
-                    if !path.exists() {
-                        // Directory was deleted (e.g., git worktree removed)
-                        // Just select the conversation but don't start a session
-                        self.selected_conversation = Some(conv);
-                        return Ok(());
-                    }
-
-                    // Check if we already have a daemon session for this Claude session
-                    let existing_session = self
-                        .session_to_claude_id
-                        .iter()
-                        .find(|(_, v)| v.as_deref() == Some(claude_session_id.as_str()))
-                        .map(|(k, _)| k.clone());
-
-                    if let Some(session_id) = existing_session {
-                        self.active_session_id = Some(session_id);
-                    } else {
-                        // Start new session with --resume
-                        self.start_session(&path, Some(&claude_session_id))?;
-                    }
-                    self.selected_conversation = Some(conv);
-                    self.focus = Focus::Terminal(TerminalPaneId::Primary);
-                    self.enter_insert_mode();
+                if let Some(conv) = target {
+                    self.resume_conversation(conv)?;
                 }
             }
+            Some(SidebarItem::Subagent {
+                group_key,
+                index,
+                child,
+            }) => {
+                // Subagents can't be resumed; show their transcript instead
+                self.open_sidebar_subagent(group_key, *index, *child);
+                self.focus_transcript();
+            }
             Some(SidebarItem::GroupHeader { key, .. }) => {
                 // Start new conversation in the group's project directory
                 let mut project_path: Option<PathBuf> = None;
@@ -77,7 +63,7 @@ impl App {
                 if let Some(path) = project_path {
                     self.start_session(&path, None)?;
                     self.selected_conversation = None;
-                    self.focus = Focus::Terminal(TerminalPaneId::Primary);
+                    self.focus = Focus::Terminal(self.tiling.focused());
                     self.enter_insert_mode();
                 }
             }
@@ -85,7 +71,7 @@ impl App {
                 // Switch to the already-running ephemeral session
                 self.active_session_id = Some(session_id.clone());
                 self.selected_conversation = None;
-                self.focus = Focus::Terminal(TerminalPaneId::Primary);
+                self.focus = Focus::Terminal(self.tiling.focused());
                 self.enter_insert_mode();
             }
             Some(SidebarItem::SectionControl { key, kind, action }) => {
@@ -120,12 +106,45 @@ impl App {
             Some(SidebarItem::AddWorkspace) => {
                 self.open_workspace_modal();
             }
-            Some(SidebarItem::WorkspaceSectionHeader) | None => {}
+            Some(SidebarItem::PinnedHeader | SidebarItem::WorkspaceSectionHeader) | None => {}
         }
 
         Ok(())
     }
 
+    /// Resume a conversation in the focused pane, switching to its session if
+    /// it is already running.
+    pub(crate) fn resume_conversation(&mut self, conv: Conversation) -> Result<()> {
+        let path = conv.project_path.clone();
+        // Check if the working directory still exists
+        if !path.exists() {
+            // Directory was deleted (e.g., git worktree removed)
+            // Just select the conversation but don't start a session
+            self.selected_conversation = Some(conv);
+            return Ok(());
+        }
+
+        // Check if we already have a daemon session for this Claude session
+        if let Some(session_id) = self.running_session_for(&conv.session_id) {
+            self.active_session_id = Some(session_id);
+        } else {
+            // Start new session with --resume
+            self.start_session(&path, Some(&conv.session_id))?;
+        }
+        self.selected_conversation = Some(conv);
+        self.focus = Focus::Terminal(self.tiling.focused());
+        self.enter_insert_mode();
+        Ok(())
+    }
+
+    /// Daemon session ID running the given Claude session, if any.
+    fn running_session_for(&self, claude_session_id: &str) -> Option<String> {
+        self.session_to_claude_id
+            .iter()
+            .find(|(_, v)| v.as_deref() == Some(claude_session_id))
+            .map(|(k, _)| k.clone())
+    }
+
     /// Create a new conversation in whichever group the selected sidebar item belongs to.
     ///
     /// Unlike `open_selected()`, this always starts a fresh conversation regardless
@@ -138,7 +157,10 @@ impl App {
         // Extract the group key from whatever item is selected
         let group_key = match items.get(selected) {
             Some(SidebarItem::GroupHeader { key, .. }) => Some(key.clone()),
-            Some(SidebarItem::Conversation { group_key, .. }) => Some(group_key.clone()),
+            Some(
+                SidebarItem::Conversation { group_key, .. }
+                | SidebarItem::Subagent { group_key, .. },
+            ) => Some(group_key.clone()),
             Some(SidebarItem::EphemeralSession { group_key, .. }) => Some(group_key.clone()),
             Some(SidebarItem::SectionControl {
                 key,
@@ -182,7 +204,7 @@ impl App {
                     }
                 }
 
-                self.focus = Focus::Terminal(TerminalPaneId::Primary);
+                self.focus = Focus::Terminal(self.tiling.focused());
                 self.enter_insert_mode();
             }
         }
@@ -192,61 +214,44 @@ impl App {
 
     /// Preview the selected sidebar item in the terminal pane without leaving the sidebar.
     ///
-    /// Shows the session output in the terminal pane but keeps focus on the sidebar
-    /// and stays in normal mode. Pressing `p` again on the same conversation is a
-    /// no-op; use `clear_preview()` (Escape) to exit preview.
+    /// Running conversations and ephemeral sessions show their live PTY output.
+    /// Other conversations are shown as a read-only transcript parsed from the
+    /// session JSONL, so nothing is spawned. Focus stays on the sidebar; use
+    /// `clear_preview()` (Escape) to exit preview.
     pub fn preview_selected(&mut self) -> Result<()> {
         let items = self.sidebar_items();
         let selected = self.sidebar_state.list_state.selected().unwrap_or(0);
 
         match items.get(selected) {
             Some(SidebarItem::Conversation { group_key, index }) => {
-                let mut target: Option<(PathBuf, String)> = None;
+                let mut target: Option<Conversation> = None;
                 for group in &self.groups {
                     if &group.key() == group_key {
                         if let Some(conv) = group.conversations().get(*index) {
-                            target = Some((conv.project_path.clone(), conv.session_id.clone()));
+                            target = Some(conv.clone());
                             break;
                         }
                     }
                 }
 
-                if let Some((path, claude_session_id)) = target {
-                    // Toggle: if already previewing this session, clear preview
-                    let existing_session = self
-                        .session_to_claude_id
-                        .iter()
-                        .find(|(_, v)| v.as_deref() == Some(claude_session_id.as_str()))
-                        .map(|(k, _)| k.clone());
-
-                    if let Some(ref sid) = existing_session {
-                        if self.preview_session_id.as_ref() == Some(sid) {
-                            return Ok(());
-                        }
-                    }
-
-                    // Check if directory still exists
-                    if !path.exists() {
-                        return Ok(());
-                    }
-
-                    if let Some(session_id) = existing_session {
+                if let Some(conv) = target {
+                    if let Some(session_id) = self.running_session_for(&conv.session_id) {
+                        self.transcript_view = None;
                         self.preview_session_id = Some(session_id);
                     } else {
-                        // Need to spawn a session — save/restore active_session_id
-                        let prev_active = self.active_session_id.clone();
-                        self.start_session(&path, Some(&claude_session_id))?;
-                        let new_session_id = self.active_session_id.clone();
-                        self.active_session_id = prev_active;
-                        self.preview_session_id = new_session_id;
+                        self.open_transcript(conv, None);
                     }
                 }
             }
+            Some(SidebarItem::Subagent {
+                group_key,
+                index,
+                child,
+            }) => {
+                self.open_sidebar_subagent(group_key, *index, *child);
+            }
             Some(SidebarItem::EphemeralSession { session_id, .. }) => {
-                // Already previewing this session — no-op
-                if self.preview_session_id.as_ref() == Some(session_id) {
-                    return Ok(());
-                }
+                self.transcript_view = None;
                 self.preview_session_id = Some(session_id.clone());
             }
             _ => {}
@@ -255,9 +260,132 @@ impl App {
         Ok(())
     }
 
-    /// Clear the preview session
+    /// Clear the preview session and transcript view
     pub fn clear_preview(&mut self) {
         self.preview_session_id = None;
+        self.transcript_view = None;
+        if self.focus == Focus::Transcript {
+            self.focus = Focus::Sidebar;
+        }
+    }
+
+    /// Show a conversation's transcript in the focused pane, optionally
+    /// positioned at a message index.
+    ///
+    /// Re-opening the conversation already shown keeps its fold and scroll state.
+    pub fn open_transcript(&mut self, conv: Conversation, message: Option<usize>) {
+        self.preview_session_id = None;
+        let already_open = self
+            .transcript_view
+            .as_ref()
+            .is_some_and(|v| v.conversation.session_id == conv.session_id);
+
+        if !already_open {
+            match parse_transcript(&conv.file_path) {
+                Ok(transcript) => {
+                    let subagents = load_subagents(&conv);
+                    self.transcript_view =
+                        Some(TranscriptViewState::new(conv, transcript).with_subagents(subagents));
+                }
+                Err(e) => {
+                    self.transcript_view = None;
+                    self.toast_error(format!("Failed to read transcript: {}", e));
+                    return;
+                }
+            }
+        }
+        if let (Some(view), Some(message)) = (self.transcript_view.as_mut(), message) {
+            view.jump_to_message(message);
+        }
+    }
+
+    /// Show the transcript of a subagent of the conversation in the transcript
+    /// view. Leaving it returns to the parent's transcript.
+    pub fn open_subagent_transcript(&mut self, agent_id: &str) {
+        let Some(view) = self.transcript_view.take() else {
+            return;
+        };
+        let Some(subagent) = view
+            .subagents()
+            .iter()
+            .find(|s| s.agent_id == agent_id)
+            .cloned()
+        else {
+            self.transcript_view = Some(view);
+            return;
+        };
+
+        match parse_transcript(&subagent.file_path) {
+            Ok(transcript) => {
+                let conv = subagent.conversation(&view.conversation);
+                self.transcript_view =
+                    Some(TranscriptViewState::new(conv, transcript).with_parent(view));
+            }
+            Err(e) => {
+                self.transcript_view = Some(view);
+                self.toast_error(format!("Failed to read subagent transcript: {}", e));
+            }
+        }
+    }
+
+    /// Show a subagent listed in the sidebar, on top of its parent's transcript.
+    fn open_sidebar_subagent(&mut self, group_key: &str, index: usize, child: usize) {
+        let Some(conv) = self.conversation_at(group_key, index).cloned() else {
+            return;
+        };
+        let Some(agent_id) = self
+            .subagents
+            .get(&conv.session_id)
+            .and_then(|subagents| subagents.get(child))
+            .map(|s| s.agent_id.clone())
+        else {
+            return;
+        };
+        // Start from the parent's own transcript rather than a subagent view
+        while let Some(parent) = self
+            .transcript_view
+            .as_mut()
+            .and_then(TranscriptViewState::take_parent)
+        {
+            self.transcript_view = Some(parent);
+        }
+        self.open_transcript(conv, None);
+        self.open_subagent_transcript(&agent_id);
+    }
+
+    /// Leave the transcript view: back to the parent transcript when showing a
+    /// subagent, otherwise to the sidebar.
+    pub fn transcript_back(&mut self) {
+        let parent = self
+            .transcript_view
+            .as_mut()
+            .and_then(TranscriptViewState::take_parent);
+        match parent {
+            Some(parent) => self.transcript_view = Some(parent),
+            None => self.focus = Focus::Sidebar,
+        }
+    }
+
+    /// Move focus into the transcript view (if one is shown).
+    pub fn focus_transcript(&mut self) {
+        if self.transcript_view.is_some() {
+            self.focus = Focus::Transcript;
+            self.input_mode = InputMode::Normal;
+        }
+    }
+
+    /// Resume the conversation shown in the transcript view (the parent
+    /// conversation when showing a subagent).
+    pub fn resume_transcript(&mut self) -> Result<()> {
+        let Some(conv) = self
+            .transcript_view
+            .as_ref()
+            .map(|v| v.root_conversation().clone())
+        else {
+            return Ok(());
+        };
+        self.clear_preview();
+        self.resume_conversation(conv)
     }
 
     /// Start a new session (or resume one) in the given directory.
@@ -268,17 +396,49 @@ impl App {
         &mut self,
         working_dir: &std::path::Path,
         claude_session_id: Option<&str>,
+    ) -> Result<()> {
+        self.launch_session(working_dir, claude_session_id, None)
+    }
+
+    /// Start a session that forks a conversation at an earlier assistant
+    /// message. Claude gives the fork a new session ID, so until its file
+    /// appears it is tracked like a new conversation.
+    pub(crate) fn fork_session(
+        &mut self,
+        working_dir: &std::path::Path,
+        claude_session_id: &str,
+        message_uuid: &str,
+    ) -> Result<()> {
+        self.launch_session(working_dir, Some(claude_session_id), Some(message_uuid))
+    }
+
+    fn launch_session(
+        &mut self,
+        working_dir: &std::path::Path,
+        claude_session_id: Option<&str>,
+        fork_at: Option<&str>,
     ) -> Result<()> {
         let (rows, cols) = self.calculate_terminal_dimensions();
+        let config_dir = self
+            .claude_dir_of(claude_session_id)
+            .config_dir_env()
+            .map(Path::to_path_buf);
 
         let result = self.session_manager.create_session(
             working_dir,
-            claude_session_id,
             rows,
             cols,
-            self.dangerous_mode,
+            SpawnOptions {
+                config_dir: config_dir.as_deref(),
+                claude_session_id,
+                fork_at,
+                dangerous_mode: self.dangerous_mode,
+            },
         );
 
+        // A fork writes to a conversation file that doesn't exist yet
+        let claude_session_id = claude_session_id.filter(|_| fork_at.is_none());
+
         match result {
             Ok(session_id) => {
                 // Track the mapping from session ID to Claude session
@@ -286,6 +446,9 @@ impl App {
                     session_id.clone(),
                     claude_session_id.map(ToString::to_string),
                 );
+                if claude_session_id.is_some() {
+                    self.refresh_health();
+                }
 
                 // Track ephemeral sessions (new sessions without a saved conversation file)
                 if claude_session_id.is_none() {
@@ -310,6 +473,20 @@ impl App {
         }
     }
 
+    /// The data directory a conversation is stored in, or the primary one
+    /// for new sessions.
+    fn claude_dir_of(&self, claude_session_id: Option<&str>) -> &ClaudeDir {
+        claude_session_id
+            .and_then(|sid| {
+                self.groups
+                    .iter()
+                    .flat_map(ConversationGroup::conversations)
+                    .find(|c| c.session_id == sid)
+            })
+            .and_then(|conv| source_of(&self.claude_dirs, &conv.file_path))
+            .unwrap_or(&self.claude_dirs[0])
+    }
+
     /// Get the cached session state for rendering
     pub fn get_session_state(&self) -> Option<&SessionState> {
         self.session_state_cache.as_ref()
@@ -326,8 +503,8 @@ impl App {
     pub fn running_session_ids(&self) -> HashSet<String> {
         // Return Claude session IDs for sessions that are running
         self.session_to_claude_id
-            .iter()
-            .filter_map(|(_, claude_id)| claude_id.clone())
+            .values()
+            .filter_map(Clone::clone)
             .collect()
     }
 
@@ -336,6 +513,8 @@ impl App {
     /// Prefers preview_session_id when set (preview mode shows the previewed session),
     /// otherwise falls back to active_session_id.
     pub fn update_session_state(&mut self) {
+        self.sync_focused_pane();
+
         let display_session = self
             .preview_session_id
             .as_ref()
@@ -360,7 +539,7 @@ impl App {
 
     /// Get the session ID currently displayed (preview takes priority over active).
     /// Mirrors the logic in `update_session_state`.
-    fn display_session_id(&self) -> Option<String> {
+    pub(crate) fn display_session_id(&self) -> Option<String> {
         self.preview_session_id
             .clone()
             .or(self.active_session_id.clone())
@@ -369,6 +548,10 @@ impl App {
     /// Scroll up by the specified number of lines in the displayed session
     pub fn scroll_up(&mut self, lines: usize) {
         self.text_selection = None;
+        if let Some(ref mut view) = self.transcript_view {
+            view.scroll_by(-(lines as isize));
+            return;
+        }
         if let Some(ref session_id) = self.display_session_id() {
             if let Some(session) = self.session_manager.get_session_mut(session_id) {
                 session.scroll_up(lines);
@@ -379,6 +562,10 @@ impl App {
     /// Scroll down by the specified number of lines in the displayed session
     pub fn scroll_down(&mut self, lines: usize) {
         self.text_selection = None;
+        if let Some(ref mut view) = self.transcript_view {
+            view.scroll_by(lines as isize);
+            return;
+        }
         if let Some(ref session_id) = self.display_session_id() {
             if let Some(session) = self.session_manager.get_session_mut(session_id) {
                 session.scroll_down(lines);
@@ -418,6 +605,8 @@ impl App {
     pub fn resize(&mut self, width: u16, height: u16) -> Result<()> {
         self.text_selection = None;
         self.term_size = (width, height);
+        // Pane rects are stale until the next render
+        self.pane_areas.clear();
 
         let (rows, cols) = self.calculate_terminal_dimensions();
         self.session_manager.resize_all(rows, cols);
@@ -425,8 +614,16 @@ impl App {
         Ok(())
     }
 
-    /// Calculate terminal pane dimensions based on current config and term size
+    /// Calculate terminal pane dimensions for the focused pane.
+    ///
+    /// Uses the focused pane's rect from the last render when available,
+    /// otherwise estimates from the current config and term size.
     pub(crate) fn calculate_terminal_dimensions(&self) -> (u16, u16) {
+        let focused = self.tiling.focused();
+        if let Some((_, rect)) = self.pane_areas.iter().find(|(id, _)| *id == focused) {
+            return (rect.height.saturating_sub(2), rect.width.saturating_sub(2));
+        }
+
         let sidebar_pct = if self.config.layout.sidebar_minimized {
             3 // Minimized sidebar takes ~3 columns
         } else {
@@ -463,6 +660,7 @@ impl App {
             self.cleanup_persisted_ephemeral_sessions();
         }
 
+        let any_dead = !dead_sessions.is_empty();
         for session_id in dead_sessions {
             self.session_to_claude_id.remove(&session_id);
 
@@ -485,24 +683,38 @@ impl App {
                 }
             }
         }
+        // Unfinished turns of sessions that stopped count again
+        if any_dead {
+            self.refresh_health();
+        }
     }
 
-    /// Check for sessions-index.json changes and reload conversations if needed.
-    /// Uses preserve-order loading to maintain stable group positions during auto-refresh.
+    /// Check for session file changes and reload the affected conversations
+    /// once they settle. Group positions and the sidebar selection are kept
+    /// stable during auto-refresh.
     pub fn check_sessions_updates(&mut self) {
-        if let Some(ref watcher) = self.sessions_watcher {
-            // Drain all pending notifications
-            let mut should_reload = false;
-            while watcher.try_recv().is_some() {
-                should_reload = true;
-            }
+        let Some(changes) = self
+            .sessions_watcher
+            .as_mut()
+            .and_then(SessionsWatcher::poll)
+        else {
+            return;
+        };
 
-            if should_reload && self.load_conversations_preserve_order().is_ok() {
-                self.cleanup_persisted_ephemeral_sessions();
-                self.last_refresh = Some(Instant::now());
-                self.last_refresh_was_auto = true;
+        let reloaded = self.with_sidebar_selection_preserved(|app| {
+            if changes.full {
+                app.load_conversations_preserve_order().is_ok()
+            } else {
+                app.reload_project_dirs(&changes.project_dirs);
+                true
             }
+        });
+        if reloaded {
+            self.cleanup_persisted_ephemeral_sessions();
+            self.last_refresh = Some(Instant::now());
+            self.last_refresh_was_auto = true;
         }
+        self.search_engine.start_index_refresh();
     }
 
     /// Remove ephemeral sessions that have been persisted to disk
@@ -588,7 +800,13 @@ impl App {
     /// Close a session by its ID, cleaning up all associated state
     pub fn close_session(&mut self, session_id: &str) {
         self.session_manager.close_session(session_id);
-        self.session_to_claude_id.remove(session_id);
+        if self
+            .session_to_claude_id
+            .remove(session_id)
+            .is_some_and(|id| id.is_some())
+        {
+            self.refresh_health();
+        }
         self.ephemeral_sessions.remove(session_id);
 
         // Clear preview if we're closing the previewed session
@@ -649,9 +867,11 @@ impl App {
             }
             Some(
                 SidebarItem::GroupHeader { .. }
+                | SidebarItem::Subagent { .. }
                 | SidebarItem::OtherHeader { .. }
                 | SidebarItem::SectionControl { .. }
                 | SidebarItem::ProjectHeader { .. }
+                | SidebarItem::PinnedHeader
                 | SidebarItem::WorkspaceSectionHeader
                 | SidebarItem::AddWorkspace,
             )
@@ -740,8 +960,9 @@ impl App {
 
     /// Toggle mosaic view on/off.
     pub fn toggle_mosaic_view(&mut self) {
+        self.pane_zoomed = false;
         if self.split_mode == SplitMode::Mosaic {
-            self.split_mode = SplitMode::None;
+            self.split_mode = SplitMode::Tiled;
             self.focus = Focus::Sidebar;
             self.mosaic_state_cache.clear();
         } else {
@@ -92,60 +92,16 @@ impl TextSelection {
     }
 }
 
-/// Split mode configuration for dual-pane terminal layout.
+/// How the terminal area is laid out.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SplitMode {
-    /// Single pane (default)
+    /// Panes arranged by the tiling layout tree (a single pane by default)
     #[default]
-    None,
-    /// Side-by-side (left/right)
-    Horizontal,
-    /// Stacked (top/bottom)
-    Vertical,
+    Tiled,
     /// All active PTY sessions in a dynamic grid
     Mosaic,
 }
 
-impl SplitMode {
-    /// Cycle to the next split mode (None -> Horizontal -> Vertical -> None).
-    /// Mosaic is intentionally excluded — it has its own toggle.
-    pub fn cycle(&self) -> Self {
-        match self {
-            SplitMode::None => SplitMode::Horizontal,
-            SplitMode::Horizontal => SplitMode::Vertical,
-            SplitMode::Vertical | SplitMode::Mosaic => SplitMode::None,
-        }
-    }
-}
-
-/// Identifies which terminal pane is active in split mode.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum TerminalPaneId {
-    /// Left or top pane
-    #[default]
-    Primary,
-    /// Right or bottom pane (only in split mode)
-    Secondary,
-}
-
-impl TerminalPaneId {
-    /// Convert to array index (0 for Primary, 1 for Secondary)
-    pub fn index(&self) -> usize {
-        match self {
-            TerminalPaneId::Primary => 0,
-            TerminalPaneId::Secondary => 1,
-        }
-    }
-
-    /// Toggle to the other pane
-    pub fn toggle(&self) -> Self {
-        match self {
-            TerminalPaneId::Primary => TerminalPaneId::Secondary,
-            TerminalPaneId::Secondary => TerminalPaneId::Primary,
-        }
-    }
-}
-
 /// Configuration for each terminal pane
 #[derive(Debug, Clone, Default)]
 pub struct PaneConfig {
@@ -22,6 +22,9 @@ pub struct ArchiveState {
     #[serde(default = "default_auto_archive_days")]
     pub auto_archive_days: Option<u32>, // None = disabled
     pub archived_sessions: HashMap<String, ArchiveEntry>,
+    /// Starred sessions, shown pinned at the top of the sidebar, with when they were pinned
+    #[serde(default)]
+    pub pinned_sessions: HashMap<String, DateTime<Utc>>,
 }
 
 fn default_auto_archive_days() -> Option<u32> {
@@ -34,6 +37,7 @@ impl Default for ArchiveState {
             version: 1,
             auto_archive_days: Some(DEFAULT_AUTO_ARCHIVE_DAYS),
             archived_sessions: HashMap::new(),
+            pinned_sessions: HashMap::new(),
         }
     }
 }
@@ -98,6 +102,34 @@ impl ArchiveManager {
         }
     }
 
+    /// Forget a session's archive and pin state (when it is deleted for good)
+    pub fn remove(&mut self, session_id: &str) {
+        let archived = self.state.archived_sessions.remove(session_id).is_some();
+        let pinned = self.state.pinned_sessions.remove(session_id).is_some();
+        if archived || pinned {
+            self.dirty = true;
+        }
+    }
+
+    /// Get when a session was pinned (if pinned)
+    pub fn get_pinned_at(&self, session_id: &str) -> Option<DateTime<Utc>> {
+        self.state.pinned_sessions.get(session_id).copied()
+    }
+
+    /// Pin a session to the top of the sidebar, or unpin it if already pinned.
+    /// Returns whether the session is now pinned.
+    pub fn toggle_pinned(&mut self, session_id: &str) -> bool {
+        self.dirty = true;
+        if self.state.pinned_sessions.remove(session_id).is_some() {
+            false
+        } else {
+            self.state
+                .pinned_sessions
+                .insert(session_id.to_string(), Utc::now());
+            true
+        }
+    }
+
     /// Check if a conversation should be auto-archived based on its timestamp
     pub fn should_auto_archive(&self, timestamp_ms: i64) -> bool {
         let Some(days) = self.state.auto_archive_days else {
@@ -255,6 +287,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn toggle_pinned_pins_then_unpins_and_persists() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        {
+            let mut manager = ArchiveManager::new(temp_dir.path()).unwrap();
+            assert!(manager.toggle_pinned("session-1"));
+            assert!(manager.toggle_pinned("session-2"));
+            assert!(!manager.toggle_pinned("session-2"));
+            manager.save().unwrap();
+        }
+
+        let manager = ArchiveManager::new(temp_dir.path()).unwrap();
+        assert!(manager.get_pinned_at("session-1").is_some());
+        assert!(manager.get_pinned_at("session-2").is_none());
+    }
+
+    #[test]
+    fn archive_files_without_pins_still_load() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("claudatui-archive.json"),
+            r#"{"version":1,"auto_archive_days":30,"archived_sessions":{}}"#,
+        )
+        .unwrap();
+        let manager = ArchiveManager::new(temp_dir.path()).unwrap();
+        assert!(manager.get_pinned_at("session-1").is_none());
+    }
+
     #[test]
     fn get_archived_at_returns_timestamp_within_archive_window() {
         let temp_dir = tempfile::tempdir().unwrap();
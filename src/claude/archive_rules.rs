@@ -0,0 +1,480 @@
+//! Bulk and rule-based archiving.
+//!
+//! Archive rules come from the config file; each names the conditions a
+//! conversation must meet to be archived. Rules, like bulk archiving of a
+//! group or everything idle since a cutoff, produce a list of candidates that
+//! is previewed before anything is archived.
+
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::conversation::Conversation;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// When to archive conversations. Every condition that is set must hold;
+/// a rule without conditions archives nothing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveRule {
+    /// Only conversations in this directory or below (`~` expands to the
+    /// home directory). All projects when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Idle for at least this many days
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub older_than_days: Option<u32>,
+    /// The conversation's git branch was merged into the repository's
+    /// default branch, or deleted
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub branch_gone: bool,
+    /// The conversation's directory (usually a removed worktree) no longer exists
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub worktree_gone: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl ArchiveRule {
+    /// Whether the rule has any condition to check.
+    pub fn has_conditions(&self) -> bool {
+        self.older_than_days.is_some() || self.branch_gone || self.worktree_gone
+    }
+
+    /// Short description, e.g. "idle 14+ days, branch merged or deleted in ~/work".
+    pub fn describe(&self) -> String {
+        let mut conditions = Vec::new();
+        if let Some(days) = self.older_than_days {
+            conditions.push(format!("idle {}+ days", days));
+        }
+        if self.branch_gone {
+            conditions.push("branch merged or deleted".to_string());
+        }
+        if self.worktree_gone {
+            conditions.push("worktree gone".to_string());
+        }
+        if conditions.is_empty() {
+            conditions.push("no conditions".to_string());
+        }
+        let mut text = conditions.join(", ");
+        if let Some(ref project) = self.project {
+            text.push_str(" in ");
+            text.push_str(project);
+        }
+        text
+    }
+
+    /// Whether `conv` meets every condition of the rule. `branch_gone` tells
+    /// whether a branch of the repository at a path is merged or deleted.
+    fn matches(
+        &self,
+        conv: &Conversation,
+        now_ms: i64,
+        home: Option<&Path>,
+        branch_gone: &mut impl FnMut(&Path, &str) -> bool,
+    ) -> bool {
+        if !self.has_conditions() {
+            return false;
+        }
+        if let Some(ref project) = self.project {
+            if !conv.project_path.starts_with(expand_home(project, home)) {
+                return false;
+            }
+        }
+        if let Some(days) = self.older_than_days {
+            if now_ms - conv.timestamp < i64::from(days) * DAY_MS {
+                return false;
+            }
+        }
+        if self.worktree_gone && conv.project_path.exists() {
+            return false;
+        }
+        if self.branch_gone {
+            let Some(ref branch) = conv.git_branch else {
+                return false;
+            };
+            if branch == "HEAD" || !branch_gone(&conv.project_path, branch) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn expand_home(path: &str, home: Option<&Path>) -> PathBuf {
+    match (path.strip_prefix("~"), home) {
+        (Some(rest), Some(home)) => home.join(rest.trim_start_matches('/')),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// A conversation a rule or bulk operation would archive.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArchiveCandidate {
+    pub session_id: String,
+    pub title: String,
+    pub project_path: PathBuf,
+    /// Last activity (ms)
+    pub timestamp: i64,
+    /// The rule that matched, for rule-based archiving
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl ArchiveCandidate {
+    pub fn new(conv: &Conversation, reason: Option<String>) -> Self {
+        Self {
+            session_id: conv.session_id.clone(),
+            title: conv.display.clone(),
+            project_path: conv.project_path.clone(),
+            timestamp: conv.timestamp,
+            reason,
+        }
+    }
+}
+
+/// Whether a conversation may be archived in bulk: not archived yet and not
+/// running.
+fn archivable(conv: &Conversation, running: &HashSet<String>) -> bool {
+    !conv.is_archived && !running.contains(&conv.session_id)
+}
+
+/// Conversations from `conversations` that may be archived in bulk, most
+/// recent first.
+pub fn bulk_candidates<'a>(
+    conversations: impl IntoIterator<Item = &'a Conversation>,
+    running: &HashSet<String>,
+) -> Vec<ArchiveCandidate> {
+    let mut candidates: Vec<ArchiveCandidate> = conversations
+        .into_iter()
+        .filter(|conv| archivable(conv, running))
+        .map(|conv| ArchiveCandidate::new(conv, None))
+        .collect();
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.timestamp));
+    candidates
+}
+
+/// Conversations that `rules` would archive, each with the first rule that
+/// matched, least recently active first. Starred conversations are left alone.
+pub fn rule_candidates<'a>(
+    rules: &[ArchiveRule],
+    conversations: impl IntoIterator<Item = &'a Conversation>,
+    running: &HashSet<String>,
+    now_ms: i64,
+) -> Vec<ArchiveCandidate> {
+    let mut branches = BranchChecker::default();
+    let mut branch_gone = |path: &Path, branch: &str| branches.is_gone(path, branch);
+    match_rules(
+        rules,
+        conversations,
+        running,
+        now_ms,
+        dirs::home_dir().as_deref(),
+        &mut branch_gone,
+    )
+}
+
+fn match_rules<'a>(
+    rules: &[ArchiveRule],
+    conversations: impl IntoIterator<Item = &'a Conversation>,
+    running: &HashSet<String>,
+    now_ms: i64,
+    home: Option<&Path>,
+    branch_gone: &mut impl FnMut(&Path, &str) -> bool,
+) -> Vec<ArchiveCandidate> {
+    let mut seen = HashSet::new();
+    let mut candidates = Vec::new();
+    for conv in conversations {
+        if !archivable(conv, running) || conv.pinned_at.is_some() {
+            continue;
+        }
+        // A session can be listed twice when it is shared between data directories
+        if !seen.insert(conv.session_id.as_str()) {
+            continue;
+        }
+        if let Some(rule) = rules
+            .iter()
+            .find(|rule| rule.matches(conv, now_ms, home, branch_gone))
+        {
+            candidates.push(ArchiveCandidate::new(conv, Some(rule.describe())));
+        }
+    }
+    candidates.sort_by_key(|c| c.timestamp);
+    candidates
+}
+
+/// Looks up whether branches are merged or deleted, running git once per
+/// directory and branch.
+#[derive(Debug, Default)]
+struct BranchChecker {
+    gone: HashMap<(PathBuf, String), bool>,
+}
+
+impl BranchChecker {
+    fn is_gone(&mut self, dir: &Path, branch: &str) -> bool {
+        let key = (dir.to_path_buf(), branch.to_string());
+        *self
+            .gone
+            .entry(key)
+            .or_insert_with(|| branch_is_gone(dir, branch))
+    }
+}
+
+/// Whether `branch` of the repository at `dir` is deleted or merged into the
+/// default branch (like `git branch --merged`, but only once it has commits
+/// of its own). A branch only left on `origin` still counts as there.
+/// `false` when it can't be told, e.g. `dir` is gone or isn't a repository.
+fn branch_is_gone(dir: &Path, branch: &str) -> bool {
+    if !dir.is_dir() || !git_succeeds(dir, &["rev-parse", "--git-dir"]) {
+        return false;
+    }
+    let Some(default) = default_branch(dir) else {
+        return false;
+    };
+    if default == branch || default.strip_prefix("origin/") == Some(branch) {
+        return false;
+    }
+    let Some(tip) = [
+        format!("refs/heads/{}", branch),
+        format!("refs/remotes/origin/{}", branch),
+    ]
+    .into_iter()
+    .find(|r| git_succeeds(dir, &["rev-parse", "--verify", "--quiet", r])) else {
+        return true;
+    };
+    git_succeeds(dir, &["merge-base", "--is-ancestor", &tip, &default])
+        && has_own_commits(dir, &tip, &default)
+}
+
+/// Whether the branch at `tip`, already merged into `default`, was worked
+/// on rather than just created from it: it came in through a merge commit
+/// (so its tip is off `default`'s first-parent line), or its reflog records
+/// a commit made on it (it was fast-forwarded).
+fn has_own_commits(dir: &Path, tip: &str, default: &str) -> bool {
+    let range = format!("{}..{}", tip, default);
+    let Some(walked) = git_output(dir, &["rev-list", "--first-parent", "--reverse", &range]) else {
+        return false;
+    };
+    let on_first_parent_line = match walked.lines().next() {
+        // `default` is the tip itself
+        None => true,
+        Some(oldest) => git_output(dir, &["rev-parse", &format!("{}^", oldest)])
+            .zip(git_output(dir, &["rev-parse", tip]))
+            .is_some_and(|(parent, tip)| parent.trim() == tip.trim()),
+    };
+    !on_first_parent_line
+        || git_output(dir, &["reflog", "show", "--format=%gs", tip])
+            .is_some_and(|log| log.lines().any(|entry| entry.starts_with("commit")))
+}
+
+/// The default branch: what `origin/HEAD` points to, else a local `main` or `master`.
+fn default_branch(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args([
+            "symbolic-ref",
+            "--quiet",
+            "--short",
+            "refs/remotes/origin/HEAD",
+        ])
+        .output()
+        .ok()?;
+    if output.status.success() {
+        let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !name.is_empty() {
+            return Some(name);
+        }
+    }
+    ["main", "master"]
+        .into_iter()
+        .find(|name| {
+            git_succeeds(
+                dir,
+                &[
+                    "rev-parse",
+                    "--verify",
+                    "--quiet",
+                    &format!("refs/heads/{}", name),
+                ],
+            )
+        })
+        .map(str::to_string)
+}
+
+/// Standard output of a git command, if it succeeds.
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn git_succeeds(dir: &Path, args: &[&str]) -> bool {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Parse an age cutoff: a number of days (`30d`), weeks (`2w`) or a date
+/// (`2026-01-31`, local midnight). Returns the cutoff time in ms; conversations
+/// last active before it count as older.
+pub fn parse_cutoff(text: &str, now: DateTime<Utc>) -> Option<i64> {
+    let text = text.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        let midnight = date.and_hms_opt(0, 0, 0)?;
+        return Local
+            .from_local_datetime(&midnight)
+            .earliest()
+            .map(|dt| dt.timestamp_millis());
+    }
+    let split = text.find(|c: char| !c.is_ascii_digit())?;
+    let (count, unit) = text.split_at(split);
+    let count: i64 = count.parse().ok()?;
+    let days = match unit {
+        "d" => count,
+        "w" => count * 7,
+        _ => return None,
+    };
+    Some((now - Duration::days(days)).timestamp_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_790_000_000_000;
+
+    fn conversation(
+        id: &str,
+        project: &Path,
+        days_idle: i64,
+        branch: Option<&str>,
+    ) -> Conversation {
+        Conversation {
+            display: format!("Conversation {}", id),
+            timestamp: NOW - days_idle * DAY_MS,
+            message_count: Some(2),
+            git_branch: branch.map(str::to_string),
+            ..Conversation::for_test(id, project)
+        }
+    }
+
+    fn ids(candidates: &[ArchiveCandidate]) -> Vec<&str> {
+        candidates.iter().map(|c| c.session_id.as_str()).collect()
+    }
+
+    #[test]
+    fn rules_match_when_every_condition_holds() {
+        let dir = tempfile::tempdir().unwrap();
+        let scratch = dir.path().join("scratch");
+        let app = dir.path().join("app");
+        std::fs::create_dir_all(&scratch).unwrap();
+        std::fs::create_dir_all(&app).unwrap();
+        let removed = dir.path().join("app-feature");
+
+        let mut pinned = conversation("pinned", &scratch, 30, None);
+        pinned.pinned_at = Some(Utc::now());
+        let mut archived = conversation("archived", &scratch, 30, None);
+        archived.is_archived = true;
+        let conversations = [
+            conversation("old-scratch", &scratch, 10, None),
+            conversation("new-scratch", &scratch, 2, None),
+            conversation("old-app", &app, 10, Some("main")),
+            conversation("merged", &app, 1, Some("feature")),
+            conversation("removed", &removed, 1, None),
+            conversation("running", &scratch, 30, None),
+            pinned,
+            archived,
+        ];
+        let rules = [
+            ArchiveRule {
+                project: Some("~/scratch".to_string()),
+                older_than_days: Some(7),
+                ..Default::default()
+            },
+            ArchiveRule {
+                branch_gone: true,
+                ..Default::default()
+            },
+            ArchiveRule {
+                worktree_gone: true,
+                ..Default::default()
+            },
+            // No conditions: never matches
+            ArchiveRule::default(),
+        ];
+        let running: HashSet<String> = ["running".to_string()].into();
+        let mut branch_gone = |_: &Path, branch: &str| branch == "feature";
+
+        let candidates = match_rules(
+            &rules,
+            &conversations,
+            &running,
+            NOW,
+            Some(dir.path()),
+            &mut branch_gone,
+        );
+        assert_eq!(ids(&candidates), vec!["old-scratch", "merged", "removed"]);
+        assert_eq!(
+            candidates[0].reason.as_deref(),
+            Some("idle 7+ days in ~/scratch")
+        );
+        assert_eq!(
+            candidates[1].reason.as_deref(),
+            Some("branch merged or deleted")
+        );
+        assert_eq!(candidates[2].reason.as_deref(), Some("worktree gone"));
+    }
+
+    #[test]
+    fn bulk_candidates_skip_archived_and_running_conversations() {
+        let project = Path::new("/tmp/app");
+        let mut archived = conversation("archived", project, 3, None);
+        archived.is_archived = true;
+        let conversations = [
+            conversation("older", project, 5, None),
+            conversation("newer", project, 1, None),
+            conversation("running", project, 2, None),
+            archived,
+        ];
+        let running: HashSet<String> = ["running".to_string()].into();
+
+        let candidates = bulk_candidates(&conversations, &running);
+        assert_eq!(ids(&candidates), vec!["newer", "older"]);
+        assert!(candidates.iter().all(|c| c.reason.is_none()));
+    }
+
+    #[test]
+    fn parse_cutoff_accepts_days_weeks_and_dates() {
+        let now = DateTime::from_timestamp_millis(NOW).unwrap();
+        assert_eq!(parse_cutoff("30d", now), Some(NOW - 30 * DAY_MS));
+        assert_eq!(parse_cutoff(" 2w ", now), Some(NOW - 14 * DAY_MS));
+        let date = parse_cutoff("2026-01-31", now).unwrap();
+        assert_eq!(
+            DateTime::from_timestamp_millis(date)
+                .unwrap()
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+            "2026-01-31 00:00"
+        );
+        assert_eq!(parse_cutoff("", now), None);
+        assert_eq!(parse_cutoff("30", now), None);
+        assert_eq!(parse_cutoff("d", now), None);
+        assert_eq!(parse_cutoff("3 months", now), None);
+    }
+}
@@ -0,0 +1,330 @@
+//! Conversation branches reconstructed from `uuid`/`parentUuid` links.
+//!
+//! Every line of a session log points at the line it follows. Editing an
+//! earlier prompt or rewinding makes a new line point at an old parent, so
+//! the log is really a tree; `claude --resume` continues from its most recent
+//! leaf. Here the tree is split into branches: the main branch runs from the
+//! root to the newest leaf, and every other leaf gets a branch reaching back
+//! to the point where it diverged.
+
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::claude::log_tail::read_lines;
+use crate::claude::transcript::{parse_line, Role, TranscriptBlock, TranscriptMessage};
+
+/// One branch of a conversation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Branch {
+    /// UUID of the last message on the branch
+    pub tip: String,
+    /// Branch this one split off from (`None` for the main branch)
+    pub parent: Option<usize>,
+    /// Nesting level below the main branch
+    pub depth: usize,
+    /// Preview of the last shared message before the split
+    pub fork_text: Option<String>,
+    /// Timestamp of the first message after the split
+    pub forked_at: Option<String>,
+    /// Displayable messages on this branch after the split
+    pub message_count: usize,
+    /// Preview of the first message after the split
+    pub first_text: String,
+    /// Preview of the tip message
+    pub last_text: String,
+    pub last_timestamp: Option<String>,
+    /// Last assistant message on the branch's path, where a session can be
+    /// resumed from
+    pub resume_at: Option<String>,
+}
+
+/// Branches of a conversation, main branch first (the one `claude --resume`
+/// continues), then depth-first with children in the order they diverged.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BranchTree {
+    pub branches: Vec<Branch>,
+}
+
+impl BranchTree {
+    /// Whether the conversation never branched.
+    pub fn is_linear(&self) -> bool {
+        self.branches.len() <= 1
+    }
+}
+
+/// One log line that takes part in the tree.
+#[derive(Debug)]
+struct Node {
+    uuid: String,
+    parent: Option<String>,
+    /// The displayable message on this line, if any
+    message: Option<TranscriptMessage>,
+}
+
+/// Read a session log and split it into branches.
+pub fn parse_branches(path: &Path) -> Result<BranchTree> {
+    let mut nodes = Vec::new();
+    read_lines(path, &mut 0, |line| nodes.extend(parse_node(line)))?;
+    Ok(build_tree(&nodes))
+}
+
+fn parse_node(line: &str) -> Option<Node> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    if value.get("isSidechain").and_then(Value::as_bool) == Some(true) {
+        return None;
+    }
+    let uuid = value.get("uuid").and_then(Value::as_str)?.to_string();
+    // Compaction starts a fresh chain but remembers where it came from
+    let parent = ["parentUuid", "logicalParentUuid"]
+        .iter()
+        .find_map(|key| value.get(*key).and_then(Value::as_str))
+        .map(String::from);
+    Some(Node {
+        uuid,
+        parent,
+        message: parse_line(line),
+    })
+}
+
+fn build_tree(nodes: &[Node]) -> BranchTree {
+    let index: HashMap<&str, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.uuid.as_str(), i))
+        .collect();
+    let parent_of: Vec<Option<usize>> = nodes
+        .iter()
+        .map(|n| n.parent.as_deref().and_then(|p| index.get(p).copied()))
+        .collect();
+
+    // A node is live if it or a descendant is a displayable message, so
+    // trailing system lines and hook output don't count as branches. Parents
+    // precede their children in the log, so one backwards pass suffices.
+    let mut live: Vec<bool> = nodes.iter().map(|n| n.message.is_some()).collect();
+    let mut has_live_child = vec![false; nodes.len()];
+    for i in (0..nodes.len()).rev() {
+        if let (true, Some(p)) = (live[i], parent_of[i]) {
+            live[p] = true;
+            has_live_child[p] = true;
+        }
+    }
+
+    // Newest leaf first: it is the main branch
+    let tips: Vec<usize> = (0..nodes.len())
+        .rev()
+        .filter(|&i| live[i] && !has_live_child[i])
+        .collect();
+
+    let mut owner: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut raw: Vec<RawBranch> = Vec::new();
+    for tip in tips {
+        let id = raw.len();
+        let mut path = Vec::new();
+        let mut fork = None;
+        let mut cursor = Some(tip);
+        while let Some(i) = cursor {
+            if owner[i].is_some() {
+                fork = Some(i);
+                break;
+            }
+            owner[i] = Some(id);
+            path.push(i);
+            cursor = parent_of[i];
+        }
+        path.reverse();
+        raw.push(RawBranch {
+            tip,
+            fork,
+            parent: fork.and_then(|f| owner[f]),
+            path,
+        });
+    }
+
+    let mut order = Vec::with_capacity(raw.len());
+    let mut depths = vec![0; raw.len()];
+    for root in (0..raw.len()).filter(|&b| raw[b].parent.is_none()) {
+        visit(root, 0, &raw, &mut order, &mut depths);
+    }
+    let position: HashMap<usize, usize> = order.iter().enumerate().map(|(p, &b)| (b, p)).collect();
+
+    let branches = order
+        .iter()
+        .map(|&b| {
+            let branch = &raw[b];
+            let messages: Vec<&TranscriptMessage> = branch
+                .path
+                .iter()
+                .filter_map(|&i| nodes[i].message.as_ref())
+                .collect();
+            let first = messages.first();
+            let tip = nodes[branch.tip].message.as_ref();
+            let nearest_message = |from: usize, role: Option<Role>| {
+                ancestors(from, &parent_of)
+                    .filter_map(|i| nodes[i].message.as_ref())
+                    .find(|m| role.is_none_or(|r| m.role == r))
+            };
+            Branch {
+                tip: nodes[branch.tip].uuid.clone(),
+                parent: branch.parent.map(|p| position[&p]),
+                depth: depths[b],
+                fork_text: branch
+                    .fork
+                    .and_then(|f| nearest_message(f, None))
+                    .map(preview),
+                forked_at: first
+                    .filter(|_| branch.fork.is_some())
+                    .and_then(|m| m.timestamp.clone()),
+                message_count: messages.len(),
+                first_text: first.map(|m| preview(m)).unwrap_or_default(),
+                last_text: tip.map(preview).unwrap_or_default(),
+                last_timestamp: tip.and_then(|m| m.timestamp.clone()),
+                resume_at: nearest_message(branch.tip, Some(Role::Assistant))
+                    .and_then(|m| m.uuid.clone()),
+            }
+        })
+        .collect();
+
+    BranchTree { branches }
+}
+
+/// A branch before ordering: node indices from just after the fork to the tip.
+struct RawBranch {
+    tip: usize,
+    /// Node shared with the parent branch that this one grew from
+    fork: Option<usize>,
+    parent: Option<usize>,
+    path: Vec<usize>,
+}
+
+/// Depth-first order, children sorted by where they split off.
+fn visit(b: usize, depth: usize, raw: &[RawBranch], order: &mut Vec<usize>, depths: &mut [usize]) {
+    order.push(b);
+    depths[b] = depth;
+    let mut children: Vec<usize> = (0..raw.len())
+        .filter(|&c| raw[c].parent == Some(b))
+        .collect();
+    children.sort_by_key(|&c| (raw[c].fork, raw[c].path.first().copied()));
+    for child in children {
+        visit(child, depth + 1, raw, order, depths);
+    }
+}
+
+fn ancestors(from: usize, parent_of: &[Option<usize>]) -> impl Iterator<Item = usize> + '_ {
+    std::iter::successors(Some(from), move |&i| parent_of[i])
+}
+
+/// One-line summary of a message: its first line of text, or the tool it ran.
+fn preview(message: &TranscriptMessage) -> String {
+    let text = message.blocks.iter().find_map(|block| match block {
+        TranscriptBlock::Text(text) => Some(text.clone()),
+        _ => None,
+    });
+    let text = text.or_else(|| {
+        message.blocks.iter().find_map(|block| match block {
+            TranscriptBlock::ToolUse { name, summary, .. } => {
+                Some(format!("{} {}", name, summary).trim_end().to_string())
+            }
+            TranscriptBlock::ToolResult { .. } => Some("tool result".to_string()),
+            _ => None,
+        })
+    });
+    text.unwrap_or_default()
+        .lines()
+        .find(|l| !l.trim().is_empty())
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(kind: &str, uuid: &str, parent: Option<&str>, text: &str) -> String {
+        let parent = parent.map_or("null".to_string(), |p| format!("\"{}\"", p));
+        match kind {
+            "system" => format!(
+                r#"{{"type":"system","uuid":"{}","parentUuid":{},"content":"{}"}}"#,
+                uuid, parent, text
+            ),
+            _ => format!(
+                r#"{{"type":"{}","uuid":"{}","parentUuid":{},"message":{{"role":"{}","content":"{}"}}}}"#,
+                kind, uuid, parent, kind, text
+            ),
+        }
+    }
+
+    fn tree(lines: &[String]) -> BranchTree {
+        let nodes: Vec<Node> = lines.iter().filter_map(|l| parse_node(l)).collect();
+        build_tree(&nodes)
+    }
+
+    #[test]
+    fn linear_conversation_has_one_branch() {
+        let tree = tree(&[
+            line("user", "u1", None, "hello"),
+            line("assistant", "a1", Some("u1"), "hi there"),
+            line("system", "s1", Some("a1"), "hook ran"),
+        ]);
+        assert!(tree.is_linear());
+        let main = &tree.branches[0];
+        assert_eq!(main.parent, None);
+        assert_eq!(main.tip, "a1");
+        assert_eq!(main.message_count, 2);
+        assert_eq!(main.last_text, "hi there");
+        assert_eq!(main.resume_at.as_deref(), Some("a1"));
+    }
+
+    #[test]
+    fn edited_prompt_forks_a_branch_off_the_shared_prefix() {
+        let tree = tree(&[
+            line("user", "u1", None, "hello"),
+            line("assistant", "a1", Some("u1"), "hi"),
+            line("user", "u2", Some("a1"), "first try"),
+            line("assistant", "a2", Some("u2"), "answer one"),
+            // Prompt edited: a new child of a1
+            line("user", "u3", Some("a1"), "second try"),
+            line("assistant", "a3", Some("u3"), "answer two"),
+        ]);
+
+        assert_eq!(tree.branches.len(), 2);
+        let main = &tree.branches[0];
+        assert_eq!(main.tip, "a3");
+        assert_eq!(main.message_count, 4);
+        assert!(main.fork_text.is_none());
+
+        let old = &tree.branches[1];
+        assert_eq!(old.parent, Some(0));
+        assert_eq!(old.depth, 1);
+        assert_eq!(old.tip, "a2");
+        assert_eq!(old.fork_text.as_deref(), Some("hi"));
+        assert_eq!(old.first_text, "first try");
+        assert_eq!(old.last_text, "answer one");
+        assert_eq!(old.message_count, 2);
+        assert_eq!(old.resume_at.as_deref(), Some("a2"));
+    }
+
+    #[test]
+    fn nested_branches_are_listed_depth_first() {
+        let tree = tree(&[
+            line("user", "u1", None, "root"),
+            line("assistant", "a1", Some("u1"), "r"),
+            line("user", "u2", Some("a1"), "b"),
+            line("assistant", "a2", Some("u2"), "b reply"),
+            line("user", "u4", Some("a2"), "b-nested"),
+            line("user", "u5", Some("a2"), "b-latest"),
+            line("user", "u3", Some("a1"), "main"),
+        ]);
+        let tips: Vec<(&str, usize)> = tree
+            .branches
+            .iter()
+            .map(|b| (b.tip.as_str(), b.depth))
+            .collect();
+        assert_eq!(tips, vec![("u3", 0), ("u5", 1), ("u4", 2)]);
+        assert_eq!(tree.branches[2].parent, Some(1));
+        // A branch ending on a user prompt resumes at the reply before it
+        assert_eq!(tree.branches[2].resume_at.as_deref(), Some("a2"));
+    }
+}
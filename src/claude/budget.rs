@@ -0,0 +1,342 @@
+//! Rolling usage windows and budget alerts.
+//!
+//! Claude plans meter usage in 5-hour blocks: a block starts at the top of
+//! the hour of the first message sent after the previous block ended, and
+//! lasts five hours. Blocks are reconstructed from the message timestamps in
+//! the local logs (see `LogStatsTracker::recent_events`), so everything here
+//! works offline. Alongside the current block, usage for the current local
+//! day is tracked, and both are checked against the configured budgets.
+
+use chrono::{Local, NaiveDate, TimeZone};
+
+use crate::claude::usage::{format_cost, PriceTable, TokenUsage, UsageEvent};
+use crate::config::BudgetConfig;
+
+const HOUR_MS: i64 = 60 * 60 * 1000;
+/// Length of a usage block.
+pub const BLOCK_MS: i64 = 5 * HOUR_MS;
+
+/// Tokens and cost accumulated over a window.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WindowUsage {
+    pub tokens: TokenUsage,
+    /// USD
+    pub cost: f64,
+}
+
+impl WindowUsage {
+    fn add(&mut self, event: &UsageEvent, prices: &PriceTable) {
+        self.tokens += event.usage;
+        self.cost += prices.cost(&event.model, &event.usage);
+    }
+}
+
+/// A 5-hour usage block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UsageBlock {
+    /// Block start (ms, on the hour)
+    pub start: i64,
+    pub usage: WindowUsage,
+}
+
+impl UsageBlock {
+    /// When the block resets (ms).
+    pub fn end(&self) -> i64 {
+        self.start + BLOCK_MS
+    }
+
+    /// Time until the block resets (ms), or `None` once it has ended.
+    pub fn remaining_ms(&self, now_ms: i64) -> Option<i64> {
+        let remaining = self.end() - now_ms;
+        (remaining > 0).then_some(remaining)
+    }
+}
+
+/// Usage in the current 5-hour block and the current day.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageWindows {
+    /// The block containing the most recent message, if it has not ended
+    pub block: Option<UsageBlock>,
+    /// The local day `today` refers to
+    pub day: NaiveDate,
+    pub today: WindowUsage,
+}
+
+impl UsageWindows {
+    /// Compute the windows as of `now_ms` from recent per-message usage.
+    pub fn compute<'a>(
+        events: impl IntoIterator<Item = &'a UsageEvent>,
+        prices: &PriceTable,
+        now_ms: i64,
+    ) -> Self {
+        let day = local_day(now_ms);
+        let mut events: Vec<&UsageEvent> = events
+            .into_iter()
+            .filter(|e| e.timestamp <= now_ms)
+            .collect();
+        events.sort_by_key(|e| e.timestamp);
+
+        let mut today = WindowUsage::default();
+        let mut block: Option<UsageBlock> = None;
+        for event in events {
+            if local_day(event.timestamp) == day {
+                today.add(event, prices);
+            }
+            let current = match block.as_mut() {
+                Some(block) if event.timestamp < block.end() => block,
+                _ => block.insert(UsageBlock {
+                    start: event.timestamp - event.timestamp.rem_euclid(HOUR_MS),
+                    usage: WindowUsage::default(),
+                }),
+            };
+            current.usage.add(event, prices);
+        }
+
+        Self {
+            block: block.filter(|b| b.remaining_ms(now_ms).is_some()),
+            day,
+            today,
+        }
+    }
+
+    /// The current block, if it is still running at `now_ms`.
+    pub fn active_block(&self, now_ms: i64) -> Option<&UsageBlock> {
+        self.block
+            .as_ref()
+            .filter(|b| b.remaining_ms(now_ms).is_some())
+    }
+
+    /// Today's usage, or zero if the day has rolled over since computing.
+    pub fn today(&self, now_ms: i64) -> WindowUsage {
+        if self.day == local_day(now_ms) {
+            self.today
+        } else {
+            WindowUsage::default()
+        }
+    }
+}
+
+fn local_day(ms: i64) -> NaiveDate {
+    Local
+        .timestamp_millis_opt(ms)
+        .single()
+        .map_or_else(|| Local::now().date_naive(), |dt| dt.date_naive())
+}
+
+/// How close spending is to a budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BudgetLevel {
+    Ok,
+    /// At or above the warning threshold
+    Warning,
+    Exceeded,
+}
+
+impl BudgetLevel {
+    /// Level of `spent` against `budget` with a warning at `warn_percent`.
+    pub fn of(spent: f64, budget: f64, warn_percent: u8) -> Self {
+        if spent >= budget {
+            Self::Exceeded
+        } else if spent >= budget * f64::from(warn_percent) / 100.0 {
+            Self::Warning
+        } else {
+            Self::Ok
+        }
+    }
+}
+
+/// Tracks which budget alerts have been raised, so each threshold is only
+/// reported once per block and once per day.
+#[derive(Debug, Default)]
+pub struct BudgetAlerts {
+    /// Highest level reported for the block starting at this time
+    block: Option<(i64, BudgetLevel)>,
+    /// Highest level reported for this day
+    day: Option<(NaiveDate, BudgetLevel)>,
+}
+
+impl BudgetAlerts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Alert messages for budgets newly crossed since the last check.
+    pub fn check(
+        &mut self,
+        windows: &UsageWindows,
+        budget: &BudgetConfig,
+        now_ms: i64,
+    ) -> Vec<String> {
+        let mut alerts = Vec::new();
+
+        if let (Some(limit), Some(block)) = (budget.window, windows.active_block(now_ms)) {
+            let level = BudgetLevel::of(block.usage.cost, limit, budget.warn_percent);
+            if raise(&mut self.block, block.start, level) {
+                alerts.push(alert_message("5-hour", block.usage.cost, limit, level));
+            }
+        }
+
+        if let Some(limit) = budget.daily {
+            let spent = windows.today(now_ms).cost;
+            let level = BudgetLevel::of(spent, limit, budget.warn_percent);
+            if raise(&mut self.day, windows.day, level) {
+                alerts.push(alert_message("Daily", spent, limit, level));
+            }
+        }
+
+        alerts
+    }
+}
+
+/// Record `level` for `key`, returning whether it is a new, higher alert level.
+fn raise<K: PartialEq>(last: &mut Option<(K, BudgetLevel)>, key: K, level: BudgetLevel) -> bool {
+    let previous = match last {
+        Some((k, previous)) if *k == key => *previous,
+        _ => BudgetLevel::Ok,
+    };
+    *last = Some((key, level.max(previous)));
+    level > previous
+}
+
+fn alert_message(window: &str, spent: f64, limit: f64, level: BudgetLevel) -> String {
+    if level == BudgetLevel::Exceeded {
+        format!(
+            "{} budget of {} exceeded ({} used)",
+            window,
+            format_cost(limit),
+            format_cost(spent)
+        )
+    } else {
+        format!(
+            "{} usage at {:.0}% of {} budget ({} used)",
+            window,
+            spent / limit * 100.0,
+            format_cost(limit),
+            format_cost(spent)
+        )
+    }
+}
+
+/// Format a duration compactly: `2h13m`, `45m`.
+pub fn format_remaining(ms: i64) -> String {
+    let minutes = (ms.max(0) + 59_999) / 60_000;
+    if minutes >= 60 {
+        format!("{}h{:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2026-09-01 10:00 local time, ms
+    fn base() -> i64 {
+        Local
+            .with_ymd_and_hms(2026, 9, 1, 10, 0, 0)
+            .single()
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    fn event(offset_min: i64, output: u64) -> UsageEvent {
+        UsageEvent {
+            timestamp: base() + offset_min * 60_000,
+            model: "claude-sonnet-4".to_string(),
+            usage: TokenUsage {
+                output,
+                ..TokenUsage::default()
+            },
+        }
+    }
+
+    #[test]
+    fn block_starts_on_the_hour_and_resets_after_a_gap() {
+        let prices = PriceTable::default();
+        // 10:20 and 12:00 share a block; 16:30 is past its 15:00 end
+        let events = [event(20, 100), event(120, 100), event(390, 7)];
+
+        let windows = UsageWindows::compute(&events[..2], &prices, base() + 130 * 60_000);
+        let block = windows.block.unwrap();
+        assert_eq!(block.start, base());
+        assert_eq!(block.usage.tokens.output, 200);
+        assert_eq!(
+            block.remaining_ms(base() + 130 * 60_000),
+            Some(170 * 60_000)
+        );
+
+        let windows = UsageWindows::compute(&events, &prices, base() + 400 * 60_000);
+        let block = windows.block.unwrap();
+        assert_eq!(block.start, base() + 6 * 60 * 60_000);
+        assert_eq!(block.usage.tokens.output, 7);
+        assert_eq!(windows.today.tokens.output, 207);
+    }
+
+    #[test]
+    fn block_ends_five_hours_after_it_starts() {
+        let prices = PriceTable::default();
+        let events = [event(20, 100)];
+        let windows = UsageWindows::compute(&events, &prices, base() + 5 * 60 * 60_000);
+        assert!(windows.block.is_none());
+        assert_eq!(windows.today.tokens.output, 100);
+    }
+
+    #[test]
+    fn today_excludes_previous_days() {
+        let prices = PriceTable::default();
+        let events = [event(-11 * 60, 50), event(10, 5)];
+        let windows = UsageWindows::compute(&events, &prices, base() + 20 * 60_000);
+        assert_eq!(windows.today.tokens.output, 5);
+        assert_eq!(
+            windows.today(base() + 24 * 60 * 60_000),
+            WindowUsage::default()
+        );
+    }
+
+    #[test]
+    fn alerts_fire_once_per_level_and_window() {
+        let budget = BudgetConfig {
+            daily: Some(10.0),
+            window: Some(5.0),
+            warn_percent: 80,
+        };
+        let now = base() + 60_000;
+        let mut windows = UsageWindows {
+            block: Some(UsageBlock {
+                start: base(),
+                usage: WindowUsage {
+                    tokens: TokenUsage::default(),
+                    cost: 4.2,
+                },
+            }),
+            day: local_day(now),
+            today: WindowUsage {
+                tokens: TokenUsage::default(),
+                cost: 4.2,
+            },
+        };
+        let mut alerts = BudgetAlerts::new();
+
+        let raised = alerts.check(&windows, &budget, now);
+        assert_eq!(
+            raised,
+            vec!["5-hour usage at 84% of $5.00 budget ($4.20 used)"]
+        );
+        assert!(alerts.check(&windows, &budget, now).is_empty());
+
+        windows.block.as_mut().unwrap().usage.cost = 5.5;
+        windows.today.cost = 8.5;
+        let raised = alerts.check(&windows, &budget, now);
+        assert_eq!(raised.len(), 2);
+        assert!(raised[0].starts_with("5-hour budget of $5.00 exceeded"));
+        assert!(raised[1].starts_with("Daily usage at 85%"));
+    }
+
+    #[test]
+    fn format_remaining_rounds_up_to_minutes() {
+        assert_eq!(format_remaining(30_000), "1m");
+        assert_eq!(format_remaining(45 * 60_000), "45m");
+        assert_eq!(format_remaining(133 * 60_000), "2h13m");
+    }
+}
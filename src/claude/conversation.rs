@@ -1,29 +1,204 @@
-use chrono::{DateTime, Utc};
-use std::path::PathBuf;
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use super::archive::ArchiveManager;
+use super::health::Health;
+use super::metadata::MetadataManager;
+use super::plans::strip_plan_prefix;
+use super::sessions::SessionEntry;
 
 /// A Claude conversation
 #[derive(Debug, Clone)]
 pub struct Conversation {
     /// Session ID
     pub session_id: String,
-    /// Display text (first user message, truncated)
+    /// Display text (custom title, else summary or first user message)
     pub display: String,
     /// AI-generated summary (if available)
     pub summary: Option<String>,
     /// Timestamp in milliseconds (file_mtime for sorting)
     pub timestamp: i64,
+    /// ISO 8601 created timestamp
+    pub created: String,
     /// ISO 8601 modified timestamp
     pub modified: String,
     /// Project path
     pub project_path: PathBuf,
-    /// Number of messages in the conversation
-    pub message_count: u32,
+    /// Path to the session's JSONL transcript
+    pub file_path: PathBuf,
+    /// Number of messages in the conversation, when known (sessions missing
+    /// from Claude's session index have no stored count)
+    pub message_count: Option<u32>,
     /// Git branch (if in a git repo)
     pub git_branch: Option<String>,
     /// Whether this is a plan implementation conversation (hidden from sidebar)
     pub is_plan_implementation: bool,
+    /// Session ID of the conversation that wrote the plan this one implements
+    pub plan_parent: Option<String>,
+    /// Why the conversation needs attention, judged by how its log ends
+    pub health: Option<Health>,
     /// Whether this conversation is archived
     pub is_archived: bool,
     /// When this conversation was archived (if archived)
     pub archived_at: Option<DateTime<Utc>>,
+    /// When this conversation was starred (pinned to the top of the sidebar)
+    pub pinned_at: Option<DateTime<Utc>>,
+    /// Sidechain (subagent) transcripts launched from this conversation
+    pub subagent_files: Vec<PathBuf>,
+    /// User-assigned tags
+    pub tags: Vec<String>,
+    /// User-written note
+    pub note: Option<String>,
+}
+
+impl Conversation {
+    /// Build a conversation from a session index entry, looking up its archive
+    /// and pin status and user metadata. A first prompt starting with one of
+    /// `plan_prefixes` marks a plan implementation.
+    pub fn from_session(
+        session: SessionEntry,
+        archive_manager: &ArchiveManager,
+        metadata: &MetadataManager,
+        plan_prefixes: &[String],
+    ) -> Self {
+        let plan = strip_plan_prefix(&session.first_prompt, plan_prefixes);
+        let is_plan_implementation = plan.is_some();
+
+        // Check archive status
+        let is_archived = archive_manager.is_archived(&session.session_id);
+        let archived_at = archive_manager.get_archived_at(&session.session_id);
+        let pinned_at = archive_manager.get_pinned_at(&session.session_id);
+        let meta = metadata
+            .get(&session.session_id)
+            .cloned()
+            .unwrap_or_default();
+
+        Self {
+            display: meta
+                .title
+                .or_else(|| session.summary.clone())
+                .unwrap_or_else(|| {
+                    let stripped = plan.unwrap_or(session.first_prompt.trim());
+                    stripped.strip_prefix("# ").unwrap_or(stripped).to_string()
+                }),
+            session_id: session.session_id,
+            summary: session.summary,
+            timestamp: session.file_mtime,
+            created: session.created,
+            modified: session.modified,
+            project_path: PathBuf::from(&session.project_path),
+            file_path: PathBuf::from(&session.full_path),
+            message_count: session.message_count,
+            git_branch: session.git_branch,
+            is_plan_implementation,
+            plan_parent: None,
+            health: None,
+            is_archived,
+            archived_at,
+            pinned_at,
+            subagent_files: session.subagent_files,
+            tags: meta.tags,
+            note: meta.note,
+        }
+    }
+
+    /// A conversation logged at `file_path` in `project_path`, with every
+    /// other field empty; callers fill in the rest with struct update syntax.
+    pub fn new(
+        session_id: impl Into<String>,
+        project_path: impl Into<PathBuf>,
+        file_path: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            session_id: session_id.into(),
+            display: String::new(),
+            summary: None,
+            timestamp: 0,
+            created: String::new(),
+            modified: String::new(),
+            project_path: project_path.into(),
+            file_path: file_path.into(),
+            message_count: None,
+            git_branch: None,
+            is_plan_implementation: false,
+            plan_parent: None,
+            health: None,
+            is_archived: false,
+            archived_at: None,
+            pinned_at: None,
+            subagent_files: Vec::new(),
+            tags: Vec::new(),
+            note: None,
+        }
+    }
+
+    /// When the conversation started and last changed.
+    ///
+    /// Conversations missing from Claude's session index have no stored
+    /// timestamps; their first logged message and the file's modification time
+    /// stand in.
+    pub fn active_span(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let start = parse_time(&self.created).or_else(|| first_timestamp(&self.file_path))?;
+        let end = parse_time(&self.modified)
+            .or_else(|| Utc.timestamp_millis_opt(self.timestamp).single())?;
+        Some((start, end.max(start)))
+    }
+}
+
+#[cfg(test)]
+impl Conversation {
+    /// Test fixture: a conversation in `project_path` with every other field
+    /// empty. Tests set what they need with struct update syntax.
+    pub(crate) fn for_test(session_id: &str, project_path: impl Into<PathBuf>) -> Self {
+        Self::new(session_id, project_path, PathBuf::new())
+    }
+}
+
+/// Parse an RFC 3339 timestamp as UTC.
+pub(crate) fn parse_time(text: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(text.trim())
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Timestamp of the first logged line that has one.
+fn first_timestamp(path: &Path) -> Option<DateTime<Utc>> {
+    let file = File::open(path).ok()?;
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .find_map(|line| {
+            let value: Value = serde_json::from_str(&line).ok()?;
+            parse_time(value.get("timestamp")?.as_str()?)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn active_span_falls_back_to_the_file_when_unindexed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        std::fs::write(
+            &path,
+            "{\"type\":\"summary\"}\n{\"type\":\"user\",\"timestamp\":\"2026-03-01T09:00:00Z\"}\n",
+        )
+        .unwrap();
+        let time = |text| parse_time(text).unwrap();
+        let conv = Conversation {
+            timestamp: time("2026-03-01T10:00:00Z").timestamp_millis(),
+            file_path: path,
+            ..Conversation::for_test("session-1", "/repo")
+        };
+
+        assert_eq!(
+            conv.active_span(),
+            Some((time("2026-03-01T09:00:00Z"), time("2026-03-01T10:00:00Z")))
+        );
+    }
 }
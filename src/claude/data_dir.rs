@@ -0,0 +1,223 @@
+//! Claude data directories.
+//!
+//! Claude Code keeps its config and conversation logs in `~/.claude`, or in
+//! `$CLAUDE_CONFIG_DIR` when set, which is how separate accounts are kept
+//! apart. Several directories can be shown in one sidebar: the primary one
+//! comes first, and each conversation belongs to the directory its log is in.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::config::ClaudeDirEntry;
+
+/// Environment variable Claude Code reads its data directory from.
+pub const CONFIG_DIR_ENV: &str = "CLAUDE_CONFIG_DIR";
+
+/// A Claude data directory and the label its conversations are tagged with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaudeDir {
+    pub path: PathBuf,
+    pub label: String,
+}
+
+impl ClaudeDir {
+    /// A directory labelled after its name (`~/.claude-work` is "claude-work").
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let label = path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .map(|name| name.trim_start_matches('.').to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| path.display().to_string());
+        Self { path, label }
+    }
+
+    /// Where the conversation logs are (`<dir>/projects`).
+    pub fn projects_dir(&self) -> PathBuf {
+        self.path.join("projects")
+    }
+
+    /// The `CLAUDE_CONFIG_DIR` a `claude` process needs to use this
+    /// directory, or `None` for `~/.claude`, which needs the variable unset.
+    pub fn config_dir_env(&self) -> Option<&Path> {
+        let home_default = dirs::home_dir().map(|home| home.join(".claude"));
+        (home_default.as_deref() != Some(self.path.as_path())).then_some(self.path.as_path())
+    }
+
+    /// Whether `file` (a session log) lives in this directory.
+    pub fn contains(&self, file: &Path) -> bool {
+        file.starts_with(&self.path)
+    }
+}
+
+/// The directory a session log belongs to.
+pub fn source_of<'a>(claude_dirs: &'a [ClaudeDir], file: &Path) -> Option<&'a ClaudeDir> {
+    claude_dirs.iter().find(|dir| dir.contains(file))
+}
+
+/// The directories to load: the `--claude-dir` flags if any were given,
+/// otherwise `$CLAUDE_CONFIG_DIR` (or `~/.claude`) followed by the ones in
+/// the config file.
+pub fn resolve_claude_dirs(
+    flags: &[PathBuf],
+    configured: &[ClaudeDirEntry],
+) -> Result<Vec<ClaudeDir>> {
+    let env = std::env::var_os(CONFIG_DIR_ENV)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from);
+    let home = dirs::home_dir().context("Could not find home directory")?;
+    Ok(resolve_from(flags, env, configured, &home))
+}
+
+fn resolve_from(
+    flags: &[PathBuf],
+    env: Option<PathBuf>,
+    configured: &[ClaudeDirEntry],
+    home: &Path,
+) -> Vec<ClaudeDir> {
+    let candidates: Vec<ClaudeDir> = if flags.is_empty() {
+        let primary = env.unwrap_or_else(|| home.join(".claude"));
+        std::iter::once(ClaudeDir::new(expand_home(&primary, home)))
+            .chain(configured.iter().map(|entry| {
+                let mut dir = ClaudeDir::new(expand_home(Path::new(&entry.path), home));
+                if let Some(label) = &entry.label {
+                    dir.label.clone_from(label);
+                }
+                dir
+            }))
+            .collect()
+    } else {
+        flags
+            .iter()
+            .map(|path| ClaudeDir::new(expand_home(path, home)))
+            .collect()
+    };
+
+    let mut dirs: Vec<ClaudeDir> = Vec::new();
+    for dir in candidates {
+        if !dirs.iter().any(|d| d.path == dir.path) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+fn expand_home(path: &Path, home: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(rest) => home.join(rest),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Remove `--claude-dir <path>` (or `--claude-dir=<path>`, repeatable) from
+/// the command line, returning the paths.
+pub fn take_claude_dir_args(args: &mut Vec<String>) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        if let Some(value) = args[i].strip_prefix("--claude-dir=") {
+            dirs.push(PathBuf::from(value));
+            args.remove(i);
+        } else if args[i] == "--claude-dir" {
+            if i + 1 >= args.len() {
+                bail!("--claude-dir needs a directory");
+            }
+            dirs.push(PathBuf::from(args.remove(i + 1)));
+            args.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    Ok(dirs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, label: Option<&str>) -> ClaudeDirEntry {
+        ClaudeDirEntry {
+            path: path.to_string(),
+            label: label.map(String::from),
+        }
+    }
+
+    fn paths(dirs: &[ClaudeDir]) -> Vec<&Path> {
+        dirs.iter().map(|d| d.path.as_path()).collect()
+    }
+
+    #[test]
+    fn primary_dir_comes_from_the_environment_then_home() {
+        let home = Path::new("/home/me");
+        let dirs = resolve_from(&[], None, &[], home);
+        assert_eq!(paths(&dirs), vec![Path::new("/home/me/.claude")]);
+        assert_eq!(dirs[0].label, "claude");
+
+        let dirs = resolve_from(&[], Some(PathBuf::from("/home/me/.claude-work")), &[], home);
+        assert_eq!(paths(&dirs), vec![Path::new("/home/me/.claude-work")]);
+        assert_eq!(dirs[0].label, "claude-work");
+    }
+
+    #[test]
+    fn configured_dirs_are_added_after_the_primary_one() {
+        let home = Path::new("/home/me");
+        let configured = [
+            entry("~/.claude-work", Some("work")),
+            entry("/home/me/.claude", None),
+        ];
+        let dirs = resolve_from(&[], None, &configured, home);
+        assert_eq!(
+            paths(&dirs),
+            vec![
+                Path::new("/home/me/.claude"),
+                Path::new("/home/me/.claude-work")
+            ]
+        );
+        assert_eq!(dirs[1].label, "work");
+    }
+
+    #[test]
+    fn flags_replace_the_environment_and_config() {
+        let home = Path::new("/home/me");
+        let dirs = resolve_from(
+            &[PathBuf::from("/data/a"), PathBuf::from("~/b")],
+            Some(PathBuf::from("/env")),
+            &[entry("/configured", None)],
+            home,
+        );
+        assert_eq!(
+            paths(&dirs),
+            vec![Path::new("/data/a"), Path::new("/home/me/b")]
+        );
+    }
+
+    #[test]
+    fn claude_dir_flags_are_taken_out_of_the_arguments() {
+        let mut args: Vec<String> = ["--claude-dir", "/a", "list", "--claude-dir=/b", "--json"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let dirs = take_claude_dir_args(&mut args).unwrap();
+        assert_eq!(dirs, vec![PathBuf::from("/a"), PathBuf::from("/b")]);
+        assert_eq!(args, vec!["list", "--json"]);
+
+        let mut args = vec!["--claude-dir".to_string()];
+        assert!(take_claude_dir_args(&mut args).is_err());
+    }
+
+    #[test]
+    fn conversations_belong_to_the_dir_holding_their_log() {
+        let dirs = [
+            ClaudeDir::new("/home/me/.claude"),
+            ClaudeDir::new("/home/me/.claude-work"),
+        ];
+        let file = Path::new("/home/me/.claude-work/projects/-tmp/abc.jsonl");
+        assert_eq!(
+            source_of(&dirs, file).map(|d| d.label.as_str()),
+            Some("claude-work")
+        );
+        assert!(source_of(&dirs, Path::new("/elsewhere/abc.jsonl")).is_none());
+    }
+}
@@ -0,0 +1,136 @@
+//! Conversation details that the session index doesn't keep: the full
+//! first prompt and the assistant's last reply, plus when the log starts and
+//! ends for sessions the index doesn't list.
+//!
+//! Only the head and the tail of the session log are read, so details can
+//! be looked up on every selection change.
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use super::conversation::parse_time;
+use super::health::{find_last, texts};
+use super::sessions::prompt_text;
+
+/// Lines read from the start of a log when looking for the first prompt.
+const HEAD_LINES: usize = 200;
+
+/// Text read from a conversation's log.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConversationDetails {
+    /// The first prompt, untruncated
+    pub first_prompt: Option<String>,
+    /// The text of the last assistant message
+    pub last_reply: Option<String>,
+    /// Time of the first timestamped line
+    pub started: Option<DateTime<Utc>>,
+    /// Time of the last timestamped line
+    pub last_active: Option<DateTime<Utc>>,
+}
+
+impl ConversationDetails {
+    /// Read the details of the log at `path`. Missing parts are left empty.
+    pub fn read(path: &Path) -> Self {
+        let (first_prompt, started) = read_head(path);
+        Self {
+            first_prompt,
+            last_reply: find_last(path, reply_of),
+            started,
+            last_active: find_last(path, time_of),
+        }
+    }
+}
+
+/// Whether a log line belongs to the main conversation.
+fn is_main_line(value: &Value) -> bool {
+    let flag = |key: &str| value.get(key).and_then(Value::as_bool) == Some(true);
+    !flag("isSidechain") && !flag("isMeta")
+}
+
+/// The first prompt and the first timestamp within the head of a log.
+fn read_head(path: &Path) -> (Option<String>, Option<DateTime<Utc>>) {
+    let Ok(file) = File::open(path) else {
+        return (None, None);
+    };
+    let mut prompt = None;
+    let mut started = None;
+    for value in BufReader::new(file)
+        .lines()
+        .take(HEAD_LINES)
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok())
+    {
+        if started.is_none() {
+            started = time_of(&value);
+        }
+        if prompt.is_none() && is_main_line(&value) {
+            prompt = prompt_text(&value).map(|text| text.trim().to_string());
+        }
+        if prompt.is_some() && started.is_some() {
+            break;
+        }
+    }
+    (prompt, started)
+}
+
+fn time_of(value: &Value) -> Option<DateTime<Utc>> {
+    value
+        .get("timestamp")
+        .and_then(Value::as_str)
+        .and_then(parse_time)
+}
+
+/// The text of an assistant line, if it has any.
+fn reply_of(value: &Value) -> Option<String> {
+    if value.get("type").and_then(Value::as_str) != Some("assistant") || !is_main_line(value) {
+        return None;
+    }
+    let content = value.get("message")?.get("content")?;
+    let text = texts(content)
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_takes_the_first_prompt_and_last_reply_of_the_main_conversation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let long_prompt = "Add a login page. ".repeat(30);
+        let lines = [
+            r#"{"type":"user","isMeta":true,"timestamp":"2026-03-01T10:00:00Z","message":{"content":"Caveat: local commands"}}"#
+                .to_string(),
+            format!(
+                r#"{{"type":"user","message":{{"content":{}}}}}"#,
+                serde_json::to_string(&long_prompt).unwrap()
+            ),
+            r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Starting."}]}}"#
+                .to_string(),
+            r#"{"type":"assistant","message":{"content":[{"type":"text","text":"The page is done."},{"type":"tool_use","id":"t1","name":"Bash","input":{}}]}}"#
+                .to_string(),
+            r#"{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"t1","content":"ok"}]}}"#
+                .to_string(),
+            r#"{"type":"assistant","isSidechain":true,"timestamp":"2026-03-01T10:05:00Z","message":{"content":[{"type":"text","text":"Subagent report"}]}}"#
+                .to_string(),
+        ];
+        std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+        let details = ConversationDetails::read(&path);
+        assert_eq!(details.first_prompt.as_deref(), Some(long_prompt.trim()));
+        assert_eq!(details.last_reply.as_deref(), Some("The page is done."));
+        assert_eq!(details.started, parse_time("2026-03-01T10:00:00Z"));
+        assert_eq!(details.last_active, parse_time("2026-03-01T10:05:00Z"));
+
+        let missing = ConversationDetails::read(&dir.path().join("missing.jsonl"));
+        assert_eq!(missing, ConversationDetails::default());
+    }
+}
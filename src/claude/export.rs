@@ -0,0 +1,552 @@
+//! Export a conversation transcript as Markdown, a self-contained HTML page
+//! or normalized JSON.
+//!
+//! Assistant turns are often split over several JSONL lines (one per content
+//! block) and tool results arrive as `user` lines, so consecutive messages
+//! are merged into turns: a tool-result-only message continues the turn that
+//! made the call instead of starting a new "User" section.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::claude::conversation::Conversation;
+use crate::claude::transcript::{Role, Transcript, TranscriptBlock};
+
+/// Output format of an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    pub const ALL: [Self; 3] = [Self::Markdown, Self::Html, Self::Json];
+
+    /// Display name (also accepted by `from_name`).
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Markdown => "markdown",
+            Self::Html => "html",
+            Self::Json => "json",
+        }
+    }
+
+    /// File extension, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+            Self::Json => "json",
+        }
+    }
+
+    /// Parse a format name or file extension (`md`, `markdown`, `html`, `htm`, `json`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "md" | "markdown" => Some(Self::Markdown),
+            "html" | "htm" => Some(Self::Html),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// Infer the format from a path's extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_name)
+    }
+}
+
+/// Which block kinds to include besides plain text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportOptions {
+    pub tool_calls: bool,
+    pub tool_results: bool,
+    pub thinking: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            tool_calls: true,
+            tool_results: false,
+            thinking: false,
+        }
+    }
+}
+
+impl ExportOptions {
+    fn includes(&self, block: &TranscriptBlock) -> bool {
+        match block {
+            TranscriptBlock::Text(_) => true,
+            TranscriptBlock::Thinking(_) => self.thinking,
+            TranscriptBlock::ToolUse { .. } => self.tool_calls,
+            TranscriptBlock::ToolResult { .. } => self.tool_results,
+        }
+    }
+}
+
+/// A run of consecutive messages from one side of the conversation.
+struct Turn<'a> {
+    role: Role,
+    timestamp: Option<&'a str>,
+    blocks: Vec<&'a TranscriptBlock>,
+}
+
+/// Group messages into turns, dropping blocks the options exclude.
+fn turns<'a>(transcript: &'a Transcript, options: &ExportOptions) -> Vec<Turn<'a>> {
+    let mut turns: Vec<Turn> = Vec::new();
+    // Speaker of the last message, whether or not any of its blocks were kept
+    let mut speaker: Option<Role> = None;
+    for message in &transcript.messages {
+        // Tool results are sent as user messages but belong to the caller's turn
+        let continues = message
+            .blocks
+            .iter()
+            .all(|b| matches!(b, TranscriptBlock::ToolResult { .. }));
+        let role = match speaker {
+            Some(role) if continues => role,
+            _ => message.role,
+        };
+        speaker = Some(role);
+
+        let blocks: Vec<&TranscriptBlock> = message
+            .blocks
+            .iter()
+            .filter(|b| options.includes(b))
+            .collect();
+        if blocks.is_empty() {
+            continue;
+        }
+        match turns.last_mut() {
+            Some(last) if last.role == role => last.blocks.extend(blocks),
+            _ => turns.push(Turn {
+                role,
+                timestamp: message.timestamp.as_deref(),
+                blocks,
+            }),
+        }
+    }
+    turns
+}
+
+fn role_name(role: Role) -> &'static str {
+    match role {
+        Role::User => "User",
+        Role::Assistant => "Assistant",
+    }
+}
+
+/// Render `transcript` in the given format.
+pub fn export(
+    conv: &Conversation,
+    transcript: &Transcript,
+    format: ExportFormat,
+    options: &ExportOptions,
+) -> Result<String> {
+    Ok(match format {
+        ExportFormat::Markdown => to_markdown(conv, transcript, options),
+        ExportFormat::Html => to_html(conv, transcript, options),
+        ExportFormat::Json => to_json(conv, transcript, options)?,
+    })
+}
+
+/// Default file name for an export: a slug of the title plus the short session ID.
+pub fn file_name(conv: &Conversation, format: ExportFormat) -> PathBuf {
+    let slug: String = conv
+        .display
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let slug: Vec<&str> = slug.split('-').filter(|s| !s.is_empty()).take(8).collect();
+    let short_id = &conv.session_id[..conv.session_id.len().min(8)];
+    let stem = if slug.is_empty() {
+        short_id.to_string()
+    } else {
+        format!("{}-{}", slug.join("-"), short_id)
+    };
+    PathBuf::from(format!("{}.{}", stem, format.extension()))
+}
+
+// =============================================================================
+// Markdown
+// =============================================================================
+
+/// A code fence longer than any backtick run in `content`.
+fn fence(content: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in content.chars() {
+        if c == '`' {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    "`".repeat((longest + 1).max(3))
+}
+
+fn code_block(out: &mut String, lang: &str, content: &str) {
+    let fence = fence(content);
+    let _ = writeln!(
+        out,
+        "{}{}\n{}\n{}\n",
+        fence,
+        lang,
+        content.trim_end(),
+        fence
+    );
+}
+
+fn to_markdown(conv: &Conversation, transcript: &Transcript, options: &ExportOptions) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", conv.display);
+    let _ = writeln!(out, "- Session: `{}`", conv.session_id);
+    let _ = writeln!(out, "- Project: `{}`", conv.project_path.display());
+    if let Some(ref branch) = conv.git_branch {
+        let _ = writeln!(out, "- Branch: `{}`", branch);
+    }
+    if !conv.modified.is_empty() {
+        let _ = writeln!(out, "- Modified: {}", conv.modified);
+    }
+    out.push('\n');
+
+    for turn in turns(transcript, options) {
+        let _ = writeln!(out, "## {}\n", role_name(turn.role));
+        for block in turn.blocks {
+            match block {
+                TranscriptBlock::Text(text) => {
+                    let _ = writeln!(out, "{}\n", text.trim());
+                }
+                TranscriptBlock::Thinking(text) => {
+                    out.push_str("> *Thinking*\n>\n");
+                    for line in text.trim().lines() {
+                        let _ = writeln!(out, "> {}", line);
+                    }
+                    out.push('\n');
+                }
+                TranscriptBlock::ToolUse {
+                    name,
+                    summary,
+                    input,
+                } => {
+                    if summary.is_empty() {
+                        let _ = writeln!(out, "**Tool: {}**\n", name);
+                    } else {
+                        let _ = writeln!(out, "**Tool: {}** `{}`\n", name, summary);
+                    }
+                    code_block(&mut out, "json", input);
+                }
+                TranscriptBlock::ToolResult { content, is_error } => {
+                    let label = if *is_error {
+                        "Tool error"
+                    } else {
+                        "Tool result"
+                    };
+                    let _ = writeln!(out, "*{}:*\n", label);
+                    code_block(&mut out, "", content);
+                }
+            }
+        }
+    }
+    out.trim_end().to_string() + "\n"
+}
+
+// =============================================================================
+// HTML
+// =============================================================================
+
+const HTML_STYLE: &str = "\
+body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;max-width:52rem;margin:2rem auto;padding:0 1rem;color:#1f2328;line-height:1.5}
+h1{font-size:1.5rem;margin-bottom:.25rem}
+.meta{color:#59636e;font-size:.875rem;margin-bottom:2rem}
+.meta code{font-size:.8rem}
+.turn{border-left:3px solid #d0d7de;padding:.25rem 0 .25rem 1rem;margin:1.5rem 0}
+.turn.user{border-color:#0969da}
+.turn.assistant{border-color:#8250df}
+.role{font-weight:600;font-size:.875rem;color:#59636e;margin-bottom:.5rem}
+.role time{font-weight:400;margin-left:.5rem}
+.text{white-space:pre-wrap;margin:.5rem 0}
+details{margin:.5rem 0;background:#f6f8fa;border-radius:6px;padding:.25rem .75rem}
+details.error{background:#ffebe9}
+summary{cursor:pointer;font-size:.875rem;color:#59636e}
+summary code{color:#1f2328}
+pre{white-space:pre-wrap;word-break:break-word;font-size:.8rem;margin:.5rem 0}
+.thinking{color:#59636e;font-style:italic}";
+
+/// Escape text for HTML element content and attribute values.
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn to_html(conv: &Conversation, transcript: &Transcript, options: &ExportOptions) -> String {
+    let mut out = String::new();
+    let title = escape_html(&conv.display);
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        title, HTML_STYLE, title
+    );
+
+    let _ = write!(
+        out,
+        "<div class=\"meta\">Session <code>{}</code> · <code>{}</code>",
+        escape_html(&conv.session_id),
+        escape_html(&conv.project_path.to_string_lossy())
+    );
+    if let Some(ref branch) = conv.git_branch {
+        let _ = write!(out, " · branch <code>{}</code>", escape_html(branch));
+    }
+    out.push_str("</div>\n");
+
+    for turn in turns(transcript, options) {
+        let role = role_name(turn.role);
+        let _ = write!(
+            out,
+            "<section class=\"turn {}\">\n<div class=\"role\">{}",
+            role.to_lowercase(),
+            role
+        );
+        if let Some(ts) = turn.timestamp {
+            let _ = write!(out, "<time>{}</time>", escape_html(ts));
+        }
+        out.push_str("</div>\n");
+
+        for block in turn.blocks {
+            match block {
+                TranscriptBlock::Text(text) => {
+                    let _ = writeln!(
+                        out,
+                        "<div class=\"text\">{}</div>",
+                        escape_html(text.trim())
+                    );
+                }
+                TranscriptBlock::Thinking(text) => {
+                    let _ = writeln!(
+                        out,
+                        "<details class=\"thinking\"><summary>Thinking</summary><pre>{}</pre></details>",
+                        escape_html(text.trim())
+                    );
+                }
+                TranscriptBlock::ToolUse {
+                    name,
+                    summary,
+                    input,
+                } => {
+                    let _ = writeln!(
+                        out,
+                        "<details><summary>{} <code>{}</code></summary><pre>{}</pre></details>",
+                        escape_html(name),
+                        escape_html(summary),
+                        escape_html(input)
+                    );
+                }
+                TranscriptBlock::ToolResult { content, is_error } => {
+                    let (class, label) = if *is_error {
+                        (" class=\"error\"", "Tool error")
+                    } else {
+                        ("", "Tool result")
+                    };
+                    let _ = writeln!(
+                        out,
+                        "<details{}><summary>{}</summary><pre>{}</pre></details>",
+                        class,
+                        label,
+                        escape_html(content.trim_end())
+                    );
+                }
+            }
+        }
+        out.push_str("</section>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+// =============================================================================
+// JSON
+// =============================================================================
+
+#[derive(Serialize)]
+struct JsonExport<'a> {
+    session_id: &'a str,
+    title: &'a str,
+    project: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    git_branch: Option<&'a str>,
+    turns: Vec<JsonTurn<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonTurn<'a> {
+    role: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<&'a str>,
+    blocks: Vec<JsonBlock<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum JsonBlock<'a> {
+    Text { text: &'a str },
+    Thinking { text: &'a str },
+    ToolUse { name: &'a str, input: Value },
+    ToolResult { content: &'a str, is_error: bool },
+}
+
+fn to_json(
+    conv: &Conversation,
+    transcript: &Transcript,
+    options: &ExportOptions,
+) -> Result<String> {
+    let turns = turns(transcript, options)
+        .into_iter()
+        .map(|turn| JsonTurn {
+            role: match turn.role {
+                Role::User => "user",
+                Role::Assistant => "assistant",
+            },
+            timestamp: turn.timestamp,
+            blocks: turn
+                .blocks
+                .into_iter()
+                .map(|block| match block {
+                    TranscriptBlock::Text(text) => JsonBlock::Text { text },
+                    TranscriptBlock::Thinking(text) => JsonBlock::Thinking { text },
+                    TranscriptBlock::ToolUse { name, input, .. } => JsonBlock::ToolUse {
+                        name,
+                        // The transcript keeps the input pretty-printed; restore the value
+                        input: serde_json::from_str(input)
+                            .unwrap_or_else(|_| Value::String(input.clone())),
+                    },
+                    TranscriptBlock::ToolResult { content, is_error } => JsonBlock::ToolResult {
+                        content,
+                        is_error: *is_error,
+                    },
+                })
+                .collect(),
+        })
+        .collect();
+
+    let export = JsonExport {
+        session_id: &conv.session_id,
+        title: &conv.display,
+        project: conv.project_path.to_string_lossy().into_owned(),
+        git_branch: conv.git_branch.as_deref(),
+        turns,
+    };
+    Ok(serde_json::to_string_pretty(&export)? + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::transcript::parse_line;
+
+    fn conversation() -> Conversation {
+        Conversation {
+            display: "Fix <login> flow".to_string(),
+            message_count: Some(4),
+            git_branch: Some("feat/login".to_string()),
+            ..Conversation::for_test("d90ed21d-ed03-4e94-87d7-dbc5de6cc828", "/work/app")
+        }
+    }
+
+    fn transcript() -> Transcript {
+        let lines = [
+            r#"{"type":"user","message":{"content":"Why does login fail?"}}"#,
+            r#"{"type":"assistant","message":{"content":[{"type":"thinking","thinking":"Check the handler"},{"type":"text","text":"Let me look."}]}}"#,
+            r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Bash","input":{"command":"cat login.rs"}}]}}"#,
+            r#"{"type":"user","message":{"content":[{"type":"tool_result","content":"fn login() { ``` }"}]}}"#,
+            r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Found it."}]}}"#,
+        ];
+        Transcript {
+            messages: lines.iter().filter_map(|l| parse_line(l)).collect(),
+        }
+    }
+
+    #[test]
+    fn markdown_merges_tool_results_into_the_assistant_turn() {
+        let options = ExportOptions {
+            tool_results: true,
+            ..ExportOptions::default()
+        };
+        let md = to_markdown(&conversation(), &transcript(), &options);
+        assert_eq!(md.matches("## Assistant").count(), 1);
+        assert_eq!(md.matches("## User").count(), 1);
+        assert!(md.contains("**Tool: Bash** `cat login.rs`"));
+        // Result contains a triple backtick, so the fence must be longer
+        assert!(md.contains("````\nfn login() { ``` }\n````"));
+        assert!(!md.contains("Check the handler"));
+    }
+
+    #[test]
+    fn options_omit_tool_calls_and_include_thinking() {
+        let options = ExportOptions {
+            tool_calls: false,
+            tool_results: false,
+            thinking: true,
+        };
+        let md = to_markdown(&conversation(), &transcript(), &options);
+        assert!(!md.contains("Bash"));
+        assert!(md.contains("> Check the handler"));
+    }
+
+    #[test]
+    fn html_escapes_content() {
+        let html = to_html(&conversation(), &transcript(), &ExportOptions::default());
+        assert!(html.contains("<title>Fix &lt;login&gt; flow</title>"));
+        assert!(!html.contains("<login>"));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn json_restores_tool_input_values() {
+        let json = to_json(&conversation(), &transcript(), &ExportOptions::default()).unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["git_branch"], "feat/login");
+        let assistant = &value["turns"][1];
+        assert_eq!(assistant["role"], "assistant");
+        let tool = &assistant["blocks"][1];
+        assert_eq!(tool["type"], "tool_use");
+        assert_eq!(tool["input"]["command"], "cat login.rs");
+    }
+
+    #[test]
+    fn file_name_slugs_title_and_infers_format() {
+        let name = file_name(&conversation(), ExportFormat::Html);
+        assert_eq!(name, PathBuf::from("fix-login-flow-d90ed21d.html"));
+        assert_eq!(
+            ExportFormat::from_path(Path::new("out/notes.md")),
+            Some(ExportFormat::Markdown)
+        );
+    }
+}
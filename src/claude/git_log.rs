@@ -0,0 +1,285 @@
+//! Git commits made while a conversation was active.
+//!
+//! A conversation's time span (created → last modified) is matched against
+//! `git log` in its project directory, which ties agent work to the commits
+//! it produced. The reverse lookup starts from a commit and finds the
+//! conversations that were active in one of the repository's worktrees when
+//! it was made.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::conversation::{parse_time, Conversation};
+
+/// Commits made this long after a conversation's last message still count
+/// towards it (the user often commits right after the agent finishes).
+const COMMIT_GRACE_MINUTES: i64 = 10;
+
+/// `git log` format: a record separator, then tab-free fields separated by
+/// unit separators. `--shortstat` output follows each header.
+const LOG_FORMAT: &str = "--format=%x1e%H%x1f%an%x1f%cI%x1f%s";
+
+/// Lines changed by a commit, from `git log --shortstat`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct DiffStat {
+    pub files_changed: u32,
+    pub insertions: u32,
+    pub deletions: u32,
+}
+
+impl DiffStat {
+    /// Compact summary, e.g. `3 files +10 -2`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} file{} +{} -{}",
+            self.files_changed,
+            if self.files_changed == 1 { "" } else { "s" },
+            self.insertions,
+            self.deletions
+        )
+    }
+}
+
+/// A single commit.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Commit {
+    pub hash: String,
+    pub author: String,
+    /// Committer date (what `--since`/`--until` filter on)
+    pub time: DateTime<Utc>,
+    pub subject: String,
+    #[serde(flatten)]
+    pub stat: DiffStat,
+}
+
+impl Commit {
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+}
+
+/// Commits on the conversation's branch made while it was active, oldest
+/// first. Without a recorded branch the checked-out one is used; once the
+/// branch is gone every ref is searched.
+///
+/// Runs `git rev-parse` and `git log` and waits for them; the TUI calls this
+/// on the UI thread when the commits modal opens.
+pub fn session_commits(conv: &Conversation) -> Result<Vec<Commit>> {
+    let Some((start, end)) = conv.active_span() else {
+        bail!("Conversation has no timestamps");
+    };
+    let rev = match conv.git_branch.as_deref() {
+        None | Some("" | "HEAD") => "HEAD".to_string(),
+        Some(branch) if branch_exists(&conv.project_path, branch) => branch.to_string(),
+        Some(_) => "--all".to_string(),
+    };
+    commits_between(
+        &conv.project_path,
+        &rev,
+        start,
+        end + Duration::minutes(COMMIT_GRACE_MINUTES),
+    )
+}
+
+/// Commits reachable from `rev` (a revision or `--all`) in `dir` committed
+/// between `since` and `until`, oldest first.
+pub fn commits_between(
+    dir: &Path,
+    rev: &str,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<Vec<Commit>> {
+    let output = git(
+        dir,
+        &[
+            "log",
+            "--reverse",
+            "--shortstat",
+            LOG_FORMAT,
+            &format!("--since={}", since.to_rfc3339()),
+            &format!("--until={}", until.to_rfc3339()),
+            rev,
+            "--",
+        ],
+    )?;
+    Ok(parse_log(&output))
+}
+
+/// Whether `branch` is a local branch in the repository containing `dir`.
+fn branch_exists(dir: &Path, branch: &str) -> bool {
+    git(
+        dir,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{}", branch),
+        ],
+    )
+    .is_ok()
+}
+
+/// Look up a single commit (any revision `git` understands) in `dir`.
+pub fn find_commit(dir: &Path, rev: &str) -> Result<Commit> {
+    let output = git(dir, &["log", "-1", "--shortstat", LOG_FORMAT, rev, "--"])?;
+    parse_log(&output)
+        .into_iter()
+        .next()
+        .with_context(|| format!("No commit '{}'", rev))
+}
+
+/// Every worktree of the repository containing `dir`.
+pub fn worktree_paths(dir: &Path) -> Result<Vec<PathBuf>> {
+    let output = git(dir, &["worktree", "list", "--porcelain"])?;
+    Ok(output
+        .lines()
+        .filter_map(|line| line.strip_prefix("worktree "))
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// Conversations in one of `worktrees` that were active at `time`, best
+/// match first: those still running at the time before those that had
+/// just finished, then the most recently started.
+pub fn conversations_active_at<'a>(
+    conversations: impl IntoIterator<Item = &'a Conversation>,
+    worktrees: &[PathBuf],
+    time: DateTime<Utc>,
+) -> Vec<&'a Conversation> {
+    let grace = Duration::minutes(COMMIT_GRACE_MINUTES);
+    let mut matches: Vec<(&Conversation, DateTime<Utc>, DateTime<Utc>)> = conversations
+        .into_iter()
+        .filter(|c| worktrees.iter().any(|w| c.project_path.starts_with(w)))
+        .filter_map(|c| c.active_span().map(|(start, end)| (c, start, end)))
+        .filter(|(_, start, end)| *start <= time && time <= *end + grace)
+        .collect();
+    matches.sort_by_key(|(_, start, end)| (time > *end, std::cmp::Reverse(*start)));
+    matches.into_iter().map(|(c, _, _)| c).collect()
+}
+
+/// Run git in `dir` and return its stdout.
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    // `parse_shortstat` reads git's English wording
+    let output = Command::new("git")
+        .env("LC_ALL", "C")
+        .env("LANGUAGE", "C")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git {} failed: {}", args[0], stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parse `git log --shortstat` output written with [`LOG_FORMAT`].
+fn parse_log(output: &str) -> Vec<Commit> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let mut fields = lines.next()?.splitn(4, '\x1f');
+            let hash = fields.next()?.to_string();
+            let author = fields.next()?.to_string();
+            let time = parse_time(fields.next()?)?;
+            let subject = fields.next().unwrap_or_default().to_string();
+            // Merges and empty commits have no stat line
+            let stat = lines
+                .find(|l| l.contains("changed"))
+                .map(parse_shortstat)
+                .unwrap_or_default();
+            Some(Commit {
+                hash,
+                author,
+                time,
+                subject,
+                stat,
+            })
+        })
+        .collect()
+}
+
+/// Parse ` 3 files changed, 10 insertions(+), 2 deletions(-)`.
+fn parse_shortstat(line: &str) -> DiffStat {
+    let mut stat = DiffStat::default();
+    for part in line.split(',') {
+        let mut words = part.split_whitespace();
+        let (Some(count), Some(kind)) = (words.next(), words.next()) else {
+            continue;
+        };
+        let Ok(count) = count.parse() else {
+            continue;
+        };
+        if kind.starts_with("file") {
+            stat.files_changed = count;
+        } else if kind.starts_with("insertion") {
+            stat.insertions = count;
+        } else if kind.starts_with("deletion") {
+            stat.deletions = count;
+        }
+    }
+    stat
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conversation(project: &str, created: &str, modified: &str) -> Conversation {
+        Conversation {
+            created: created.to_string(),
+            modified: modified.to_string(),
+            ..Conversation::for_test(&format!("{}-{}", project, created), project)
+        }
+    }
+
+    fn time(text: &str) -> DateTime<Utc> {
+        parse_time(text).unwrap()
+    }
+
+    #[test]
+    fn parse_log_reads_headers_and_shortstats() {
+        let output = "\x1eabc1234def\x1fAda\x1f2026-03-01T10:00:00+01:00\x1fAdd login\n\n \
+                      3 files changed, 10 insertions(+), 2 deletions(-)\n\
+                      \x1e0123456789\x1fBob\x1f2026-03-01T11:00:00Z\x1fMerge branch 'x'\n\
+                      \x1efedcba9876\x1fAda\x1f2026-03-01T12:00:00Z\x1fDrop a field\n\n \
+                      1 file changed, 4 deletions(-)\n";
+        let commits = parse_log(output);
+        assert_eq!(commits.len(), 3);
+
+        assert_eq!(commits[0].short_hash(), "abc1234");
+        assert_eq!(commits[0].author, "Ada");
+        assert_eq!(commits[0].time, time("2026-03-01T09:00:00Z"));
+        assert_eq!(commits[0].subject, "Add login");
+        assert_eq!(commits[0].stat.summary(), "3 files +10 -2");
+
+        assert_eq!(commits[1].stat, DiffStat::default());
+        assert_eq!(commits[2].stat.summary(), "1 file +0 -4");
+    }
+
+    #[test]
+    fn conversations_active_at_prefers_running_sessions_in_the_worktree() {
+        let conversations = [
+            // Finished shortly before the commit
+            conversation("/repo", "2026-03-01T08:00:00Z", "2026-03-01T09:55:00Z"),
+            // Running at the time, in a subdirectory of the worktree
+            conversation("/repo/web", "2026-03-01T09:30:00Z", "2026-03-01T11:00:00Z"),
+            // Running, but in another repository
+            conversation("/other", "2026-03-01T09:00:00Z", "2026-03-01T11:00:00Z"),
+            // Finished too long before
+            conversation("/repo", "2026-03-01T07:00:00Z", "2026-03-01T08:00:00Z"),
+        ];
+        let worktrees = [PathBuf::from("/repo"), PathBuf::from("/repo-feature")];
+
+        let found =
+            conversations_active_at(&conversations, &worktrees, time("2026-03-01T10:00:00Z"));
+        let projects: Vec<_> = found.iter().map(|c| c.project_path.as_path()).collect();
+        assert_eq!(projects, [Path::new("/repo/web"), Path::new("/repo")]);
+    }
+}
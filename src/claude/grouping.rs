@@ -321,7 +321,7 @@ pub fn group_conversations_unordered(conversations: Vec<Conversation>) -> Vec<Co
     for group in &mut result {
         group
             .conversations_mut()
-            .sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+            .sort_by_key(|c| std::cmp::Reverse(c.timestamp));
     }
 
     result
@@ -389,17 +389,12 @@ mod tests {
 
     fn make_conversation(project_path: &str, timestamp: i64) -> Conversation {
         Conversation {
-            session_id: format!("session-{}", timestamp),
             display: format!("Conv at {}", timestamp),
-            summary: None,
             timestamp,
+            created: format!("2024-01-01T00:00:{}Z", timestamp),
             modified: format!("2024-01-01T00:00:{}Z", timestamp),
-            project_path: PathBuf::from(project_path),
-            message_count: 1,
-            git_branch: None,
-            is_plan_implementation: false,
-            is_archived: false,
-            archived_at: None,
+            message_count: Some(1),
+            ..Conversation::for_test(&format!("session-{}", timestamp), project_path)
         }
     }
 
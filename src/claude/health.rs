@@ -0,0 +1,384 @@
+//! Health of a conversation, judged by how its last turn ended.
+//!
+//! Only the tail of each session log is read: the last conversation line
+//! tells whether the user interrupted the agent, the API returned an error,
+//! a tool call failed, or the assistant never finished its turn. Such
+//! conversations are flagged so abandoned work gets picked back up.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::conversation::parse_time;
+use super::grouping::ConversationGroup;
+
+/// Bytes read from the end of a log at first. Grown when the last
+/// conversation line is longer (e.g. a large tool result).
+const TAIL_BYTES: u64 = 64 * 1024;
+
+/// A turn that stopped mid-way (failed tool call, unanswered prompt) only
+/// counts once the log has been quiet this long; before that the agent may
+/// still be working on it.
+const SETTLE_MINUTES: i64 = 5;
+
+/// Why a conversation needs attention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Health {
+    /// The user interrupted the last turn
+    Interrupted,
+    /// The last turn ended in an API error
+    ApiError,
+    /// The last tool call failed and the agent never followed up
+    ToolFailed,
+    /// The last assistant turn never completed
+    Incomplete,
+}
+
+impl Health {
+    /// Short description for badges and detail views.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Interrupted => "interrupted",
+            Self::ApiError => "API error",
+            Self::ToolFailed => "tool call failed",
+            Self::Incomplete => "turn never completed",
+        }
+    }
+
+    /// Whether the state may still resolve itself while the agent is working.
+    fn is_transient(self) -> bool {
+        matches!(self, Self::ToolFailed | Self::Incomplete)
+    }
+}
+
+/// How a log ends: the state of its last conversation line and when it was written.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Ending {
+    health: Option<Health>,
+    at: Option<DateTime<Utc>>,
+}
+
+impl Ending {
+    /// The health to report at `now`, leaving out states that may be transient.
+    fn health_at(&self, now: DateTime<Utc>) -> Option<Health> {
+        let health = self.health?;
+        let settled = self
+            .at
+            .is_none_or(|at| now - at >= Duration::minutes(SETTLE_MINUTES));
+        (!health.is_transient() || settled).then_some(health)
+    }
+}
+
+/// Read how the log at `path` ends.
+fn read_ending(path: &Path) -> Ending {
+    find_last(path, ending_of).unwrap_or_default()
+}
+
+/// The result of `f` for the last line of the log at `path` it accepts,
+/// reading only as much of the file's tail as needed.
+pub(super) fn find_last<T>(path: &Path, f: impl Fn(&Value) -> Option<T>) -> Option<T> {
+    let mut file = File::open(path).ok()?;
+    let len = file.metadata().map_or(0, |m| m.len());
+    let mut window = TAIL_BYTES;
+    loop {
+        let start = len.saturating_sub(window);
+        let mut bytes = Vec::new();
+        file.seek(SeekFrom::Start(start)).ok()?;
+        file.read_to_end(&mut bytes).ok()?;
+        let text = String::from_utf8_lossy(&bytes);
+        // The first line is cut off unless the whole file was read
+        let lines: Vec<&str> = text.lines().skip(usize::from(start > 0)).collect();
+        let found = lines
+            .into_iter()
+            .rev()
+            .find_map(|line| serde_json::from_str::<Value>(line).ok().and_then(|v| f(&v)));
+        if found.is_some() || start == 0 {
+            return found;
+        }
+        window *= 4;
+    }
+}
+
+/// The ending a log line represents, or `None` for lines that aren't part
+/// of the main conversation (sidechains, meta and local command output,
+/// snapshots, summaries).
+fn ending_of(value: &Value) -> Option<Ending> {
+    let kind = value.get("type").and_then(Value::as_str)?;
+    if kind != "user" && kind != "assistant" {
+        return None;
+    }
+    let flag = |key: &str| value.get(key).and_then(Value::as_bool) == Some(true);
+    if flag("isSidechain") || flag("isMeta") {
+        return None;
+    }
+    let at = value
+        .get("timestamp")
+        .and_then(Value::as_str)
+        .and_then(parse_time);
+    let message = value.get("message")?;
+    let content = message.get("content")?;
+
+    let health = if kind == "user" {
+        user_health(content)?
+    } else if flag("isApiErrorMessage")
+        || message.get("model").and_then(Value::as_str) == Some("<synthetic>")
+            && texts(content).any(|t| t.starts_with("API Error"))
+    {
+        Some(Health::ApiError)
+    } else {
+        // A turn ends with text; a trailing tool call or thinking block means
+        // the assistant was cut off
+        let last = content
+            .as_array()
+            .and_then(|items| items.last())
+            .and_then(|item| item.get("type"))
+            .and_then(Value::as_str);
+        match last {
+            Some("tool_use" | "thinking" | "redacted_thinking") => Some(Health::Incomplete),
+            _ => None,
+        }
+    };
+    Some(Ending { health, at })
+}
+
+/// Health after a user line: `None` (outer) for lines to skip.
+fn user_health(content: &Value) -> Option<Option<Health>> {
+    let results: Vec<&Value> = content
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter(|item| item.get("type").and_then(Value::as_str) == Some("tool_result"))
+                .collect()
+        })
+        .unwrap_or_default();
+    if !results.is_empty() {
+        let failed = results
+            .iter()
+            .any(|r| r.get("is_error").and_then(Value::as_bool) == Some(true));
+        return Some(Some(if failed {
+            Health::ToolFailed
+        } else {
+            Health::Incomplete
+        }));
+    }
+
+    let text = texts(content).next()?.trim();
+    if text.starts_with("[Request interrupted by user") {
+        Some(Some(Health::Interrupted))
+    } else if text.starts_with("<command-") || text.starts_with("<local-command-") {
+        // Slash commands handled locally get no assistant reply
+        None
+    } else {
+        // A prompt nobody answered
+        Some(Some(Health::Incomplete))
+    }
+}
+
+/// The text of a message's content: a plain string or its text blocks.
+pub(super) fn texts(content: &Value) -> Box<dyn Iterator<Item = &str> + '_> {
+    match content {
+        Value::String(text) => Box::new(std::iter::once(text.as_str())),
+        Value::Array(items) => Box::new(
+            items
+                .iter()
+                .filter(|item| item.get("type").and_then(Value::as_str) == Some("text"))
+                .filter_map(|item| item.get("text").and_then(Value::as_str)),
+        ),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+fn stamp_of(path: &Path) -> Option<(u64, SystemTime)> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.len(), meta.modified().ok()?))
+}
+
+/// Sets the health of conversations, re-reading a log's tail only when it changed.
+#[derive(Debug, Default)]
+pub struct HealthTracker {
+    endings: HashMap<PathBuf, (Option<(u64, SystemTime)>, Ending)>,
+}
+
+impl HealthTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set `health` on every conversation in `groups`. A session in
+    /// `running_sessions` with an unfinished turn is likely just waiting at a
+    /// permission prompt, so it isn't flagged as incomplete.
+    pub fn refresh(
+        &mut self,
+        groups: &mut [ConversationGroup],
+        running_sessions: &HashSet<String>,
+    ) {
+        let now = Utc::now();
+        let mut seen: HashSet<PathBuf> = HashSet::new();
+        for conv in groups
+            .iter_mut()
+            .flat_map(ConversationGroup::conversations_mut)
+        {
+            let stamp = stamp_of(&conv.file_path);
+            let cached = self
+                .endings
+                .get(&conv.file_path)
+                .filter(|(cached, _)| cached.is_some() && *cached == stamp)
+                .map(|(_, ending)| *ending);
+            let ending = cached.unwrap_or_else(|| {
+                let ending = read_ending(&conv.file_path);
+                self.endings.insert(conv.file_path.clone(), (stamp, ending));
+                ending
+            });
+            seen.insert(conv.file_path.clone());
+            let running = running_sessions.contains(&conv.session_id);
+            conv.health = ending
+                .health_at(now)
+                .filter(|health| !(running && *health == Health::Incomplete));
+        }
+        self.endings.retain(|path, _| seen.contains(path));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::conversation::Conversation;
+
+    fn ending(line: &str) -> Option<Option<Health>> {
+        ending_of(&serde_json::from_str(line).unwrap()).map(|e| e.health)
+    }
+
+    #[test]
+    fn ending_of_classifies_the_last_conversation_line() {
+        assert_eq!(
+            ending(
+                r#"{"type":"user","message":{"content":[{"type":"text","text":"[Request interrupted by user for tool use]"}]}}"#
+            ),
+            Some(Some(Health::Interrupted))
+        );
+        assert_eq!(
+            ending(
+                r#"{"type":"assistant","isApiErrorMessage":true,"message":{"model":"<synthetic>","content":[{"type":"text","text":"API Error: 529 Overloaded"}]}}"#
+            ),
+            Some(Some(Health::ApiError))
+        );
+        assert_eq!(
+            ending(
+                r#"{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"t1","is_error":true,"content":"exit 1"}]}}"#
+            ),
+            Some(Some(Health::ToolFailed))
+        );
+        assert_eq!(
+            ending(
+                r#"{"type":"assistant","message":{"content":[{"type":"tool_use","id":"t1","name":"Bash","input":{}}]}}"#
+            ),
+            Some(Some(Health::Incomplete))
+        );
+        assert_eq!(
+            ending(r#"{"type":"user","message":{"content":"Add a login page"}}"#),
+            Some(Some(Health::Incomplete))
+        );
+        assert_eq!(
+            ending(
+                r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Done."}]}}"#
+            ),
+            Some(None)
+        );
+
+        // Not part of the main conversation
+        assert_eq!(
+            ending(
+                r#"{"type":"user","message":{"content":"<local-command-stdout>Total cost: $0.12</local-command-stdout>"}}"#
+            ),
+            None
+        );
+        assert_eq!(
+            ending(
+                r#"{"type":"assistant","isSidechain":true,"message":{"content":[{"type":"tool_use","id":"t1","name":"Bash","input":{}}]}}"#
+            ),
+            None
+        );
+        assert_eq!(ending(r#"{"type":"summary","summary":"Login page"}"#), None);
+    }
+
+    #[test]
+    fn transient_states_wait_until_the_log_settles() {
+        let at = parse_time("2026-03-01T10:00:00Z");
+        let failed = Ending {
+            health: Some(Health::ToolFailed),
+            at,
+        };
+        let interrupted = Ending {
+            health: Some(Health::Interrupted),
+            at,
+        };
+        let soon = parse_time("2026-03-01T10:01:00Z").unwrap();
+        let later = parse_time("2026-03-01T10:10:00Z").unwrap();
+
+        assert_eq!(failed.health_at(soon), None);
+        assert_eq!(failed.health_at(later), Some(Health::ToolFailed));
+        assert_eq!(interrupted.health_at(soon), Some(Health::Interrupted));
+    }
+
+    #[test]
+    fn running_sessions_are_not_flagged_incomplete() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.jsonl");
+        std::fs::write(
+            &path,
+            concat!(
+                r#"{"type":"assistant","timestamp":"2026-03-01T10:00:00Z","message":{"content":[{"type":"tool_use","id":"t1","name":"Bash","input":{}}]}}"#,
+                "\n"
+            ),
+        )
+        .unwrap();
+        let mut groups = vec![ConversationGroup::Directory {
+            parent: "work".to_string(),
+            project: "app".to_string(),
+            conversations: vec![Conversation {
+                file_path: path,
+                ..Conversation::for_test("s1", "/work/app")
+            }],
+        }];
+        let health = |groups: &[ConversationGroup]| groups[0].conversations()[0].health;
+
+        let mut tracker = HealthTracker::new();
+        tracker.refresh(&mut groups, &HashSet::new());
+        assert_eq!(health(&groups), Some(Health::Incomplete));
+        tracker.refresh(&mut groups, &HashSet::from(["s1".to_string()]));
+        assert_eq!(health(&groups), None);
+    }
+
+    #[test]
+    fn read_ending_skips_trailing_lines_outside_the_conversation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let big = "x".repeat(TAIL_BYTES as usize * 2);
+        std::fs::write(
+            &path,
+            format!(
+                concat!(
+                    r#"{{"type":"user","timestamp":"2026-03-01T10:00:00Z","message":{{"content":"Run it"}}}}"#,
+                    "\n",
+                    r#"{{"type":"user","timestamp":"2026-03-01T10:00:05Z","message":{{"content":"[Request interrupted by user]"}}}}"#,
+                    "\n",
+                    r#"{{"type":"summary","summary":"{}"}}"#,
+                    "\n",
+                ),
+                big
+            ),
+        )
+        .unwrap();
+
+        let ending = read_ending(&path);
+        assert_eq!(ending.health, Some(Health::Interrupted));
+        assert_eq!(ending.at, parse_time("2026-03-01T10:00:05Z"));
+    }
+}
@@ -0,0 +1,266 @@
+//! Usage and tool stats of every conversation, from one pass over the logs.
+//!
+//! Each log is read incrementally (see `log_tail`) and every appended line
+//! goes to both the usage and the tool call parser, so keeping both up to
+//! date costs a single read of whatever the logs gained. The TUI reads logs
+//! on a background thread (`BackgroundLogStats`) and shows the stats of the
+//! last finished refresh in the meantime.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use chrono::Local;
+
+use super::grouping::ConversationGroup;
+use super::log_tail::{LogChange, LogTail};
+use super::tool_stats::{ToolLog, ToolStats};
+use super::usage::{PriceTable, SessionUsage, UsageEvent, UsageLog};
+
+/// A conversation's log to bring up to date.
+#[derive(Debug, Clone)]
+struct LogFile {
+    session_id: String,
+    path: PathBuf,
+    mtime: i64,
+}
+
+fn log_files(groups: &[ConversationGroup]) -> Vec<LogFile> {
+    groups
+        .iter()
+        .flat_map(ConversationGroup::conversations)
+        .map(|conv| LogFile {
+            session_id: conv.session_id.clone(),
+            path: conv.file_path.clone(),
+            mtime: conv.timestamp,
+        })
+        .collect()
+}
+
+/// Parse state of one conversation's log.
+#[derive(Debug, Default)]
+struct SessionLog {
+    tail: LogTail,
+    usage: UsageLog,
+    tools: ToolLog,
+}
+
+/// Per-session usage and tool stats, as of the last refresh.
+#[derive(Debug, Clone, Default)]
+pub struct LogStats {
+    usage: HashMap<String, SessionUsage>,
+    tools: HashMap<String, ToolStats>,
+    /// Messages from the last 24 hours across all sessions
+    recent: Vec<UsageEvent>,
+}
+
+impl LogStats {
+    /// Usage of one session.
+    pub fn usage(&self, session_id: &str) -> Option<&SessionUsage> {
+        self.usage.get(session_id)
+    }
+
+    /// Tool stats of one session.
+    pub fn tools(&self, session_id: &str) -> Option<&ToolStats> {
+        self.tools.get(session_id)
+    }
+
+    /// Messages from the last 24 hours across all sessions, in no particular order.
+    pub fn recent_events(&self) -> impl Iterator<Item = &UsageEvent> {
+        self.recent.iter()
+    }
+
+    /// Cost of every tracked session, keyed by session ID.
+    pub fn session_costs(&self, prices: &PriceTable) -> HashMap<String, f64> {
+        self.usage
+            .iter()
+            .map(|(id, usage)| (id.clone(), usage.cost(prices)))
+            .collect()
+    }
+}
+
+/// Parse state of every known conversation's log, kept up to date
+/// incrementally.
+#[derive(Debug, Default)]
+pub struct LogStatsTracker {
+    sessions: HashMap<String, SessionLog>,
+}
+
+impl LogStatsTracker {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bring stats up to date with the conversations' logs. Unchanged logs
+    /// are skipped, grown logs only have their new tail parsed, and sessions
+    /// no longer present are dropped.
+    pub fn refresh(&mut self, groups: &[ConversationGroup]) {
+        self.refresh_files(&log_files(groups));
+    }
+
+    fn refresh_files(&mut self, files: &[LogFile]) {
+        let mut present = HashSet::new();
+        for file in files {
+            present.insert(file.session_id.as_str());
+            self.refresh_file(file);
+        }
+        self.sessions.retain(|id, _| present.contains(id.as_str()));
+
+        let now = Local::now().timestamp_millis();
+        for log in self.sessions.values_mut() {
+            log.usage.prune_recent(now);
+        }
+    }
+
+    /// Read what one log gained since it was last read.
+    fn refresh_file(&mut self, file: &LogFile) {
+        let log = match self.sessions.entry(file.session_id.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) if file.path.exists() => entry.insert(SessionLog::default()),
+            Entry::Vacant(_) => return,
+        };
+        match log.tail.check(&file.path, file.mtime) {
+            LogChange::Unchanged => return,
+            LogChange::Replaced => {
+                log.usage = UsageLog::default();
+                log.tools = ToolLog::default();
+            }
+            LogChange::Appended => {}
+        }
+        let SessionLog { tail, usage, tools } = log;
+        // Unreadable logs keep their last known stats
+        let _ = tail.read(|line| {
+            usage.read_line(line);
+            tools.read_line(line);
+        });
+    }
+
+    /// The stats parsed so far.
+    pub fn stats(&self) -> LogStats {
+        let mut stats = LogStats::default();
+        for (id, log) in &self.sessions {
+            stats.usage.insert(id.clone(), log.usage.usage.clone());
+            stats.tools.insert(id.clone(), log.tools.stats.clone());
+            stats.recent.extend_from_slice(&log.usage.recent);
+        }
+        stats
+    }
+}
+
+/// Log stats refreshed on a background thread, so the UI never waits for
+/// logs to be read.
+#[derive(Default)]
+pub struct BackgroundLogStats {
+    stats: LogStats,
+    /// The tracker, while no refresh is running with it
+    tracker: Option<LogStatsTracker>,
+    pending: Option<Receiver<(LogStatsTracker, LogStats)>>,
+    /// Logs to read once the running refresh finishes
+    queued: Option<Vec<LogFile>>,
+}
+
+impl BackgroundLogStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stats of the last finished refresh.
+    pub fn stats(&self) -> &LogStats {
+        &self.stats
+    }
+
+    /// Start reading the conversations' changed logs on a background thread;
+    /// see [`Self::poll_refresh`]. While one refresh runs, another is queued
+    /// to follow it.
+    pub fn start_refresh(&mut self, groups: &[ConversationGroup]) {
+        let files = log_files(groups);
+        if self.pending.is_some() {
+            self.queued = Some(files);
+        } else {
+            self.spawn(files);
+        }
+    }
+
+    fn spawn(&mut self, files: Vec<LogFile>) {
+        // A refresh that died took the tracker with it; start over
+        let mut tracker = self.tracker.take().unwrap_or_default();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            tracker.refresh_files(&files);
+            let stats = tracker.stats();
+            let _ = tx.send((tracker, stats));
+        });
+        self.pending = Some(rx);
+    }
+
+    /// Take the stats of a finished background refresh. Returns whether
+    /// they changed.
+    pub fn poll_refresh(&mut self) -> bool {
+        let Some(ref rx) = self.pending else {
+            return false;
+        };
+        let updated = match rx.try_recv() {
+            Ok((tracker, stats)) => {
+                self.tracker = Some(tracker);
+                self.stats = stats;
+                true
+            }
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => false,
+        };
+        self.pending = None;
+        if let Some(files) = self.queued.take() {
+            self.spawn(files);
+        }
+        updated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::conversation::Conversation;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn background_refresh_reads_usage_and_tools_in_one_pass() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.jsonl");
+        std::fs::write(
+            &path,
+            concat!(
+                r#"{"type":"assistant","timestamp":"2026-09-01T12:00:00Z","message":{"id":"m1","model":"claude-sonnet-4","#,
+                r#""usage":{"input_tokens":10,"output_tokens":20},"#,
+                r#""content":[{"type":"tool_use","id":"t1","name":"Bash","input":{}}]}}"#,
+                "\n"
+            ),
+        )
+        .unwrap();
+        let groups = vec![ConversationGroup::Directory {
+            parent: "work".to_string(),
+            project: "app".to_string(),
+            conversations: vec![Conversation {
+                timestamp: 1,
+                file_path: path,
+                ..Conversation::for_test("s1", "/work/app")
+            }],
+        }];
+
+        let mut background = BackgroundLogStats::new();
+        background.start_refresh(&groups);
+        // Asked for again while the first refresh runs: queued behind it
+        background.start_refresh(&groups);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !background.poll_refresh() {
+            assert!(Instant::now() < deadline, "refresh never finished");
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        let stats = background.stats();
+        assert_eq!(stats.usage("s1").unwrap().total().input, 10);
+        assert_eq!(stats.tools("s1").unwrap().calls_of("Bash"), 1);
+    }
+}
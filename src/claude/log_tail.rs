@@ -0,0 +1,119 @@
+//! Incremental reading of session logs.
+//!
+//! Session logs only ever grow, so a reader remembers how far it got and
+//! only parses what was appended since. A log that shrank or was replaced
+//! by another file is read again from the start.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// What a log gained since it was last read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogChange {
+    /// Nothing new (or the file can't be read)
+    Unchanged,
+    /// Lines were appended
+    Appended,
+    /// The log is another file or shrank; whatever was parsed from it
+    /// before no longer applies
+    Replaced,
+}
+
+/// How far one session log has been read.
+#[derive(Debug, Clone, Default)]
+pub struct LogTail {
+    path: PathBuf,
+    mtime: i64,
+    /// Bytes parsed so far (always ends at a line boundary)
+    parsed_bytes: u64,
+}
+
+impl LogTail {
+    /// Compare the log at `path`, last modified at `mtime`, with what was
+    /// read. A replaced log is read from the start next.
+    pub fn check(&mut self, path: &Path, mtime: i64) -> LogChange {
+        let Ok(meta) = std::fs::metadata(path) else {
+            return LogChange::Unchanged;
+        };
+        let size = meta.len();
+        let change = if self.path != path || size < self.parsed_bytes {
+            *self = Self {
+                path: path.to_path_buf(),
+                ..Self::default()
+            };
+            LogChange::Replaced
+        } else if self.mtime == mtime && self.parsed_bytes == size {
+            return LogChange::Unchanged;
+        } else {
+            LogChange::Appended
+        };
+        self.mtime = mtime;
+        change
+    }
+
+    /// Pass every complete line appended since the last read to `on_line`.
+    pub fn read(&mut self, on_line: impl FnMut(&str)) -> Result<()> {
+        read_lines(&self.path, &mut self.parsed_bytes, on_line)
+    }
+}
+
+/// Pass each complete line of `path` from byte `offset` on to `on_line`
+/// (decoded lossily, without its line ending), advancing `offset` past it.
+/// Stops at EOF or a partially written last line.
+pub fn read_lines(path: &Path, offset: &mut u64, mut on_line: impl FnMut(&str)) -> Result<()> {
+    let mut file = File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
+    file.seek(SeekFrom::Start(*offset))?;
+    let mut reader = BufReader::new(file);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        let n = reader.read_until(b'\n', &mut buf)?;
+        if n == 0 || buf.last() != Some(&b'\n') {
+            break;
+        }
+        *offset += n as u64;
+        on_line(String::from_utf8_lossy(&buf).trim_end());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn read_all(tail: &mut LogTail) -> Vec<String> {
+        let mut lines = Vec::new();
+        tail.read(|line| lines.push(line.to_string())).unwrap();
+        lines
+    }
+
+    #[test]
+    fn reads_only_complete_appended_lines_and_restarts_on_a_shrunk_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.jsonl");
+        std::fs::write(&path, "one\ntwo\nhalf").unwrap();
+
+        let mut tail = LogTail::default();
+        assert_eq!(tail.check(&path, 1), LogChange::Replaced);
+        assert_eq!(read_all(&mut tail), vec!["one", "two"]);
+        assert_eq!(tail.check(&path, 1), LogChange::Appended);
+        assert!(read_all(&mut tail).is_empty());
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(b"-done\n").unwrap();
+        assert_eq!(tail.check(&path, 2), LogChange::Appended);
+        assert_eq!(read_all(&mut tail), vec!["half-done"]);
+        assert_eq!(tail.check(&path, 2), LogChange::Unchanged);
+
+        std::fs::write(&path, "new\n").unwrap();
+        assert_eq!(tail.check(&path, 3), LogChange::Replaced);
+        assert_eq!(read_all(&mut tail), vec!["new"]);
+    }
+}
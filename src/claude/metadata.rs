@@ -0,0 +1,217 @@
+//! User-editable conversation metadata: a custom title, tags and a note.
+//!
+//! Kept in a sidecar file next to the archive state rather than in Claude's
+//! own files, so it survives Claude rewriting its session index.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Labels the user has given a single session
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationMeta {
+    /// Replaces the summary / first prompt as the conversation's title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Free-form, possibly multi-line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl ConversationMeta {
+    /// Build metadata from edited text, dropping blank fields and repeated tags.
+    pub fn new(title: &str, tags: &str, note: &str) -> Self {
+        let non_empty = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+        Self {
+            title: non_empty(title),
+            tags: parse_tags(tags),
+            note: non_empty(note),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.tags.is_empty() && self.note.is_none()
+    }
+
+    /// Tags as typed in the editor.
+    pub fn tags_text(&self) -> String {
+        format_tags(&self.tags)
+    }
+}
+
+/// Tags as shown to the user: `#a #b`.
+pub fn format_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|t| format!("#{}", t))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split tag text on commas and whitespace, dropping `#` prefixes and duplicates.
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|t| t.trim_start_matches('#'))
+        .filter(|t| !t.is_empty())
+    {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// Full metadata state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataState {
+    pub version: u32,
+    pub sessions: HashMap<String, ConversationMeta>,
+}
+
+impl Default for MetadataState {
+    fn default() -> Self {
+        Self {
+            version: 1,
+            sessions: HashMap::new(),
+        }
+    }
+}
+
+/// Metadata manager for persistence
+pub struct MetadataManager {
+    metadata_path: PathBuf,
+    state: MetadataState,
+    dirty: bool,
+}
+
+impl MetadataManager {
+    /// Create a new MetadataManager, loading existing state or creating defaults
+    pub fn new(claude_dir: &Path) -> Result<Self> {
+        let metadata_path = claude_dir.join("claudatui-metadata.json");
+
+        let state = if metadata_path.exists() {
+            let content = fs::read_to_string(&metadata_path)
+                .with_context(|| format!("Failed to read metadata file: {:?}", metadata_path))?;
+
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse metadata file: {:?}", metadata_path))?
+        } else {
+            MetadataState::default()
+        };
+
+        Ok(Self {
+            metadata_path,
+            state,
+            dirty: false,
+        })
+    }
+
+    /// Get the metadata for a session (if any was set)
+    pub fn get(&self, session_id: &str) -> Option<&ConversationMeta> {
+        self.state.sessions.get(session_id)
+    }
+
+    /// Replace a session's metadata; empty metadata removes the entry
+    pub fn set(&mut self, session_id: &str, meta: ConversationMeta) {
+        if self.get(session_id) == Some(&meta) {
+            return;
+        }
+        if meta.is_empty() {
+            if self.state.sessions.remove(session_id).is_none() {
+                return;
+            }
+        } else {
+            self.state.sessions.insert(session_id.to_string(), meta);
+        }
+        self.dirty = true;
+    }
+
+    /// Forget a session's metadata (when it is deleted for good)
+    pub fn remove(&mut self, session_id: &str) {
+        if self.state.sessions.remove(session_id).is_some() {
+            self.dirty = true;
+        }
+    }
+
+    /// Check if state needs to be saved
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Save metadata to disk
+    /// Uses atomic write (write to temp, then rename) for safety
+    pub fn save(&mut self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let json = serde_json::to_string_pretty(&self.state)
+            .context("Failed to serialize conversation metadata")?;
+
+        let temp_path = self.metadata_path.with_extension("tmp");
+        fs::write(&temp_path, json)
+            .with_context(|| format!("Failed to write temp metadata file: {:?}", temp_path))?;
+
+        fs::rename(&temp_path, &self.metadata_path)
+            .with_context(|| format!("Failed to rename metadata file: {:?}", self.metadata_path))?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_split_on_commas_and_spaces_without_duplicates() {
+        assert_eq!(
+            parse_tags("#review, auth  Review,,#bug"),
+            vec!["review", "auth", "bug"]
+        );
+        assert!(parse_tags("  , # ").is_empty());
+    }
+
+    #[test]
+    fn blank_fields_are_dropped_and_empty_metadata_is_removed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut manager = MetadataManager::new(temp_dir.path()).unwrap();
+
+        let meta = ConversationMeta::new("  Auth review ", "", "\n");
+        assert_eq!(meta.title.as_deref(), Some("Auth review"));
+        assert!(meta.note.is_none());
+        manager.set("session-1", meta);
+        assert!(manager.is_dirty());
+        assert!(manager.get("session-1").is_some());
+
+        manager.set("session-1", ConversationMeta::new("", " ", ""));
+        assert!(manager.get("session-1").is_none());
+    }
+
+    #[test]
+    fn save_and_load_preserves_metadata() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        {
+            let mut manager = MetadataManager::new(temp_dir.path()).unwrap();
+            manager.set(
+                "session-1",
+                ConversationMeta::new("Auth review", "#auth #review", "Line one\nLine two"),
+            );
+            manager.save().unwrap();
+            assert!(!manager.is_dirty());
+        }
+
+        let manager = MetadataManager::new(temp_dir.path()).unwrap();
+        let meta = manager.get("session-1").unwrap();
+        assert_eq!(meta.title.as_deref(), Some("Auth review"));
+        assert_eq!(meta.tags, vec!["auth", "review"]);
+        assert_eq!(meta.tags_text(), "#auth #review");
+        assert_eq!(meta.note.as_deref(), Some("Line one\nLine two"));
+    }
+}
@@ -1,11 +1,34 @@
-//! Claude conversation data: parsing, grouping, archiving, and file watching.
+//! Claude conversation data: parsing, grouping, archiving, metadata, and file watching.
 
 pub mod archive;
+pub mod archive_rules;
+pub mod branches;
+pub mod budget;
 pub mod conversation;
+pub mod data_dir;
+pub mod details;
+pub mod export;
+pub mod git_log;
 pub mod grouping;
+pub mod health;
+pub mod log_stats;
+pub mod log_tail;
+pub mod metadata;
+pub mod plans;
+pub mod session_cache;
 pub mod sessions;
+pub mod subagents;
+pub mod tool_stats;
+pub mod transcript;
+pub mod trash;
+pub mod usage;
 pub mod watcher;
 pub mod worktree;
 
 pub use archive::ArchiveManager;
+pub use data_dir::ClaudeDir;
+pub use health::HealthTracker;
+pub use metadata::MetadataManager;
+pub use plans::PlanLinker;
+pub use trash::TrashManager;
 pub use watcher::SessionsWatcher;
@@ -0,0 +1,374 @@
+//! Links plan-implementation conversations to the conversation that wrote the plan.
+//!
+//! Accepting a plan with a fresh context starts a new session whose first
+//! prompt is the plan behind a fixed prefix (`Implement the following plan:`
+//! by default, configurable as `plan_prefixes`). The planning session holds
+//! the same text in its `ExitPlanMode` tool call. An implementation is linked
+//! to the conversation in the same project that proposed a matching plan most
+//! recently before the implementation started; when no plan text matches, a
+//! plan proposed shortly before it started is taken instead.
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::conversation::{parse_time, Conversation};
+use super::grouping::ConversationGroup;
+use super::log_tail::{LogChange, LogTail};
+use super::sessions::user_prompt;
+
+/// The prefix Claude Code puts in front of an accepted plan.
+pub const DEFAULT_PLAN_PREFIX: &str = "Implement the following plan:";
+
+/// Leading characters of a plan (whitespace collapsed) that have to agree.
+/// Prompts kept in the session index are truncated, so whole plans can't be
+/// compared.
+const MATCH_CHARS: usize = 100;
+
+/// Without a text match, a plan proposed at most this long before an
+/// implementation started is taken to be its plan.
+const FALLBACK_MINUTES: i64 = 30;
+
+/// Conversations idle for longer than this before an implementation started
+/// are not considered as its planning conversation.
+const LOOKBACK_HOURS: i64 = 24;
+
+/// The plan text of a prompt starting with one of `prefixes`.
+pub fn strip_plan_prefix<'a>(prompt: &'a str, prefixes: &[String]) -> Option<&'a str> {
+    let prompt = prompt.trim_start();
+    prefixes
+        .iter()
+        .filter(|prefix| !prefix.trim().is_empty())
+        .find_map(|prefix| prompt.strip_prefix(prefix.as_str()))
+        .map(str::trim)
+}
+
+/// Comparable head of a plan: whitespace collapsed, at most `MATCH_CHARS`.
+fn fingerprint(plan: &str) -> String {
+    plan.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(MATCH_CHARS)
+        .collect()
+}
+
+/// Whether two fingerprints are of the same plan (one may be truncated).
+fn same_plan(a: &str, b: &str) -> bool {
+    !a.is_empty() && !b.is_empty() && (a.starts_with(b) || b.starts_with(a))
+}
+
+/// A plan proposed with `ExitPlanMode`.
+#[derive(Debug, Clone, PartialEq)]
+struct ProposedPlan {
+    at: DateTime<Utc>,
+    fingerprint: String,
+}
+
+/// What was read from a session log so far; grown logs only have their new
+/// lines read.
+#[derive(Debug, Default)]
+struct ScannedLog {
+    tail: LogTail,
+    first_prompt: Option<String>,
+    plans: Vec<ProposedPlan>,
+}
+
+impl ScannedLog {
+    /// Read what the log at `path`, last modified at `mtime`, gained since
+    /// it was last read.
+    fn update(&mut self, path: &Path, mtime: i64) {
+        match self.tail.check(path, mtime) {
+            LogChange::Unchanged => return,
+            LogChange::Replaced => {
+                self.first_prompt = None;
+                self.plans.clear();
+            }
+            LogChange::Appended => {}
+        }
+        let Self {
+            tail,
+            first_prompt,
+            plans,
+        } = self;
+        // An unreadable log keeps what was read from it before
+        let _ = tail.read(|line| {
+            // Only lines that can matter are parsed
+            let wanted = first_prompt.is_none() || line.contains("\"ExitPlanMode\"");
+            if !wanted {
+                return;
+            }
+            let Ok(value) = serde_json::from_str::<Value>(line) else {
+                return;
+            };
+            if first_prompt.is_none() {
+                *first_prompt = user_prompt(&value);
+            }
+            plans.extend(proposed_plans(&value));
+        });
+    }
+}
+
+/// `ExitPlanMode` calls in an assistant log line.
+fn proposed_plans(value: &Value) -> Vec<ProposedPlan> {
+    let Some(at) = value
+        .get("timestamp")
+        .and_then(Value::as_str)
+        .and_then(parse_time)
+    else {
+        return Vec::new();
+    };
+    let Some(content) = value
+        .get("message")
+        .and_then(|m| m.get("content"))
+        .and_then(Value::as_array)
+    else {
+        return Vec::new();
+    };
+    content
+        .iter()
+        .filter(|item| item.get("type").and_then(Value::as_str) == Some("tool_use"))
+        .filter(|item| item.get("name").and_then(Value::as_str) == Some("ExitPlanMode"))
+        .filter_map(|item| item.get("input")?.get("plan")?.as_str())
+        .map(|plan| ProposedPlan {
+            at,
+            fingerprint: fingerprint(plan),
+        })
+        .collect()
+}
+
+/// Pick the planning conversation among `candidates` (session ID and the
+/// plans it proposed) for an implementation started at `started` whose plan
+/// has `plan_fingerprint`.
+fn choose_parent<'a>(
+    started: DateTime<Utc>,
+    plan_fingerprint: Option<&str>,
+    candidates: &[(&'a str, &[ProposedPlan])],
+) -> Option<&'a str> {
+    // Log timestamps of the two sessions can be a little out of order
+    let latest = started + Duration::minutes(1);
+    let proposals = || {
+        candidates.iter().flat_map(|(id, plans)| {
+            plans
+                .iter()
+                .filter(move |p| p.at <= latest)
+                .map(move |p| (*id, p))
+        })
+    };
+    let by_text = proposals()
+        .filter(|(_, p)| plan_fingerprint.is_some_and(|f| same_plan(f, &p.fingerprint)))
+        .max_by_key(|(_, p)| p.at);
+    let by_time = || {
+        proposals()
+            .filter(|(_, p)| p.at >= started - Duration::minutes(FALLBACK_MINUTES))
+            .max_by_key(|(_, p)| p.at)
+    };
+    by_text.or_else(by_time).map(|(id, _)| id)
+}
+
+/// Links plan implementations to their planning conversations, remembering
+/// what was read from each log until it changes.
+#[derive(Debug, Default)]
+pub struct PlanLinker {
+    /// Prompt prefixes that mark a plan implementation
+    prefixes: Vec<String>,
+    logs: HashMap<PathBuf, ScannedLog>,
+}
+
+impl PlanLinker {
+    pub fn new(prefixes: Vec<String>) -> Self {
+        Self {
+            prefixes,
+            logs: HashMap::new(),
+        }
+    }
+
+    /// Make sure what was read from `conv`'s log is current.
+    fn scanned(&mut self, conv: &Conversation) {
+        self.logs
+            .entry(conv.file_path.clone())
+            .or_default()
+            .update(&conv.file_path, conv.timestamp);
+    }
+
+    /// Set `plan_parent` on every plan implementation in `groups`.
+    pub fn link(&mut self, groups: &mut [ConversationGroup]) {
+        let conversations: Vec<&Conversation> = groups
+            .iter()
+            .flat_map(ConversationGroup::conversations)
+            .collect();
+        let mut links: HashMap<String, String> = HashMap::new();
+        let mut seen: HashSet<PathBuf> = HashSet::new();
+
+        for conv in conversations.iter().filter(|c| c.is_plan_implementation) {
+            let Some((started, _)) = conv.active_span() else {
+                continue;
+            };
+            seen.insert(conv.file_path.clone());
+            self.scanned(conv);
+            let plan = self.logs[&conv.file_path]
+                .first_prompt
+                .as_deref()
+                .and_then(|prompt| strip_plan_prefix(prompt, &self.prefixes))
+                .map(fingerprint);
+
+            let candidates: Vec<&Conversation> = conversations
+                .iter()
+                .copied()
+                .filter(|c| c.session_id != conv.session_id && c.project_path == conv.project_path)
+                .filter(|c| {
+                    c.active_span().is_some_and(|(start, end)| {
+                        start <= started && end >= started - Duration::hours(LOOKBACK_HOURS)
+                    })
+                })
+                .collect();
+            for candidate in &candidates {
+                seen.insert(candidate.file_path.clone());
+                self.scanned(candidate);
+            }
+            let scanned: Vec<(&str, &[ProposedPlan])> = candidates
+                .iter()
+                .map(|c| {
+                    let plans = self
+                        .logs
+                        .get(&c.file_path)
+                        .map_or(&[][..], |log| &log.plans[..]);
+                    (c.session_id.as_str(), plans)
+                })
+                .collect();
+            if let Some(parent) = choose_parent(started, plan.as_deref(), &scanned) {
+                links.insert(conv.session_id.clone(), parent.to_string());
+            }
+        }
+
+        self.logs.retain(|path, _| seen.contains(path));
+        for conv in groups
+            .iter_mut()
+            .flat_map(ConversationGroup::conversations_mut)
+        {
+            conv.plan_parent = links.get(&conv.session_id).cloned();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn time(text: &str) -> DateTime<Utc> {
+        parse_time(text).unwrap()
+    }
+
+    fn plan(at: &str, text: &str) -> ProposedPlan {
+        ProposedPlan {
+            at: time(at),
+            fingerprint: fingerprint(text),
+        }
+    }
+
+    #[test]
+    fn strip_plan_prefix_uses_any_configured_prefix() {
+        let prefixes = vec![
+            DEFAULT_PLAN_PREFIX.to_string(),
+            "Execute this plan:".to_string(),
+            "  ".to_string(),
+        ];
+        assert_eq!(
+            strip_plan_prefix("Implement the following plan:\n\n# Auth\nSteps", &prefixes),
+            Some("# Auth\nSteps")
+        );
+        assert_eq!(
+            strip_plan_prefix("Execute this plan: # Auth", &prefixes),
+            Some("# Auth")
+        );
+        assert_eq!(strip_plan_prefix("Fix the login bug", &prefixes), None);
+        assert_eq!(strip_plan_prefix("Fix the login bug", &[]), None);
+    }
+
+    #[test]
+    fn choose_parent_prefers_matching_plan_text_over_timing() {
+        let planner = [plan("2026-03-01T09:00:00Z", "# Auth\n\n1. Add login form")];
+        // Proposed a different plan more recently
+        let other = [plan("2026-03-01T09:55:00Z", "# Billing\n\n1. Add invoices")];
+        let candidates = [("planner", &planner[..]), ("other", &other[..])];
+        let started = time("2026-03-01T10:00:00Z");
+
+        // The implementation's prompt may be truncated
+        let fingerprint = fingerprint("# Auth 1. Add login");
+        assert_eq!(
+            choose_parent(started, Some(&fingerprint), &candidates),
+            Some("planner")
+        );
+        // Nothing matches: the plan proposed just before it started wins
+        assert_eq!(
+            choose_parent(started, Some("# Search"), &candidates),
+            Some("other")
+        );
+        assert_eq!(choose_parent(started, None, &candidates), Some("other"));
+    }
+
+    #[test]
+    fn choose_parent_ignores_plans_proposed_after_or_long_before() {
+        let late = [plan("2026-03-01T10:30:00Z", "# Auth")];
+        let early = [plan("2026-03-01T08:00:00Z", "# Auth")];
+        let started = time("2026-03-01T10:00:00Z");
+
+        assert_eq!(
+            choose_parent(started, Some("# Auth"), &[("late", &late[..])]),
+            None
+        );
+        assert_eq!(
+            choose_parent(started, Some("# Other"), &[("early", &early[..])]),
+            None
+        );
+        assert_eq!(
+            choose_parent(started, Some("# Auth"), &[("early", &early[..])]),
+            Some("early")
+        );
+    }
+
+    #[test]
+    fn scanned_log_reads_exit_plan_mode_calls() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        std::fs::write(
+            &path,
+            concat!(
+                r#"{"type":"user","timestamp":"2026-03-01T09:00:00Z","message":{"role":"user","content":"Plan the auth work"}}"#,
+                "\n",
+                r##"{"type":"assistant","timestamp":"2026-03-01T09:10:00Z","message":{"role":"assistant","content":[{"type":"tool_use","name":"ExitPlanMode","input":{"plan":"# Auth\n\n1. Add   login"}}]}}"##,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let mut scanned = ScannedLog::default();
+        scanned.update(&path, 1);
+        assert_eq!(scanned.first_prompt.as_deref(), Some("Plan the auth work"));
+        assert_eq!(
+            scanned.plans,
+            vec![plan("2026-03-01T09:10:00Z", "# Auth 1. Add login")]
+        );
+
+        // Only the appended plan is read next
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        writeln!(
+            file,
+            r##"{{"type":"assistant","timestamp":"2026-03-01T09:20:00Z","message":{{"role":"assistant","content":[{{"type":"tool_use","name":"ExitPlanMode","input":{{"plan":"# Auth v2"}}}}]}}}}"##
+        )
+        .unwrap();
+        scanned.update(&path, 2);
+        assert_eq!(
+            scanned.plans,
+            vec![
+                plan("2026-03-01T09:10:00Z", "# Auth 1. Add login"),
+                plan("2026-03-01T09:20:00Z", "# Auth v2"),
+            ]
+        );
+    }
+}
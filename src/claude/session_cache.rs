@@ -0,0 +1,401 @@
+//! Persistent cache behind session discovery.
+//!
+//! `parse_all_sessions` runs on every watcher event, so everything it derives
+//! from disk is remembered between runs: resolved project paths, parsed
+//! `sessions-index.json` files, and what was read from the head of each
+//! unindexed JSONL (first prompt, sidechain parent). Files are keyed by path
+//! and re-read only when their mtime or size changes; a grown file resumes
+//! scanning where it left off, and once its first prompt and header are known
+//! it isn't read again at all.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use super::sessions::{extract_project_path, load_index_cache, user_prompt, SessionEntryRaw};
+use super::subagents::{sidechain_session_id, HEADER_LINES};
+
+/// Bump when the on-disk format or what gets extracted changes (forces a rebuild).
+const CACHE_VERSION: u32 = 1;
+
+/// Modification time (ms) and size of a file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct FileStamp {
+    mtime: i64,
+    size: u64,
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> Option<Self> {
+        let meta = fs::metadata(path).ok()?;
+        let mtime = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_millis() as i64);
+        Some(Self {
+            mtime,
+            size: meta.len(),
+        })
+    }
+}
+
+/// What has been read from the head of a session JSONL.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(super) struct ScannedFile {
+    stamp: FileStamp,
+    /// Bytes scanned so far (always ends at a line boundary)
+    scanned_bytes: u64,
+    /// Complete lines scanned so far
+    scanned_lines: usize,
+    /// First real user prompt
+    pub(super) first_prompt: Option<String>,
+    /// `sessionId` of the first sidechain line within the header
+    session_id: Option<String>,
+}
+
+impl ScannedFile {
+    /// Nothing more to learn from further lines.
+    fn is_complete(&self) -> bool {
+        self.first_prompt.is_some()
+            && (self.session_id.is_some() || self.scanned_lines >= HEADER_LINES)
+    }
+
+    /// The parent session, if the file is a sidechain of another session.
+    pub(super) fn sidechain_parent(&self, own_id: &str) -> Option<String> {
+        self.session_id.clone().filter(|parent| parent != own_id)
+    }
+
+    /// Scan complete lines from `scanned_bytes` until nothing more is needed.
+    fn scan(&mut self, path: &Path) -> Result<()> {
+        let mut handle = File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
+        handle.seek(SeekFrom::Start(self.scanned_bytes))?;
+        let mut reader = BufReader::new(handle);
+
+        let mut buf = Vec::new();
+        while !self.is_complete() {
+            buf.clear();
+            let n = reader.read_until(b'\n', &mut buf)?;
+            if n == 0 {
+                break;
+            }
+            // A last line without a newline may still be being written: use
+            // it if it parses, but read it again next time
+            let partial = buf.last() != Some(&b'\n');
+            if !partial {
+                self.scanned_bytes += n as u64;
+                self.scanned_lines += 1;
+            }
+            if let Ok(value) = serde_json::from_slice::<Value>(&buf) {
+                self.record(&value, self.scanned_lines + usize::from(partial));
+            }
+            if partial {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Learn what a line (1-based `line_number`) says about the file.
+    fn record(&mut self, value: &Value, line_number: usize) {
+        if self.session_id.is_none() && line_number <= HEADER_LINES {
+            self.session_id = sidechain_session_id(value).map(String::from);
+        }
+        if self.first_prompt.is_none() {
+            self.first_prompt = user_prompt(value);
+        }
+    }
+}
+
+/// A parsed `sessions-index.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedIndex {
+    stamp: FileStamp,
+    entries: HashMap<String, SessionEntryRaw>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheData {
+    version: u32,
+    /// Project directory name -> resolved project path
+    projects: BTreeMap<String, String>,
+    /// Index file path -> parsed entries
+    indexes: BTreeMap<PathBuf, CachedIndex>,
+    /// JSONL path -> scanned head
+    files: BTreeMap<PathBuf, ScannedFile>,
+}
+
+impl Default for CacheData {
+    fn default() -> Self {
+        Self {
+            version: CACHE_VERSION,
+            projects: BTreeMap::new(),
+            indexes: BTreeMap::new(),
+            files: BTreeMap::new(),
+        }
+    }
+}
+
+/// Cache of everything session discovery reads from disk.
+#[derive(Debug, Default)]
+pub struct SessionCache {
+    /// Where the cache is persisted (None = in-memory only)
+    path: Option<PathBuf>,
+    data: CacheData,
+    dirty: bool,
+    /// Paths looked up since `begin_refresh`, to prune the rest afterwards
+    seen: HashSet<PathBuf>,
+}
+
+impl SessionCache {
+    /// Create an empty in-memory cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the cache from `path`, starting empty if it is missing, corrupt or
+    /// from an older format.
+    pub fn load(path: &Path) -> Self {
+        let data = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheData>(&content).ok())
+            .filter(|data| data.version == CACHE_VERSION)
+            .unwrap_or_default();
+
+        Self {
+            path: Some(path.to_path_buf()),
+            data,
+            dirty: false,
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Default cache location: `<cache dir>/claudatui/session-cache.json`.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("claudatui").join("session-cache.json"))
+    }
+
+    /// Number of cached JSONL files.
+    pub fn file_count(&self) -> usize {
+        self.data.files.len()
+    }
+
+    /// Start a full pass over the session files.
+    pub(super) fn begin_refresh(&mut self) {
+        self.seen.clear();
+    }
+
+    /// Drop entries for files not looked up since `begin_refresh`.
+    pub(super) fn finish_refresh(&mut self) {
+        let seen = std::mem::take(&mut self.seen);
+        let before = self.data.files.len() + self.data.indexes.len();
+        self.data.files.retain(|path, _| seen.contains(path));
+        self.data.indexes.retain(|path, _| seen.contains(path));
+        if self.data.files.len() + self.data.indexes.len() != before {
+            self.dirty = true;
+        }
+    }
+
+    /// Project path for an escaped project directory name. Resolving probes
+    /// the filesystem, so it is only redone once the cached path disappears.
+    pub(super) fn project_path(&mut self, dir_name: &str) -> String {
+        if let Some(path) = self.data.projects.get(dir_name) {
+            if Path::new(path).exists() {
+                return path.clone();
+            }
+        }
+        let path = extract_project_path(dir_name);
+        if self.data.projects.get(dir_name) != Some(&path) {
+            self.data
+                .projects
+                .insert(dir_name.to_string(), path.clone());
+            self.dirty = true;
+        }
+        path
+    }
+
+    /// Entries of a project's `sessions-index.json`, reparsed only when it changed.
+    pub(super) fn index_entries(&mut self, project_dir: &Path) -> HashMap<String, SessionEntryRaw> {
+        let index_path = project_dir.join("sessions-index.json");
+        let Some(stamp) = FileStamp::of(&index_path) else {
+            return HashMap::new();
+        };
+        self.seen.insert(index_path.clone());
+        if let Some(cached) = self.data.indexes.get(&index_path) {
+            if cached.stamp == stamp {
+                return cached.entries.clone();
+            }
+        }
+        let entries = load_index_cache(project_dir);
+        self.data.indexes.insert(
+            index_path,
+            CachedIndex {
+                stamp,
+                entries: entries.clone(),
+            },
+        );
+        self.dirty = true;
+        entries
+    }
+
+    /// The scanned head of a JSONL, reading only what changed since last time.
+    pub(super) fn scan(&mut self, path: &Path) -> ScannedFile {
+        self.seen.insert(path.to_path_buf());
+        let Some(stamp) = FileStamp::of(path) else {
+            return ScannedFile::default();
+        };
+        let entry = self.data.files.entry(path.to_path_buf()).or_default();
+        if entry.stamp == stamp {
+            return entry.clone();
+        }
+        // Session files are append-only; anything else means it was rewritten
+        if stamp.size < entry.scanned_bytes {
+            *entry = ScannedFile::default();
+        }
+        entry.stamp = stamp;
+        if !entry.is_complete() {
+            // An unreadable file is retried when it next changes
+            let _ = entry.scan(path);
+        }
+        self.dirty = true;
+        entry.clone()
+    }
+
+    /// The parent session of a sidechain file: the `sessionId` of its first
+    /// sidechain line, when that differs from the file's own name.
+    pub(super) fn sidechain_parent(&mut self, path: &Path) -> Option<String> {
+        let own_id = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        self.scan(path).sidechain_parent(own_id)
+    }
+
+    /// Persist the cache if it changed since it was loaded.
+    pub fn save(&mut self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        if !self.dirty {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create cache directory: {:?}", parent))?;
+        }
+
+        let json = serde_json::to_string(&self.data).context("Failed to serialize cache")?;
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, json)
+            .with_context(|| format!("Failed to write temp cache file: {:?}", temp_path))?;
+        fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to rename cache file: {:?}", path))?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::data_dir::ClaudeDir;
+    use crate::claude::sessions::parse_all_sessions_cached;
+    use std::io::Write;
+
+    const SESSION: &str = "d90ed21d-ed03-4e94-87d7-dbc5de6cc828";
+
+    fn user_line(text: &str) -> String {
+        format!(
+            "{{\"type\":\"user\",\"message\":{{\"content\":{}}}}}\n",
+            serde_json::to_string(text).unwrap()
+        )
+    }
+
+    fn setup(content: &str) -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("projects").join("-tmp");
+        fs::create_dir_all(&project).unwrap();
+        let file = project.join(format!("{}.jsonl", SESSION));
+        fs::write(&file, content).unwrap();
+        (dir, file)
+    }
+
+    fn append(path: &Path, content: &str) {
+        let mut file = fs::OpenOptions::new().append(true).open(path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+    }
+
+    fn first_prompts(dir: &Path, cache: &mut SessionCache) -> Vec<String> {
+        parse_all_sessions_cached(&[ClaudeDir::new(dir)], cache)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.first_prompt)
+            .collect()
+    }
+
+    #[test]
+    fn grown_file_resumes_scanning_after_what_was_read() {
+        let (dir, file) = setup("{\"type\":\"summary\"}\n");
+        let mut cache = SessionCache::new();
+        // No prompt yet: the session is still empty
+        assert!(first_prompts(dir.path(), &mut cache).is_empty());
+        assert_eq!(cache.data.files[&file].scanned_lines, 1);
+
+        append(&file, &user_line("[Request interrupted by user]"));
+        append(&file, &user_line("fix the login flow"));
+        assert_eq!(
+            first_prompts(dir.path(), &mut cache),
+            ["fix the login flow"]
+        );
+        assert_eq!(cache.data.files[&file].scanned_lines, 3);
+
+        // Once the prompt and header are known, growth isn't read
+        let scanned = cache.data.files[&file].scanned_bytes;
+        for i in 0..HEADER_LINES {
+            append(&file, &user_line(&format!("more {}", i)));
+        }
+        first_prompts(dir.path(), &mut cache);
+        append(&file, &user_line("even more"));
+        first_prompts(dir.path(), &mut cache);
+        let cached = &cache.data.files[&file];
+        assert!(cached.scanned_bytes > scanned);
+        assert_eq!(cached.scanned_lines, HEADER_LINES);
+        assert_eq!(cached.stamp, FileStamp::of(&file).unwrap());
+    }
+
+    #[test]
+    fn rewritten_file_is_rescanned_and_deleted_one_dropped() {
+        let (dir, file) = setup(&(user_line("first version") + &user_line("padding")));
+        let mut cache = SessionCache::new();
+        assert_eq!(first_prompts(dir.path(), &mut cache), ["first version"]);
+
+        fs::write(&file, user_line("second")).unwrap();
+        assert_eq!(first_prompts(dir.path(), &mut cache), ["second"]);
+
+        fs::remove_file(&file).unwrap();
+        assert!(first_prompts(dir.path(), &mut cache).is_empty());
+        assert_eq!(cache.file_count(), 0);
+    }
+
+    #[test]
+    fn save_and_load_roundtrip() {
+        let (dir, file) = setup(&user_line("fix the login flow"));
+        let cache_path = dir.path().join("cache").join("sessions.json");
+        let mut cache = SessionCache::load(&cache_path);
+        first_prompts(dir.path(), &mut cache);
+        cache.save().unwrap();
+
+        let loaded = SessionCache::load(&cache_path);
+        assert_eq!(loaded.file_count(), 1);
+        assert_eq!(
+            loaded.data.files[&file].first_prompt.as_deref(),
+            Some("fix the login flow")
+        );
+        assert_eq!(loaded.data.projects["-tmp"], "/tmp");
+    }
+}
@@ -1,11 +1,15 @@
 use anyhow::{Context, Result};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs::{self, File};
-use std::io::{BufRead, BufReader};
+use std::io::BufReader;
 use std::path::{Path, PathBuf};
 
+use super::data_dir::ClaudeDir;
+use super::session_cache::SessionCache;
+use super::subagents::discover_sidechains;
+
 /// Parsed sessions-index.json file
 #[derive(Debug, Deserialize)]
 struct SessionsIndex {
@@ -15,8 +19,8 @@ struct SessionsIndex {
 }
 
 /// Raw entry from sessions-index.json (matches JSON structure)
-#[derive(Debug, Clone, Deserialize)]
-struct SessionEntryRaw {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct SessionEntryRaw {
     #[serde(rename = "sessionId")]
     session_id: String,
     #[serde(rename = "fullPath")]
@@ -46,11 +50,14 @@ pub struct SessionEntry {
     pub file_mtime: i64,
     pub first_prompt: String,
     pub summary: Option<String>,
-    pub message_count: u32,
+    /// Only known for sessions listed in sessions-index.json
+    pub message_count: Option<u32>,
     pub created: String,
     pub modified: String,
     pub git_branch: Option<String>,
     pub project_path: String,
+    /// Sidechain (subagent) transcripts launched from this session
+    pub subagent_files: Vec<PathBuf>,
 }
 
 impl From<SessionEntryRaw> for SessionEntry {
@@ -61,7 +68,7 @@ impl From<SessionEntryRaw> for SessionEntry {
             file_mtime: raw.file_mtime,
             first_prompt: raw.first_prompt,
             summary: raw.summary,
-            message_count: raw.message_count,
+            message_count: Some(raw.message_count),
             created: raw.created,
             modified: raw.modified,
             git_branch: if raw.git_branch.as_deref() == Some("") {
@@ -70,14 +77,15 @@ impl From<SessionEntryRaw> for SessionEntry {
                 raw.git_branch
             },
             project_path: raw.project_path,
+            subagent_files: Vec::new(),
         }
     }
 }
 
 /// Represents a discovered session file
-struct SessionFile {
-    path: PathBuf,
-    file_mtime: i64,
+pub(crate) struct SessionFile {
+    pub(crate) path: PathBuf,
+    pub(crate) file_mtime: i64,
 }
 
 /// Check if a string looks like a UUID
@@ -101,7 +109,7 @@ fn is_uuid(s: &str) -> bool {
 }
 
 /// Discover all session files (.jsonl) in a project directory
-fn discover_session_files(project_dir: &Path) -> HashMap<String, SessionFile> {
+pub(crate) fn discover_session_files(project_dir: &Path) -> HashMap<String, SessionFile> {
     let mut sessions = HashMap::new();
 
     let Ok(entries) = fs::read_dir(project_dir) else {
@@ -139,68 +147,44 @@ fn discover_session_files(project_dir: &Path) -> HashMap<String, SessionFile> {
     sessions
 }
 
-/// Parse the first user prompt from a session JSONL file
-fn parse_first_user_prompt(path: &Path) -> Option<String> {
-    let file = File::open(path).ok()?;
-    let reader = BufReader::new(file);
-
-    for line in reader.lines().map_while(Result::ok) {
-        // Parse each line as JSON
-        let value: Value = match serde_json::from_str(&line) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-
-        // Check if it's a user message
-        if value.get("type").and_then(|t| t.as_str()) != Some("user") {
-            continue;
-        }
+/// The prompt text of a JSONL line, truncated for display, if it is a user
+/// message with real text (not just tool results or system markers).
+pub(super) fn user_prompt(value: &Value) -> Option<String> {
+    prompt_text(value).map(truncate_prompt)
+}
 
-        // Extract content from message
-        let content = value.get("message")?.get("content")?;
-
-        // Content can be a string or an array
-        let text = match content {
-            Value::String(s) => s.clone(),
-            Value::Array(arr) => {
-                // Look for text content in the array
-                for item in arr {
-                    if item.get("type").and_then(|t| t.as_str()) == Some("text") {
-                        if let Some(text) = item.get("text").and_then(|t| t.as_str()) {
-                            let trimmed = text.trim();
-                            // Skip empty or system markers
-                            if trimmed.is_empty()
-                                || (trimmed.starts_with('[') && trimmed.ends_with(']'))
-                            {
-                                continue;
-                            }
-                            return Some(truncate_prompt(text));
-                        }
-                    }
-                    // Skip tool_result items - keep looking for actual text
-                    if item.get("type").and_then(|t| t.as_str()) == Some("tool_result") {
-                        continue;
-                    }
-                }
-                continue; // No text found in array, try next message
-            }
-            _ => continue,
-        };
+/// The full prompt text of a JSONL line, if it is a user message with real
+/// text (not just tool results or system markers).
+pub(super) fn prompt_text(value: &Value) -> Option<&str> {
+    // Check if it's a user message
+    if value.get("type").and_then(|t| t.as_str()) != Some("user") {
+        return None;
+    }
 
-        // Skip empty prompts and system-generated markers
-        let trimmed = text.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-        // Skip system markers like "[Request interrupted by user]"
-        if trimmed.starts_with('[') && trimmed.ends_with(']') {
-            continue;
+    // Extract content from message
+    let content = value.get("message")?.get("content")?;
+
+    // Content can be a string or an array
+    let text = match content {
+        Value::String(s) => s,
+        Value::Array(arr) => {
+            // Look for text content in the array, skipping tool_result items
+            return arr
+                .iter()
+                .filter(|item| item.get("type").and_then(|t| t.as_str()) == Some("text"))
+                .filter_map(|item| item.get("text").and_then(|t| t.as_str()))
+                .find(|text| !is_system_marker(text));
         }
+        _ => return None,
+    };
 
-        return Some(truncate_prompt(&text));
-    }
+    (!is_system_marker(text)).then_some(text.as_str())
+}
 
-    None
+/// Empty prompts and system-generated markers like "[Request interrupted by user]"
+fn is_system_marker(text: &str) -> bool {
+    let trimmed = text.trim();
+    trimmed.is_empty() || (trimmed.starts_with('[') && trimmed.ends_with(']'))
 }
 
 /// Truncate a prompt to a reasonable length for display
@@ -220,7 +204,7 @@ fn truncate_prompt(text: &str) -> String {
 }
 
 /// Load sessions-index.json as a HashMap for O(1) lookup
-fn load_index_cache(project_dir: &Path) -> HashMap<String, SessionEntryRaw> {
+pub(super) fn load_index_cache(project_dir: &Path) -> HashMap<String, SessionEntryRaw> {
     let index_path = project_dir.join("sessions-index.json");
     if !index_path.exists() {
         return HashMap::new();
@@ -326,7 +310,7 @@ fn resolve_segments(segments: &[&str], base: &Path) -> Option<PathBuf> {
 ///
 /// Example: `-Users-brandon-work-fluid-mono-with-backend` becomes
 /// `/Users/brandon/work/fluid-mono-with-backend` if that path exists
-fn extract_project_path(dir_name: &str) -> String {
+pub(super) fn extract_project_path(dir_name: &str) -> String {
     // Remove leading dash if present (indicates absolute path starting with /)
     let cleaned = dir_name.strip_prefix('-').unwrap_or(dir_name);
 
@@ -419,85 +403,129 @@ fn extract_project_path(dir_name: &str) -> String {
     path.to_string_lossy().into_owned()
 }
 
+/// Sessions of one project directory (`~/.claude/projects/<escaped path>`),
+/// in no particular order.
+pub fn parse_project_sessions(project_dir: &Path, cache: &mut SessionCache) -> Vec<SessionEntry> {
+    // Phase 1: Discover all session files (authoritative)
+    let session_files = discover_session_files(project_dir);
+
+    if session_files.is_empty() {
+        return Vec::new();
+    }
+
+    // Extract project path from directory name
+    let project_path = project_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(|name| cache.project_path(name))
+        .unwrap_or_default();
+
+    // Phase 2: Load index cache for metadata enrichment
+    let index_cache = cache.index_entries(project_dir);
+    let mut sidechains = discover_sidechains(project_dir, |path| cache.sidechain_parent(path));
+    let mut entries = Vec::new();
+
+    // Build session entries
+    for (session_id, session_file) in session_files {
+        // Check if this session is in the index cache
+        if let Some(cached) = index_cache.get(&session_id) {
+            // Sidechain sessions belong to their parent
+            if cached.is_sidechain {
+                if let Some(parent) = cache.scan(&session_file.path).sidechain_parent(&session_id) {
+                    sidechains
+                        .entry(parent)
+                        .or_default()
+                        .push(session_file.path);
+                }
+                continue;
+            }
+            // Use cached metadata
+            entries.push(SessionEntry::from(cached.clone()));
+        } else {
+            let scanned = cache.scan(&session_file.path);
+
+            // Not in cache - attach unindexed sidechains to their parent
+            if let Some(parent) = scanned.sidechain_parent(&session_id) {
+                sidechains
+                    .entry(parent)
+                    .or_default()
+                    .push(session_file.path);
+                continue;
+            }
+
+            // Skip sessions with no user content (empty/abandoned sessions)
+            let Some(first_prompt) = scanned.first_prompt else {
+                continue; // Skip empty sessions
+            };
+
+            // Create entry with minimal metadata
+            entries.push(SessionEntry {
+                session_id,
+                full_path: session_file.path.to_string_lossy().into_owned(),
+                file_mtime: session_file.file_mtime,
+                first_prompt,
+                summary: None,
+                message_count: None,
+                created: String::new(),
+                modified: String::new(),
+                git_branch: None,
+                project_path: project_path.clone(),
+                subagent_files: Vec::new(),
+            });
+        }
+    }
+
+    for entry in &mut entries {
+        if let Some(files) = sidechains.remove(&entry.session_id) {
+            entry.subagent_files = files;
+        }
+    }
+
+    entries
+}
+
 /// Parse all sessions from ~/.claude/projects/*/
 ///
 /// Uses two-phase discovery:
 /// 1. Scan .jsonl files (authoritative source of which sessions exist)
 /// 2. Enrich with metadata from sessions-index.json where available
 ///
-/// Returns sessions sorted by file modification time (most recent first),
-/// filtering out sidechain sessions (background agents).
+/// Returns sessions sorted by file modification time (most recent first).
+/// Sidechain sessions (subagents) are not listed themselves but attached to
+/// their parent session's `subagent_files`.
 pub fn parse_all_sessions(claude_dir: &Path) -> Result<Vec<SessionEntry>> {
-    let projects_dir = claude_dir.join("projects");
-
-    if !projects_dir.exists() {
-        return Ok(Vec::new());
-    }
+    parse_all_sessions_cached(&[ClaudeDir::new(claude_dir)], &mut SessionCache::new())
+}
 
+/// `parse_all_sessions` over one or more Claude data directories, reusing
+/// whatever `cache` already knows so that only new and changed files are read.
+pub fn parse_all_sessions_cached(
+    claude_dirs: &[ClaudeDir],
+    cache: &mut SessionCache,
+) -> Result<Vec<SessionEntry>> {
     let mut all_entries = Vec::new();
 
-    // Read all project directories
-    let entries = fs::read_dir(&projects_dir)
-        .with_context(|| format!("Failed to read projects directory: {:?}", projects_dir))?;
-
-    for entry in entries {
-        let Ok(entry) = entry else { continue };
-
-        let project_dir = entry.path();
-        if !project_dir.is_dir() {
+    cache.begin_refresh();
+    for claude_dir in claude_dirs {
+        let projects_dir = claude_dir.projects_dir();
+        if !projects_dir.exists() {
             continue;
         }
 
-        // Extract project path from directory name
-        let project_path = project_dir
-            .file_name()
-            .and_then(|n| n.to_str())
-            .map(extract_project_path)
-            .unwrap_or_default();
-
-        // Phase 1: Discover all session files (authoritative)
-        let session_files = discover_session_files(&project_dir);
-
-        if session_files.is_empty() {
-            continue;
-        }
+        // Read all project directories
+        let entries = fs::read_dir(&projects_dir)
+            .with_context(|| format!("Failed to read projects directory: {:?}", projects_dir))?;
 
-        // Phase 2: Load index cache for metadata enrichment
-        let index_cache = load_index_cache(&project_dir);
+        for entry in entries {
+            let Ok(entry) = entry else { continue };
 
-        // Build session entries
-        for (session_id, session_file) in session_files {
-            // Check if this session is in the index cache
-            if let Some(cached) = index_cache.get(&session_id) {
-                // Skip sidechain sessions
-                if cached.is_sidechain {
-                    continue;
-                }
-                // Use cached metadata
-                all_entries.push(SessionEntry::from(cached.clone()));
-            } else {
-                // Not in cache - parse first prompt on-demand
-                // Skip sessions with no user content (empty/abandoned sessions)
-                let Some(first_prompt) = parse_first_user_prompt(&session_file.path) else {
-                    continue; // Skip empty sessions
-                };
-
-                // Create entry with minimal metadata
-                all_entries.push(SessionEntry {
-                    session_id,
-                    full_path: session_file.path.to_string_lossy().into_owned(),
-                    file_mtime: session_file.file_mtime,
-                    first_prompt,
-                    summary: None,
-                    message_count: 0,
-                    created: String::new(),
-                    modified: String::new(),
-                    git_branch: None,
-                    project_path: project_path.clone(),
-                });
+            let project_dir = entry.path();
+            if project_dir.is_dir() {
+                all_entries.extend(parse_project_sessions(&project_dir, cache));
             }
         }
     }
+    cache.finish_refresh();
 
     // Sort by file_mtime descending (most recent first)
     // For sessions with cached data, use their modified timestamp if available
@@ -704,4 +732,32 @@ mod tests {
         assert!(truncated.ends_with("..."));
         assert_eq!(truncated.len(), 203); // 200 chars + "..."
     }
+
+    #[test]
+    fn parse_all_sessions_attaches_sidechains_to_their_parent() {
+        let claude_dir = tempfile::tempdir().unwrap();
+        let project_dir = claude_dir.path().join("projects/-tmp");
+        fs::create_dir_all(&project_dir).unwrap();
+        let parent = "d90ed21d-ed03-4e94-87d7-dbc5de6cc828";
+        fs::write(
+            project_dir.join(format!("{parent}.jsonl")),
+            r#"{"type":"user","message":{"role":"user","content":"Refactor the parser"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            project_dir.join("agent-1a2b3c4d.jsonl"),
+            format!(
+                r#"{{"type":"user","isSidechain":true,"sessionId":"{parent}","message":{{"role":"user","content":"Find the parser"}}}}"#
+            ),
+        )
+        .unwrap();
+
+        let sessions = parse_all_sessions(claude_dir.path()).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].session_id, parent);
+        assert_eq!(
+            sessions[0].subagent_files,
+            vec![project_dir.join("agent-1a2b3c4d.jsonl")]
+        );
+    }
 }
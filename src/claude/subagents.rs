@@ -0,0 +1,337 @@
+//! Subagent (sidechain) transcripts linked to their parent conversation.
+//!
+//! A Task tool invocation runs a subagent whose messages are logged to a
+//! separate sidechain JSONL, either `agent-<id>.jsonl` next to the parent
+//! session or `<session-id>/subagents/agent-<id>.jsonl` below it. Every line
+//! is marked `isSidechain` and carries the parent's `sessionId`. The parent's
+//! Task tool result names the `agentId`, which is how a subagent gets the
+//! description it was launched with.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::claude::conversation::Conversation;
+use crate::claude::transcript::{parse_transcript, Role, TranscriptBlock};
+use crate::claude::usage::{parse_session_usage, SessionUsage};
+
+/// Tools that launch a subagent.
+const TASK_TOOLS: &[&str] = &["Task", "Agent"];
+
+/// How many lines to read looking for the session ID of a sidechain file.
+pub(crate) const HEADER_LINES: usize = 20;
+
+/// A subagent run belonging to a conversation.
+#[derive(Debug, Clone)]
+pub struct Subagent {
+    pub agent_id: String,
+    /// Path to the sidechain JSONL
+    pub file_path: PathBuf,
+    /// Task description, or the subagent's first prompt
+    pub description: String,
+    /// Agent type the Task asked for (e.g. `Explore`)
+    pub subagent_type: Option<String>,
+    pub message_count: usize,
+    pub usage: SessionUsage,
+    /// File modification time in ms
+    pub timestamp: i64,
+}
+
+impl Subagent {
+    /// A conversation for viewing the subagent's transcript, inheriting the
+    /// parent's project and branch.
+    pub fn conversation(&self, parent: &Conversation) -> Conversation {
+        Conversation {
+            display: self.description.clone(),
+            timestamp: self.timestamp,
+            message_count: Some(self.message_count as u32),
+            git_branch: parent.git_branch.clone(),
+            is_archived: parent.is_archived,
+            ..Conversation::new(
+                self.agent_id.clone(),
+                parent.project_path.clone(),
+                self.file_path.clone(),
+            )
+        }
+    }
+}
+
+/// Find the sidechain files in a project directory, keyed by parent session ID.
+///
+/// `parent_of` reads the parent of a flat `agent-*.jsonl` file (see
+/// `SessionCache::sidechain_parent`).
+pub(crate) fn discover_sidechains(
+    project_dir: &Path,
+    mut parent_of: impl FnMut(&Path) -> Option<String>,
+) -> HashMap<String, Vec<PathBuf>> {
+    let mut sidechains: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    let Ok(entries) = fs::read_dir(project_dir) else {
+        return sidechains;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        if path.is_dir() {
+            // <session-id>/subagents/*.jsonl
+            let parent = name.to_string();
+            for file in jsonl_files(&path.join("subagents")) {
+                sidechains.entry(parent.clone()).or_default().push(file);
+            }
+        } else if name.starts_with("agent-") && name.ends_with(".jsonl") {
+            if let Some(parent) = parent_of(&path) {
+                sidechains.entry(parent).or_default().push(path);
+            }
+        }
+    }
+
+    for files in sidechains.values_mut() {
+        files.sort();
+    }
+    sidechains
+}
+
+fn jsonl_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect()
+}
+
+/// The `sessionId` of a JSONL line marked `isSidechain`.
+pub(crate) fn sidechain_session_id(value: &Value) -> Option<&str> {
+    if value.get("isSidechain").and_then(Value::as_bool) != Some(true) {
+        return None;
+    }
+    value.get("sessionId").and_then(Value::as_str)
+}
+
+/// What the parent's Task call said about a subagent.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct TaskLink {
+    description: String,
+    subagent_type: Option<String>,
+}
+
+/// Link agent IDs to the Task calls that launched them, from the parent's JSONL.
+fn parse_task_links(path: &Path) -> HashMap<String, TaskLink> {
+    let Ok(file) = File::open(path) else {
+        return HashMap::new();
+    };
+
+    let mut calls: HashMap<String, TaskLink> = HashMap::new();
+    let mut links = HashMap::new();
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        // Only tool calls and results matter; skip the rest unparsed
+        if !line.contains("tool_use") {
+            continue;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let Some(content) = value
+            .get("message")
+            .and_then(|m| m.get("content"))
+            .and_then(Value::as_array)
+        else {
+            continue;
+        };
+
+        for item in content {
+            match item.get("type").and_then(Value::as_str) {
+                Some("tool_use") => {
+                    let is_task = item
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .is_some_and(|name| TASK_TOOLS.contains(&name));
+                    let Some(id) = item.get("id").and_then(Value::as_str).filter(|_| is_task)
+                    else {
+                        continue;
+                    };
+                    let input = item.get("input").unwrap_or(&Value::Null);
+                    let field =
+                        |key: &str| input.get(key).and_then(Value::as_str).map(String::from);
+                    calls.insert(
+                        id.to_string(),
+                        TaskLink {
+                            description: field("description").unwrap_or_default(),
+                            subagent_type: field("subagent_type"),
+                        },
+                    );
+                }
+                Some("tool_result") => {
+                    let agent_id = value
+                        .get("toolUseResult")
+                        .and_then(|r| r.get("agentId"))
+                        .and_then(Value::as_str);
+                    let call = item
+                        .get("tool_use_id")
+                        .and_then(Value::as_str)
+                        .and_then(|id| calls.get(id));
+                    if let (Some(agent_id), Some(call)) = (agent_id, call) {
+                        links.insert(agent_id.to_string(), call.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    links
+}
+
+/// Load the subagents of a conversation, oldest first.
+pub fn load_subagents(conv: &Conversation) -> Vec<Subagent> {
+    if conv.subagent_files.is_empty() {
+        return Vec::new();
+    }
+    let links = parse_task_links(&conv.file_path);
+
+    let mut subagents: Vec<Subagent> = conv
+        .subagent_files
+        .iter()
+        .filter_map(|path| load_subagent(path, &links))
+        .collect();
+    subagents.sort_by_key(|s| s.timestamp);
+    subagents
+}
+
+fn load_subagent(path: &Path, links: &HashMap<String, TaskLink>) -> Option<Subagent> {
+    let transcript = parse_transcript(path).ok()?;
+    let stem = path.file_stem()?.to_str()?;
+    let agent_id = stem.strip_prefix("agent-").unwrap_or(stem).to_string();
+    let link = links.get(&agent_id).cloned().unwrap_or_default();
+
+    let description = if link.description.is_empty() {
+        transcript
+            .messages
+            .iter()
+            .filter(|m| m.role == Role::User)
+            .flat_map(|m| &m.blocks)
+            .find_map(|block| match block {
+                TranscriptBlock::Text(text) => text.lines().find(|l| !l.trim().is_empty()),
+                _ => None,
+            })
+            .unwrap_or("Subagent")
+            .trim()
+            .to_string()
+    } else {
+        link.description
+    };
+
+    let timestamp = fs::metadata(path)
+        .ok()
+        .and_then(|meta| meta.modified().ok())
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_millis() as i64);
+
+    Some(Subagent {
+        agent_id,
+        file_path: path.to_path_buf(),
+        description,
+        subagent_type: link.subagent_type,
+        message_count: transcript.messages.len(),
+        usage: parse_session_usage(path).unwrap_or_default(),
+        timestamp,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::session_cache::SessionCache;
+
+    const PARENT: &str = "11111111-2222-3333-4444-555555555555";
+
+    fn write(path: &Path, lines: &[&str]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, lines.join("\n") + "\n").unwrap();
+    }
+
+    fn sidechain_lines(agent_id: &str) -> Vec<String> {
+        vec![
+            format!(
+                r#"{{"type":"user","isSidechain":true,"sessionId":"{PARENT}","agentId":"{agent_id}","message":{{"role":"user","content":"Find the config loader"}}}}"#
+            ),
+            format!(
+                r#"{{"type":"assistant","isSidechain":true,"sessionId":"{PARENT}","agentId":"{agent_id}","message":{{"id":"m1","model":"claude-sonnet-4","role":"assistant","content":[{{"type":"text","text":"Found it"}}],"usage":{{"input_tokens":10,"output_tokens":5}}}}}}"#
+            ),
+        ]
+    }
+
+    #[test]
+    fn discovers_sidechains_next_to_and_below_the_parent() {
+        let dir = tempfile::tempdir().unwrap();
+        let flat = dir.path().join("agent-aaaa1111.jsonl");
+        let nested = dir
+            .path()
+            .join(PARENT)
+            .join("subagents/agent-bbbb2222.jsonl");
+        let lines = sidechain_lines("aaaa1111");
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        write(&flat, &lines);
+        write(&nested, &lines);
+        // An agent file without a sidechain marker is not linked
+        write(
+            &dir.path().join("agent-cccc3333.jsonl"),
+            &[r#"{"type":"user"}"#],
+        );
+
+        let mut cache = SessionCache::new();
+        let sidechains = discover_sidechains(dir.path(), |path| cache.sidechain_parent(path));
+        assert_eq!(sidechains.len(), 1);
+        assert_eq!(sidechains[PARENT], vec![nested, flat]);
+    }
+
+    #[test]
+    fn subagents_take_their_description_from_the_task_call() {
+        let dir = tempfile::tempdir().unwrap();
+        let parent_file = dir.path().join(format!("{PARENT}.jsonl"));
+        write(
+            &parent_file,
+            &[
+                r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","id":"toolu_1","name":"Task","input":{"description":"Locate config","subagent_type":"Explore","prompt":"Find the config loader"}}]}}"#,
+                r#"{"type":"user","toolUseResult":{"agentId":"aaaa1111","status":"completed"},"message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":"done"}]}}"#,
+            ],
+        );
+        let agent_file = dir.path().join("agent-aaaa1111.jsonl");
+        let other_file = dir.path().join("agent-dddd4444.jsonl");
+        for (file, id) in [(&agent_file, "aaaa1111"), (&other_file, "dddd4444")] {
+            let lines = sidechain_lines(id);
+            let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+            write(file, &lines);
+        }
+
+        let conv = Conversation {
+            display: "Parent".to_string(),
+            file_path: parent_file,
+            message_count: Some(2),
+            subagent_files: vec![agent_file, other_file],
+            ..Conversation::for_test(PARENT, "/tmp/project")
+        };
+
+        let mut subagents = load_subagents(&conv);
+        subagents.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+        assert_eq!(subagents.len(), 2);
+
+        let linked = &subagents[0];
+        assert_eq!(linked.agent_id, "aaaa1111");
+        assert_eq!(linked.description, "Locate config");
+        assert_eq!(linked.subagent_type.as_deref(), Some("Explore"));
+        assert_eq!(linked.message_count, 2);
+        assert_eq!(linked.usage.total().output, 5);
+
+        // Without a Task call the first prompt describes the subagent
+        assert_eq!(subagents[1].description, "Find the config loader");
+        assert_eq!(subagents[1].subagent_type, None);
+    }
+}
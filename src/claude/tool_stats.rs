@@ -0,0 +1,469 @@
+//! Tool call analytics from session JSONL files.
+//!
+//! Assistant lines carry `tool_use` blocks and the following user lines the
+//! matching `tool_result` (with `is_error` when the call failed). Results
+//! that were refused at a permission prompt are counted as denials rather
+//! than errors. A turn runs from a real user prompt to the last line before
+//! the next one, which gives its wall-clock duration. Like usage, stats are
+//! kept up to date by `log_stats`, which parses only the tail appended to
+//! each file.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+
+use super::conversation::parse_time;
+use super::grouping::ConversationGroup;
+use super::log_stats::LogStats;
+use super::log_tail::read_lines;
+use super::sessions::user_prompt;
+
+/// Prefix of tools provided by MCP servers (`mcp__<server>__<tool>`).
+const MCP_PREFIX: &str = "mcp__";
+
+/// Tools shown as their own column in the per-project table, besides MCP.
+pub const TOOL_COLUMNS: [&str; 5] = ["Bash", "Edit", "Read", "Grep", "Task"];
+
+/// Phrases of tool results produced when a permission prompt refused a call.
+const PERMISSION_MARKERS: [&str; 4] = [
+    "doesn't want to proceed with this tool use",
+    "tool use was rejected",
+    "requested permissions to",
+    "permission to use",
+];
+
+/// Calls of one tool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ToolCount {
+    pub calls: u32,
+    /// Failed calls (not counting denials)
+    pub errors: u32,
+    /// Calls refused at a permission prompt
+    pub denied: u32,
+}
+
+/// The longest run of consecutive failed calls to one tool.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FailureStreak {
+    pub tool: String,
+    pub count: u32,
+}
+
+/// Tool calls and turns of one or more conversations.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ToolStats {
+    /// Calls by tool name
+    pub tools: BTreeMap<String, ToolCount>,
+    /// Wall-clock duration of each turn (ms), in order
+    pub turn_ms: Vec<i64>,
+    pub failure_streak: Option<FailureStreak>,
+}
+
+impl ToolStats {
+    fn sum(&self, field: impl Fn(&ToolCount) -> u32) -> u32 {
+        self.tools.values().map(field).sum()
+    }
+
+    pub fn calls(&self) -> u32 {
+        self.sum(|c| c.calls)
+    }
+
+    pub fn errors(&self) -> u32 {
+        self.sum(|c| c.errors)
+    }
+
+    pub fn denied(&self) -> u32 {
+        self.sum(|c| c.denied)
+    }
+
+    /// Calls of the tool named `name`, with all MCP tools counted as `MCP`.
+    pub fn calls_of(&self, name: &str) -> u32 {
+        if name == "MCP" {
+            self.tools
+                .iter()
+                .filter(|(tool, _)| tool.starts_with(MCP_PREFIX))
+                .map(|(_, c)| c.calls)
+                .sum()
+        } else {
+            self.tools.get(name).map_or(0, |c| c.calls)
+        }
+    }
+
+    /// Share of calls that failed (0 without calls).
+    pub fn error_rate(&self) -> f64 {
+        ratio(self.errors(), self.calls())
+    }
+
+    /// Share of calls refused at a permission prompt (0 without calls).
+    pub fn denial_rate(&self) -> f64 {
+        ratio(self.denied(), self.calls())
+    }
+
+    pub fn turns(&self) -> usize {
+        self.turn_ms.len()
+    }
+
+    pub fn average_turn_ms(&self) -> Option<i64> {
+        (!self.turn_ms.is_empty())
+            .then(|| self.turn_ms.iter().sum::<i64>() / self.turn_ms.len() as i64)
+    }
+
+    pub fn longest_turn_ms(&self) -> Option<i64> {
+        self.turn_ms.iter().copied().max()
+    }
+
+    /// Add the stats of another conversation.
+    pub fn merge(&mut self, other: &ToolStats) {
+        for (tool, count) in &other.tools {
+            let entry = self.tools.entry(tool.clone()).or_default();
+            entry.calls += count.calls;
+            entry.errors += count.errors;
+            entry.denied += count.denied;
+        }
+        self.turn_ms.extend_from_slice(&other.turn_ms);
+        if let Some(ref streak) = other.failure_streak {
+            if self
+                .failure_streak
+                .as_ref()
+                .is_none_or(|s| streak.count > s.count)
+            {
+                self.failure_streak = Some(streak.clone());
+            }
+        }
+    }
+}
+
+fn ratio(part: u32, whole: u32) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+/// Tool stats parsed from one session log so far.
+#[derive(Debug, Default)]
+pub(crate) struct ToolLog {
+    /// Tool names of calls still waiting for their result, by tool use ID
+    pending: HashMap<String, String>,
+    /// Start of the current turn (ms)
+    turn_start: Option<i64>,
+    /// Consecutive failed calls of one tool, ending at the latest result
+    streak: Option<FailureStreak>,
+    pub stats: ToolStats,
+}
+
+impl ToolLog {
+    /// Count the tool calls, results and turn progress of one log line.
+    pub fn read_line(&mut self, line: &str) {
+        // Only conversation lines count; snapshots and summaries don't
+        let is_user = line.contains("\"type\":\"user\"");
+        if !is_user && !line.contains("\"type\":\"assistant\"") {
+            return;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            return;
+        };
+        let time = value
+            .get("timestamp")
+            .and_then(Value::as_str)
+            .and_then(parse_time)
+            .map(|t| t.timestamp_millis());
+        let sidechain = value.get("isSidechain").and_then(Value::as_bool) == Some(true);
+        let meta = value.get("isMeta").and_then(Value::as_bool) == Some(true);
+
+        if is_user && !sidechain && !meta && user_prompt(&value).is_some() {
+            if let Some(time) = time {
+                self.turn_start = Some(time);
+                self.stats.turn_ms.push(0);
+            }
+        } else if let (Some(start), Some(time)) = (self.turn_start, time) {
+            if let Some(last) = self.stats.turn_ms.last_mut() {
+                *last = (*last).max(time - start);
+            }
+        }
+
+        let Some(content) = value
+            .get("message")
+            .and_then(|m| m.get("content"))
+            .and_then(Value::as_array)
+        else {
+            return;
+        };
+        for item in content {
+            match item.get("type").and_then(Value::as_str) {
+                Some("tool_use") => self.record_call(item),
+                Some("tool_result") => self.record_result(item),
+                _ => {}
+            }
+        }
+    }
+
+    fn record_call(&mut self, item: &Value) {
+        let Some(name) = item.get("name").and_then(Value::as_str) else {
+            return;
+        };
+        self.stats.tools.entry(name.to_string()).or_default().calls += 1;
+        if let Some(id) = item.get("id").and_then(Value::as_str) {
+            self.pending.insert(id.to_string(), name.to_string());
+        }
+    }
+
+    fn record_result(&mut self, item: &Value) {
+        let Some(tool) = item
+            .get("tool_use_id")
+            .and_then(Value::as_str)
+            .and_then(|id| self.pending.remove(id))
+        else {
+            return;
+        };
+        let failed = item.get("is_error").and_then(Value::as_bool) == Some(true);
+        let count = self.stats.tools.entry(tool.clone()).or_default();
+
+        if failed && is_permission_denial(item.get("content")) {
+            count.denied += 1;
+        } else if failed {
+            count.errors += 1;
+            let streak = match self.streak.take() {
+                Some(mut streak) if streak.tool == tool => {
+                    streak.count += 1;
+                    streak
+                }
+                _ => FailureStreak { tool, count: 1 },
+            };
+            if self
+                .stats
+                .failure_streak
+                .as_ref()
+                .is_none_or(|s| streak.count > s.count)
+            {
+                self.stats.failure_streak = Some(streak.clone());
+            }
+            self.streak = Some(streak);
+        } else if self.streak.as_ref().is_some_and(|s| s.tool == tool) {
+            // A success of the failing tool ends its streak
+            self.streak = None;
+        }
+    }
+}
+
+/// Whether the content of a failed tool result says a permission prompt refused it.
+fn is_permission_denial(content: Option<&Value>) -> bool {
+    let text = match content {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| item.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => return false,
+    };
+    PERMISSION_MARKERS
+        .iter()
+        .any(|marker| text.contains(marker))
+}
+
+/// Parse the tool stats of a session file.
+pub fn parse_session_tools(path: &Path) -> Result<ToolStats> {
+    let mut log = ToolLog::default();
+    read_lines(path, &mut 0, |line| log.read_line(line))?;
+    Ok(log.stats)
+}
+
+/// Tool stats of the conversations of one project.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ToolProjectRow {
+    pub label: String,
+    pub conversations: usize,
+    #[serde(flatten)]
+    pub stats: ToolStats,
+}
+
+/// Tool stats rolled up by project, busiest first.
+pub fn project_rows(stats: &LogStats, groups: &[ConversationGroup]) -> Vec<ToolProjectRow> {
+    let mut rows: BTreeMap<String, ToolProjectRow> = BTreeMap::new();
+    for group in groups {
+        let label = group.project_display_name();
+        for conv in group.conversations() {
+            let Some(conv_stats) = stats.tools(&conv.session_id) else {
+                continue;
+            };
+            let row = rows.entry(label.clone()).or_insert_with(|| ToolProjectRow {
+                label: label.clone(),
+                ..ToolProjectRow::default()
+            });
+            row.conversations += 1;
+            row.stats.merge(conv_stats);
+        }
+    }
+    let mut rows: Vec<ToolProjectRow> = rows.into_values().collect();
+    rows.sort_by_key(|row| std::cmp::Reverse(row.stats.calls()));
+    rows
+}
+
+/// Format a duration compactly: `42s`, `3m12s`, `1h05m`.
+pub fn format_duration(ms: i64) -> String {
+    let seconds = ms.max(0) / 1000;
+    if seconds >= 3600 {
+        format!("{}h{:02}m", seconds / 3600, seconds % 3600 / 60)
+    } else if seconds >= 60 {
+        format!("{}m{:02}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Format a share as a whole percentage: `12%`.
+pub fn format_percent(share: f64) -> String {
+    format!("{:.0}%", share * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(ts: &str, text: &str) -> String {
+        format!(
+            r#"{{"type":"user","timestamp":"{}","message":{{"role":"user","content":"{}"}}}}"#,
+            ts, text
+        )
+    }
+
+    fn call(ts: &str, id: &str, name: &str) -> String {
+        format!(
+            r#"{{"type":"assistant","timestamp":"{}","message":{{"role":"assistant","content":[{{"type":"tool_use","id":"{}","name":"{}","input":{{}}}}]}}}}"#,
+            ts, id, name
+        )
+    }
+
+    fn result(ts: &str, id: &str, is_error: bool, text: &str) -> String {
+        format!(
+            r#"{{"type":"user","timestamp":"{}","message":{{"role":"user","content":[{{"type":"tool_result","tool_use_id":"{}","is_error":{},"content":"{}"}}]}}}}"#,
+            ts, id, is_error, text
+        )
+    }
+
+    fn write_log(path: &Path, lines: &[String]) {
+        let mut text = lines.join("\n");
+        text.push('\n');
+        std::fs::write(path, text).unwrap();
+    }
+
+    #[test]
+    fn parse_counts_calls_errors_denials_and_turns() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        write_log(
+            &path,
+            &[
+                prompt("2026-03-01T10:00:00Z", "Fix the build"),
+                call("2026-03-01T10:00:05Z", "t1", "Bash"),
+                result("2026-03-01T10:00:10Z", "t1", true, "exit code 1"),
+                call("2026-03-01T10:00:15Z", "t2", "Read"),
+                result("2026-03-01T10:00:16Z", "t2", false, "fn main() {}"),
+                call("2026-03-01T10:00:20Z", "t3", "Bash"),
+                result("2026-03-01T10:00:25Z", "t3", true, "exit code 1"),
+                call("2026-03-01T10:00:30Z", "t4", "mcp__github__create_issue"),
+                result(
+                    "2026-03-01T10:01:00Z",
+                    "t4",
+                    true,
+                    "The user doesn't want to proceed with this tool use.",
+                ),
+                prompt("2026-03-01T10:05:00Z", "[Request interrupted by user]"),
+                prompt("2026-03-01T11:00:00Z", "Try again"),
+                call("2026-03-01T11:00:30Z", "t5", "Bash"),
+                result("2026-03-01T11:00:42Z", "t5", false, "ok"),
+            ],
+        );
+
+        let stats = parse_session_tools(&path).unwrap();
+        assert_eq!(
+            stats.tools["Bash"],
+            ToolCount {
+                calls: 3,
+                errors: 2,
+                denied: 0
+            }
+        );
+        assert_eq!(stats.tools["Read"].errors, 0);
+        assert_eq!(stats.calls_of("MCP"), 1);
+        assert_eq!(stats.denied(), 1);
+        assert_eq!(stats.calls(), 5);
+        assert!((stats.error_rate() - 0.4).abs() < 1e-9);
+
+        // Interruption markers don't start a turn, but extend the current one
+        assert_eq!(stats.turn_ms, vec![300_000, 42_000]);
+        // The Read in between doesn't break the Bash streak
+        assert_eq!(
+            stats.failure_streak,
+            Some(FailureStreak {
+                tool: "Bash".to_string(),
+                count: 2
+            })
+        );
+    }
+
+    #[test]
+    fn tracker_reads_appended_tail_across_a_pending_call() {
+        use crate::claude::conversation::Conversation;
+        use crate::claude::log_stats::LogStatsTracker;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.jsonl");
+        write_log(
+            &path,
+            &[
+                prompt("2026-03-01T10:00:00Z", "Run tests"),
+                call("2026-03-01T10:00:05Z", "t1", "Bash"),
+            ],
+        );
+        let mut conv = Conversation {
+            timestamp: 1,
+            file_path: path.clone(),
+            ..Conversation::for_test("s1", "/proj")
+        };
+        let group = |conv: &Conversation| ConversationGroup::Directory {
+            parent: "work".to_string(),
+            project: "proj".to_string(),
+            conversations: vec![conv.clone()],
+        };
+
+        let mut tracker = LogStatsTracker::new();
+        tracker.refresh(&[group(&conv)]);
+        assert_eq!(tracker.stats().tools("s1").unwrap().calls(), 1);
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            format!("{}\n", result("2026-03-01T10:00:20Z", "t1", true, "failed")).as_bytes(),
+        )
+        .unwrap();
+        conv.timestamp = 2;
+        tracker.refresh(&[group(&conv)]);
+
+        let log_stats = tracker.stats();
+        let stats = log_stats.tools("s1").unwrap();
+        assert_eq!(stats.errors(), 1);
+        assert_eq!(stats.turn_ms, vec![20_000]);
+
+        let rows = project_rows(&log_stats, &[group(&conv)]);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].conversations, 1);
+        assert_eq!(rows[0].stats.calls_of("Bash"), 1);
+    }
+
+    #[test]
+    fn format_helpers_are_compact() {
+        assert_eq!(format_duration(42_500), "42s");
+        assert_eq!(format_duration(192_000), "3m12s");
+        assert_eq!(format_duration(3_900_000), "1h05m");
+        assert_eq!(format_percent(0.125), "12%");
+    }
+}
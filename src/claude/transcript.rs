@@ -0,0 +1,297 @@
+//! Read-only transcript parsing from session JSONL files.
+//!
+//! Only `user` and `assistant` lines are kept; summaries, snapshots and
+//! meta messages (command caveats etc.) are skipped.
+
+use anyhow::Result;
+use serde_json::Value;
+use std::path::Path;
+
+use crate::claude::log_tail::read_lines;
+
+/// Who sent a transcript message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    User,
+    Assistant,
+}
+
+/// One content block within a message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TranscriptBlock {
+    /// Plain text
+    Text(String),
+    /// Extended thinking
+    Thinking(String),
+    /// A tool invocation; `summary` is the most telling input field
+    /// (command, file path, pattern...) and `input` is pretty-printed JSON
+    ToolUse {
+        name: String,
+        summary: String,
+        input: String,
+    },
+    /// Output returned to the model for a tool invocation
+    ToolResult { content: String, is_error: bool },
+}
+
+/// A single user or assistant turn.
+#[derive(Debug, Clone)]
+pub struct TranscriptMessage {
+    /// Message UUID (if present)
+    pub uuid: Option<String>,
+    pub role: Role,
+    /// ISO 8601 timestamp (if present)
+    pub timestamp: Option<String>,
+    pub blocks: Vec<TranscriptBlock>,
+}
+
+impl TranscriptMessage {
+    /// All searchable text in the message (block bodies, tool names and I/O).
+    pub fn text(&self) -> String {
+        self.blocks
+            .iter()
+            .map(|block| match block {
+                TranscriptBlock::Text(text) | TranscriptBlock::Thinking(text) => text.clone(),
+                TranscriptBlock::ToolUse { name, input, .. } => format!("{}\n{}", name, input),
+                TranscriptBlock::ToolResult { content, .. } => content.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// A parsed conversation transcript.
+#[derive(Debug, Clone, Default)]
+pub struct Transcript {
+    pub messages: Vec<TranscriptMessage>,
+}
+
+impl Transcript {
+    /// Index of the message with the given UUID.
+    pub fn message_index(&self, uuid: &str) -> Option<usize> {
+        self.messages
+            .iter()
+            .position(|m| m.uuid.as_deref() == Some(uuid))
+    }
+}
+
+/// Parse a session JSONL file into a transcript.
+///
+/// Malformed lines are skipped rather than failing the whole file, and a
+/// partially written last line (of a running session) is left out. Invalid
+/// UTF-8 is decoded lossily, like the content index does, so message
+/// indices agree with it.
+pub fn parse_transcript(path: &Path) -> Result<Transcript> {
+    let mut messages = Vec::new();
+    read_lines(path, &mut 0, |line| messages.extend(parse_line(line)))?;
+    Ok(Transcript { messages })
+}
+
+/// Parse one JSONL line into a message, if it is a displayable turn.
+pub(crate) fn parse_line(line: &str) -> Option<TranscriptMessage> {
+    let value: Value = serde_json::from_str(line).ok()?;
+
+    let role = match value.get("type").and_then(Value::as_str)? {
+        "user" => Role::User,
+        "assistant" => Role::Assistant,
+        _ => return None,
+    };
+    if value.get("isMeta").and_then(Value::as_bool) == Some(true) {
+        return None;
+    }
+
+    let content = value.get("message")?.get("content")?;
+    let blocks = parse_content(content);
+    if blocks.is_empty() {
+        return None;
+    }
+
+    Some(TranscriptMessage {
+        uuid: value.get("uuid").and_then(Value::as_str).map(String::from),
+        role,
+        timestamp: value
+            .get("timestamp")
+            .and_then(Value::as_str)
+            .map(String::from),
+        blocks,
+    })
+}
+
+/// Convert message content (a string or an array of blocks) into blocks.
+fn parse_content(content: &Value) -> Vec<TranscriptBlock> {
+    match content {
+        Value::String(s) if !s.trim().is_empty() => vec![TranscriptBlock::Text(s.clone())],
+        Value::Array(items) => items.iter().filter_map(parse_block).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_block(item: &Value) -> Option<TranscriptBlock> {
+    match item.get("type").and_then(Value::as_str)? {
+        "text" => {
+            let text = item.get("text").and_then(Value::as_str)?;
+            (!text.trim().is_empty()).then(|| TranscriptBlock::Text(text.to_string()))
+        }
+        "thinking" => {
+            let text = item.get("thinking").and_then(Value::as_str)?;
+            (!text.trim().is_empty()).then(|| TranscriptBlock::Thinking(text.to_string()))
+        }
+        "tool_use" => {
+            let name = item
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or("tool")
+                .to_string();
+            let input = item.get("input").unwrap_or(&Value::Null);
+            Some(TranscriptBlock::ToolUse {
+                name,
+                summary: tool_input_summary(input),
+                input: serde_json::to_string_pretty(input).unwrap_or_default(),
+            })
+        }
+        "tool_result" => Some(TranscriptBlock::ToolResult {
+            content: item
+                .get("content")
+                .map(tool_result_text)
+                .unwrap_or_default(),
+            is_error: item.get("is_error").and_then(Value::as_bool) == Some(true),
+        }),
+        _ => None,
+    }
+}
+
+/// One-line summary of a tool's input, taken from the first well-known field.
+fn tool_input_summary(input: &Value) -> String {
+    const KEYS: &[&str] = &[
+        "command",
+        "file_path",
+        "path",
+        "pattern",
+        "url",
+        "query",
+        "description",
+        "prompt",
+    ];
+    KEYS.iter()
+        .find_map(|key| input.get(*key).and_then(Value::as_str))
+        .and_then(|s| s.lines().next())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Tool result content is either a string or an array of text/image blocks.
+fn tool_result_text(content: &Value) -> String {
+    match content {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| match item.get("type").and_then(Value::as_str) {
+                Some("text") => item.get("text").and_then(Value::as_str).map(String::from),
+                Some("image") => Some("[image]".to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn parses_string_user_content() {
+        let msg = parse_line(
+            r#"{"type":"user","uuid":"u1","timestamp":"2026-01-01T00:00:00Z","message":{"role":"user","content":"hello"}}"#,
+        )
+        .unwrap();
+        assert_eq!(msg.role, Role::User);
+        assert_eq!(msg.uuid.as_deref(), Some("u1"));
+        assert_eq!(msg.blocks, vec![TranscriptBlock::Text("hello".to_string())]);
+    }
+
+    #[test]
+    fn parses_assistant_thinking_text_and_tool_use() {
+        let msg = parse_line(
+            r#"{"type":"assistant","message":{"content":[
+                {"type":"thinking","thinking":"let me look"},
+                {"type":"text","text":"Running it"},
+                {"type":"tool_use","id":"t1","name":"Bash","input":{"command":"ls"}}
+            ]}}"#,
+        )
+        .unwrap();
+        assert_eq!(msg.role, Role::Assistant);
+        assert_eq!(msg.blocks.len(), 3);
+        assert_eq!(
+            msg.blocks[0],
+            TranscriptBlock::Thinking("let me look".to_string())
+        );
+        match &msg.blocks[2] {
+            TranscriptBlock::ToolUse {
+                name,
+                summary,
+                input,
+            } => {
+                assert_eq!(name, "Bash");
+                assert_eq!(summary, "ls");
+                assert!(input.contains("\"command\": \"ls\""));
+            }
+            other => panic!("expected tool use, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_tool_result_array_content_and_error_flag() {
+        let msg = parse_line(
+            r#"{"type":"user","message":{"content":[
+                {"type":"tool_result","tool_use_id":"t1","is_error":true,
+                 "content":[{"type":"text","text":"line one"},{"type":"text","text":"line two"}]}
+            ]}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            msg.blocks,
+            vec![TranscriptBlock::ToolResult {
+                content: "line one\nline two".to_string(),
+                is_error: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn skips_meta_summary_and_malformed_lines() {
+        assert!(parse_line(r#"{"type":"summary","summary":"x"}"#).is_none());
+        assert!(
+            parse_line(r#"{"type":"user","isMeta":true,"message":{"content":"caveat"}}"#).is_none()
+        );
+        assert!(parse_line(r#"{"type":"user","message":{"content":"   "}}"#).is_none());
+        assert!(parse_line("{not json").is_none());
+    }
+
+    #[test]
+    fn parse_transcript_reads_file_in_order() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            r#"{{"type":"user","uuid":"a","message":{{"content":"first"}}}}"#
+        )
+        .unwrap();
+        writeln!(file, r#"{{"type":"summary","summary":"s"}}"#).unwrap();
+        // Invalid UTF-8 doesn't end the transcript
+        file.write_all(b"{\"type\":\"user\",\"message\":{\"content\":\"caf\xe9\"}}\n")
+            .unwrap();
+        writeln!(
+            file,
+            r#"{{"type":"assistant","uuid":"b","message":{{"content":[{{"type":"text","text":"second"}}]}}}}"#
+        )
+        .unwrap();
+        write!(file, r#"{{"type":"user","message":"#).unwrap();
+
+        let transcript = parse_transcript(file.path()).unwrap();
+        assert_eq!(transcript.messages.len(), 3);
+        assert_eq!(transcript.messages[1].text(), "caf\u{fffd}");
+        assert_eq!(transcript.message_index("b"), Some(2));
+    }
+}
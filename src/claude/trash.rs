@@ -0,0 +1,538 @@
+//! Recoverable deletion of conversations.
+//!
+//! Deleting a conversation moves its session log, its `<session-id>/`
+//! directory and any flat subagent logs into `claudatui-trash/` next to the
+//! archive file, and takes its entry out of `sessions-index.json`. The
+//! manifest remembers where everything came from so it can be put back.
+//! Each deletion is a separate entry, so a session deleted again after it
+//! reappeared keeps both copies. Entries older than `purge_after_days` are
+//! deleted for good. The session's archive, pin and metadata entries are
+//! left alone while it is in the trash, so restoring it brings them back;
+//! the app forgets them once no copy of the session is left.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::conversation::Conversation;
+
+/// Default number of days trashed conversations are kept
+const DEFAULT_PURGE_AFTER_DAYS: u32 = 30;
+
+const INDEX_FILE: &str = "sessions-index.json";
+
+/// A file or directory moved into the trash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedFile {
+    pub original: PathBuf,
+    pub stored: PathBuf,
+}
+
+/// A `sessions-index.json` entry removed along with its session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedIndexEntry {
+    pub index_path: PathBuf,
+    pub entry: Value,
+}
+
+/// A deleted conversation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    /// Unique key of the entry, also the name of its directory in the trash
+    /// (the session ID for entries from before entries had their own key)
+    #[serde(default)]
+    pub id: String,
+    pub session_id: String,
+    pub title: String,
+    pub project_path: PathBuf,
+    pub trashed_at: DateTime<Utc>,
+    pub files: Vec<TrashedFile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_entry: Option<TrashedIndexEntry>,
+}
+
+/// Full trash state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashState {
+    pub version: u32,
+    #[serde(default = "default_purge_after_days")]
+    pub purge_after_days: Option<u32>, // None = keep forever
+    pub entries: Vec<TrashEntry>,
+}
+
+fn default_purge_after_days() -> Option<u32> {
+    Some(DEFAULT_PURGE_AFTER_DAYS)
+}
+
+impl Default for TrashState {
+    fn default() -> Self {
+        Self {
+            version: 1,
+            purge_after_days: Some(DEFAULT_PURGE_AFTER_DAYS),
+            entries: Vec::new(),
+        }
+    }
+}
+
+/// Trash manager for moving conversations out and back
+pub struct TrashManager {
+    trash_dir: PathBuf,
+    state: TrashState,
+    dirty: bool,
+}
+
+impl TrashManager {
+    /// Create a TrashManager for `claude_dir/claudatui-trash`, loading its
+    /// manifest if there is one
+    pub fn new(claude_dir: &Path) -> Result<Self> {
+        let trash_dir = claude_dir.join("claudatui-trash");
+        let manifest = trash_dir.join("trash.json");
+
+        let mut state: TrashState = if manifest.exists() {
+            let content = fs::read_to_string(&manifest)
+                .with_context(|| format!("Failed to read trash manifest: {:?}", manifest))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse trash manifest: {:?}", manifest))?
+        } else {
+            TrashState::default()
+        };
+        for entry in &mut state.entries {
+            if entry.id.is_empty() {
+                entry.id.clone_from(&entry.session_id);
+            }
+        }
+
+        Ok(Self {
+            trash_dir,
+            state,
+            dirty: false,
+        })
+    }
+
+    /// Trashed conversations, most recently deleted first
+    pub fn entries(&self) -> &[TrashEntry] {
+        &self.state.entries
+    }
+
+    /// Whether any trashed entry holds a copy of the session.
+    pub fn contains(&self, session_id: &str) -> bool {
+        self.state
+            .entries
+            .iter()
+            .any(|e| e.session_id == session_id)
+    }
+
+    /// Get purge-after days (None if trashed conversations are kept forever)
+    pub fn purge_after_days(&self) -> Option<u32> {
+        self.state.purge_after_days
+    }
+
+    /// Set purge-after days (None to keep trashed conversations forever)
+    pub fn set_purge_after_days(&mut self, days: Option<u32>) {
+        self.state.purge_after_days = days;
+        self.dirty = true;
+    }
+
+    /// Move a conversation's files into the trash. Returns the new entry's ID.
+    pub fn trash(&mut self, conv: &Conversation) -> Result<String> {
+        if !conv.file_path.exists() {
+            bail!("{:?} no longer exists", conv.file_path);
+        }
+
+        let trashed_at = Utc::now();
+        let mut id = format!("{}-{}", conv.session_id, trashed_at.timestamp_millis());
+        while self.entry(&id).is_some() || self.trash_dir.join(&id).exists() {
+            id.push('x');
+        }
+        let entry_dir = self.trash_dir.join(&id);
+        fs::create_dir_all(&entry_dir)
+            .with_context(|| format!("Failed to create trash directory: {:?}", entry_dir))?;
+
+        let session_dir = conv.file_path.with_extension("");
+        let mut originals = vec![conv.file_path.clone()];
+        if session_dir.is_dir() {
+            originals.push(session_dir.clone());
+        }
+        originals.extend(
+            conv.subagent_files
+                .iter()
+                .filter(|f| !f.starts_with(&session_dir) && f.exists())
+                .cloned(),
+        );
+
+        let mut files: Vec<TrashedFile> = Vec::new();
+        for (i, original) in originals.into_iter().enumerate() {
+            let name = original
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let stored = entry_dir.join(format!("{}-{}", i, name));
+            if let Err(e) = move_path(&original, &stored) {
+                // Put back what was already moved so nothing is half-deleted
+                for file in files.iter().rev() {
+                    let _ = move_path(&file.stored, &file.original);
+                }
+                let _ = fs::remove_dir_all(&entry_dir);
+                return Err(e).with_context(|| format!("Failed to move {:?} to trash", original));
+            }
+            files.push(TrashedFile { original, stored });
+        }
+
+        let index_entry = conv
+            .file_path
+            .parent()
+            .map(|dir| dir.join(INDEX_FILE))
+            .and_then(|index_path| {
+                let entry = remove_index_entry(&index_path, &conv.session_id).ok()??;
+                Some(TrashedIndexEntry { index_path, entry })
+            });
+
+        self.state.entries.insert(
+            0,
+            TrashEntry {
+                id: id.clone(),
+                session_id: conv.session_id.clone(),
+                title: conv.display.clone(),
+                project_path: conv.project_path.clone(),
+                trashed_at,
+                files,
+                index_entry,
+            },
+        );
+        self.dirty = true;
+        Ok(id)
+    }
+
+    fn entry(&self, id: &str) -> Option<&TrashEntry> {
+        self.state.entries.iter().find(|e| e.id == id)
+    }
+
+    /// Move a trashed conversation back where it came from.
+    pub fn restore(&mut self, id: &str) -> Result<TrashEntry> {
+        let Some(pos) = self.state.entries.iter().position(|e| e.id == id) else {
+            bail!("'{}' is not in the trash", id);
+        };
+        let entry = &self.state.entries[pos];
+        if let Some(file) = entry.files.iter().find(|f| f.original.exists()) {
+            bail!("{:?} already exists", file.original);
+        }
+
+        let mut restored: Vec<&TrashedFile> = Vec::new();
+        for file in &entry.files {
+            let result = file
+                .original
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| move_path(&file.stored, &file.original));
+            if let Err(e) = result {
+                // Put back in the trash what was already restored, so the
+                // entry stays whole
+                for file in restored.iter().rev() {
+                    let _ = move_path(&file.original, &file.stored);
+                }
+                return Err(e).with_context(|| format!("Failed to restore {:?}", file.original));
+            }
+            restored.push(file);
+        }
+        if let Some(index) = &entry.index_entry {
+            // Sessions are found from their files; the index only adds metadata
+            let _ = insert_index_entry(&index.index_path, &index.entry);
+        }
+
+        let entry = self.state.entries.remove(pos);
+        let _ = fs::remove_dir_all(self.trash_dir.join(&entry.id));
+        self.dirty = true;
+        Ok(entry)
+    }
+
+    /// Delete a trashed conversation permanently. Returns the entry, if it
+    /// was in the trash.
+    pub fn purge(&mut self, id: &str) -> Result<Option<TrashEntry>> {
+        let entry_dir = self.trash_dir.join(id);
+        if entry_dir.exists() {
+            fs::remove_dir_all(&entry_dir)
+                .with_context(|| format!("Failed to delete {:?}", entry_dir))?;
+        }
+        let Some(pos) = self.state.entries.iter().position(|e| e.id == id) else {
+            return Ok(None);
+        };
+        self.dirty = true;
+        Ok(Some(self.state.entries.remove(pos)))
+    }
+
+    /// Permanently delete conversations trashed more than `purge_after_days`
+    /// ago. An entry that can't be deleted doesn't stop the others. Returns
+    /// the purged entries and the errors of those that failed.
+    pub fn purge_expired(&mut self, now: DateTime<Utc>) -> (Vec<TrashEntry>, Vec<anyhow::Error>) {
+        let Some(days) = self.state.purge_after_days else {
+            return (Vec::new(), Vec::new());
+        };
+        let expired: Vec<String> = self
+            .state
+            .entries
+            .iter()
+            .filter(|e| now.signed_duration_since(e.trashed_at).num_days() >= days as i64)
+            .map(|e| e.id.clone())
+            .collect();
+        let mut purged = Vec::new();
+        let mut errors = Vec::new();
+        for id in &expired {
+            match self.purge(id) {
+                Ok(entry) => purged.extend(entry),
+                Err(e) => errors.push(e),
+            }
+        }
+        (purged, errors)
+    }
+
+    /// Whether nothing is left of a purged entry's session: no other copy in
+    /// the trash and no log back at its original place. Its metadata can go.
+    pub fn is_gone(&self, purged: &TrashEntry) -> bool {
+        !self.contains(&purged.session_id)
+            && purged.files.first().is_none_or(|f| !f.original.exists())
+    }
+
+    /// Check if state needs to be saved
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Save the manifest to disk
+    /// Uses atomic write (write to temp, then rename) for safety
+    pub fn save(&mut self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        fs::create_dir_all(&self.trash_dir)
+            .with_context(|| format!("Failed to create trash directory: {:?}", self.trash_dir))?;
+        let json =
+            serde_json::to_string_pretty(&self.state).context("Failed to serialize trash state")?;
+
+        let manifest = self.trash_dir.join("trash.json");
+        let temp_path = manifest.with_extension("tmp");
+        fs::write(&temp_path, json)
+            .with_context(|| format!("Failed to write temp trash manifest: {:?}", temp_path))?;
+        fs::rename(&temp_path, &manifest)
+            .with_context(|| format!("Failed to rename trash manifest: {:?}", manifest))?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// Rename, falling back to copy-and-delete across filesystems.
+fn move_path(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Ok(()) => Ok(()),
+        Err(_) if from.exists() => {
+            copy_recursive(from, to)?;
+            if from.is_dir() {
+                fs::remove_dir_all(from)
+            } else {
+                fs::remove_file(from)
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> io::Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to).map(|_| ())
+    }
+}
+
+/// Take a session's entry out of a `sessions-index.json`, keeping every
+/// other field of the file as it was.
+fn remove_index_entry(index_path: &Path, session_id: &str) -> Result<Option<Value>> {
+    if !index_path.exists() {
+        return Ok(None);
+    }
+    let mut index: Value = serde_json::from_str(&fs::read_to_string(index_path)?)?;
+    let Some(entries) = index.get_mut("entries").and_then(Value::as_array_mut) else {
+        return Ok(None);
+    };
+    let Some(pos) = entries
+        .iter()
+        .position(|e| e.get("sessionId").and_then(Value::as_str) == Some(session_id))
+    else {
+        return Ok(None);
+    };
+    let entry = entries.remove(pos);
+    fs::write(index_path, serde_json::to_string_pretty(&index)?)?;
+    Ok(Some(entry))
+}
+
+fn insert_index_entry(index_path: &Path, entry: &Value) -> Result<()> {
+    if !index_path.exists() {
+        return Ok(());
+    }
+    let mut index: Value = serde_json::from_str(&fs::read_to_string(index_path)?)?;
+    let Some(entries) = index.get_mut("entries").and_then(Value::as_array_mut) else {
+        return Ok(());
+    };
+    entries.push(entry.clone());
+    fs::write(index_path, serde_json::to_string_pretty(&index)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    const SESSION: &str = "d90ed21d-ed03-4e94-87d7-dbc5de6cc828";
+
+    /// A project with one session, a subagent directory, a flat sidechain and
+    /// an index listing the session next to another one.
+    fn setup(claude_dir: &Path) -> Conversation {
+        let project = claude_dir.join("projects/-tmp-app");
+        fs::create_dir_all(project.join(SESSION).join("subagents")).unwrap();
+        let file_path = project.join(format!("{SESSION}.jsonl"));
+        fs::write(&file_path, "{}\n").unwrap();
+        fs::write(
+            project.join(SESSION).join("subagents/agent-a.jsonl"),
+            "{}\n",
+        )
+        .unwrap();
+        let flat = project.join("agent-b.jsonl");
+        fs::write(&flat, "{}\n").unwrap();
+        fs::write(
+            project.join(INDEX_FILE),
+            format!(
+                r#"{{"version":1,"entries":[{{"sessionId":"{SESSION}","summary":"Fix login"}},{{"sessionId":"other"}}]}}"#
+            ),
+        )
+        .unwrap();
+
+        Conversation {
+            display: "Fix login".to_string(),
+            file_path,
+            message_count: Some(1),
+            is_archived: true,
+            subagent_files: vec![project.join(SESSION).join("subagents/agent-a.jsonl"), flat],
+            ..Conversation::for_test(SESSION, "/tmp/app")
+        }
+    }
+
+    fn index_ids(claude_dir: &Path) -> Vec<String> {
+        let index: Value = serde_json::from_str(
+            &fs::read_to_string(claude_dir.join("projects/-tmp-app").join(INDEX_FILE)).unwrap(),
+        )
+        .unwrap();
+        index["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["sessionId"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn trash_moves_files_out_and_restore_puts_them_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let conv = setup(dir.path());
+        let mut trash = TrashManager::new(dir.path()).unwrap();
+
+        let id = trash.trash(&conv).unwrap();
+        assert!(!conv.file_path.exists());
+        assert!(!conv.file_path.with_extension("").exists());
+        assert!(!conv.subagent_files[1].exists());
+        assert_eq!(index_ids(dir.path()), vec!["other"]);
+        assert_eq!(trash.entries().len(), 1);
+        assert_eq!(trash.entries()[0].files.len(), 3);
+
+        // The manifest survives a restart
+        trash.save().unwrap();
+        let mut trash = TrashManager::new(dir.path()).unwrap();
+        let entry = trash.restore(&id).unwrap();
+        assert_eq!(entry.title, "Fix login");
+        assert!(conv.file_path.exists());
+        assert!(conv.subagent_files.iter().all(|f| f.exists()));
+        assert_eq!(index_ids(dir.path()), vec!["other", SESSION]);
+        assert!(trash.entries().is_empty());
+        assert!(!dir.path().join("claudatui-trash").join(&id).exists());
+    }
+
+    #[test]
+    fn restore_refuses_to_overwrite_a_session_that_reappeared() {
+        let dir = tempfile::tempdir().unwrap();
+        let conv = setup(dir.path());
+        let mut trash = TrashManager::new(dir.path()).unwrap();
+        let id = trash.trash(&conv).unwrap();
+
+        fs::write(&conv.file_path, "new\n").unwrap();
+        assert!(trash.restore(&id).is_err());
+        assert!(trash.contains(SESSION));
+        assert_eq!(fs::read_to_string(&conv.file_path).unwrap(), "new\n");
+    }
+
+    #[test]
+    fn purge_expired_deletes_only_old_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let conv = setup(dir.path());
+        let mut trash = TrashManager::new(dir.path()).unwrap();
+        let id = trash.trash(&conv).unwrap();
+
+        let now = Utc::now();
+        let (purged, errors) = trash.purge_expired(now + Duration::days(29));
+        assert!(purged.is_empty() && errors.is_empty());
+        let (purged, errors) = trash.purge_expired(now + Duration::days(30));
+        assert!(errors.is_empty());
+        assert_eq!(purged.len(), 1);
+        assert!(trash.is_gone(&purged[0]));
+        assert!(trash.entries().is_empty());
+        assert!(!dir.path().join("claudatui-trash").join(&id).exists());
+
+        trash.set_purge_after_days(None);
+        let (purged, errors) = trash.purge_expired(now + Duration::days(365));
+        assert!(purged.is_empty() && errors.is_empty());
+    }
+
+    #[test]
+    fn failed_restore_puts_restored_files_back_in_the_trash() {
+        let dir = tempfile::tempdir().unwrap();
+        let conv = setup(dir.path());
+        let mut trash = TrashManager::new(dir.path()).unwrap();
+        let id = trash.trash(&conv).unwrap();
+
+        // The subagent directory went missing from the trash
+        let files = trash.entries()[0].files.clone();
+        fs::remove_dir_all(&files[1].stored).unwrap();
+        assert!(trash.restore(&id).is_err());
+        assert!(!conv.file_path.exists());
+        assert!(files[0].stored.exists());
+        assert!(trash.contains(SESSION));
+    }
+
+    #[test]
+    fn deleting_a_reappeared_session_keeps_both_copies() {
+        let dir = tempfile::tempdir().unwrap();
+        let conv = setup(dir.path());
+        let mut trash = TrashManager::new(dir.path()).unwrap();
+        let first = trash.trash(&conv).unwrap();
+
+        fs::write(&conv.file_path, "new\n").unwrap();
+        let second = trash.trash(&conv).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(trash.entries().len(), 2);
+
+        // Purging one copy leaves the other, so the session isn't gone
+        let purged = trash.purge(&second).unwrap().unwrap();
+        assert!(!trash.is_gone(&purged));
+        trash.restore(&first).unwrap();
+        assert_eq!(fs::read_to_string(&conv.file_path).unwrap(), "{}\n");
+    }
+}
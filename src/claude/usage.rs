@@ -0,0 +1,650 @@
+//! Token usage and cost accounting from session JSONL files.
+//!
+//! Assistant messages carry a `usage` block (input, output and cache tokens)
+//! and the `model` that produced them. Claude Code writes one line per
+//! content block, repeating the same usage, so lines are deduplicated by
+//! message ID. Usage is kept per (day, model) so it can be priced and rolled
+//! up by conversation, group, project, profile and day; messages from the
+//! last day are also kept individually for rolling usage windows. Logs are
+//! read incrementally by `log_stats`, together with tool stats.
+
+use std::collections::{BTreeMap, HashSet};
+use std::ops::AddAssign;
+use std::path::Path;
+
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::claude::conversation::Conversation;
+use crate::claude::grouping::ConversationGroup;
+use crate::claude::log_stats::LogStats;
+use crate::claude::log_tail::read_lines;
+use crate::config::ProfileEntry;
+
+/// Token counts for one or more API responses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TokenUsage {
+    pub input: u64,
+    pub output: u64,
+    /// Tokens written to the prompt cache
+    pub cache_write: u64,
+    /// Tokens read from the prompt cache
+    pub cache_read: u64,
+}
+
+impl TokenUsage {
+    /// All tokens, cached or not.
+    pub fn total(&self) -> u64 {
+        self.input + self.output + self.cache_write + self.cache_read
+    }
+}
+
+impl AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_write += other.cache_write;
+        self.cache_read += other.cache_read;
+    }
+}
+
+/// Price of a model family in USD per million tokens.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    /// Matched as a case-insensitive substring of the model name (longest match wins)
+    pub model: String,
+    pub input: f64,
+    pub output: f64,
+    pub cache_write: f64,
+    pub cache_read: f64,
+}
+
+impl ModelPrice {
+    fn new(model: &str, input: f64, output: f64, cache_write: f64, cache_read: f64) -> Self {
+        Self {
+            model: model.to_string(),
+            input,
+            output,
+            cache_write,
+            cache_read,
+        }
+    }
+
+    fn cost(&self, usage: &TokenUsage) -> f64 {
+        (usage.input as f64 * self.input
+            + usage.output as f64 * self.output
+            + usage.cache_write as f64 * self.cache_write
+            + usage.cache_read as f64 * self.cache_read)
+            / 1_000_000.0
+    }
+}
+
+/// Prices used to turn token counts into cost.
+#[derive(Debug, Clone)]
+pub struct PriceTable {
+    prices: Vec<ModelPrice>,
+}
+
+impl Default for PriceTable {
+    fn default() -> Self {
+        Self {
+            prices: vec![
+                ModelPrice::new("opus", 5.0, 25.0, 6.25, 0.5),
+                // Opus before 4.5 (Opus 4 only has dated IDs: `claude-opus-4-2025…`)
+                ModelPrice::new("claude-opus-4-1", 15.0, 75.0, 18.75, 1.5),
+                ModelPrice::new("claude-opus-4-2025", 15.0, 75.0, 18.75, 1.5),
+                ModelPrice::new("claude-3-opus", 15.0, 75.0, 18.75, 1.5),
+                ModelPrice::new("sonnet", 3.0, 15.0, 3.75, 0.3),
+                ModelPrice::new("haiku", 1.0, 5.0, 1.25, 0.1),
+                ModelPrice::new("3-5-haiku", 0.8, 4.0, 1.0, 0.08),
+                ModelPrice::new("3-haiku", 0.25, 1.25, 0.3, 0.03),
+            ],
+        }
+    }
+}
+
+impl PriceTable {
+    /// Built-in prices, overridden (or extended) by configured entries.
+    pub fn with_overrides(overrides: &[ModelPrice]) -> Self {
+        let mut table = Self::default();
+        for price in overrides {
+            table
+                .prices
+                .retain(|p| !p.model.eq_ignore_ascii_case(&price.model));
+            table.prices.push(price.clone());
+        }
+        table
+    }
+
+    /// The most specific price entry for a model.
+    pub fn price_for(&self, model: &str) -> Option<&ModelPrice> {
+        let model = model.to_lowercase();
+        self.prices
+            .iter()
+            .filter(|p| model.contains(&p.model.to_lowercase()))
+            .max_by_key(|p| p.model.len())
+    }
+
+    /// Cost of `usage` on `model` in USD (zero for unknown models).
+    pub fn cost(&self, model: &str, usage: &TokenUsage) -> f64 {
+        self.price_for(model).map_or(0.0, |p| p.cost(usage))
+    }
+}
+
+/// Usage of one session, keyed by (local day, model).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionUsage {
+    pub by_day_model: BTreeMap<(NaiveDate, String), TokenUsage>,
+}
+
+impl SessionUsage {
+    /// Sum over all days and models.
+    pub fn total(&self) -> TokenUsage {
+        let mut total = TokenUsage::default();
+        for usage in self.by_day_model.values() {
+            total += *usage;
+        }
+        total
+    }
+
+    /// Total cost in USD.
+    pub fn cost(&self, prices: &PriceTable) -> f64 {
+        self.by_day_model
+            .iter()
+            .map(|((_, model), usage)| prices.cost(model, usage))
+            .sum()
+    }
+
+    fn record(&mut self, record: UsageRecord) {
+        *self
+            .by_day_model
+            .entry((record.day, record.model))
+            .or_default() += record.usage;
+    }
+}
+
+/// How long per-message usage is kept for rolling windows (24 hours).
+const RECENT_EVENTS_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// Usage of one assistant message, kept for recent messages so rolling
+/// windows (see `claude::budget`) can be computed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageEvent {
+    /// When the message was written (ms)
+    pub timestamp: i64,
+    pub model: String,
+    pub usage: TokenUsage,
+}
+
+/// Usage reported by one assistant JSONL line.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct UsageRecord {
+    /// Message ID (or request ID) used to deduplicate repeated lines
+    pub id: Option<String>,
+    /// Message time in ms (parse time if the line has no timestamp)
+    pub timestamp: i64,
+    pub day: NaiveDate,
+    pub model: String,
+    pub usage: TokenUsage,
+}
+
+#[derive(Deserialize)]
+struct RawLine {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    timestamp: Option<String>,
+    #[serde(rename = "requestId")]
+    request_id: Option<String>,
+    message: Option<RawMessage>,
+}
+
+#[derive(Deserialize)]
+struct RawMessage {
+    id: Option<String>,
+    model: Option<String>,
+    usage: Option<RawUsage>,
+}
+
+#[derive(Deserialize)]
+struct RawUsage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+    #[serde(default)]
+    cache_creation_input_tokens: u64,
+    #[serde(default)]
+    cache_read_input_tokens: u64,
+}
+
+/// Parse the usage from one JSONL line, if it is an assistant message with usage.
+pub(crate) fn parse_usage_line(line: &str) -> Option<UsageRecord> {
+    // Cheap pre-check: most lines (user messages, tool results) have no usage
+    if !line.contains("\"usage\"") {
+        return None;
+    }
+    let raw: RawLine = serde_json::from_str(line).ok()?;
+    if raw.kind.as_deref() != Some("assistant") {
+        return None;
+    }
+    let message = raw.message?;
+    let usage = message.usage?;
+    let model = message.model?;
+    // Locally generated messages (errors, interrupts) cost nothing
+    if model.starts_with('<') {
+        return None;
+    }
+    let time = raw
+        .timestamp
+        .as_deref()
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map_or_else(Local::now, |dt| dt.with_timezone(&Local));
+
+    Some(UsageRecord {
+        id: message.id.or(raw.request_id),
+        timestamp: time.timestamp_millis(),
+        day: time.date_naive(),
+        model,
+        usage: TokenUsage {
+            input: usage.input_tokens,
+            output: usage.output_tokens,
+            cache_write: usage.cache_creation_input_tokens,
+            cache_read: usage.cache_read_input_tokens,
+        },
+    })
+}
+
+/// Parse the total usage of a session file.
+pub fn parse_session_usage(path: &Path) -> Result<SessionUsage> {
+    let mut log = UsageLog::default();
+    read_lines(path, &mut 0, |line| log.read_line(line))?;
+    Ok(log.usage)
+}
+
+/// Usage parsed from one session log so far.
+#[derive(Debug, Default)]
+pub(crate) struct UsageLog {
+    /// Message IDs already counted
+    seen: HashSet<String>,
+    pub usage: SessionUsage,
+    /// Messages from the last `RECENT_EVENTS_MS`
+    pub recent: Vec<UsageEvent>,
+}
+
+impl UsageLog {
+    /// Count the usage of one log line.
+    pub fn read_line(&mut self, line: &str) {
+        let Some(record) = parse_usage_line(line) else {
+            return;
+        };
+        if let Some(ref id) = record.id {
+            if !self.seen.insert(id.clone()) {
+                return;
+            }
+        }
+        self.recent.push(UsageEvent {
+            timestamp: record.timestamp,
+            model: record.model.clone(),
+            usage: record.usage,
+        });
+        self.usage.record(record);
+    }
+
+    /// Drop per-message usage older than rolling windows look back.
+    pub fn prune_recent(&mut self, now: i64) {
+        let cutoff = now - RECENT_EVENTS_MS;
+        self.recent.retain(|event| event.timestamp >= cutoff);
+    }
+}
+
+/// One row of a usage rollup.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageRow {
+    pub label: String,
+    pub tokens: TokenUsage,
+    /// USD
+    pub cost: f64,
+}
+
+impl UsageRow {
+    fn add(&mut self, tokens: TokenUsage, cost: f64) {
+        self.tokens += tokens;
+        self.cost += cost;
+    }
+}
+
+/// Dimension a usage report is broken down by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UsageDimension {
+    #[default]
+    Project,
+    Group,
+    Profile,
+    Day,
+    Model,
+    Conversation,
+}
+
+impl UsageDimension {
+    /// All dimensions, in display order.
+    pub const ALL: [Self; 6] = [
+        Self::Project,
+        Self::Group,
+        Self::Profile,
+        Self::Day,
+        Self::Model,
+        Self::Conversation,
+    ];
+
+    /// Display name (also accepted by `from_name`).
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Project => "project",
+            Self::Group => "group",
+            Self::Profile => "profile",
+            Self::Day => "day",
+            Self::Model => "model",
+            Self::Conversation => "conversation",
+        }
+    }
+
+    /// Parse a dimension name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.name() == name)
+    }
+
+    /// The next dimension (wrapping).
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|d| *d == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// Usage rolled up along one dimension.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UsageReport {
+    pub total: UsageRow,
+    /// Sorted by cost (descending), or by date (newest first) for days
+    pub rows: Vec<UsageRow>,
+}
+
+impl UsageReport {
+    /// Roll up the usage of all conversations in `groups` along `dimension`.
+    pub fn build(
+        stats: &LogStats,
+        groups: &[ConversationGroup],
+        prices: &PriceTable,
+        profiles: &[ProfileEntry],
+        dimension: UsageDimension,
+    ) -> Self {
+        let mut rows: BTreeMap<String, UsageRow> = BTreeMap::new();
+        let mut total = UsageRow {
+            label: "Total".to_string(),
+            ..UsageRow::default()
+        };
+
+        for group in groups {
+            for conv in group.conversations() {
+                let Some(usage) = stats.usage(&conv.session_id) else {
+                    continue;
+                };
+                for ((day, model), tokens) in &usage.by_day_model {
+                    let cost = prices.cost(model, tokens);
+                    total.add(*tokens, cost);
+                    for label in row_labels(dimension, group, conv, profiles, *day, model) {
+                        rows.entry(label.clone())
+                            .or_insert_with(|| UsageRow {
+                                label,
+                                ..UsageRow::default()
+                            })
+                            .add(*tokens, cost);
+                    }
+                }
+            }
+        }
+
+        let mut rows: Vec<UsageRow> = rows.into_values().collect();
+        if dimension == UsageDimension::Day {
+            rows.reverse();
+        } else {
+            rows.sort_by(|a, b| b.cost.total_cmp(&a.cost));
+        }
+        Self { total, rows }
+    }
+}
+
+/// Labels of the rows a conversation's usage counts towards. Profiles can
+/// overlap, so a conversation may count towards several (or none).
+fn row_labels(
+    dimension: UsageDimension,
+    group: &ConversationGroup,
+    conv: &Conversation,
+    profiles: &[ProfileEntry],
+    day: NaiveDate,
+    model: &str,
+) -> Vec<String> {
+    match dimension {
+        UsageDimension::Project => vec![group.project_display_name()],
+        UsageDimension::Group => vec![group.display_name()],
+        UsageDimension::Profile => {
+            let path = conv.project_path.to_string_lossy();
+            profiles
+                .iter()
+                .filter(|p| p.workspaces.iter().any(|ws| path.starts_with(ws.as_str())))
+                .map(|p| p.name.clone())
+                .collect()
+        }
+        UsageDimension::Day => vec![day.format("%Y-%m-%d").to_string()],
+        UsageDimension::Model => vec![model.to_string()],
+        UsageDimension::Conversation => vec![format!(
+            "{} ({})",
+            conv.display,
+            &conv.session_id[..conv.session_id.len().min(8)]
+        )],
+    }
+}
+
+/// Format a cost for compact display: `$0.42`, `$12.30`, `$1234`.
+pub fn format_cost(cost: f64) -> String {
+    if cost >= 100.0 {
+        format!("${:.0}", cost)
+    } else {
+        format!("${:.2}", cost)
+    }
+}
+
+/// Format a token count compactly: `950`, `12.3k`, `4.5M`.
+pub fn format_tokens(tokens: u64) -> String {
+    if tokens >= 1_000_000 {
+        format!("{:.1}M", tokens as f64 / 1_000_000.0)
+    } else if tokens >= 1_000 {
+        format!("{:.1}k", tokens as f64 / 1_000.0)
+    } else {
+        tokens.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::log_stats::LogStatsTracker;
+    use std::io::Write;
+
+    fn assistant_line(id: &str, model: &str, input: u64, output: u64, ts: &str) -> String {
+        format!(
+            concat!(
+                r#"{{"type":"assistant","timestamp":"{}","message":{{"id":"{}","model":"{}","#,
+                r#""usage":{{"input_tokens":{},"output_tokens":{},"#,
+                r#""cache_creation_input_tokens":100,"cache_read_input_tokens":1000}}}}}}"#,
+                "\n"
+            ),
+            ts, id, model, input, output
+        )
+    }
+
+    fn conversation(session_id: &str, path: &Path, project: &str) -> Conversation {
+        Conversation {
+            display: "Task".to_string(),
+            timestamp: 1,
+            file_path: path.to_path_buf(),
+            message_count: Some(1),
+            ..Conversation::for_test(session_id, project)
+        }
+    }
+
+    #[test]
+    fn parse_usage_line_reads_assistant_usage_and_skips_others() {
+        let line = assistant_line("m1", "claude-sonnet-4", 10, 20, "2026-09-01T12:00:00Z");
+        let record = parse_usage_line(line.trim_end()).unwrap();
+        assert_eq!(record.id.as_deref(), Some("m1"));
+        assert_eq!(record.model, "claude-sonnet-4");
+        assert_eq!(
+            record.usage,
+            TokenUsage {
+                input: 10,
+                output: 20,
+                cache_write: 100,
+                cache_read: 1000
+            }
+        );
+
+        assert!(parse_usage_line(r#"{"type":"user","message":{"content":"hi"}}"#).is_none());
+        let synthetic = assistant_line("m2", "<synthetic>", 1, 1, "2026-09-01T12:00:00Z");
+        assert!(parse_usage_line(synthetic.trim_end()).is_none());
+    }
+
+    #[test]
+    fn price_table_prefers_most_specific_entry_and_allows_overrides() {
+        let prices = PriceTable::default();
+        assert_eq!(
+            prices.price_for("claude-opus-4-5-20251101").unwrap().input,
+            5.0
+        );
+        assert_eq!(prices.price_for("claude-opus-4-6").unwrap().input, 5.0);
+        assert_eq!(
+            prices.price_for("claude-opus-4-1-20250805").unwrap().input,
+            15.0
+        );
+        assert_eq!(
+            prices.price_for("claude-opus-4-20250514").unwrap().input,
+            15.0
+        );
+        assert_eq!(
+            prices.price_for("claude-3-opus-20240229").unwrap().input,
+            15.0
+        );
+        assert_eq!(
+            prices.price_for("claude-3-5-haiku-20241022").unwrap().input,
+            0.8
+        );
+        assert_eq!(
+            prices.price_for("claude-3-haiku-20240307").unwrap().input,
+            0.25
+        );
+        assert!(prices.price_for("gpt-4").is_none());
+
+        let usage = TokenUsage {
+            input: 1_000_000,
+            output: 1_000_000,
+            ..TokenUsage::default()
+        };
+        assert!((prices.cost("claude-sonnet-4", &usage) - 18.0).abs() < 1e-9);
+
+        let custom = PriceTable::with_overrides(&[ModelPrice::new("sonnet", 1.0, 1.0, 0.0, 0.0)]);
+        assert!((custom.cost("claude-sonnet-4", &usage) - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tracker_deduplicates_messages_and_reads_appended_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.jsonl");
+        let line = assistant_line("m1", "claude-sonnet-4", 10, 20, "2026-09-01T12:00:00Z");
+        // Same message logged once per content block
+        std::fs::write(&path, line.repeat(2)).unwrap();
+
+        let mut conv = conversation("s1", &path, "/projects/app");
+        let groups = |conv: &Conversation| {
+            vec![ConversationGroup::Directory {
+                parent: "projects".to_string(),
+                project: "app".to_string(),
+                conversations: vec![conv.clone()],
+            }]
+        };
+        let mut tracker = LogStatsTracker::new();
+        tracker.refresh(&groups(&conv));
+        assert_eq!(tracker.stats().usage("s1").unwrap().total().input, 10);
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(
+            assistant_line("m2", "claude-sonnet-4", 5, 5, "2026-09-02T12:00:00Z").as_bytes(),
+        )
+        .unwrap();
+        conv.timestamp = 2;
+        tracker.refresh(&groups(&conv));
+        let stats = tracker.stats();
+        let usage = stats.usage("s1").unwrap();
+        assert_eq!(usage.total().input, 15);
+        assert_eq!(usage.by_day_model.len(), 2);
+    }
+
+    #[test]
+    fn report_rolls_up_by_project_and_model() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.jsonl");
+        let b = dir.path().join("b.jsonl");
+        std::fs::write(
+            &a,
+            assistant_line("m1", "claude-opus-4-1", 1000, 1000, "2026-09-01T12:00:00Z"),
+        )
+        .unwrap();
+        std::fs::write(
+            &b,
+            assistant_line("m2", "claude-haiku-4-5", 1000, 1000, "2026-09-01T12:00:00Z"),
+        )
+        .unwrap();
+        let groups = vec![
+            ConversationGroup::Directory {
+                parent: "work".to_string(),
+                project: "api".to_string(),
+                conversations: vec![conversation("a", &a, "/work/api")],
+            },
+            ConversationGroup::Directory {
+                parent: "work".to_string(),
+                project: "web".to_string(),
+                conversations: vec![conversation("b", &b, "/work/web")],
+            },
+        ];
+        let mut tracker = LogStatsTracker::new();
+        tracker.refresh(&groups);
+        let stats = tracker.stats();
+        let prices = PriceTable::default();
+
+        let report = UsageReport::build(&stats, &groups, &prices, &[], UsageDimension::Model);
+        assert_eq!(report.rows.len(), 2);
+        assert_eq!(report.rows[0].label, "claude-opus-4-1");
+        assert!(report.rows[0].cost > report.rows[1].cost);
+        assert_eq!(report.total.tokens.input, 2000);
+
+        let profiles = vec![ProfileEntry {
+            name: "API".to_string(),
+            workspaces: vec!["/work/api".to_string()],
+        }];
+        let report =
+            UsageReport::build(&stats, &groups, &prices, &profiles, UsageDimension::Profile);
+        assert_eq!(report.rows.len(), 1);
+        assert_eq!(report.rows[0].tokens.input, 1000);
+    }
+
+    #[test]
+    fn format_helpers_are_compact() {
+        assert_eq!(format_cost(0.4213), "$0.42");
+        assert_eq!(format_cost(1234.5), "$1234");
+        assert_eq!(format_tokens(950), "950");
+        assert_eq!(format_tokens(12_345), "12.3k");
+        assert_eq!(format_tokens(4_500_000), "4.5M");
+    }
+}
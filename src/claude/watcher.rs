@@ -1,25 +1,93 @@
 use anyhow::Result;
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::{Path, PathBuf};
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
 use std::sync::mpsc::{self, Receiver};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-/// File system watcher for sessions-index.json changes
+use super::data_dir::ClaudeDir;
+
+/// Quiet period after the last event before changes are reported.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+/// Longest a burst of events is held back, so a streaming session still
+/// updates while it writes.
+const MAX_DELAY: Duration = Duration::from_millis(1500);
+
+/// Session files changed on disk, by project directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionChanges {
+    /// Project directories (`projects/<escaped path>`) with changed files
+    pub project_dirs: HashSet<PathBuf>,
+    /// Something outside a single project changed; rescan everything
+    pub full: bool,
+}
+
+/// Groups change notifications by project directory and holds them back
+/// until they settle.
+#[derive(Debug)]
+struct Debouncer {
+    /// `projects` directories of the watched Claude data directories
+    projects_dirs: Vec<PathBuf>,
+    pending: SessionChanges,
+    /// When the pending burst started and last saw an event
+    burst: Option<(Instant, Instant)>,
+}
+
+impl Debouncer {
+    fn new(projects_dirs: Vec<PathBuf>) -> Self {
+        Self {
+            projects_dirs,
+            pending: SessionChanges::default(),
+            burst: None,
+        }
+    }
+
+    fn push(&mut self, path: &Path, now: Instant) {
+        let project_dir = self.projects_dirs.iter().find_map(|projects_dir| {
+            match path.strip_prefix(projects_dir).ok()?.components().next() {
+                Some(Component::Normal(dir)) => Some(projects_dir.join(dir)),
+                _ => None,
+            }
+        });
+        match project_dir {
+            Some(dir) => {
+                self.pending.project_dirs.insert(dir);
+            }
+            None => self.pending.full = true,
+        }
+        self.burst = Some(self.burst.map_or((now, now), |(start, _)| (start, now)));
+    }
+
+    /// The pending changes, once quiet for `DEBOUNCE` or held for `MAX_DELAY`.
+    fn take_ready(&mut self, now: Instant) -> Option<SessionChanges> {
+        let (start, last) = self.burst?;
+        if now.duration_since(last) < DEBOUNCE && now.duration_since(start) < MAX_DELAY {
+            return None;
+        }
+        self.burst = None;
+        Some(std::mem::take(&mut self.pending))
+    }
+}
+
+/// File system watcher for session file changes
 pub struct SessionsWatcher {
     _watcher: RecommendedWatcher,
     rx: Receiver<PathBuf>,
+    debouncer: Debouncer,
 }
 
 impl SessionsWatcher {
-    /// Create a new watcher for sessions-index.json files
-    pub fn new(claude_dir: &Path) -> Result<Self> {
+    /// Create a new watcher for session JSONL and sessions-index.json files
+    /// in the given Claude data directories
+    pub fn new(claude_dirs: &[ClaudeDir]) -> Result<Self> {
         let (tx, rx) = mpsc::channel();
 
         let mut watcher = RecommendedWatcher::new(
             move |res: Result<Event, notify::Error>| {
                 if let Ok(event) = res {
-                    // Only care about modifications and creates
-                    if event.kind.is_modify() || event.kind.is_create() {
+                    // Renames are reported as modifications
+                    let removed = event.kind.is_remove();
+                    if event.kind.is_modify() || event.kind.is_create() || removed {
                         for path in event.paths {
                             // Watch for sessions-index.json and .jsonl file changes
                             // This ensures new sessions appear immediately when their file is created
@@ -27,7 +95,8 @@ impl SessionsWatcher {
                                 path.file_name().is_some_and(|n| n == "sessions-index.json");
                             let is_jsonl = path.extension().is_some_and(|ext| ext == "jsonl");
 
-                            if is_index || is_jsonl {
+                            // A removed directory takes its sessions with it
+                            if is_index || is_jsonl || removed {
                                 let _ = tx.send(path);
                             }
                         }
@@ -37,20 +106,82 @@ impl SessionsWatcher {
             Config::default().with_poll_interval(Duration::from_millis(500)),
         )?;
 
-        // Watch the projects directory recursively
-        let projects_dir = claude_dir.join("projects");
-        if projects_dir.exists() {
-            watcher.watch(&projects_dir, RecursiveMode::Recursive)?;
+        // Watch the projects directories recursively
+        let projects_dirs: Vec<PathBuf> = claude_dirs.iter().map(ClaudeDir::projects_dir).collect();
+        for projects_dir in &projects_dirs {
+            if projects_dir.exists() {
+                watcher.watch(projects_dir, RecursiveMode::Recursive)?;
+            }
         }
 
         Ok(Self {
             _watcher: watcher,
             rx,
+            debouncer: Debouncer::new(projects_dirs),
         })
     }
 
-    /// Try to receive a change notification (non-blocking)
-    pub fn try_recv(&self) -> Option<PathBuf> {
-        self.rx.try_recv().ok()
+    /// Drain change notifications (non-blocking), returning the affected
+    /// project directories once a burst of changes has settled.
+    pub fn poll(&mut self) -> Option<SessionChanges> {
+        let now = Instant::now();
+        while let Ok(path) = self.rx.try_recv() {
+            self.debouncer.push(&path, now);
+        }
+        self.debouncer.take_ready(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn projects() -> PathBuf {
+        PathBuf::from("/home/me/.claude/projects")
+    }
+
+    fn work_projects() -> PathBuf {
+        PathBuf::from("/home/me/.claude-work/projects")
+    }
+
+    #[test]
+    fn changes_are_grouped_by_project_and_held_until_quiet() {
+        let mut debouncer = Debouncer::new(vec![projects(), work_projects()]);
+        let t0 = Instant::now();
+        debouncer.push(&projects().join("-tmp-a/one.jsonl"), t0);
+        debouncer.push(&projects().join("-tmp-a/sub/subagents/x.jsonl"), t0);
+        debouncer.push(&work_projects().join("-tmp-b/sessions-index.json"), t0);
+        assert_eq!(debouncer.take_ready(t0 + DEBOUNCE / 2), None);
+
+        let changes = debouncer.take_ready(t0 + DEBOUNCE).unwrap();
+        assert!(!changes.full);
+        assert_eq!(
+            changes.project_dirs,
+            HashSet::from([projects().join("-tmp-a"), work_projects().join("-tmp-b")])
+        );
+        assert_eq!(debouncer.take_ready(t0 + DEBOUNCE * 2), None);
+    }
+
+    #[test]
+    fn continuous_writes_are_flushed_after_the_max_delay() {
+        let mut debouncer = Debouncer::new(vec![projects(), work_projects()]);
+        let t0 = Instant::now();
+        let file = projects().join("-tmp-a/one.jsonl");
+        let step = DEBOUNCE / 3;
+        let mut now = t0;
+        while now.duration_since(t0) < MAX_DELAY {
+            debouncer.push(&file, now);
+            assert_eq!(debouncer.take_ready(now), None);
+            now += step;
+        }
+        assert!(debouncer.take_ready(now).is_some());
+    }
+
+    #[test]
+    fn changes_outside_a_project_request_a_full_rescan() {
+        let mut debouncer = Debouncer::new(vec![projects(), work_projects()]);
+        let t0 = Instant::now();
+        debouncer.push(&projects(), t0);
+        assert!(debouncer.take_ready(t0 + DEBOUNCE).unwrap().full);
     }
 }
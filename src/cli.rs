@@ -0,0 +1,1036 @@
+//! Non-interactive subcommands (`claudatui list`, `claudatui search ...`, ...).
+//!
+//! These run without touching the terminal, so their output can be piped or
+//! scripted. Anything that is not a known subcommand falls through to the TUI.
+
+use std::collections::HashSet;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use chrono::{Local, TimeZone};
+use serde::Serialize;
+
+use crate::claude::archive_rules::{self, ArchiveCandidate};
+use crate::claude::conversation::Conversation;
+use crate::claude::data_dir::{resolve_claude_dirs, source_of, CONFIG_DIR_ENV};
+use crate::claude::export::{self, ExportFormat, ExportOptions};
+use crate::claude::git_log::{self, Commit};
+use crate::claude::grouping::{group_conversations, retain_existing_groups, ConversationGroup};
+use crate::claude::health::Health;
+use crate::claude::log_stats::LogStatsTracker;
+use crate::claude::session_cache::SessionCache;
+use crate::claude::sessions::parse_all_sessions_cached;
+use crate::claude::tool_stats::{
+    self, format_duration, format_percent, ToolProjectRow, ToolStats, TOOL_COLUMNS,
+};
+use crate::claude::transcript::parse_transcript;
+use crate::claude::usage::{
+    format_cost, format_tokens, PriceTable, UsageDimension, UsageReport, UsageRow,
+};
+use crate::claude::{ArchiveManager, ClaudeDir, HealthTracker, MetadataManager, PlanLinker};
+use crate::config::Config;
+use crate::search::{ContentIndex, SearchEngine, SearchFilterType, SearchQuery};
+
+const USAGE: &str = "\
+Usage: claudatui [command]
+
+Without a command, starts the interactive TUI.
+
+Options:
+  --claude-dir DIR  Claude data directory to read (repeatable). Defaults to
+                    $CLAUDE_CONFIG_DIR or ~/.claude, plus `claude_dirs` from
+                    the config file
+
+Commands:
+  list       List conversations
+  search     Search conversations
+  archive    Archive a conversation, or many at once by age, project or rule
+  unarchive  Unarchive a conversation
+  resume     Resume a conversation with claude in its project directory
+  export     Export a conversation to Markdown, HTML or JSON
+  stats      Show token usage and estimated cost
+  tools      Show tool calls, failures and turn durations
+  commits    Show the git commits made during a conversation
+  commit     Find the conversation that was active when a commit was made
+
+Run `claudatui <command> --help` for a command's options.";
+
+const LIST_USAGE: &str = "\
+Usage: claudatui list [--json] [--project] [--archived | --all]
+
+Lists conversations, most recent first, one per line:
+session id, last modified, project and title separated by tabs.
+
+Options:
+  --json      Print conversations as JSON
+  --project   Only conversations for the current directory
+  --archived  Only archived conversations
+  --all       Active and archived conversations";
+
+const SEARCH_USAGE: &str = "\
+Usage: claudatui search <query> [--json] [--limit N]
+
+Searches conversations with the same query language as the TUI
+(e.g. `login branch:feat -draft is:archived`). Best matches first.
+
+Options:
+  --json       Print results as JSON
+  --limit N    Print at most N results";
+
+const ARCHIVE_USAGE: &str = "\
+Usage: claudatui archive <session-id>
+       claudatui archive [--older-than AGE] [--project DIR] [--dry-run] [--json]
+       claudatui archive --rules [--dry-run] [--json]
+       claudatui unarchive <session-id>
+
+The session id may be abbreviated to a unique prefix. Archiving in bulk
+prints the conversations archived, one per line.
+
+Options:
+  --older-than AGE  Only conversations idle longer than AGE (30d, 2w) or
+                    since before a date (YYYY-MM-DD)
+  --project DIR     Only conversations in DIR or below, e.g. a project or worktree
+  --rules           Apply the `archive_rules` from the config file
+  --dry-run         Print what would be archived without archiving it
+  --json            Print the conversations as JSON";
+
+const RESUME_USAGE: &str = "\
+Usage: claudatui resume <session-id> [--dangerous]
+
+Runs `claude --resume <session-id>` in the conversation's project directory.
+The session id may be abbreviated to a unique prefix.
+
+Options:
+  --dangerous  Pass --dangerously-skip-permissions to claude";
+
+const STATS_USAGE: &str = "\
+Usage: claudatui stats [--by <dimension>] [--json]
+
+Token usage and estimated cost from conversation logs.
+
+Options:
+  --by <dimension>  project, group, profile, day, model or conversation (default: project)
+  --json            Print the report as JSON";
+
+const TOOLS_USAGE: &str = "\
+Usage: claudatui tools [<session-id>] [--json]
+
+Tool calls, failed calls, calls refused at a permission prompt and turn
+durations from conversation logs. Without a session id, rolled up by project;
+with one, broken down by tool for that conversation (a unique prefix is enough).
+
+Options:
+  --json  Print the stats as JSON";
+
+const EXPORT_USAGE: &str = "\
+Usage: claudatui export <session-id> [--format md|html|json] [-o FILE]
+                        [--no-tool-calls] [--tool-results] [--thinking]
+
+Export a conversation. The session id may be abbreviated to a unique prefix.
+
+Options:
+  --format <format>  md, html or json (default: inferred from FILE, else md)
+  -o, --output FILE  Write to FILE instead of stdout
+  --no-tool-calls    Leave out tool calls
+  --tool-results     Include tool results
+  --thinking         Include thinking blocks";
+
+const COMMITS_USAGE: &str = "\
+Usage: claudatui commits <session-id> [--json]
+
+Lists the commits on the conversation's project checkout made between its
+start and last message (plus a few minutes), oldest first, with their diffstat.
+The session id may be abbreviated to a unique prefix.
+
+Options:
+  --json  Print commits as JSON";
+
+const COMMIT_USAGE: &str = "\
+Usage: claudatui commit [<rev>] [-C DIR] [--json]
+
+Finds the conversations that were active in any worktree of the repository
+when <rev> (default: HEAD) was committed, best match first.
+
+Options:
+  -C DIR  Look up <rev> in the repository at DIR (default: current directory)
+  --json  Print the commit and conversations as JSON";
+
+/// Run a subcommand if `args` (without the program name) names one.
+///
+/// Returns `None` when the arguments are not a subcommand and the TUI should start.
+/// `claude_dirs` are the `--claude-dir` arguments, already taken out of `args`.
+pub fn run(args: &[String], claude_dirs: &[PathBuf]) -> Option<Result<()>> {
+    let (command, rest) = args.split_first()?;
+    match command.as_str() {
+        "list" => Some(list(rest, claude_dirs)),
+        "search" => Some(search(rest, claude_dirs)),
+        "archive" => Some(archive(rest, claude_dirs)),
+        "unarchive" => Some(set_archived(rest, false, claude_dirs)),
+        "resume" => Some(resume(rest, claude_dirs)),
+        "stats" => Some(stats(rest, claude_dirs)),
+        "tools" => Some(tools(rest, claude_dirs)),
+        "export" => Some(export(rest, claude_dirs)),
+        "commits" => Some(commits(rest, claude_dirs)),
+        "commit" => Some(commit(rest, claude_dirs)),
+        "help" | "-h" | "--help" => {
+            println!("{USAGE}");
+            Some(Ok(()))
+        }
+        _ => None,
+    }
+}
+
+/// A conversation as printed by `list --json` and `search --json`.
+#[derive(Serialize)]
+struct ConversationSummary<'a> {
+    session_id: &'a str,
+    title: &'a str,
+    project: &'a Path,
+    git_branch: Option<&'a str>,
+    modified: &'a str,
+    /// `null` when Claude's session index doesn't list the conversation
+    message_count: Option<u32>,
+    archived: bool,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+    /// Session ID of the conversation that wrote the plan this one implements
+    #[serde(skip_serializing_if = "Option::is_none")]
+    plan_parent: Option<&'a str>,
+    /// Why the conversation needs attention
+    #[serde(skip_serializing_if = "Option::is_none")]
+    health: Option<Health>,
+    file: &'a Path,
+    /// Label of the Claude data directory, when several are loaded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<&'a str>,
+    /// Matching text, for search results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snippet: Option<&'a str>,
+}
+
+impl<'a> ConversationSummary<'a> {
+    fn new(conv: &'a Conversation) -> Self {
+        Self {
+            session_id: &conv.session_id,
+            title: &conv.display,
+            project: &conv.project_path,
+            git_branch: conv.git_branch.as_deref(),
+            modified: &conv.modified,
+            message_count: conv.message_count,
+            archived: conv.is_archived,
+            tags: &conv.tags,
+            plan_parent: conv.plan_parent.as_deref(),
+            health: conv.health,
+            file: &conv.file_path,
+            source: None,
+            snippet: None,
+        }
+    }
+}
+
+/// Print one conversation per line as tab-separated fields, for `cut` and `fzf`.
+fn print_conversation_line(conv: &Conversation) {
+    let modified = Local
+        .timestamp_millis_opt(conv.timestamp)
+        .single()
+        .map_or_else(String::new, |dt| dt.format("%Y-%m-%d %H:%M").to_string());
+    println!(
+        "{}\t{}\t{}\t{}",
+        conv.session_id,
+        modified,
+        conv.project_path.display(),
+        conv.display.replace(['\t', '\n'], " ")
+    );
+}
+
+/// Take the single positional session id from `args`, or fail with `usage`.
+///
+/// Returns `None` after printing `usage` for `--help`.
+fn session_id_arg(args: &[String], usage: &str) -> Result<Option<String>> {
+    match args {
+        [flag] if flag == "-h" || flag == "--help" => {
+            println!("{usage}");
+            Ok(None)
+        }
+        [id] if !id.starts_with('-') => Ok(Some(id.clone())),
+        [] => bail!("Missing session id\n\n{usage}"),
+        _ => bail!("Expected a single session id\n\n{usage}"),
+    }
+}
+
+/// Conversation data loaded the same way the TUI loads it.
+struct Workspace {
+    claude_dirs: Vec<ClaudeDir>,
+    config: Config,
+    archive_manager: ArchiveManager,
+    groups: Vec<ConversationGroup>,
+    /// Conversations left out of `groups` because their directory is gone
+    unlisted: Vec<Conversation>,
+}
+
+impl Workspace {
+    fn load(claude_dir_flags: &[PathBuf]) -> Result<Self> {
+        let config = Config::load().unwrap_or_default();
+        let claude_dirs = resolve_claude_dirs(claude_dir_flags, &config.claude_dirs)?;
+        let archive_manager = ArchiveManager::new(&claude_dirs[0].path)
+            .context("Failed to create archive manager")?;
+        let metadata = MetadataManager::new(&claude_dirs[0].path)
+            .context("Failed to load conversation metadata")?;
+        let mut cache = SessionCache::default_path()
+            .map_or_else(SessionCache::new, |path| SessionCache::load(&path));
+        let sessions = parse_all_sessions_cached(&claude_dirs, &mut cache)?;
+        let _ = cache.save();
+        let conversations = sessions
+            .into_iter()
+            .map(|session| {
+                Conversation::from_session(
+                    session,
+                    &archive_manager,
+                    &metadata,
+                    &config.plan_prefixes,
+                )
+            })
+            .collect();
+        let mut groups = group_conversations(conversations);
+        let unlisted = groups
+            .iter()
+            .filter(|g| !g.project_path_exists())
+            .flat_map(|g| g.conversations().iter().cloned())
+            .collect();
+        retain_existing_groups(&mut groups);
+        PlanLinker::new(config.plan_prefixes.clone()).link(&mut groups);
+        HealthTracker::new().refresh(&mut groups, &HashSet::new());
+
+        Ok(Self {
+            claude_dirs,
+            config,
+            archive_manager,
+            groups,
+            unlisted,
+        })
+    }
+
+    /// Every conversation the sidebar would list, most recent first. Plan
+    /// implementations are included, with `plan_parent` linking them to the
+    /// conversation that planned them.
+    fn conversations(&self) -> Vec<&Conversation> {
+        let mut conversations: Vec<&Conversation> = self
+            .groups
+            .iter()
+            .flat_map(ConversationGroup::conversations)
+            .collect();
+        conversations.sort_by_key(|c| std::cmp::Reverse(c.timestamp));
+        conversations
+    }
+
+    /// The data directory a conversation came from, when there are several.
+    fn source(&self, conv: &Conversation) -> Option<&str> {
+        if self.claude_dirs.len() < 2 {
+            return None;
+        }
+        source_of(&self.claude_dirs, &conv.file_path).map(|dir| dir.label.as_str())
+    }
+
+    /// Find the conversation whose session id starts with `prefix`, listed
+    /// or not.
+    fn find_conversation(&self, prefix: &str) -> Result<&Conversation> {
+        let mut matches = self
+            .groups
+            .iter()
+            .flat_map(ConversationGroup::conversations)
+            .chain(&self.unlisted)
+            .filter(|c| c.session_id.starts_with(prefix));
+        let Some(conv) = matches.next() else {
+            bail!("No conversation matches '{prefix}'");
+        };
+        if matches.any(|other| other.session_id != conv.session_id) {
+            bail!("'{prefix}' matches more than one conversation");
+        }
+        Ok(conv)
+    }
+}
+
+fn list(args: &[String], claude_dirs: &[PathBuf]) -> Result<()> {
+    let mut json = false;
+    let mut current_project = false;
+    let mut archived = Some(false);
+
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--project" => current_project = true,
+            "--archived" => archived = Some(true),
+            "--all" => archived = None,
+            "-h" | "--help" => {
+                println!("{LIST_USAGE}");
+                return Ok(());
+            }
+            other => bail!("Unknown argument '{other}'\n\n{LIST_USAGE}"),
+        }
+    }
+
+    let project = if current_project {
+        Some(std::env::current_dir().context("Failed to get current directory")?)
+    } else {
+        None
+    };
+
+    let workspace = Workspace::load(claude_dirs)?;
+    let conversations: Vec<&Conversation> = workspace
+        .conversations()
+        .into_iter()
+        .filter(|c| archived.is_none_or(|archived| c.is_archived == archived))
+        .filter(|c| project.as_ref().is_none_or(|p| c.project_path == *p))
+        .collect();
+
+    if json {
+        let summaries: Vec<ConversationSummary> = conversations
+            .iter()
+            .map(|c| ConversationSummary {
+                source: workspace.source(c),
+                ..ConversationSummary::new(c)
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&summaries)?);
+    } else {
+        for conv in conversations {
+            print_conversation_line(conv);
+        }
+    }
+    Ok(())
+}
+
+fn search(args: &[String], claude_dirs: &[PathBuf]) -> Result<()> {
+    let mut json = false;
+    let mut limit = None;
+    let mut words = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--limit" => {
+                let Some(value) = args.next() else {
+                    bail!("--limit needs a value\n\n{SEARCH_USAGE}");
+                };
+                limit = Some(
+                    value
+                        .parse::<usize>()
+                        .with_context(|| format!("Invalid limit '{value}'\n\n{SEARCH_USAGE}"))?,
+                );
+            }
+            "-h" | "--help" => {
+                println!("{SEARCH_USAGE}");
+                return Ok(());
+            }
+            other => words.push(other),
+        }
+    }
+
+    let query_text = words.join(" ");
+    if query_text.trim().is_empty() {
+        bail!("Missing query\n\n{SEARCH_USAGE}");
+    }
+
+    let workspace = Workspace::load(claude_dirs)?;
+    let content_index = ContentIndex::default_path()
+        .map_or_else(ContentIndex::new, |path| ContentIndex::load(&path));
+    let mut engine =
+        SearchEngine::new(workspace.claude_dirs.clone()).with_content_index(content_index);
+    engine.refresh_index()?;
+
+    let mut query = SearchQuery::new(query_text, SearchFilterType::All);
+    engine.resolve_query(&mut query, &HashSet::new());
+    let mut results = engine.search(&query, &workspace.groups);
+    if let Some(limit) = limit {
+        results.truncate(limit);
+    }
+
+    if json {
+        let summaries: Vec<ConversationSummary> = results
+            .iter()
+            .map(|result| ConversationSummary {
+                snippet: Some(&result.preview_snippet),
+                source: workspace.source(&result.conversation),
+                ..ConversationSummary::new(&result.conversation)
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&summaries)?);
+    } else {
+        for result in &results {
+            print_conversation_line(&result.conversation);
+        }
+    }
+    Ok(())
+}
+
+fn archive(args: &[String], claude_dirs: &[PathBuf]) -> Result<()> {
+    let bulk = args
+        .iter()
+        .any(|a| matches!(a.as_str(), "--older-than" | "--project" | "--rules"));
+    if !bulk {
+        return set_archived(args, true, claude_dirs);
+    }
+
+    let mut cutoff = None;
+    let mut project = None;
+    let mut rules = false;
+    let mut dry_run = false;
+    let mut json = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--older-than" => {
+                let Some(value) = args.next() else {
+                    bail!("--older-than needs a value\n\n{ARCHIVE_USAGE}");
+                };
+                let Some(ms) = archive_rules::parse_cutoff(value, chrono::Utc::now()) else {
+                    bail!("Invalid age '{value}'\n\n{ARCHIVE_USAGE}");
+                };
+                cutoff = Some(ms);
+            }
+            "--project" => {
+                let Some(value) = args.next() else {
+                    bail!("--project needs a directory\n\n{ARCHIVE_USAGE}");
+                };
+                project = Some(absolute_dir(value)?);
+            }
+            "--rules" => rules = true,
+            "--dry-run" => dry_run = true,
+            "--json" => json = true,
+            "-h" | "--help" => {
+                println!("{ARCHIVE_USAGE}");
+                return Ok(());
+            }
+            other => bail!("Unknown argument '{other}'\n\n{ARCHIVE_USAGE}"),
+        }
+    }
+    if rules && (cutoff.is_some() || project.is_some()) {
+        bail!("--rules can't be combined with --older-than or --project\n\n{ARCHIVE_USAGE}");
+    }
+
+    let mut workspace = Workspace::load(claude_dirs)?;
+    // Conversations whose directory is gone aren't listed, but can still be archived
+    let conversations = workspace
+        .conversations()
+        .into_iter()
+        .chain(&workspace.unlisted);
+    let running = HashSet::new();
+    let candidates = if rules {
+        if workspace.config.archive_rules.is_empty() {
+            bail!("No archive_rules in the config file");
+        }
+        archive_rules::rule_candidates(
+            &workspace.config.archive_rules,
+            conversations,
+            &running,
+            chrono::Utc::now().timestamp_millis(),
+        )
+    } else {
+        archive_rules::bulk_candidates(
+            conversations
+                .filter(|c| cutoff.is_none_or(|cutoff| c.timestamp < cutoff))
+                .filter(|c| {
+                    project
+                        .as_ref()
+                        .is_none_or(|p| c.project_path.starts_with(p))
+                }),
+            &running,
+        )
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&candidates)?);
+    } else {
+        for candidate in &candidates {
+            print_candidate_line(candidate);
+        }
+    }
+    let count = candidates.len();
+    let noun = if count == 1 {
+        "conversation"
+    } else {
+        "conversations"
+    };
+    if dry_run {
+        eprintln!("{count} {noun} would be archived");
+        return Ok(());
+    }
+    for candidate in &candidates {
+        workspace
+            .archive_manager
+            .archive(&candidate.session_id, rules);
+    }
+    workspace
+        .archive_manager
+        .save()
+        .context("Failed to save archive state")?;
+    eprintln!("Archived {count} {noun}");
+    Ok(())
+}
+
+/// `dir` as an absolute path, resolved against the current directory.
+fn absolute_dir(dir: &str) -> Result<PathBuf> {
+    let path = Path::new(dir);
+    if path.is_absolute() {
+        return Ok(path.to_path_buf());
+    }
+    let cwd = std::env::current_dir().context("Failed to get current directory")?;
+    Ok(cwd.join(path))
+}
+
+/// Print an archive candidate like `print_conversation_line`, followed by
+/// the rule that matched it, if any.
+fn print_candidate_line(candidate: &ArchiveCandidate) {
+    let modified = Local
+        .timestamp_millis_opt(candidate.timestamp)
+        .single()
+        .map_or_else(String::new, |dt| dt.format("%Y-%m-%d %H:%M").to_string());
+    let mut line = format!(
+        "{}\t{}\t{}\t{}",
+        candidate.session_id,
+        modified,
+        candidate.project_path.display(),
+        candidate.title.replace(['\t', '\n'], " ")
+    );
+    if let Some(ref reason) = candidate.reason {
+        line.push('\t');
+        line.push_str(reason);
+    }
+    println!("{line}");
+}
+
+fn set_archived(args: &[String], archived: bool, claude_dirs: &[PathBuf]) -> Result<()> {
+    let Some(session_id) = session_id_arg(args, ARCHIVE_USAGE)? else {
+        return Ok(());
+    };
+    let mut workspace = Workspace::load(claude_dirs)?;
+    let conv = workspace.find_conversation(&session_id)?;
+    let (session_id, is_archived) = (conv.session_id.clone(), conv.is_archived);
+
+    if is_archived == archived {
+        let state = if archived { "archived" } else { "not archived" };
+        eprintln!("{session_id} is already {state}");
+        return Ok(());
+    }
+
+    if archived {
+        workspace.archive_manager.archive(&session_id, false);
+    } else {
+        workspace.archive_manager.unarchive(&session_id);
+    }
+    workspace
+        .archive_manager
+        .save()
+        .context("Failed to save archive state")?;
+    println!(
+        "{} {session_id}",
+        if archived { "Archived" } else { "Unarchived" }
+    );
+    Ok(())
+}
+
+fn resume(args: &[String], claude_dirs: &[PathBuf]) -> Result<()> {
+    let dangerous = args.iter().any(|a| a == "--dangerous");
+    let args: Vec<String> = args
+        .iter()
+        .filter(|a| *a != "--dangerous")
+        .cloned()
+        .collect();
+    let Some(session_id) = session_id_arg(&args, RESUME_USAGE)? else {
+        return Ok(());
+    };
+
+    let workspace = Workspace::load(claude_dirs)?;
+    let conv = workspace.find_conversation(&session_id)?;
+    if !conv.project_path.is_dir() {
+        bail!(
+            "Project directory {} no longer exists",
+            conv.project_path.display()
+        );
+    }
+
+    let mut cmd = Command::new("claude");
+    cmd.current_dir(&conv.project_path);
+    if dangerous {
+        cmd.arg("--dangerously-skip-permissions");
+    }
+    cmd.arg("--resume").arg(&conv.session_id);
+    let config_dir =
+        source_of(&workspace.claude_dirs, &conv.file_path).and_then(ClaudeDir::config_dir_env);
+    match config_dir {
+        Some(dir) => cmd.env(CONFIG_DIR_ENV, dir),
+        None => cmd.env_remove(CONFIG_DIR_ENV),
+    };
+
+    // exec only returns on failure
+    let err = cmd.exec();
+    Err(err).context("Failed to run claude")
+}
+
+fn stats(args: &[String], claude_dirs: &[PathBuf]) -> Result<()> {
+    let mut dimension = UsageDimension::Project;
+    let mut json = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--by" => {
+                let Some(value) = args.next() else {
+                    bail!("--by needs a value\n\n{STATS_USAGE}");
+                };
+                dimension = UsageDimension::from_name(value)
+                    .with_context(|| format!("Unknown dimension '{value}'\n\n{STATS_USAGE}"))?;
+            }
+            "-h" | "--help" => {
+                println!("{STATS_USAGE}");
+                return Ok(());
+            }
+            other => bail!("Unknown argument '{other}'\n\n{STATS_USAGE}"),
+        }
+    }
+
+    let workspace = Workspace::load(claude_dirs)?;
+    let prices = PriceTable::with_overrides(&workspace.config.prices);
+    let mut tracker = LogStatsTracker::new();
+    tracker.refresh(&workspace.groups);
+    let report = UsageReport::build(
+        &tracker.stats(),
+        &workspace.groups,
+        &prices,
+        &workspace.config.profiles,
+        dimension,
+    );
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(dimension, &report);
+    }
+    Ok(())
+}
+
+fn tools(args: &[String], claude_dirs: &[PathBuf]) -> Result<()> {
+    let json = args.iter().any(|a| a == "--json");
+    let args: Vec<String> = args.iter().filter(|a| *a != "--json").cloned().collect();
+    let session_id = match args.as_slice() {
+        [] => None,
+        args => match session_id_arg(args, TOOLS_USAGE)? {
+            Some(id) => Some(id),
+            None => return Ok(()),
+        },
+    };
+
+    let workspace = Workspace::load(claude_dirs)?;
+    if let Some(session_id) = session_id {
+        let conv = workspace.find_conversation(&session_id)?;
+        let stats = tool_stats::parse_session_tools(&conv.file_path)?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&stats)?);
+        } else {
+            print_tool_breakdown(&stats);
+        }
+        return Ok(());
+    }
+
+    let mut tracker = LogStatsTracker::new();
+    tracker.refresh(&workspace.groups);
+    let rows = tool_stats::project_rows(&tracker.stats(), &workspace.groups);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+    } else {
+        print_tool_projects(&rows);
+    }
+    Ok(())
+}
+
+fn export(args: &[String], claude_dirs: &[PathBuf]) -> Result<()> {
+    let mut session_id = None;
+    let mut format = None;
+    let mut output: Option<PathBuf> = None;
+    let mut options = ExportOptions::default();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let Some(value) = args.next() else {
+                    bail!("--format needs a value\n\n{EXPORT_USAGE}");
+                };
+                format = Some(
+                    ExportFormat::from_name(value)
+                        .with_context(|| format!("Unknown format '{value}'\n\n{EXPORT_USAGE}"))?,
+                );
+            }
+            "-o" | "--output" => {
+                let Some(value) = args.next() else {
+                    bail!("{arg} needs a value\n\n{EXPORT_USAGE}");
+                };
+                output = Some(PathBuf::from(value));
+            }
+            "--no-tool-calls" => options.tool_calls = false,
+            "--tool-results" => options.tool_results = true,
+            "--thinking" => options.thinking = true,
+            "-h" | "--help" => {
+                println!("{EXPORT_USAGE}");
+                return Ok(());
+            }
+            other if other.starts_with('-') => {
+                bail!("Unknown argument '{other}'\n\n{EXPORT_USAGE}")
+            }
+            other if session_id.is_none() => session_id = Some(other.to_string()),
+            other => bail!("Unexpected argument '{other}'\n\n{EXPORT_USAGE}"),
+        }
+    }
+
+    let Some(session_id) = session_id else {
+        bail!("Missing session id\n\n{EXPORT_USAGE}");
+    };
+    let format = format
+        .or_else(|| output.as_deref().and_then(ExportFormat::from_path))
+        .unwrap_or_default();
+
+    let workspace = Workspace::load(claude_dirs)?;
+    let conv = workspace.find_conversation(&session_id)?;
+    let transcript = parse_transcript(&conv.file_path)?;
+    let content = export::export(conv, &transcript, format, &options)?;
+
+    match output {
+        Some(path) => std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?,
+        None => print!("{content}"),
+    }
+    Ok(())
+}
+
+fn commits(args: &[String], claude_dirs: &[PathBuf]) -> Result<()> {
+    let json = args.iter().any(|a| a == "--json");
+    let args: Vec<String> = args.iter().filter(|a| *a != "--json").cloned().collect();
+    let Some(session_id) = session_id_arg(&args, COMMITS_USAGE)? else {
+        return Ok(());
+    };
+
+    let workspace = Workspace::load(claude_dirs)?;
+    let conv = workspace.find_conversation(&session_id)?;
+    let commits = git_log::session_commits(conv)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&commits)?);
+    } else {
+        for commit in &commits {
+            print_commit_line(commit);
+        }
+    }
+    Ok(())
+}
+
+/// The answer to `commit --json`.
+#[derive(Serialize)]
+struct CommitConversations<'a> {
+    commit: &'a Commit,
+    conversations: Vec<ConversationSummary<'a>>,
+}
+
+fn commit(args: &[String], claude_dirs: &[PathBuf]) -> Result<()> {
+    let mut json = false;
+    let mut dir = None;
+    let mut rev = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-C" => {
+                let Some(value) = args.next() else {
+                    bail!("-C needs a value\n\n{COMMIT_USAGE}");
+                };
+                dir = Some(PathBuf::from(value));
+            }
+            "-h" | "--help" => {
+                println!("{COMMIT_USAGE}");
+                return Ok(());
+            }
+            other if other.starts_with('-') || rev.is_some() => {
+                bail!("Unexpected argument '{other}'\n\n{COMMIT_USAGE}");
+            }
+            other => rev = Some(other.to_string()),
+        }
+    }
+
+    let dir = match dir {
+        Some(dir) => dir,
+        None => std::env::current_dir().context("Failed to get current directory")?,
+    };
+    let commit = git_log::find_commit(&dir, rev.as_deref().unwrap_or("HEAD"))?;
+    let worktrees = git_log::worktree_paths(&dir)?;
+
+    let workspace = Workspace::load(claude_dirs)?;
+    let conversations = git_log::conversations_active_at(
+        workspace
+            .groups
+            .iter()
+            .flat_map(ConversationGroup::conversations),
+        &worktrees,
+        commit.time,
+    );
+
+    if json {
+        let summaries = conversations
+            .iter()
+            .map(|c| ConversationSummary {
+                source: workspace.source(c),
+                ..ConversationSummary::new(c)
+            })
+            .collect();
+        let answer = CommitConversations {
+            commit: &commit,
+            conversations: summaries,
+        };
+        println!("{}", serde_json::to_string_pretty(&answer)?);
+    } else if conversations.is_empty() {
+        eprintln!(
+            "No conversation was active when {} was committed",
+            commit.short_hash()
+        );
+    } else {
+        for conv in conversations {
+            print_conversation_line(conv);
+        }
+    }
+    Ok(())
+}
+
+/// Print a commit as tab-separated fields: hash, time, diffstat and subject.
+fn print_commit_line(commit: &Commit) {
+    println!(
+        "{}\t{}\t{}\t{}",
+        commit.short_hash(),
+        commit.time.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+        commit.stat.summary(),
+        commit.subject
+    );
+}
+
+fn print_report(dimension: UsageDimension, report: &UsageReport) {
+    let label_width = report
+        .rows
+        .iter()
+        .map(|row| row.label.chars().count())
+        .chain([dimension.name().len(), report.total.label.len()])
+        .max()
+        .unwrap_or(0);
+
+    println!(
+        "{:<label_width$}  {:>9}{:>9}{:>9}{:>9}{:>10}",
+        dimension.name(),
+        "input",
+        "output",
+        "cache w",
+        "cache r",
+        "cost",
+    );
+    for row in &report.rows {
+        print_row(row, label_width);
+    }
+    print_row(&report.total, label_width);
+}
+
+/// Summary line of `tools`: turns, turn durations, failures and denials.
+fn print_tool_summary(stats: &ToolStats) {
+    let duration = |ms: Option<i64>| ms.map_or_else(|| "-".to_string(), format_duration);
+    println!(
+        "{} turns, avg {}, longest {}; {} calls, {} failed ({}), {} denied ({})",
+        stats.turns(),
+        duration(stats.average_turn_ms()),
+        duration(stats.longest_turn_ms()),
+        stats.calls(),
+        stats.errors(),
+        format_percent(stats.error_rate()),
+        stats.denied(),
+        format_percent(stats.denial_rate()),
+    );
+    if let Some(streak) = stats.failure_streak.as_ref().filter(|s| s.count > 1) {
+        println!(
+            "longest failure streak: {} consecutive {} calls",
+            streak.count, streak.tool
+        );
+    }
+}
+
+fn print_tool_breakdown(stats: &ToolStats) {
+    print_tool_summary(stats);
+    let mut tools: Vec<_> = stats.tools.iter().collect();
+    tools.sort_by_key(|(_, count)| std::cmp::Reverse(count.calls));
+    let label_width = tools
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .chain(["tool".len()])
+        .max()
+        .unwrap_or(0);
+
+    println!();
+    println!(
+        "{:<label_width$}  {:>8}{:>8}{:>8}",
+        "tool", "calls", "errors", "denied"
+    );
+    for (name, count) in tools {
+        println!(
+            "{:<label_width$}  {:>8}{:>8}{:>8}",
+            name, count.calls, count.errors, count.denied
+        );
+    }
+}
+
+fn print_tool_projects(rows: &[ToolProjectRow]) {
+    let label_width = rows
+        .iter()
+        .map(|row| row.label.chars().count())
+        .chain(["project".len()])
+        .max()
+        .unwrap_or(0);
+
+    let mut header = format!(
+        "{:<label_width$}  {:>7}{:>7}{:>7}",
+        "project", "turns", "avg", "calls"
+    );
+    for name in TOOL_COLUMNS.iter().chain(["MCP"].iter()) {
+        header.push_str(&format!("{:>7}", name));
+    }
+    println!("{header}{:>7}{:>7}", "err%", "deny%");
+
+    for row in rows {
+        let stats = &row.stats;
+        let mut line = format!(
+            "{:<label_width$}  {:>7}{:>7}{:>7}",
+            row.label,
+            stats.turns(),
+            stats
+                .average_turn_ms()
+                .map_or_else(|| "-".to_string(), format_duration),
+            stats.calls(),
+        );
+        for name in TOOL_COLUMNS.iter().chain(["MCP"].iter()) {
+            line.push_str(&format!("{:>7}", stats.calls_of(name)));
+        }
+        println!(
+            "{line}{:>7}{:>7}",
+            format_percent(stats.error_rate()),
+            format_percent(stats.denial_rate())
+        );
+    }
+}
+
+fn print_row(row: &UsageRow, label_width: usize) {
+    println!(
+        "{:<label_width$}  {:>9}{:>9}{:>9}{:>9}{:>10}",
+        row.label,
+        format_tokens(row.tokens.input),
+        format_tokens(row.tokens.output),
+        format_tokens(row.tokens.cache_write),
+        format_tokens(row.tokens.cache_read),
+        format_cost(row.cost),
+    );
+}
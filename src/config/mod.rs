@@ -8,6 +8,11 @@ use std::path::PathBuf;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::claude::archive_rules::ArchiveRule;
+use crate::claude::plans::DEFAULT_PLAN_PREFIX;
+use crate::claude::usage::ModelPrice;
+use crate::layout::LayoutNode;
+
 /// A named profile containing a set of workspace directories.
 ///
 /// Profiles let you scope the sidebar to a subset of your projects.
@@ -21,6 +26,64 @@ pub struct ProfileEntry {
     pub workspaces: Vec<String>,
 }
 
+/// An extra Claude data directory (a `CLAUDE_CONFIG_DIR`) whose
+/// conversations are merged into the sidebar.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaudeDirEntry {
+    /// Directory path; `~` expands to the home directory
+    pub path: String,
+    /// Tag shown on its conversations (defaults to the directory name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// A saved tiling layout that can be restored by name.
+///
+/// Only the split tree is stored; sessions are assigned to panes when the
+/// layout is applied.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamedLayout {
+    /// Human-readable layout name (e.g., "agents + tests")
+    pub name: String,
+    /// The split tree
+    pub root: LayoutNode,
+}
+
+/// Spending budgets in USD, priced with the price table. Crossing the
+/// warning threshold or the budget itself raises an alert.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetConfig {
+    /// Budget for one calendar day (local time)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily: Option<f64>,
+    /// Budget for one 5-hour usage block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<f64>,
+    /// Percentage of a budget at which to warn
+    #[serde(default = "default_warn_percent")]
+    pub warn_percent: u8,
+}
+
+fn default_warn_percent() -> u8 {
+    80
+}
+
+impl Default for BudgetConfig {
+    fn default() -> Self {
+        Self {
+            daily: None,
+            window: None,
+            warn_percent: default_warn_percent(),
+        }
+    }
+}
+
+impl BudgetConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Main configuration struct
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -44,12 +107,51 @@ pub struct Config {
     /// The active profile is selected at runtime; defaults to "All" (no filtering).
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub profiles: Vec<ProfileEntry>,
+
+    /// Named tiling layouts for the terminal area.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layouts: Vec<NamedLayout>,
+
+    /// Token prices (USD per million tokens) overriding or extending the
+    /// built-in table. Models match by substring; the longest match wins.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prices: Vec<ModelPrice>,
+
+    /// Daily and 5-hour window budgets.
+    #[serde(default, skip_serializing_if = "BudgetConfig::is_default")]
+    pub budget: BudgetConfig,
+
+    /// Claude data directories to show alongside `$CLAUDE_CONFIG_DIR` (or
+    /// `~/.claude`), e.g. one per account. Ignored when `--claude-dir` is given.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub claude_dirs: Vec<ClaudeDirEntry>,
+
+    /// First-prompt prefixes that mark a conversation as implementing a plan
+    /// written in another conversation.
+    #[serde(
+        default = "default_plan_prefixes",
+        skip_serializing_if = "is_default_plan_prefixes"
+    )]
+    pub plan_prefixes: Vec<String>,
+
+    /// Rules for archiving conversations in bulk, previewed before they run
+    /// (e.g. idle for some days in a project, branch merged, worktree removed).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub archive_rules: Vec<ArchiveRule>,
 }
 
 fn default_dangerous_mode() -> bool {
     true
 }
 
+fn default_plan_prefixes() -> Vec<String> {
+    vec![DEFAULT_PLAN_PREFIX.to_string()]
+}
+
+fn is_default_plan_prefixes(prefixes: &[String]) -> bool {
+    prefixes == default_plan_prefixes().as_slice()
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -57,6 +159,12 @@ impl Default for Config {
             dangerous_mode: true,
             workspaces: Vec::new(),
             profiles: Vec::new(),
+            layouts: Vec::new(),
+            prices: Vec::new(),
+            budget: BudgetConfig::default(),
+            claude_dirs: Vec::new(),
+            plan_prefixes: default_plan_prefixes(),
+            archive_rules: Vec::new(),
         }
     }
 }
@@ -108,6 +216,13 @@ impl Config {
         !self.profiles.is_empty()
     }
 
+    /// Find a saved layout by name (case-insensitive)
+    pub fn find_layout(&self, name: &str) -> Option<&NamedLayout> {
+        self.layouts
+            .iter()
+            .find(|l| l.name.eq_ignore_ascii_case(name))
+    }
+
     /// Check if a project path falls under any configured workspace directory (prefix match)
     pub fn is_in_workspace(&self, project_path: &str) -> bool {
         self.workspaces
@@ -137,6 +252,11 @@ pub struct LayoutConfig {
     /// Whether sidebar is minimized
     #[serde(default)]
     pub sidebar_minimized: bool,
+
+    /// Current tiling layout of the terminal area, including split ratios.
+    /// `None` means a single pane.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panes: Option<LayoutNode>,
 }
 
 fn default_sidebar_width() -> u8 {
@@ -149,6 +269,7 @@ impl Default for LayoutConfig {
             sidebar_width_pct: 25,
             sidebar_position: SidebarPosition::Left,
             sidebar_minimized: false,
+            panes: None,
         }
     }
 }
@@ -333,4 +454,96 @@ mod tests {
         assert!(!config.has_profiles());
         assert_eq!(config.workspaces, vec!["/old/path"]);
     }
+
+    #[test]
+    fn layouts_empty_by_default_and_skipped_in_serialization() {
+        let config = Config::default();
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("layouts"));
+    }
+
+    #[test]
+    fn layouts_roundtrip_through_json() {
+        use crate::ui::tiling::{SplitDirection, TilingLayout};
+
+        let mut tiling = TilingLayout::new();
+        tiling.split_focused(SplitDirection::Horizontal);
+        let config = Config {
+            layouts: vec![NamedLayout {
+                name: "Agents".to_string(),
+                root: tiling.root().clone(),
+            }],
+            ..Config::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        let parsed: Config = serde_json::from_str(&json).unwrap();
+        let found = parsed.find_layout("agents").unwrap();
+        assert_eq!(&found.root, tiling.root());
+    }
+
+    #[test]
+    fn pane_layout_persists_split_ratios() {
+        use crate::ui::tiling::{Direction, SplitDirection, TilingLayout};
+
+        let mut tiling = TilingLayout::new();
+        tiling.split_focused(SplitDirection::Horizontal);
+        tiling.resize_focused(Direction::Right, 20);
+        let config = Config {
+            layout: LayoutConfig {
+                panes: Some(tiling.root().clone()),
+                ..Default::default()
+            },
+            ..Config::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        let parsed: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.layout.panes.as_ref(), Some(tiling.root()));
+        assert!(json.contains("\"ratio\":70"));
+    }
+
+    #[test]
+    fn budget_is_skipped_until_set_and_defaults_warn_percent() {
+        let json = serde_json::to_string(&Config::default()).unwrap();
+        assert!(!json.contains("budget"));
+
+        let parsed: Config = serde_json::from_str(r#"{"budget":{"daily":20.0}}"#).unwrap();
+        assert_eq!(parsed.budget.daily, Some(20.0));
+        assert_eq!(parsed.budget.window, None);
+        assert_eq!(parsed.budget.warn_percent, 80);
+    }
+
+    #[test]
+    fn plan_prefixes_default_to_claude_code_prompt_and_are_skipped_until_changed() {
+        let json = serde_json::to_string(&Config::default()).unwrap();
+        assert!(!json.contains("plan_prefixes"));
+        let parsed: Config = serde_json::from_str("{}").unwrap();
+        assert_eq!(parsed.plan_prefixes, vec![DEFAULT_PLAN_PREFIX]);
+
+        let parsed: Config =
+            serde_json::from_str(r#"{"plan_prefixes":["Execute this plan:"]}"#).unwrap();
+        assert_eq!(parsed.plan_prefixes, vec!["Execute this plan:"]);
+        assert!(serde_json::to_string(&parsed)
+            .unwrap()
+            .contains("plan_prefixes"));
+    }
+
+    #[test]
+    fn archive_rules_parse_with_unset_conditions_off() {
+        let json = serde_json::to_string(&Config::default()).unwrap();
+        assert!(!json.contains("archive_rules"));
+
+        let parsed: Config = serde_json::from_str(
+            r#"{"archive_rules":[{"project":"~/scratch","older_than_days":7},{"branch_gone":true}]}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.archive_rules.len(), 2);
+        assert_eq!(
+            parsed.archive_rules[0].project.as_deref(),
+            Some("~/scratch")
+        );
+        assert_eq!(parsed.archive_rules[0].older_than_days, Some(7));
+        assert!(!parsed.archive_rules[0].branch_gone);
+        assert!(parsed.archive_rules[1].branch_gone);
+        assert!(!parsed.archive_rules[1].worktree_gone);
+    }
 }
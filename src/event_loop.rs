@@ -14,18 +14,23 @@ use ratatui::{
 };
 
 use crate::app::{App, ChordState, Focus, SplitMode};
+use crate::claude::budget::{format_remaining, BudgetLevel};
+use crate::claude::usage::format_cost;
 use crate::handlers::keyboard::{flush_buffered_key, handle_key_event};
 use crate::handlers::mouse::handle_mouse_event;
 use crate::input::InputMode;
-use crate::ui::layout::create_layout_with_help_config;
+use crate::ui::detail_panel::DetailPanel;
+use crate::ui::layout::{create_layout_with_help_config, split_detail_panel};
 use crate::ui::modal::{
-    NewProjectModal, ProfileModal, SearchModal, WorkspaceModal, WorktreeModal,
-    WorktreeSearchModal,
+    ArchiveModal, BranchesModal, CommitsModal, ExportModal, FilesModal, LayoutModal, MetadataModal,
+    NewProjectModal, ProfileModal, SearchModal, StatsModal, ToolsModal, TrashModal, WorkspaceModal,
+    WorktreeModal, WorktreeSearchModal,
 };
 use crate::ui::mosaic::MosaicView;
 use crate::ui::sidebar::{Sidebar, SidebarContext};
 use crate::ui::terminal_pane::TerminalPane;
 use crate::ui::toast_widget::{ToastPosition, ToastWidget};
+use crate::ui::transcript::TranscriptView;
 use crate::ui::{HelpMenuWidget, WhichKeyWidget};
 
 /// Action to take after run_app completes
@@ -71,6 +76,7 @@ pub fn run_app(
 
         // Update session state cache for rendering
         app.update_session_state();
+        app.update_pane_state_caches();
 
         // Update mosaic state cache (no-ops when not in mosaic mode)
         app.update_mosaic_state_cache();
@@ -81,12 +87,25 @@ pub fn run_app(
         // Check for sessions-index.json changes and reload if needed
         app.check_sessions_updates();
 
+        // Flag conversations whose last turn has settled in a bad state
+        app.check_health_due();
+
+        // Pick up content index changes and log stats read in the background
+        app.check_index_refresh();
+        app.check_log_stats_refresh();
+
+        // Follow the selection in the detail panel (no-op when hidden)
+        app.update_detail_panel();
+
         // Update toast manager (remove expired)
         app.toast_manager.update();
 
         // Draw UI
         terminal.draw(|f| draw_ui(f, app, &hot_reload_status))?;
 
+        // Match PTY sizes to the pane rects computed during render
+        app.fit_sessions_to_panes();
+
         // Handle events with timeout for PTY updates
         if poll(Duration::from_millis(50))? {
             let event = read()?;
@@ -138,6 +157,8 @@ fn draw_ui(f: &mut Frame, app: &mut App, hot_reload_status: &HotReloadStatus) {
 
     // Clone filter state to avoid overlapping borrows with render_stateful_widget
     let filter_query = app.sidebar_state.filter_query.clone();
+    let filter = app.sidebar_filter(&running_sessions);
+    let archive_filter = app.effective_archive_filter(&filter);
 
     // Build sidebar context with shared parameters
     let effective_workspaces = app.effective_workspaces();
@@ -147,45 +168,41 @@ fn draw_ui(f: &mut Frame, app: &mut App, hot_reload_status: &HotReloadStatus) {
         running_sessions: &running_sessions,
         ephemeral_sessions: &app.ephemeral_sessions,
         hide_inactive: app.sidebar_state.hide_inactive,
-        archive_filter: app.sidebar_state.archive_filter,
+        archive_filter,
         filter_query: &filter_query,
+        filter: &filter,
         filter_active: app.sidebar_state.filter_active,
         filter_cursor_pos: app.sidebar_state.filter_cursor_pos,
         workspaces: &effective_workspaces,
         active_profile_name: profile_name.as_deref(),
+        session_costs: &app.session_costs,
+        subagents: &app.subagents,
+        claude_dirs: &app.claude_dirs,
     };
 
     // Draw sidebar with running session indicators and ephemeral sessions
     let sidebar = Sidebar::new(&sidebar_ctx, app.focus == Focus::Sidebar);
     f.render_stateful_widget(sidebar, sidebar_area, &mut app.sidebar_state);
 
-    if app.split_mode == SplitMode::Mosaic {
-        // Render mosaic grid view
-        let mosaic = MosaicView::new(&app.mosaic_state_cache, app.mosaic_selected);
-        f.render_widget(mosaic, terminal_area);
-        // Clear terminal inner area cache (not used in mosaic mode)
-        app.terminal_inner_area = None;
+    let terminal_area = if !app.detail_panel_open {
+        Some(terminal_area)
+    } else if app.detail_panel_fills_terminal_area() {
+        f.render_widget(DetailPanel::new(app.detail_panel.as_ref()), terminal_area);
+        None
     } else {
-        // Cache terminal inner area for mouse coordinate mapping (area minus 1px border)
-        let terminal_inner = Rect {
-            x: terminal_area.x + 1,
-            y: terminal_area.y + 1,
-            width: terminal_area.width.saturating_sub(2),
-            height: terminal_area.height.saturating_sub(2),
-        };
-        app.terminal_inner_area = Some(terminal_inner);
-
-        // Draw terminal pane with session state from daemon
-        let session_state = app.get_session_state();
-        let is_preview = app.preview_session_id.is_some() && app.focus == Focus::Sidebar;
-        let selection = app.text_selection.as_ref();
-        let terminal_pane = TerminalPane::new(
-            session_state,
-            matches!(app.focus, Focus::Terminal(_)),
-            is_preview,
-            selection,
-        );
-        f.render_widget(terminal_pane, terminal_area);
+        let (detail_area, terminal_area) =
+            split_detail_panel(terminal_area, app.config.layout.sidebar_position);
+        f.render_widget(DetailPanel::new(app.detail_panel.as_ref()), detail_area);
+        Some(terminal_area)
+    };
+
+    if let Some(terminal_area) = terminal_area {
+        draw_session_area(f, app, terminal_area);
+    } else {
+        // Nothing to map mouse input or size PTYs against
+        app.terminal_inner_area = None;
+        app.tiling_area = None;
+        app.pane_areas.clear();
     }
 
     // Draw help bar or hot reload status
@@ -241,6 +258,99 @@ fn draw_ui(f: &mut Frame, app: &mut App, hot_reload_status: &HotReloadStatus) {
     draw_modal(f, app);
 }
 
+/// Draw the mosaic grid or the tiled terminal panes into `terminal_area`.
+fn draw_session_area(f: &mut Frame, app: &mut App, terminal_area: Rect) {
+    if app.split_mode == SplitMode::Mosaic {
+        // Render mosaic grid view (only the selected tile when zoomed)
+        let selected = app.mosaic_selected;
+        let mosaic = if app.pane_zoomed && selected < app.mosaic_state_cache.len() {
+            MosaicView::new(&app.mosaic_state_cache[selected..=selected], 0)
+        } else {
+            MosaicView::new(&app.mosaic_state_cache, selected)
+        };
+        f.render_widget(mosaic, terminal_area);
+        // Clear terminal inner area and pane caches (not used in mosaic mode)
+        app.terminal_inner_area = None;
+        app.pane_areas.clear();
+    } else {
+        draw_terminal_panes(f, app, terminal_area);
+    }
+}
+
+/// Draw every pane of the tiling layout into the terminal area.
+///
+/// The focused pane shows the active (or previewed) session and receives
+/// text selection; other panes render their cached session state. When zoomed,
+/// only the focused pane is drawn, filling the whole area.
+fn draw_terminal_panes(f: &mut Frame, app: &mut App, terminal_area: Rect) {
+    app.tiling_area = Some(terminal_area);
+    let focused = app.tiling.focused();
+    let rects = if app.pane_zoomed {
+        vec![(focused, terminal_area)]
+    } else {
+        app.tiling.compute_rects(terminal_area)
+    };
+    let multi_pane = app.tiling.pane_count() > 1;
+
+    for (pane_id, rect) in &rects {
+        if *pane_id == focused && app.transcript_view.is_some() {
+            // Read-only transcript preview replaces the PTY in the focused pane
+            app.terminal_inner_area = None;
+            let transcript_focused = app.focus == Focus::Transcript;
+            if let Some(ref mut view) = app.transcript_view {
+                let (width, height) = TranscriptView::content_size(*rect);
+                view.layout(width, height);
+                f.render_widget(TranscriptView::new(view, transcript_focused), *rect);
+            }
+        } else if *pane_id == focused {
+            // Cache terminal inner area for mouse coordinate mapping (area minus 1px border)
+            let terminal_inner = Rect {
+                x: rect.x + 1,
+                y: rect.y + 1,
+                width: rect.width.saturating_sub(2),
+                height: rect.height.saturating_sub(2),
+            };
+            app.terminal_inner_area = Some(terminal_inner);
+
+            let title = multi_pane.then(|| pane_title(app, app.display_session_id(), true));
+            let session_state = app.get_session_state();
+            let is_preview = app.preview_session_id.is_some() && app.focus == Focus::Sidebar;
+            let selection = app.text_selection.as_ref();
+            let mut terminal_pane = TerminalPane::new(
+                session_state,
+                matches!(app.focus, Focus::Terminal(_)),
+                is_preview,
+                selection,
+            );
+            if let Some(title) = title {
+                terminal_pane = terminal_pane.with_title(title);
+            }
+            f.render_widget(terminal_pane, *rect);
+        } else {
+            let title = pane_title(app, app.pane_session_id(*pane_id), false);
+            let state = app.pane_state_caches.get(pane_id);
+            let terminal_pane = TerminalPane::new(state, false, false, None).with_title(title);
+            f.render_widget(terminal_pane, *rect);
+        }
+    }
+
+    app.pane_areas = rects;
+}
+
+/// Title for a pane in a multi-pane layout.
+fn pane_title(app: &App, session_id: Option<String>, focused: bool) -> String {
+    let name = session_id
+        .map(|sid| app.session_display_name(&sid))
+        .unwrap_or_else(|| "empty".to_string());
+    if focused && app.pane_zoomed {
+        format!("▸ {} [ZOOM]", name)
+    } else if focused {
+        format!("▸ {}", name)
+    } else {
+        name
+    }
+}
+
 fn draw_modal(f: &mut Frame, app: &mut App) {
     match &mut app.modal_state {
         crate::app::ModalState::None => {}
@@ -274,6 +384,56 @@ fn draw_modal(f: &mut Frame, app: &mut App) {
             let modal = ProfileModal::new(state);
             f.render_widget(modal, area);
         }
+        crate::app::ModalState::Layout(ref state) => {
+            let area = LayoutModal::calculate_area(f.area());
+            let modal = LayoutModal::new(state);
+            f.render_widget(modal, area);
+        }
+        crate::app::ModalState::Stats(ref mut state) => {
+            let area = StatsModal::calculate_area(f.area());
+            let modal = StatsModal::new(state);
+            f.render_widget(modal, area);
+        }
+        crate::app::ModalState::Tools(ref mut state) => {
+            let area = ToolsModal::calculate_area(f.area());
+            let modal = ToolsModal::new(state);
+            f.render_widget(modal, area);
+        }
+        crate::app::ModalState::Export(ref state) => {
+            let area = ExportModal::calculate_area(f.area());
+            let modal = ExportModal::new(state);
+            f.render_widget(modal, area);
+        }
+        crate::app::ModalState::Files(ref mut state) => {
+            let area = FilesModal::calculate_area(f.area());
+            let modal = FilesModal::new(state);
+            f.render_widget(modal, area);
+        }
+        crate::app::ModalState::Branches(ref mut state) => {
+            let area = BranchesModal::calculate_area(f.area());
+            let modal = BranchesModal::new(state);
+            f.render_widget(modal, area);
+        }
+        crate::app::ModalState::Commits(ref mut state) => {
+            let area = CommitsModal::calculate_area(f.area());
+            let modal = CommitsModal::new(state);
+            f.render_widget(modal, area);
+        }
+        crate::app::ModalState::Trash(ref mut state) => {
+            let area = TrashModal::calculate_area(f.area());
+            let modal = TrashModal::new(state);
+            f.render_widget(modal, area);
+        }
+        crate::app::ModalState::Metadata(ref state) => {
+            let area = MetadataModal::calculate_area(f.area());
+            let modal = MetadataModal::new(state);
+            f.render_widget(modal, area);
+        }
+        crate::app::ModalState::Archive(ref mut state) => {
+            let area = ArchiveModal::calculate_area(f.area());
+            let modal = ArchiveModal::new(state);
+            f.render_widget(modal, area);
+        }
     }
 }
 
@@ -422,6 +582,33 @@ fn draw_help_bar(f: &mut Frame, area: Rect, app: &App) {
             ]);
             spans
         }
+        Focus::Transcript => {
+            let transcript_indicator = Span::styled(
+                " -- TRANSCRIPT -- ",
+                Style::default().fg(Color::Black).bg(Color::Cyan),
+            );
+            let mut spans = vec![transcript_indicator];
+            if let Some(danger) = dangerous_indicator {
+                spans.push(danger);
+            }
+            spans.extend(vec![
+                Span::styled(" j/k ", Style::default().fg(Color::Cyan)),
+                Span::raw("move "),
+                Span::styled(" Enter ", Style::default().fg(Color::Cyan)),
+                Span::raw("fold "),
+                Span::styled(" / ", Style::default().fg(Color::Cyan)),
+                Span::raw("search "),
+                Span::styled(" r ", Style::default().fg(Color::Cyan)),
+                Span::raw("resume "),
+                Span::styled(" b ", Style::default().fg(Color::Cyan)),
+                Span::raw("branches "),
+                Span::styled(" c ", Style::default().fg(Color::Cyan)),
+                Span::raw("commits "),
+                Span::styled(" q ", Style::default().fg(Color::Cyan)),
+                Span::raw("back"),
+            ]);
+            spans
+        }
         Focus::Mosaic => {
             let mosaic_indicator = Span::styled(
                 " -- MOSAIC -- ",
@@ -445,8 +632,80 @@ fn draw_help_bar(f: &mut Frame, area: Rect, app: &App) {
         }
     };
 
+    let help_width = Line::from(help_text.clone()).width();
     let help = Paragraph::new(Line::from(help_text)).style(Style::default().bg(Color::DarkGray));
     f.render_widget(help, area);
+
+    // Usage headroom, right-aligned when there is room for it
+    if let Some(usage) = build_usage_indicator(app) {
+        let width = usage.width() as u16;
+        if help_width as u16 + width < area.width {
+            let usage_area = Rect::new(area.right() - width, area.y, width, 1);
+            f.render_widget(
+                Paragraph::new(usage).style(Style::default().bg(Color::DarkGray)),
+                usage_area,
+            );
+        }
+    }
+}
+
+/// Build the usage indicator: spend (or headroom, when a budget is set) in
+/// the current 5-hour block and today.
+fn build_usage_indicator(app: &App) -> Option<Line<'static>> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let budget = &app.config.budget;
+    let windows = &app.usage_windows;
+    let mut spans = Vec::new();
+
+    let block = windows.active_block(now);
+    if block.is_some() || budget.window.is_some() {
+        let spent = block.map_or(0.0, |b| b.usage.cost);
+        let mut text = format!(" 5h {}", budget_text(spent, budget.window));
+        if let Some(remaining) = block.and_then(|b| b.remaining_ms(now)) {
+            text.push_str(&format!(" · resets {}", format_remaining(remaining)));
+        }
+        spans.push(Span::styled(
+            text,
+            budget_style(spent, budget.window, budget.warn_percent),
+        ));
+    }
+
+    let today = windows.today(now).cost;
+    if today > 0.0 || budget.daily.is_some() {
+        if !spans.is_empty() {
+            spans.push(Span::styled(" │", Style::default().fg(Color::Gray)));
+        }
+        spans.push(Span::styled(
+            format!(" today {}", budget_text(today, budget.daily)),
+            budget_style(today, budget.daily, budget.warn_percent),
+        ));
+    }
+
+    if spans.is_empty() {
+        return None;
+    }
+    spans.push(Span::raw(" "));
+    Some(Line::from(spans))
+}
+
+/// Spend, or remaining headroom against a budget.
+fn budget_text(spent: f64, budget: Option<f64>) -> String {
+    match budget {
+        Some(limit) if spent > limit => format!("{} over", format_cost(spent - limit)),
+        Some(limit) => format!("{} left", format_cost(limit - spent)),
+        None => format_cost(spent),
+    }
+}
+
+fn budget_style(spent: f64, budget: Option<f64>, warn_percent: u8) -> Style {
+    let level = budget.map_or(BudgetLevel::Ok, |limit| {
+        BudgetLevel::of(spent, limit, warn_percent)
+    });
+    match level {
+        BudgetLevel::Ok => Style::default().fg(Color::Gray),
+        BudgetLevel::Warning => Style::default().fg(Color::Yellow),
+        BudgetLevel::Exceeded => Style::default().fg(Color::Red),
+    }
 }
 
 /// Build the mode indicator span for the help bar
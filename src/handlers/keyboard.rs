@@ -3,10 +3,12 @@ use std::time::Instant;
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use crate::app::{self, App, ChordState, EscapeSequenceState, Focus, SplitMode, TerminalPaneId};
+use crate::app::{self, App, ChordState, EscapeSequenceState, Focus, SplitMode};
 use crate::input::which_key::{LeaderAction, LeaderKeyResult};
 use crate::input::InputMode;
 use crate::ui::sidebar::FilterKeyResult;
+use crate::ui::tiling::Direction;
+use crate::ui::transcript::TranscriptKeyResult;
 
 use super::modal::forward_key_to_modal;
 use crate::event_loop::{HotReloadStatus, KeyAction};
@@ -141,6 +143,11 @@ pub(crate) fn handle_key_event(
         return handle_mosaic_key(app, key);
     }
 
+    // 2.6. Transcript view — intercept before normal/sidebar handlers
+    if app.focus == Focus::Transcript {
+        return handle_transcript_key(app, key);
+    }
+
     // 3. Normal-mode keybindings (Ctrl+Q handled above in true globals)
     match (key.code, key.modifiers) {
         (KeyCode::Char('q'), KeyModifiers::NONE) if app.focus == Focus::Sidebar => {
@@ -184,6 +191,23 @@ pub(crate) fn handle_key_event(
             app.toggle_sidebar_minimized();
             return Ok(KeyAction::Continue);
         }
+        // Resize the focused pane's split (Alt+h/j/k/l moves the boundary)
+        (KeyCode::Char('h'), KeyModifiers::ALT) => {
+            app.resize_pane(Direction::Left);
+            return Ok(KeyAction::Continue);
+        }
+        (KeyCode::Char('j'), KeyModifiers::ALT) => {
+            app.resize_pane(Direction::Down);
+            return Ok(KeyAction::Continue);
+        }
+        (KeyCode::Char('k'), KeyModifiers::ALT) => {
+            app.resize_pane(Direction::Up);
+            return Ok(KeyAction::Continue);
+        }
+        (KeyCode::Char('l'), KeyModifiers::ALT) => {
+            app.resize_pane(Direction::Right);
+            return Ok(KeyAction::Continue);
+        }
         _ => {}
     }
 
@@ -279,6 +303,12 @@ pub(crate) fn execute_leader_action(app: &mut App, action: LeaderAction) -> Resu
         LeaderAction::CycleArchiveFilter => {
             app.cycle_archive_filter();
         }
+        LeaderAction::Trash => app.trash_selected_conversation(),
+        LeaderAction::TrashArchived => app.trash_archived_conversations(),
+        LeaderAction::ArchiveGroup => app.open_archive_group_modal(),
+        LeaderAction::ArchiveOlderThan => app.open_archive_older_than_modal(),
+        LeaderAction::ArchiveRules => app.open_archive_rules_modal(),
+        LeaderAction::OpenTrash => app.open_trash_modal(),
         LeaderAction::Refresh => {
             app.manual_refresh()?;
         }
@@ -312,6 +342,60 @@ pub(crate) fn execute_leader_action(app: &mut App, action: LeaderAction) -> Resu
         LeaderAction::ToggleMosaic => {
             app.toggle_mosaic_view();
         }
+        LeaderAction::SplitPane(direction) => {
+            app.split_pane(direction);
+        }
+        LeaderAction::ClosePane => {
+            app.close_pane();
+        }
+        LeaderAction::FocusPane(direction) => {
+            app.focus_pane_direction(direction);
+        }
+        LeaderAction::SwapPane(direction) => {
+            app.swap_pane(direction);
+        }
+        LeaderAction::ResizePane(direction) => {
+            app.resize_pane(direction);
+        }
+        LeaderAction::ZoomPane => {
+            app.toggle_zoom();
+        }
+        LeaderAction::ManageLayouts => {
+            app.open_layout_modal();
+        }
+        LeaderAction::ShowUsageStats => {
+            app.open_stats_modal();
+        }
+        LeaderAction::ShowToolStats => {
+            app.open_tools_modal();
+        }
+        LeaderAction::NeedsAttention => {
+            app.toggle_attention_filter();
+        }
+        LeaderAction::ToggleDetails => {
+            app.toggle_detail_panel();
+        }
+        LeaderAction::FilesTouched => {
+            app.open_files_modal();
+        }
+        LeaderAction::Export => {
+            app.open_export_modal();
+        }
+        LeaderAction::Branches => {
+            app.open_branches_modal();
+        }
+        LeaderAction::Commits => {
+            app.open_commits_modal();
+        }
+        LeaderAction::EditMetadata => {
+            app.open_metadata_modal();
+        }
+        LeaderAction::TogglePin => {
+            app.toggle_pin_selected_conversation();
+        }
+        LeaderAction::OpenPinned(number) => {
+            app.open_pinned_conversation(number)?;
+        }
     }
     Ok(())
 }
@@ -379,6 +463,13 @@ pub(crate) fn handle_modal_insert_with_escape_seq(
                 return Ok(KeyAction::Continue);
             }
         }
+        // Layout modal Input mode: Esc cancels back to List mode
+        if let crate::app::ModalState::Layout(ref state) = app.modal_state {
+            if state.mode == crate::ui::modal::layout::LayoutModalMode::Input {
+                forward_key_to_modal(app, key)?;
+                return Ok(KeyAction::Continue);
+            }
+        }
         app.close_modal();
         return Ok(KeyAction::Continue);
     }
@@ -496,17 +587,13 @@ pub(crate) fn handle_mosaic_key(app: &mut App, key: KeyEvent) -> Result<KeyActio
 
     match key.code {
         // Horizontal navigation
-        KeyCode::Char('h') | KeyCode::Left => {
-            if col > 0 {
-                app.mosaic_selected -= 1;
-            }
+        KeyCode::Char('h') | KeyCode::Left if col > 0 => {
+            app.mosaic_selected -= 1;
         }
-        KeyCode::Char('l') | KeyCode::Right => {
-            if app.mosaic_selected + 1 < count {
-                let next_col = (app.mosaic_selected + 1) % cols;
-                if next_col > col {
-                    app.mosaic_selected += 1;
-                }
+        KeyCode::Char('l') | KeyCode::Right if app.mosaic_selected + 1 < count => {
+            let next_col = (app.mosaic_selected + 1) % cols;
+            if next_col > col {
+                app.mosaic_selected += 1;
             }
         }
         // Vertical navigation
@@ -516,23 +603,30 @@ pub(crate) fn handle_mosaic_key(app: &mut App, key: KeyEvent) -> Result<KeyActio
                 app.mosaic_selected = target;
             }
         }
-        KeyCode::Char('k') | KeyCode::Up => {
-            if app.mosaic_selected >= cols {
-                app.mosaic_selected -= cols;
-            }
+        KeyCode::Char('k') | KeyCode::Up if app.mosaic_selected >= cols => {
+            app.mosaic_selected -= cols;
         }
         // Zoom into selected session
         KeyCode::Enter => {
             if let Some((sid, _, _)) = app.mosaic_state_cache.get(app.mosaic_selected) {
                 let sid = sid.clone();
                 app.active_session_id = Some(sid);
-                app.split_mode = SplitMode::None;
-                app.focus = Focus::Terminal(TerminalPaneId::Primary);
+                app.split_mode = SplitMode::Tiled;
+                app.pane_zoomed = false;
+                app.focus = Focus::Terminal(app.tiling.focused());
                 app.input_mode = InputMode::Insert;
                 app.escape_seq_state = EscapeSequenceState::None;
                 app.mosaic_state_cache.clear();
             }
         }
+        // Zoom the selected tile to fill the view (toggle)
+        KeyCode::Char('z') => {
+            app.toggle_zoom();
+        }
+        // Esc restores a zoomed grid before leaving mosaic
+        KeyCode::Esc if app.pane_zoomed => {
+            app.toggle_zoom();
+        }
         // Exit mosaic
         KeyCode::Esc | KeyCode::Char('m') => {
             app.toggle_mosaic_view();
@@ -553,6 +647,42 @@ pub(crate) fn handle_mosaic_key(app: &mut App, key: KeyEvent) -> Result<KeyActio
     Ok(KeyAction::Continue)
 }
 
+pub(crate) fn handle_transcript_key(app: &mut App, key: KeyEvent) -> Result<KeyAction> {
+    let Some(ref mut view) = app.transcript_view else {
+        app.focus = Focus::Sidebar;
+        return Ok(KeyAction::Continue);
+    };
+
+    if !view.is_searching() {
+        match key.code {
+            KeyCode::Char(' ') => {
+                app.enter_leader_mode();
+                return Ok(KeyAction::Continue);
+            }
+            KeyCode::Char('?') => {
+                app.help_menu_open = !app.help_menu_open;
+                return Ok(KeyAction::Continue);
+            }
+            _ => {}
+        }
+    }
+
+    match view.handle_key(key) {
+        TranscriptKeyResult::Continue => {}
+        TranscriptKeyResult::Back => app.transcript_back(),
+        TranscriptKeyResult::Resume => app.resume_transcript()?,
+        TranscriptKeyResult::Branches => app.open_branches_modal(),
+        TranscriptKeyResult::Commits => app.open_commits_modal(),
+        TranscriptKeyResult::PlanLink => app.open_plan_link_transcript(),
+        TranscriptKeyResult::OpenSubagent(index) => {
+            if let Some(agent_id) = view.subagents().get(index).map(|s| s.agent_id.clone()) {
+                app.open_subagent_transcript(&agent_id);
+            }
+        }
+    }
+    Ok(KeyAction::Continue)
+}
+
 pub(crate) fn handle_sidebar_key_normal(app: &mut App, key: KeyEvent) -> Result<KeyAction> {
     // Close help menu on any keypress (except '?' which toggles below)
     if app.help_menu_open && key.code != KeyCode::Char('?') {
@@ -685,6 +815,11 @@ pub(crate) fn handle_sidebar_key_normal(app: &mut App, key: KeyEvent) -> Result<
             }
         }
 
+        // Focus the transcript preview if one is shown
+        KeyCode::Char('l') if app.transcript_view.is_some() => {
+            app.help_menu_open = false;
+            app.focus_transcript();
+        }
         // Enter insert mode and focus terminal
         KeyCode::Char('l') => {
             app.help_menu_open = false;
@@ -702,6 +837,16 @@ pub(crate) fn handle_sidebar_key_normal(app: &mut App, key: KeyEvent) -> Result<
         KeyCode::Char('u') => {
             let _ = app.unarchive_selected_conversation();
         }
+        // Delete to trash (X) and undo the last delete (U)
+        KeyCode::Char('X') => app.trash_selected_conversation(),
+        KeyCode::Char('U') => app.undo_trash(),
+
+        // Label (e) or star (s) the selected conversation
+        KeyCode::Char('e') => app.open_metadata_modal(),
+        KeyCode::Char('s') => app.toggle_pin_selected_conversation(),
+
+        // Jump between a plan and its implementation
+        KeyCode::Char('P') => app.jump_to_plan_link(),
 
         // Worktree: create from selected group (w) or search all projects (W)
         KeyCode::Char('w') => app.open_worktree_modal(),
@@ -715,6 +860,12 @@ pub(crate) fn handle_sidebar_key_normal(app: &mut App, key: KeyEvent) -> Result<
             let _ = app.preview_selected();
         }
 
+        // Expand/collapse the subagents of the selected conversation
+        KeyCode::Char('o') => app.toggle_selected_subagents(),
+
+        // Show details of the selected conversation
+        KeyCode::Char('i') => app.toggle_detail_panel(),
+
         _ => {}
     }
     Ok(KeyAction::Continue)
@@ -736,7 +887,7 @@ fn handle_sidebar_enter(app: &mut App) -> Result<()> {
             // Toggle expand/collapse on group/project headers
             app.toggle_current_group();
         }
-        Some(SidebarItem::WorkspaceSectionHeader) => {
+        Some(SidebarItem::PinnedHeader | SidebarItem::WorkspaceSectionHeader) => {
             // Non-interactive item — no-op
         }
         _ => {
@@ -20,8 +20,11 @@ pub(crate) fn forward_key_to_modal(app: &mut App, key: KeyEvent) -> Result<()> {
         ModalKeyResult::PathSelected(path) => {
             app.confirm_new_project(&path)?;
         }
-        ModalKeyResult::SearchSelected(session_id) => {
-            app.navigate_to_conversation(&session_id)?;
+        ModalKeyResult::SearchSelected {
+            session_id,
+            message_index,
+        } => {
+            app.navigate_to_conversation(&session_id, message_index)?;
         }
         ModalKeyResult::SearchQueryChanged => {
             app.perform_search();
@@ -54,6 +57,53 @@ pub(crate) fn forward_key_to_modal(app: &mut App, key: KeyEvent) -> Result<()> {
         ModalKeyResult::ProfileActivated(idx) => {
             app.activate_profile_from_modal(idx);
         }
+        ModalKeyResult::LayoutSaved(name) => {
+            app.save_layout(&name);
+        }
+        ModalKeyResult::LayoutApplied(idx) => {
+            app.apply_layout(idx);
+        }
+        ModalKeyResult::LayoutDeleted(idx) => {
+            app.delete_layout(idx);
+        }
+        ModalKeyResult::ExportConfirmed {
+            session_id,
+            format,
+            options,
+            destination,
+        } => {
+            app.confirm_export(&session_id, format, &options, destination);
+        }
+        ModalKeyResult::BranchOpened { session_id, tip } => {
+            app.open_branch_tip(&session_id, &tip);
+        }
+        ModalKeyResult::BranchResumed {
+            session_id,
+            fork_at,
+        } => {
+            app.resume_branch(&session_id, fork_at.as_deref())?;
+        }
+        ModalKeyResult::CommitYanked(hash) => {
+            app.copy_commit_hash(&hash);
+        }
+        ModalKeyResult::TrashRestored(id) => {
+            app.restore_from_trash(&id);
+        }
+        ModalKeyResult::TrashPurged(id) => {
+            app.purge_from_trash(&id);
+        }
+        ModalKeyResult::ArchiveConfirmed {
+            session_ids,
+            by_rule,
+        } => {
+            app.archive_sessions(&session_ids, by_rule);
+        }
+        ModalKeyResult::TrashConfirmed { session_ids } => {
+            app.trash_sessions(&session_ids);
+        }
+        ModalKeyResult::MetadataSaved { session_id, meta } => {
+            app.save_conversation_metadata(&session_id, meta);
+        }
     }
     Ok(())
 }
@@ -1,4 +1,5 @@
 use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
 
 use crate::app::{App, TerminalPosition, TextSelection};
 
@@ -40,7 +41,36 @@ pub(crate) fn handle_mouse_event(app: &mut App, mouse: MouseEvent) {
     const SCROLL_LINES: usize = 3;
 
     match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left)
+            if app.begin_split_drag(mouse.column, mouse.row) =>
+        {
+            // Grabbed a split boundary — drag resizes instead of selecting
+            app.clear_selection();
+        }
+        MouseEventKind::Drag(MouseButton::Left) if app.split_drag.is_some() => {
+            app.update_split_drag(mouse.column, mouse.row);
+        }
+        MouseEventKind::Up(MouseButton::Left) if app.split_drag.is_some() => {
+            app.end_split_drag();
+        }
         MouseEventKind::Down(MouseButton::Left) => {
+            // Clicking an unfocused pane focuses it (no selection starts)
+            let clicked_pane = app
+                .pane_areas
+                .iter()
+                .find(|(_, r)| r.contains(Position::new(mouse.column, mouse.row)))
+                .map(|(id, _)| *id);
+            if let Some(pane_id) = clicked_pane.filter(|id| *id != app.tiling.focused()) {
+                app.clear_selection();
+                app.focus_pane(pane_id);
+                return;
+            }
+            // Clicking the transcript preview focuses it
+            if clicked_pane.is_some() && app.transcript_view.is_some() {
+                app.focus_transcript();
+                return;
+            }
+
             // Start a new selection if click is inside terminal pane
             // Do NOT change focus or input mode — mouse selection is overlay-only
             if let Some(pos) = screen_to_terminal_pos(app, mouse.column, mouse.row) {
@@ -53,30 +83,28 @@ pub(crate) fn handle_mouse_event(app: &mut App, mouse: MouseEvent) {
                 app.clear_selection();
             }
         }
-        MouseEventKind::Drag(MouseButton::Left) => {
-            if app.text_selection.is_some() {
-                if let Some(inner) = app.terminal_inner_area {
-                    // Compute new cursor position before mutating selection
-                    let clamped_col = mouse.column.max(inner.x).min(inner.x + inner.width - 1);
-                    let clamped_row = mouse.row.max(inner.y).min(inner.y + inner.height - 1);
-                    let new_pos = screen_to_terminal_pos(app, clamped_col, clamped_row);
+        MouseEventKind::Drag(MouseButton::Left) if app.text_selection.is_some() => {
+            if let Some(inner) = app.terminal_inner_area {
+                // Compute new cursor position before mutating selection
+                let clamped_col = mouse.column.max(inner.x).min(inner.x + inner.width - 1);
+                let clamped_row = mouse.row.max(inner.y).min(inner.y + inner.height - 1);
+                let new_pos = screen_to_terminal_pos(app, clamped_col, clamped_row);
 
-                    if let (Some(ref mut sel), Some(pos)) = (&mut app.text_selection, new_pos) {
-                        sel.cursor = pos;
-                    }
+                if let (Some(ref mut sel), Some(pos)) = (&mut app.text_selection, new_pos) {
+                    sel.cursor = pos;
+                }
 
-                    // Auto-scroll when dragging above or below terminal area
-                    if mouse.row < inner.y {
-                        if let Some(ref session_id) = app.active_session_id.clone() {
-                            if let Some(session) = app.session_manager.get_session_mut(session_id) {
-                                session.scroll_up(1);
-                            }
+                // Auto-scroll when dragging above or below terminal area
+                if mouse.row < inner.y {
+                    if let Some(ref session_id) = app.active_session_id.clone() {
+                        if let Some(session) = app.session_manager.get_session_mut(session_id) {
+                            session.scroll_up(1);
                         }
-                    } else if mouse.row >= inner.y + inner.height {
-                        if let Some(ref session_id) = app.active_session_id.clone() {
-                            if let Some(session) = app.session_manager.get_session_mut(session_id) {
-                                session.scroll_down(1);
-                            }
+                    }
+                } else if mouse.row >= inner.y + inner.height {
+                    if let Some(ref session_id) = app.active_session_id.clone() {
+                        if let Some(session) = app.session_manager.get_session_mut(session_id) {
+                            session.scroll_down(1);
                         }
                     }
                 }
@@ -5,6 +5,7 @@
 //! the user presses leader key sequences.
 
 use crate::config::ProfileEntry;
+use crate::ui::tiling::{Direction, SplitDirection};
 
 /// Actions that can be triggered via the leader key menu
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -12,6 +13,8 @@ pub enum LeaderAction {
     // Navigation/Search
     /// Open search modal
     SearchOpen,
+    /// Open the picker of files touched by conversations
+    FilesTouched,
     /// Open new project modal
     NewProject,
 
@@ -26,10 +29,36 @@ pub enum LeaderAction {
     Unarchive,
     /// Cycle archive filter
     CycleArchiveFilter,
+    /// Move current conversation to the trash
+    Trash,
+    /// Move all archived conversations to the trash
+    TrashArchived,
+    /// Preview archiving the selected group or project
+    ArchiveGroup,
+    /// Preview archiving conversations idle since before a cutoff
+    ArchiveOlderThan,
+    /// Preview what the configured archive rules would archive
+    ArchiveRules,
+    /// Browse the trash to restore or purge conversations
+    OpenTrash,
+
+    // Starred conversations
+    /// Star or unstar the current conversation (pins it to the top of the sidebar)
+    TogglePin,
+    /// Open the Nth (1-based) pinned conversation
+    OpenPinned(usize),
 
     // Other actions
     /// Refresh sessions list
     Refresh,
+    /// Export the selected conversation (Markdown/HTML/JSON)
+    Export,
+    /// Show the selected conversation's edit/rewind branches
+    Branches,
+    /// Show the git commits made while the selected conversation was active
+    Commits,
+    /// Edit the selected conversation's custom title, tags and note
+    EditMetadata,
     /// Yank (copy) path to clipboard
     YankPath,
     /// Toggle dangerous mode
@@ -58,6 +87,30 @@ pub enum LeaderAction {
     // View
     /// Toggle mosaic view (all active sessions in a grid)
     ToggleMosaic,
+    /// Open the token usage and cost stats
+    ShowUsageStats,
+    /// Open the tool call and turn stats
+    ShowToolStats,
+    /// Toggle the sidebar filter for conversations that need attention
+    NeedsAttention,
+    /// Toggle the detail panel of the selected conversation
+    ToggleDetails,
+
+    // Panes
+    /// Split the focused pane
+    SplitPane(SplitDirection),
+    /// Close the focused pane
+    ClosePane,
+    /// Move focus to the neighboring pane
+    FocusPane(Direction),
+    /// Swap the focused pane with its neighbor
+    SwapPane(Direction),
+    /// Move the split boundary next to the focused pane
+    ResizePane(Direction),
+    /// Toggle zoom on the focused pane (or selected mosaic tile)
+    ZoomPane,
+    /// Open the named layout modal
+    ManageLayouts,
 }
 
 /// A command entry in the which-key menu
@@ -129,6 +182,11 @@ impl WhichKeyConfig {
         vec![
             // Direct actions
             LeaderCommand::action('/', "search", LeaderAction::SearchOpen),
+            LeaderCommand::action('f', "files touched", LeaderAction::FilesTouched),
+            LeaderCommand::action('e', "export", LeaderAction::Export),
+            LeaderCommand::action('b', "branches", LeaderAction::Branches),
+            LeaderCommand::action('g', "git commits", LeaderAction::Commits),
+            LeaderCommand::action('m', "title/tags/note", LeaderAction::EditMetadata),
             LeaderCommand::action('n', "new project", LeaderAction::NewProject),
             LeaderCommand::action('c', "close session", LeaderAction::CloseSession),
             LeaderCommand::action('a', "add conversation", LeaderAction::AddConversation),
@@ -140,8 +198,32 @@ impl WhichKeyConfig {
                     LeaderCommand::action('a', "archive", LeaderAction::Archive),
                     LeaderCommand::action('u', "unarchive", LeaderAction::Unarchive),
                     LeaderCommand::action('f', "cycle filter", LeaderAction::CycleArchiveFilter),
+                    LeaderCommand::action('d', "delete", LeaderAction::Trash),
+                    LeaderCommand::action('D', "delete archived", LeaderAction::TrashArchived),
+                    LeaderCommand::action('g', "archive group", LeaderAction::ArchiveGroup),
+                    LeaderCommand::action('o', "archive older", LeaderAction::ArchiveOlderThan),
+                    LeaderCommand::action('r', "run rules", LeaderAction::ArchiveRules),
+                    LeaderCommand::action('t', "trash", LeaderAction::OpenTrash),
                 ],
             ),
+            // Starred submenu: toggle, then quick access by number
+            LeaderCommand::submenu(
+                's',
+                "starred",
+                std::iter::once(LeaderCommand::action(
+                    's',
+                    "star/unstar",
+                    LeaderAction::TogglePin,
+                ))
+                .chain((1..=9).map(|n| {
+                    LeaderCommand::action(
+                        char::from_digit(n as u32, 10).unwrap_or('0'),
+                        format!("open {}", n),
+                        LeaderAction::OpenPinned(n),
+                    )
+                }))
+                .collect(),
+            ),
             // Worktree submenu
             LeaderCommand::submenu(
                 'w',
@@ -164,11 +246,85 @@ impl WhichKeyConfig {
             LeaderCommand::submenu(
                 'v',
                 "view",
-                vec![LeaderCommand::action(
-                    'm',
-                    "mosaic",
-                    LeaderAction::ToggleMosaic,
-                )],
+                vec![
+                    LeaderCommand::action('m', "mosaic", LeaderAction::ToggleMosaic),
+                    LeaderCommand::action('s', "usage stats", LeaderAction::ShowUsageStats),
+                    LeaderCommand::action('t', "tool stats", LeaderAction::ShowToolStats),
+                    LeaderCommand::action('a', "needs attention", LeaderAction::NeedsAttention),
+                    LeaderCommand::action('d', "details", LeaderAction::ToggleDetails),
+                ],
+            ),
+            // Pane submenu
+            LeaderCommand::submenu(
+                't',
+                "panes",
+                vec![
+                    LeaderCommand::action(
+                        'v',
+                        "split right",
+                        LeaderAction::SplitPane(SplitDirection::Horizontal),
+                    ),
+                    LeaderCommand::action(
+                        's',
+                        "split below",
+                        LeaderAction::SplitPane(SplitDirection::Vertical),
+                    ),
+                    LeaderCommand::action('c', "close pane", LeaderAction::ClosePane),
+                    LeaderCommand::action(
+                        'h',
+                        "focus left",
+                        LeaderAction::FocusPane(Direction::Left),
+                    ),
+                    LeaderCommand::action(
+                        'j',
+                        "focus down",
+                        LeaderAction::FocusPane(Direction::Down),
+                    ),
+                    LeaderCommand::action('k', "focus up", LeaderAction::FocusPane(Direction::Up)),
+                    LeaderCommand::action(
+                        'l',
+                        "focus right",
+                        LeaderAction::FocusPane(Direction::Right),
+                    ),
+                    LeaderCommand::action(
+                        'H',
+                        "swap left",
+                        LeaderAction::SwapPane(Direction::Left),
+                    ),
+                    LeaderCommand::action(
+                        'J',
+                        "swap down",
+                        LeaderAction::SwapPane(Direction::Down),
+                    ),
+                    LeaderCommand::action('K', "swap up", LeaderAction::SwapPane(Direction::Up)),
+                    LeaderCommand::action(
+                        'L',
+                        "swap right",
+                        LeaderAction::SwapPane(Direction::Right),
+                    ),
+                    LeaderCommand::action(
+                        '<',
+                        "shrink width",
+                        LeaderAction::ResizePane(Direction::Left),
+                    ),
+                    LeaderCommand::action(
+                        '>',
+                        "grow width",
+                        LeaderAction::ResizePane(Direction::Right),
+                    ),
+                    LeaderCommand::action(
+                        '-',
+                        "shrink height",
+                        LeaderAction::ResizePane(Direction::Up),
+                    ),
+                    LeaderCommand::action(
+                        '+',
+                        "grow height",
+                        LeaderAction::ResizePane(Direction::Down),
+                    ),
+                    LeaderCommand::action('z', "zoom pane", LeaderAction::ZoomPane),
+                    LeaderCommand::action('o', "layouts", LeaderAction::ManageLayouts),
+                ],
             ),
             // Other actions
             LeaderCommand::action('r', "refresh", LeaderAction::Refresh),
@@ -333,6 +489,164 @@ mod tests {
         assert_eq!(config.submenu_title(&['p']), "Project");
     }
 
+    #[test]
+    fn pane_submenu_maps_split_and_focus_keys() {
+        let config = WhichKeyConfig::new();
+        assert_eq!(config.process_key(&[], 't'), LeaderKeyResult::Submenu);
+        assert_eq!(
+            config.process_key(&['t'], 'v'),
+            LeaderKeyResult::Execute(LeaderAction::SplitPane(SplitDirection::Horizontal))
+        );
+        assert_eq!(
+            config.process_key(&['t'], 'j'),
+            LeaderKeyResult::Execute(LeaderAction::FocusPane(Direction::Down))
+        );
+        assert_eq!(config.submenu_title(&['t']), "Panes");
+    }
+
+    #[test]
+    fn pane_submenu_maps_resize_and_zoom_keys() {
+        let config = WhichKeyConfig::new();
+        assert_eq!(
+            config.process_key(&['t'], '>'),
+            LeaderKeyResult::Execute(LeaderAction::ResizePane(Direction::Right))
+        );
+        assert_eq!(
+            config.process_key(&['t'], 'z'),
+            LeaderKeyResult::Execute(LeaderAction::ZoomPane)
+        );
+    }
+
+    #[test]
+    fn view_submenu_maps_usage_stats() {
+        let config = WhichKeyConfig::new();
+        assert_eq!(
+            config.process_key(&['v'], 's'),
+            LeaderKeyResult::Execute(LeaderAction::ShowUsageStats)
+        );
+    }
+
+    #[test]
+    fn view_submenu_maps_tool_stats() {
+        let config = WhichKeyConfig::new();
+        assert_eq!(
+            config.process_key(&['v'], 't'),
+            LeaderKeyResult::Execute(LeaderAction::ShowToolStats)
+        );
+    }
+
+    #[test]
+    fn view_submenu_maps_needs_attention() {
+        let config = WhichKeyConfig::new();
+        assert_eq!(
+            config.process_key(&['v'], 'a'),
+            LeaderKeyResult::Execute(LeaderAction::NeedsAttention)
+        );
+    }
+
+    #[test]
+    fn view_submenu_maps_details() {
+        let config = WhichKeyConfig::new();
+        assert_eq!(
+            config.process_key(&['v'], 'd'),
+            LeaderKeyResult::Execute(LeaderAction::ToggleDetails)
+        );
+    }
+
+    #[test]
+    fn f_opens_files_touched_picker() {
+        let config = WhichKeyConfig::new();
+        assert_eq!(
+            config.process_key(&[], 'f'),
+            LeaderKeyResult::Execute(LeaderAction::FilesTouched)
+        );
+    }
+
+    #[test]
+    fn e_opens_export() {
+        let config = WhichKeyConfig::new();
+        assert_eq!(
+            config.process_key(&[], 'e'),
+            LeaderKeyResult::Execute(LeaderAction::Export)
+        );
+    }
+
+    #[test]
+    fn starred_submenu_toggles_and_opens_pinned_by_number() {
+        let config = WhichKeyConfig::new();
+        assert_eq!(config.process_key(&[], 's'), LeaderKeyResult::Submenu);
+        assert_eq!(
+            config.process_key(&['s'], 's'),
+            LeaderKeyResult::Execute(LeaderAction::TogglePin)
+        );
+        assert_eq!(
+            config.process_key(&['s'], '3'),
+            LeaderKeyResult::Execute(LeaderAction::OpenPinned(3))
+        );
+        assert_eq!(config.submenu_title(&['s']), "Starred");
+    }
+
+    #[test]
+    fn m_edits_conversation_metadata() {
+        let config = WhichKeyConfig::new();
+        assert_eq!(
+            config.process_key(&[], 'm'),
+            LeaderKeyResult::Execute(LeaderAction::EditMetadata)
+        );
+    }
+
+    #[test]
+    fn b_opens_branches() {
+        let config = WhichKeyConfig::new();
+        assert_eq!(
+            config.process_key(&[], 'b'),
+            LeaderKeyResult::Execute(LeaderAction::Branches)
+        );
+    }
+
+    #[test]
+    fn g_opens_git_commits() {
+        let config = WhichKeyConfig::new();
+        assert_eq!(
+            config.process_key(&[], 'g'),
+            LeaderKeyResult::Execute(LeaderAction::Commits)
+        );
+    }
+
+    #[test]
+    fn archive_submenu_has_trash_actions() {
+        let config = WhichKeyConfig::new();
+        assert_eq!(
+            config.process_key(&['x'], 'd'),
+            LeaderKeyResult::Execute(LeaderAction::Trash)
+        );
+        assert_eq!(
+            config.process_key(&['x'], 'D'),
+            LeaderKeyResult::Execute(LeaderAction::TrashArchived)
+        );
+        assert_eq!(
+            config.process_key(&['x'], 't'),
+            LeaderKeyResult::Execute(LeaderAction::OpenTrash)
+        );
+    }
+
+    #[test]
+    fn archive_submenu_has_bulk_actions() {
+        let config = WhichKeyConfig::new();
+        assert_eq!(
+            config.process_key(&['x'], 'g'),
+            LeaderKeyResult::Execute(LeaderAction::ArchiveGroup)
+        );
+        assert_eq!(
+            config.process_key(&['x'], 'o'),
+            LeaderKeyResult::Execute(LeaderAction::ArchiveOlderThan)
+        );
+        assert_eq!(
+            config.process_key(&['x'], 'r'),
+            LeaderKeyResult::Execute(LeaderAction::ArchiveRules)
+        );
+    }
+
     #[test]
     fn worktree_submenu_has_no_workspace_key() {
         let config = WhichKeyConfig::new();
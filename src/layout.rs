@@ -0,0 +1,183 @@
+//! Layout tree of the terminal area's panes.
+//!
+//! A binary tree of splits whose leaves are panes identified by a
+//! [`PaneId`]. This is the part of a tiling layout that is saved in the
+//! config; `ui::tiling` computes the screen geometry from it.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+/// Stable identifier of a pane within a layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PaneId(pub u32);
+
+/// Orientation of a split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SplitDirection {
+    /// Side-by-side (left/right)
+    Horizontal,
+    /// Stacked (top/bottom)
+    Vertical,
+}
+
+/// A node in the layout tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum LayoutNode {
+    /// A single pane
+    Pane { id: PaneId },
+    /// Two children sharing the parent's area
+    Split {
+        direction: SplitDirection,
+        /// Percentage of the area given to `first` (10-90)
+        ratio: u16,
+        first: Box<LayoutNode>,
+        second: Box<LayoutNode>,
+    },
+}
+
+impl LayoutNode {
+    /// Collect pane IDs in reading order (left-to-right, top-to-bottom).
+    pub fn pane_ids(&self) -> Vec<PaneId> {
+        let mut ids = Vec::new();
+        self.collect_ids(&mut ids);
+        ids
+    }
+
+    fn collect_ids(&self, out: &mut Vec<PaneId>) {
+        match self {
+            LayoutNode::Pane { id } => out.push(*id),
+            LayoutNode::Split { first, second, .. } => {
+                first.collect_ids(out);
+                second.collect_ids(out);
+            }
+        }
+    }
+
+    /// Path from this node to the pane `target`.
+    pub(crate) fn path_to(&self, target: PaneId) -> Option<Vec<bool>> {
+        match self {
+            LayoutNode::Pane { id } => (*id == target).then(Vec::new),
+            LayoutNode::Split { first, second, .. } => {
+                if let Some(mut p) = first.path_to(target) {
+                    p.insert(0, false);
+                    Some(p)
+                } else {
+                    let mut p = second.path_to(target)?;
+                    p.insert(0, true);
+                    Some(p)
+                }
+            }
+        }
+    }
+
+    /// The node at `path`, if it exists.
+    pub(crate) fn node_at(&self, path: &[bool]) -> Option<&LayoutNode> {
+        match (path.split_first(), self) {
+            (None, _) => Some(self),
+            (Some((go_second, rest)), LayoutNode::Split { first, second, .. }) => {
+                if *go_second {
+                    second.node_at(rest)
+                } else {
+                    first.node_at(rest)
+                }
+            }
+            (Some(_), LayoutNode::Pane { .. }) => None,
+        }
+    }
+
+    /// The node at `path`, mutably.
+    pub(crate) fn node_at_mut(&mut self, path: &[bool]) -> Option<&mut LayoutNode> {
+        match path.split_first() {
+            None => Some(self),
+            Some((go_second, rest)) => match self {
+                LayoutNode::Split { first, second, .. } => {
+                    if *go_second {
+                        second.node_at_mut(rest)
+                    } else {
+                        first.node_at_mut(rest)
+                    }
+                }
+                LayoutNode::Pane { .. } => None,
+            },
+        }
+    }
+
+    /// Find the mutable leaf node holding `target`.
+    pub(crate) fn find_pane_mut(&mut self, target: PaneId) -> Option<&mut LayoutNode> {
+        match self {
+            LayoutNode::Pane { id } if *id == target => Some(self),
+            LayoutNode::Pane { .. } => None,
+            LayoutNode::Split { first, second, .. } => first
+                .find_pane_mut(target)
+                .or_else(|| second.find_pane_mut(target)),
+        }
+    }
+
+    /// Remove the pane `target`, collapsing its parent split into the sibling.
+    /// Returns `None` when the whole node was the removed pane.
+    pub(crate) fn without_pane(self, target: PaneId) -> Option<LayoutNode> {
+        match self {
+            LayoutNode::Pane { id } if id == target => None,
+            LayoutNode::Pane { .. } => Some(self),
+            LayoutNode::Split {
+                direction,
+                ratio,
+                first,
+                second,
+            } => match (first.without_pane(target), second.without_pane(target)) {
+                (Some(first), Some(second)) => Some(LayoutNode::Split {
+                    direction,
+                    ratio,
+                    first: Box::new(first),
+                    second: Box::new(second),
+                }),
+                (Some(only), None) | (None, Some(only)) => Some(only),
+                (None, None) => None,
+            },
+        }
+    }
+
+    /// Exchange the positions of two panes.
+    pub(crate) fn swap_ids(&mut self, a: PaneId, b: PaneId) {
+        match self {
+            LayoutNode::Pane { id } => {
+                if *id == a {
+                    *id = b;
+                } else if *id == b {
+                    *id = a;
+                }
+            }
+            LayoutNode::Split { first, second, .. } => {
+                first.swap_ids(a, b);
+                second.swap_ids(a, b);
+            }
+        }
+    }
+
+    /// Give each pane whose ID an earlier pane already has a fresh ID, so
+    /// every ID names exactly one pane.
+    pub fn dedup_pane_ids(&mut self) {
+        let mut next_id = self.pane_ids().iter().map(|id| id.0 + 1).max().unwrap_or(0);
+        let mut seen = HashSet::new();
+        self.for_each_pane_mut(&mut |id| {
+            if !seen.insert(*id) {
+                *id = PaneId(next_id);
+                next_id += 1;
+            }
+        });
+    }
+
+    fn for_each_pane_mut(&mut self, f: &mut impl FnMut(&mut PaneId)) {
+        match self {
+            LayoutNode::Pane { id } => f(id),
+            LayoutNode::Split { first, second, .. } => {
+                first.for_each_pane_mut(f);
+                second.for_each_pane_mut(f);
+            }
+        }
+    }
+}
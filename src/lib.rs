@@ -8,10 +8,12 @@
 
 pub mod app;
 pub mod claude;
+pub mod cli;
 pub mod config;
 pub mod event_loop;
 pub mod handlers;
 pub mod input;
+pub mod layout;
 pub mod search;
 pub mod session;
 pub mod ui;
@@ -11,16 +11,25 @@ use crossterm::{
 use ratatui::{backend::CrosstermBackend, Terminal};
 
 use claudatui::app::App;
+use claudatui::claude::data_dir::take_claude_dir_args;
 use claudatui::event_loop::{run_app, HotReloadAction};
 
 fn main() -> Result<()> {
     // Handle --version / -V before any terminal setup
-    let args: Vec<String> = std::env::args().collect();
+    let mut args: Vec<String> = std::env::args().collect();
     if args.iter().any(|a| a == "--version" || a == "-V") {
         println!("claudatui {}", env!("CARGO_PKG_VERSION"));
         return Ok(());
     }
 
+    // --claude-dir applies to the TUI and every subcommand
+    let claude_dirs = take_claude_dir_args(&mut args)?;
+
+    // Non-interactive subcommands (list, search, stats, ...) print and exit
+    if let Some(result) = claudatui::cli::run(args.get(1..).unwrap_or_default(), &claude_dirs) {
+        return result;
+    }
+
     // Check if we're in a proper terminal
     if !std::io::stdin().is_terminal() {
         anyhow::bail!("claudatui must be run in an interactive terminal");
@@ -35,7 +44,7 @@ fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend).context("Failed to create terminal")?;
 
     // Create app
-    let mut app = App::new().context("Failed to initialize application")?;
+    let mut app = App::new(&claude_dirs).context("Failed to initialize application")?;
 
     // Get initial terminal size
     let size = terminal.size().context("Failed to get terminal size")?;
@@ -43,6 +52,8 @@ fn main() -> Result<()> {
 
     // Run app
     let result = run_app(&mut terminal, &mut app);
+    // Keep what background refreshes indexed since the last save
+    let _ = app.search_engine.save_index();
 
     // Restore terminal (always try to restore even on error)
     let _ = disable_raw_mode();
@@ -59,7 +70,15 @@ fn main() -> Result<()> {
             // Re-exec the new binary
             let c_path =
                 CString::new(path.as_bytes()).context("Invalid path for hot reload binary")?;
-            let args: [CString; 1] = [c_path.clone()];
+            // Keep reading the same data directories
+            let mut args = vec![c_path.clone()];
+            for dir in &claude_dirs {
+                args.push(CString::new("--claude-dir")?);
+                args.push(
+                    CString::new(dir.to_string_lossy().as_bytes())
+                        .context("Invalid --claude-dir for hot reload")?,
+                );
+            }
             // execv never returns on success
             match nix::unistd::execv(&c_path, &args) {
                 Ok(infallible) => match infallible {},
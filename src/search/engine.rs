@@ -1,84 +1,191 @@
 //! Search engine for finding conversations by content or project.
-
-use std::path::PathBuf;
-
+//!
+//! Results are ranked: each conversation is fuzzy-scored against its title,
+//! summary (and user tags and note), git branch and project path (weighted in
+//! that order) and message bodies, then boosted by recency. The best hit
+//! comes first. Field filters, phrases and negations from the query language
+//! narrow the candidates first.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::claude::conversation::Conversation;
+use crate::claude::data_dir::ClaudeDir;
 use crate::claude::grouping::ConversationGroup;
+use crate::claude::metadata::format_tags;
+use crate::search::fuzzy::fuzzy_match;
+use crate::search::index::{tokenize, ContentHit, ContentIndex, IndexUpdate};
 use crate::search::types::{SearchFilterType, SearchQuery, SearchResult};
 
+/// Weights applied to fuzzy scores per field: title matches count the most.
+const WEIGHT_TITLE: i64 = 4;
+const WEIGHT_SUMMARY: i64 = 3;
+const WEIGHT_BRANCH: i64 = 2;
+const WEIGHT_PATH: i64 = 1;
+/// Score for a message-body hit from the content index
+const CONTENT_HIT_SCORE: i64 = 100;
+/// How many of the best results get a snippet of their matching message
+/// (reading each message means opening its session file)
+const SNIPPET_RESULTS: usize = 50;
+/// Boost for a conversation modified just now, decaying linearly to zero
+const RECENCY_BOOST: i64 = 60;
+const RECENCY_WINDOW_DAYS: i64 = 30;
+const MS_PER_DAY: i64 = 86_400_000;
+/// How often background refreshes persist the content index
+const SAVE_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Which fields a search looks at.
+#[derive(Debug, Clone, Copy)]
+struct Fields {
+    /// Title, summary, tags, note, branch and message contents
+    content: bool,
+    /// Project path
+    project: bool,
+}
+
+/// The best-scoring field of a conversation, before it becomes a result.
+#[derive(Debug, Default)]
+struct FieldMatch<'a> {
+    score: i64,
+    snippet: String,
+    snippet_ranges: Vec<Range<usize>>,
+    message_index: Option<usize>,
+    /// Matching message, whose snippet is read once results are ranked
+    content_hit: Option<&'a ContentHit>,
+}
+
+impl FieldMatch<'_> {
+    fn keep_best(best: &mut Option<Self>, candidate: Self) {
+        if best.as_ref().is_none_or(|b| candidate.score > b.score) {
+            *best = Some(candidate);
+        }
+    }
+}
+
 /// Search engine for finding conversations.
 pub struct SearchEngine {
-    /// Path to ~/.claude directory
-    #[expect(dead_code, reason = "planned for future use")]
-    claude_dir: PathBuf,
+    /// Claude data directories whose conversations are indexed
+    claude_dirs: Vec<ClaudeDir>,
+    /// Full-text index over message contents
+    content_index: ContentIndex,
+    /// Changes read by a background refresh, if one is running
+    pending_refresh: Option<Receiver<IndexUpdate>>,
+    /// Whether another refresh was asked for while one was running
+    refresh_queued: bool,
+    /// When background refreshes last persisted the index
+    saved_at: Instant,
 }
 
 impl SearchEngine {
-    /// Create a new search engine.
-    pub fn new(claude_dir: PathBuf) -> Self {
-        Self { claude_dir }
+    /// Create a new search engine with an empty in-memory content index.
+    pub fn new(claude_dirs: Vec<ClaudeDir>) -> Self {
+        Self {
+            claude_dirs,
+            content_index: ContentIndex::new(),
+            pending_refresh: None,
+            refresh_queued: false,
+            saved_at: Instant::now(),
+        }
     }
 
-    /// Search conversations by content (title/summary).
-    pub fn search_content(&self, query: &str, groups: &[ConversationGroup]) -> Vec<SearchResult> {
-        let query_lower = query.to_lowercase();
-        let mut results = Vec::new();
-
-        for group in groups {
-            for conv in group.conversations() {
-                // Skip archived conversations
-                if conv.is_archived {
-                    continue;
-                }
+    /// Use the given (typically persisted) content index.
+    pub fn with_content_index(mut self, content_index: ContentIndex) -> Self {
+        self.content_index = content_index;
+        self
+    }
 
-                // Search in display text (which contains summary or first message)
-                if conv.display.to_lowercase().contains(&query_lower) {
-                    let snippet = Self::create_snippet(&conv.display, &query_lower);
-                    results.push(SearchResult::new(conv.clone(), snippet));
-                    continue;
-                }
+    /// The content index (also used for tool and file lookups).
+    pub fn content_index(&self) -> &ContentIndex {
+        &self.content_index
+    }
 
-                // Search in summary if available
-                if let Some(ref summary) = conv.summary {
-                    if summary.to_lowercase().contains(&query_lower) {
-                        let snippet = Self::create_snippet(summary, &query_lower);
-                        results.push(SearchResult::new(conv.clone(), snippet));
-                    }
-                }
+    /// Bring the content index up to date with the session files on disk and persist it.
+    pub fn refresh_index(&mut self) -> Result<()> {
+        self.content_index.refresh(&self.claude_dirs);
+        self.content_index.save()
+    }
+
+    /// Start reading changed session files on a background thread; see
+    /// [`Self::poll_index_refresh`]. While one refresh runs, another is
+    /// queued to follow it.
+    pub fn start_index_refresh(&mut self) {
+        if self.pending_refresh.is_some() {
+            self.refresh_queued = true;
+            return;
+        }
+        let scan = self.content_index.scan();
+        let claude_dirs = self.claude_dirs.clone();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(scan.run(&claude_dirs));
+        });
+        self.pending_refresh = Some(rx);
+    }
+
+    /// Apply a finished background refresh. Returns whether the index
+    /// changed. The index is persisted at most every few minutes, and by
+    /// [`Self::save_index`].
+    pub fn poll_index_refresh(&mut self) -> Result<bool> {
+        let Some(ref rx) = self.pending_refresh else {
+            return Ok(false);
+        };
+        let update = match rx.try_recv() {
+            Ok(update) => update,
+            Err(TryRecvError::Empty) => return Ok(false),
+            Err(TryRecvError::Disconnected) => {
+                self.pending_refresh = None;
+                return Ok(false);
             }
+        };
+        self.pending_refresh = None;
+        self.content_index.apply(update);
+        if std::mem::take(&mut self.refresh_queued) {
+            self.start_index_refresh();
         }
+        if self.saved_at.elapsed() >= SAVE_INTERVAL {
+            self.save_index()?;
+        }
+        Ok(true)
+    }
+
+    /// Index what changed in one session file, without persisting.
+    pub fn refresh_index_file(&mut self, session_id: &str, path: &Path) -> Result<()> {
+        self.content_index.refresh_file(session_id, path)
+    }
 
-        results
+    /// Persist the content index if it changed.
+    pub fn save_index(&mut self) -> Result<()> {
+        self.saved_at = Instant::now();
+        self.content_index.save()
+    }
+
+    /// Fill in the sessions a query's `is:running` and `tool:` filters refer
+    /// to, and message counts the session index lacks.
+    pub fn resolve_query(&self, query: &mut SearchQuery, running_sessions: &HashSet<String>) {
+        query.resolve(
+            running_sessions,
+            |tool| self.content_index.sessions_using_tool(tool),
+            || self.content_index.message_counts(),
+        );
+    }
+
+    /// Search conversations by content: title, summary and branch, plus
+    /// message bodies and tool input/output via the content index.
+    pub fn search_content(&self, query: &str, groups: &[ConversationGroup]) -> Vec<SearchResult> {
+        let query = SearchQuery::new(query, SearchFilterType::Content);
+        self.search(&query, groups)
     }
 
     /// Search conversations by project path.
     pub fn search_project(&self, query: &str, groups: &[ConversationGroup]) -> Vec<SearchResult> {
-        let query_lower = query.to_lowercase();
-        let mut results = Vec::new();
-
-        for group in groups {
-            // Check if the group's project path matches
-            let group_matches = if let Some(project_path) = group.project_path() {
-                project_path
-                    .to_string_lossy()
-                    .to_lowercase()
-                    .contains(&query_lower)
-            } else {
-                false
-            };
-
-            if group_matches {
-                // Add all non-archived conversations from this group
-                for conv in group.conversations() {
-                    if conv.is_archived {
-                        continue;
-                    }
-                    let snippet = format!("Project: {}", conv.project_path.display());
-                    results.push(SearchResult::new(conv.clone(), snippet));
-                }
-            }
-        }
-
-        results
+        let query = SearchQuery::new(query, SearchFilterType::Project);
+        self.search(&query, groups)
     }
 
     /// Combined search with all filters.
@@ -87,69 +194,301 @@ impl SearchEngine {
             return Vec::new();
         }
 
-        match query.filter_type {
-            SearchFilterType::All => {
-                // Search both content and project, deduplicate by session_id
-                let mut results = self.search_content(&query.text, groups);
-                let project_results = self.search_project(&query.text, groups);
-
-                // Add project results that aren't already in content results
-                for result in project_results {
-                    if !results
-                        .iter()
-                        .any(|r| r.conversation.session_id == result.conversation.session_id)
-                    {
-                        results.push(result);
+        let fields = Fields {
+            content: query.filter_type != SearchFilterType::Project,
+            project: query.filter_type != SearchFilterType::Content,
+        };
+        self.rank(query, groups, fields, chrono::Utc::now().timestamp_millis())
+    }
+
+    /// Score every conversation passing the query's filters and return the
+    /// matches, best first. Each conversation appears at most once, under its
+    /// best-scoring field. Archived conversations are skipped unless the query
+    /// filters on `is:archived`.
+    fn rank(
+        &self,
+        query: &SearchQuery,
+        groups: &[ConversationGroup],
+        fields: Fields,
+        now_ms: i64,
+    ) -> Vec<SearchResult> {
+        let text = query.fuzzy_text();
+        let content_hits = if fields.content && !text.is_empty() {
+            self.content_index.first_hits(&text)
+        } else {
+            Default::default()
+        };
+        // Messages containing all of a phrase's words, by session; whether
+        // one contains the phrase itself is only checked for candidates
+        let phrase_hits: Vec<(&str, HashMap<String, Vec<ContentHit>>)> = query
+            .phrases()
+            .map(|phrase| {
+                let mut sessions: HashMap<String, Vec<ContentHit>> = HashMap::new();
+                if fields.content {
+                    for hit in self.content_index.search(phrase) {
+                        sessions
+                            .entry(hit.session_id.clone())
+                            .or_default()
+                            .push(hit);
                     }
                 }
+                (phrase, sessions)
+            })
+            .collect();
+        let include_archived = query.constrains_archived();
+
+        let mut results: Vec<(SearchResult, Option<&ContentHit>)> = groups
+            .iter()
+            .flat_map(ConversationGroup::conversations)
+            .filter(|conv| (include_archived || !conv.is_archived) && query.matches_filters(conv))
+            .filter_map(|conv| {
+                let haystack = searchable_fields(conv, fields);
+                let haystack: Vec<&str> = haystack.iter().map(String::as_str).collect();
+                if query.excludes(&haystack) {
+                    return None;
+                }
+                let has_phrases = phrase_hits.iter().all(|(phrase, sessions)| {
+                    haystack.iter().any(|f| f.contains(phrase))
+                        || sessions
+                            .get(&conv.session_id)
+                            .is_some_and(|hits| hits.iter().any(|hit| contains_phrase(hit, phrase)))
+                });
+                if !has_phrases {
+                    return None;
+                }
 
-                results
+                // A filter-only query matches everything that passes the filters
+                let best = if text.is_empty() {
+                    FieldMatch::default()
+                } else {
+                    Self::best_field(conv, &text, fields, content_hits.get(&conv.session_id))?
+                };
+                let title_ranges = if fields.content && !text.is_empty() {
+                    fuzzy_match(&text, &conv.display).map(|m| m.ranges)
+                } else {
+                    None
+                };
+                let mut result = SearchResult::new(conv.clone(), best.snippet)
+                    .with_score(best.score + recency_boost(conv.timestamp, now_ms))
+                    .with_title_ranges(title_ranges.unwrap_or_default())
+                    .with_snippet_ranges(best.snippet_ranges);
+                if let Some(message) = best.message_index {
+                    result = result.with_message(message);
+                }
+                Some((result, best.content_hit))
+            })
+            .collect();
+
+        results.sort_by(|(a, _), (b, _)| {
+            b.score
+                .cmp(&a.score)
+                .then(b.conversation.timestamp.cmp(&a.conversation.timestamp))
+        });
+        for (result, hit) in results.iter_mut().take(SNIPPET_RESULTS) {
+            if let Some(hit) = hit {
+                (result.preview_snippet, result.snippet_ranges) = Self::message_snippet(hit, &text);
             }
-            SearchFilterType::Content => self.search_content(&query.text, groups),
-            SearchFilterType::Project => self.search_project(&query.text, groups),
         }
+        results.into_iter().map(|(result, _)| result).collect()
     }
 
-    /// Create a snippet with the query highlighted (indicated by context).
-    /// Uses character indices to handle UTF-8 safely.
-    pub(crate) fn create_snippet(text: &str, query: &str) -> String {
-        let text_lower = text.to_lowercase();
-        let query_lower = query.to_lowercase();
-
-        // Find character position of match (convert byte index to char index)
-        let match_char_pos = text_lower
-            .char_indices()
-            .position(|(byte_idx, _)| text_lower[byte_idx..].starts_with(&query_lower));
-
-        if let Some(pos) = match_char_pos {
-            let char_count = text.chars().count();
-            let query_char_len = query.chars().count();
-
-            // Get context around the match (in character indices)
-            let start = pos.saturating_sub(20);
-            let end = (pos + query_char_len + 40).min(char_count);
-
-            let mut snippet = String::new();
-            if start > 0 {
-                snippet.push_str("...");
+    /// Find the highest-weighted field of `conv` matching `query`. A content
+    /// hit is only scored here; its snippet is read by the caller.
+    fn best_field<'a>(
+        conv: &Conversation,
+        query: &str,
+        fields: Fields,
+        content_hit: Option<&'a ContentHit>,
+    ) -> Option<FieldMatch<'a>> {
+        let mut best = None;
+
+        if fields.content {
+            if let Some(m) = fuzzy_match(query, &conv.display) {
+                let (snippet, _) = Self::create_snippet(&conv.display, query);
+                FieldMatch::keep_best(
+                    &mut best,
+                    FieldMatch {
+                        score: m.score * WEIGHT_TITLE,
+                        snippet,
+                        ..FieldMatch::default()
+                    },
+                );
             }
-            let slice: String = text.chars().skip(start).take(end - start).collect();
-            snippet.push_str(&slice);
-            if end < char_count {
-                snippet.push_str("...");
+
+            if let Some(summary) = conv.summary.as_deref().filter(|s| *s != conv.display) {
+                if let Some(m) = fuzzy_match(query, summary) {
+                    let (snippet, snippet_ranges) = Self::create_snippet(summary, query);
+                    FieldMatch::keep_best(
+                        &mut best,
+                        FieldMatch {
+                            score: m.score * WEIGHT_SUMMARY,
+                            snippet,
+                            snippet_ranges,
+                            message_index: None,
+                            content_hit: None,
+                        },
+                    );
+                }
             }
-            snippet
-        } else {
+
+            if !conv.tags.is_empty() {
+                let tags = format_tags(&conv.tags);
+                if let Some(m) = labeled_match(query, "Tags: ", &tags, WEIGHT_SUMMARY) {
+                    FieldMatch::keep_best(&mut best, m);
+                }
+            }
+
+            if let Some(note) = &conv.note {
+                let note = note.replace('\n', " ");
+                if let Some(m) = fuzzy_match(query, &note) {
+                    let (snippet, snippet_ranges) = Self::create_snippet(&note, query);
+                    FieldMatch::keep_best(
+                        &mut best,
+                        FieldMatch {
+                            score: m.score * WEIGHT_SUMMARY,
+                            snippet,
+                            snippet_ranges,
+                            message_index: None,
+                            content_hit: None,
+                        },
+                    );
+                }
+            }
+
+            if let Some(branch) = &conv.git_branch {
+                if let Some(m) = labeled_match(query, "Branch: ", branch, WEIGHT_BRANCH) {
+                    FieldMatch::keep_best(&mut best, m);
+                }
+            }
+
+            if let Some(hit) = content_hit {
+                FieldMatch::keep_best(
+                    &mut best,
+                    FieldMatch {
+                        score: CONTENT_HIT_SCORE,
+                        message_index: Some(hit.message_index),
+                        content_hit: Some(hit),
+                        ..FieldMatch::default()
+                    },
+                );
+            }
+        }
+
+        if fields.project {
+            let path = conv.project_path.to_string_lossy();
+            if let Some(m) = labeled_match(query, "Project: ", &path, WEIGHT_PATH) {
+                FieldMatch::keep_best(&mut best, m);
+            }
+        }
+
+        best
+    }
+
+    /// Snippet of the message `hit` points at, around its match of `query`.
+    fn message_snippet(hit: &ContentHit, query: &str) -> (String, Vec<Range<usize>>) {
+        let text = ContentIndex::message_text(hit)
+            .unwrap_or_default()
+            .replace('\n', " ");
+        let (snippet, snippet_ranges) = Self::create_snippet(&text, query);
+        if !snippet_ranges.is_empty() {
+            return (snippet, snippet_ranges);
+        }
+        // The index matches token prefixes, which fuzzy matching may not
+        let anchor = tokenize(query).next().unwrap_or_default();
+        Self::create_snippet(&text, &anchor)
+    }
+
+    /// Create a snippet around the best fuzzy match of `query` in `text`,
+    /// returning it with the matched char ranges (relative to the snippet).
+    /// Uses character indices to handle UTF-8 safely.
+    pub(crate) fn create_snippet(text: &str, query: &str) -> (String, Vec<Range<usize>>) {
+        let char_count = text.chars().count();
+
+        let Some(m) = fuzzy_match(query, text) else {
             // Truncate if no match found
-            let char_count = text.chars().count();
             if char_count > 60 {
                 let truncated: String = text.chars().take(60).collect();
-                format!("{}...", truncated)
-            } else {
-                text.to_string()
+                return (format!("{}...", truncated), Vec::new());
             }
+            return (text.to_string(), Vec::new());
+        };
+
+        // Get context around the match (in character indices)
+        let first = m.ranges.first().map_or(0, |r| r.start);
+        let last = m.ranges.last().map_or(first, |r| r.end).min(first + 60);
+        let start = first.saturating_sub(20);
+        let end = (last + 40).min(char_count);
+
+        let mut snippet = String::new();
+        if start > 0 {
+            snippet.push_str("...");
+        }
+        let offset = snippet.chars().count();
+        let slice: String = text.chars().skip(start).take(end - start).collect();
+        snippet.push_str(&slice);
+        if end < char_count {
+            snippet.push_str("...");
         }
+
+        let ranges = m
+            .ranges
+            .iter()
+            .filter(|r| r.start < end && r.end > start)
+            .map(|r| (r.start.max(start) - start + offset)..(r.end.min(end) - start + offset))
+            .collect();
+        (snippet, ranges)
+    }
+}
+
+/// Fuzzy-match a field shown as `label` + `value`, with ranges relative to that text.
+fn labeled_match(
+    query: &str,
+    label: &str,
+    value: &str,
+    weight: i64,
+) -> Option<FieldMatch<'static>> {
+    let m = fuzzy_match(query, value)?;
+    let offset = label.chars().count();
+    Some(FieldMatch {
+        score: m.score * weight,
+        snippet: format!("{}{}", label, value),
+        snippet_ranges: m
+            .ranges
+            .into_iter()
+            .map(|r| r.start + offset..r.end + offset)
+            .collect(),
+        message_index: None,
+        content_hit: None,
+    })
+}
+
+/// Whether the message `hit` points at contains `phrase` (lowercased).
+fn contains_phrase(hit: &ContentHit, phrase: &str) -> bool {
+    ContentIndex::message_text(hit).is_some_and(|text| text.to_lowercase().contains(phrase))
+}
+
+/// Lowercased text fields searched for phrases and negated terms.
+fn searchable_fields(conv: &Conversation, fields: Fields) -> Vec<String> {
+    let mut haystack = Vec::new();
+    if fields.content {
+        haystack.push(conv.display.to_lowercase());
+        haystack.extend(conv.summary.as_ref().map(|s| s.to_lowercase()));
+        if !conv.tags.is_empty() {
+            haystack.push(format_tags(&conv.tags).to_lowercase());
+        }
+        haystack.extend(conv.note.as_ref().map(|n| n.to_lowercase()));
+        haystack.extend(conv.git_branch.as_ref().map(|b| b.to_lowercase()));
     }
+    if fields.project {
+        haystack.push(conv.project_path.to_string_lossy().to_lowercase());
+    }
+    haystack
+}
+
+/// Score boost for recently modified conversations.
+fn recency_boost(timestamp_ms: i64, now_ms: i64) -> i64 {
+    let age_days = (now_ms - timestamp_ms).max(0) / MS_PER_DAY;
+    RECENCY_BOOST * (RECENCY_WINDOW_DAYS - age_days).max(0) / RECENCY_WINDOW_DAYS
 }
 
 #[cfg(test)]
@@ -164,17 +503,13 @@ mod tests {
         project_path: &str,
     ) -> Conversation {
         Conversation {
-            session_id: session_id.to_string(),
             display: display.to_string(),
             summary: summary.map(ToString::to_string),
             timestamp: 1000,
+            created: "2026-01-01T00:00:00Z".to_string(),
             modified: "2026-01-01T00:00:00Z".to_string(),
-            project_path: PathBuf::from(project_path),
-            message_count: 1,
-            git_branch: None,
-            is_plan_implementation: false,
-            is_archived: false,
-            archived_at: None,
+            message_count: Some(1),
+            ..Conversation::for_test(session_id, project_path)
         }
     }
 
@@ -188,7 +523,7 @@ mod tests {
 
     #[test]
     fn search_content_matches_display_text_case_insensitively() {
-        let engine = SearchEngine::new(PathBuf::from("/tmp"));
+        let engine = SearchEngine::new(Vec::new());
         let conv = make_conversation("s1", "Fix the Login Bug", None, "/projects/app");
         let groups = vec![make_group(vec![conv])];
 
@@ -199,7 +534,7 @@ mod tests {
 
     #[test]
     fn search_content_matches_summary_when_display_does_not_match() {
-        let engine = SearchEngine::new(PathBuf::from("/tmp"));
+        let engine = SearchEngine::new(Vec::new());
         let conv = make_conversation(
             "s1",
             "Some unrelated display text",
@@ -213,9 +548,27 @@ mod tests {
         assert_eq!(results[0].conversation.session_id, "s1");
     }
 
+    #[test]
+    fn search_content_matches_tags_and_notes() {
+        let engine = SearchEngine::new(Vec::new());
+        let mut tagged = make_conversation("s1", "Code review", None, "/projects/app");
+        tagged.tags = vec!["oauth".to_string()];
+        let mut noted = make_conversation("s2", "Code review", None, "/projects/app");
+        noted.note = Some("Follow up on\nthe flaky migration".to_string());
+        let groups = vec![make_group(vec![tagged, noted])];
+
+        let results = engine.search_content("oauth", &groups);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].preview_snippet, "Tags: #oauth");
+
+        let results = engine.search_content("flaky migration", &groups);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].conversation.session_id, "s2");
+    }
+
     #[test]
     fn search_content_skips_archived_conversations() {
-        let engine = SearchEngine::new(PathBuf::from("/tmp"));
+        let engine = SearchEngine::new(Vec::new());
         let mut conv = make_conversation("s1", "Fix login bug", None, "/projects/app");
         conv.is_archived = true;
         let groups = vec![make_group(vec![conv])];
@@ -226,7 +579,7 @@ mod tests {
 
     #[test]
     fn search_content_returns_empty_for_no_match() {
-        let engine = SearchEngine::new(PathBuf::from("/tmp"));
+        let engine = SearchEngine::new(Vec::new());
         let conv = make_conversation("s1", "Fix login bug", None, "/projects/app");
         let groups = vec![make_group(vec![conv])];
 
@@ -234,9 +587,77 @@ mod tests {
         assert!(results.is_empty());
     }
 
+    #[test]
+    fn search_content_matches_message_bodies_via_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let session_id = "d90ed21d-ed03-4e94-87d7-dbc5de6cc828";
+        let project = dir.path().join("projects").join("-projects-app");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(
+            project.join(format!("{}.jsonl", session_id)),
+            concat!(
+                r#"{"type":"user","message":{"content":"hello"}}"#,
+                "\n",
+                r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Bash","input":{"command":"kubectl rollout restart"}}]}}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let mut engine = SearchEngine::new(vec![ClaudeDir::new(dir.path())]);
+        engine.refresh_index().unwrap();
+        let conv = make_conversation(session_id, "Unrelated title", None, "/projects/app");
+        let groups = vec![make_group(vec![conv])];
+
+        let results = engine.search_content("kubectl", &groups);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].message_index, Some(1));
+        assert!(results[0].preview_snippet.contains("kubectl"));
+    }
+
+    #[test]
+    fn phrases_match_message_text_verbatim() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("projects").join("-projects-app");
+        std::fs::create_dir_all(&project).unwrap();
+        let sessions = [
+            (
+                "11111111-1111-4111-8111-111111111111",
+                "Add a Rate Limit to the API",
+            ),
+            (
+                "22222222-2222-4222-8222-222222222222",
+                "limit the rate of retries",
+            ),
+        ];
+        for (session_id, text) in sessions {
+            std::fs::write(
+                project.join(format!("{}.jsonl", session_id)),
+                format!(r#"{{"type":"user","message":{{"content":"{}"}}}}"#, text) + "\n",
+            )
+            .unwrap();
+        }
+
+        let mut engine = SearchEngine::new(vec![ClaudeDir::new(dir.path())]);
+        engine.refresh_index().unwrap();
+        let convs = sessions
+            .iter()
+            .map(|(id, _)| make_conversation(id, "Untitled", None, "/projects/app"))
+            .collect();
+        let groups = vec![make_group(convs)];
+
+        let query = SearchQuery::new(r#""rate limit""#, SearchFilterType::All);
+        let ids: Vec<_> = engine
+            .search(&query, &groups)
+            .into_iter()
+            .map(|r| r.conversation.session_id)
+            .collect();
+        assert_eq!(ids, vec![sessions[0].0]);
+    }
+
     #[test]
     fn search_project_matches_project_paths_case_insensitively() {
-        let engine = SearchEngine::new(PathBuf::from("/tmp"));
+        let engine = SearchEngine::new(Vec::new());
         let conv = make_conversation("s1", "Some task", None, "/Users/brandon/MyProject");
         let groups = vec![ConversationGroup::Directory {
             parent: "brandon".to_string(),
@@ -250,7 +671,7 @@ mod tests {
 
     #[test]
     fn search_project_skips_archived_conversations() {
-        let engine = SearchEngine::new(PathBuf::from("/tmp"));
+        let engine = SearchEngine::new(Vec::new());
         let mut conv = make_conversation("s1", "Some task", None, "/Users/brandon/MyProject");
         conv.is_archived = true;
         let groups = vec![ConversationGroup::Directory {
@@ -266,21 +687,21 @@ mod tests {
     #[test]
     fn create_snippet_extracts_context_around_match() {
         let text = "This is a long text that contains the word authentication somewhere in the middle of it all";
-        let snippet = SearchEngine::create_snippet(text, "authentication");
+        let (snippet, _) = SearchEngine::create_snippet(text, "authentication");
         assert!(snippet.contains("authentication"));
     }
 
     #[test]
     fn create_snippet_handles_utf8_text() {
         let text = "Hllo wrld with mji and special chars";
-        let snippet = SearchEngine::create_snippet(text, "mji");
+        let (snippet, _) = SearchEngine::create_snippet(text, "mji");
         assert!(snippet.contains("mji"));
     }
 
     #[test]
     fn create_snippet_adds_ellipsis_for_long_text() {
         let text = "a".repeat(100) + "target" + &"b".repeat(100);
-        let snippet = SearchEngine::create_snippet(&text, "target");
+        let (snippet, _) = SearchEngine::create_snippet(&text, "target");
         assert!(snippet.contains("..."));
         assert!(snippet.contains("target"));
     }
@@ -288,7 +709,7 @@ mod tests {
     #[test]
     fn create_snippet_truncates_when_no_match_found() {
         let text = "a".repeat(100);
-        let snippet = SearchEngine::create_snippet(&text, "xyz");
+        let (snippet, _) = SearchEngine::create_snippet(&text, "xyz");
         assert!(snippet.ends_with("..."));
         // 60 chars + "..." = 63
         assert_eq!(snippet.len(), 63);
@@ -296,13 +717,13 @@ mod tests {
 
     #[test]
     fn create_snippet_returns_full_text_when_short_and_no_match() {
-        let snippet = SearchEngine::create_snippet("short text", "xyz");
+        let (snippet, _) = SearchEngine::create_snippet("short text", "xyz");
         assert_eq!(snippet, "short text");
     }
 
     #[test]
     fn search_returns_empty_for_empty_query() {
-        let engine = SearchEngine::new(PathBuf::from("/tmp"));
+        let engine = SearchEngine::new(Vec::new());
         let conv = make_conversation("s1", "Some task", None, "/projects/app");
         let groups = vec![make_group(vec![conv])];
 
@@ -313,7 +734,7 @@ mod tests {
 
     #[test]
     fn search_with_whitespace_only_query_returns_empty() {
-        let engine = SearchEngine::new(PathBuf::from("/tmp"));
+        let engine = SearchEngine::new(Vec::new());
         let conv = make_conversation("s1", "Some task", None, "/projects/app");
         let groups = vec![make_group(vec![conv])];
 
@@ -324,7 +745,7 @@ mod tests {
 
     #[test]
     fn search_all_deduplicates_results_from_content_and_project() {
-        let engine = SearchEngine::new(PathBuf::from("/tmp"));
+        let engine = SearchEngine::new(Vec::new());
         // Display text also matches "myproject" - same as project path
         let conv = make_conversation(
             "s1",
@@ -346,7 +767,7 @@ mod tests {
 
     #[test]
     fn search_content_filter_only_searches_display_and_summary() {
-        let engine = SearchEngine::new(PathBuf::from("/tmp"));
+        let engine = SearchEngine::new(Vec::new());
         let conv = make_conversation("s1", "Fix bug", None, "/Users/brandon/myproject");
         let groups = vec![ConversationGroup::Directory {
             parent: "brandon".to_string(),
@@ -362,7 +783,7 @@ mod tests {
 
     #[test]
     fn search_project_filter_only_searches_project_paths() {
-        let engine = SearchEngine::new(PathBuf::from("/tmp"));
+        let engine = SearchEngine::new(Vec::new());
         let conv = make_conversation("s1", "Fix login bug", None, "/Users/brandon/app");
         let groups = vec![ConversationGroup::Directory {
             parent: "brandon".to_string(),
@@ -375,4 +796,143 @@ mod tests {
         // "login" is only in display, not in project path
         assert!(results.is_empty());
     }
+
+    #[test]
+    fn rank_puts_title_matches_above_weaker_fields() {
+        let engine = SearchEngine::new(Vec::new());
+        let mut branch_only = make_conversation("branch", "Tidy things up", None, "/projects/app");
+        branch_only.git_branch = Some("feat/payments".to_string());
+        let path_only = make_conversation("path", "Unrelated", None, "/projects/payments");
+        let title = make_conversation("title", "Payments retry logic", None, "/projects/app");
+        let groups = vec![make_group(vec![branch_only, path_only, title])];
+
+        let query = SearchQuery::new("payments", SearchFilterType::All);
+        let order: Vec<_> = engine
+            .search(&query, &groups)
+            .into_iter()
+            .map(|r| r.conversation.session_id)
+            .collect();
+        assert_eq!(order, vec!["title", "branch", "path"]);
+    }
+
+    #[test]
+    fn rank_boosts_recent_conversations_on_equal_matches() {
+        let engine = SearchEngine::new(Vec::new());
+        let now = 100 * MS_PER_DAY;
+        let mut old = make_conversation("old", "Fix login bug", None, "/projects/app");
+        old.timestamp = now - 60 * MS_PER_DAY;
+        let mut recent = make_conversation("recent", "Fix login bug", None, "/projects/app");
+        recent.timestamp = now - MS_PER_DAY;
+        let fields = Fields {
+            content: true,
+            project: false,
+        };
+
+        let query = SearchQuery::new("login", SearchFilterType::Content);
+        let results = engine.rank(&query, &[make_group(vec![old, recent])], fields, now);
+        assert_eq!(results[0].conversation.session_id, "recent");
+        assert!(results[0].score > results[1].score);
+    }
+
+    #[test]
+    fn results_carry_match_ranges_for_highlighting() {
+        let engine = SearchEngine::new(Vec::new());
+        let mut conv = make_conversation("s1", "Fix the Login Bug", None, "/projects/app");
+        conv.git_branch = Some("main".to_string());
+        let groups = vec![make_group(vec![conv])];
+
+        let results = engine.search_content("login", &groups);
+        assert_eq!(results[0].title_ranges, vec![8..13]);
+
+        let results = engine.search_content("main", &groups);
+        assert_eq!(results[0].preview_snippet, "Branch: main");
+        assert_eq!(results[0].snippet_ranges, vec![8..12]);
+    }
+
+    #[test]
+    fn search_content_matches_fuzzy_subsequences() {
+        let engine = SearchEngine::new(Vec::new());
+        let conv = make_conversation("s1", "Refactor authentication", None, "/projects/app");
+        let groups = vec![make_group(vec![conv])];
+
+        assert_eq!(engine.search_content("refac auth", &groups).len(), 1);
+        assert_eq!(engine.search_content("rfctr", &groups).len(), 1);
+    }
+
+    #[test]
+    fn create_snippet_reports_ranges_relative_to_snippet() {
+        let text = "a".repeat(100) + "target" + &"b".repeat(100);
+        let (snippet, ranges) = SearchEngine::create_snippet(&text, "target");
+        let highlighted: String = snippet
+            .chars()
+            .skip(ranges[0].start)
+            .take(ranges[0].len())
+            .collect();
+        assert_eq!(highlighted, "target");
+    }
+
+    #[test]
+    fn field_filters_narrow_results_and_work_without_text() {
+        let engine = SearchEngine::new(Vec::new());
+        let mut feat = make_conversation("feat", "Fix login", None, "/projects/app");
+        feat.git_branch = Some("feat/login".to_string());
+        feat.message_count = Some(50);
+        let main = make_conversation("main", "Fix login", None, "/projects/app");
+        let groups = vec![make_group(vec![feat, main])];
+
+        let query = SearchQuery::new("login branch:feat", SearchFilterType::All);
+        let results = engine.search(&query, &groups);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].conversation.session_id, "feat");
+
+        let query = SearchQuery::new("msgs:>40", SearchFilterType::All);
+        let results = engine.search(&query, &groups);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].conversation.session_id, "feat");
+    }
+
+    #[test]
+    fn is_archived_filter_searches_archived_conversations() {
+        let engine = SearchEngine::new(Vec::new());
+        let mut archived = make_conversation("old", "Fix login bug", None, "/projects/app");
+        archived.is_archived = true;
+        let active = make_conversation("new", "Fix login bug", None, "/projects/app");
+        let groups = vec![make_group(vec![archived, active])];
+
+        let query = SearchQuery::new("login is:archived", SearchFilterType::All);
+        let results = engine.search(&query, &groups);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].conversation.session_id, "old");
+    }
+
+    #[test]
+    fn phrases_and_negations_constrain_matches() {
+        let engine = SearchEngine::new(Vec::new());
+        let a = make_conversation("a", "Add rate limit to API", None, "/projects/app");
+        let b = make_conversation("b", "Limit the rate of retries", None, "/projects/app");
+        let c = make_conversation("c", "Rate limit flaky test", None, "/projects/app");
+        let groups = vec![make_group(vec![a, b, c])];
+
+        let query = SearchQuery::new(r#""rate limit" -flaky"#, SearchFilterType::All);
+        let ids: Vec<_> = engine
+            .search(&query, &groups)
+            .into_iter()
+            .map(|r| r.conversation.session_id)
+            .collect();
+        assert_eq!(ids, vec!["a"]);
+    }
+
+    #[test]
+    fn running_filter_uses_resolved_sessions() {
+        let engine = SearchEngine::new(Vec::new());
+        let a = make_conversation("a", "Fix login", None, "/projects/app");
+        let b = make_conversation("b", "Fix login", None, "/projects/app");
+        let groups = vec![make_group(vec![a, b])];
+
+        let mut query = SearchQuery::new("is:running", SearchFilterType::All);
+        engine.resolve_query(&mut query, &HashSet::from(["b".to_string()]));
+        let results = engine.search(&query, &groups);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].conversation.session_id, "b");
+    }
 }
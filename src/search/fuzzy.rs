@@ -0,0 +1,196 @@
+//! Fuzzy matching with scores and matched character ranges for highlighting.
+//!
+//! Each whitespace-separated query word must match as a case-insensitive
+//! subsequence of the text. Contiguous runs, word starts and early matches
+//! score higher; exact substrings get an extra bonus.
+
+use std::ops::Range;
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_CONSECUTIVE: i64 = 24;
+const BONUS_BOUNDARY: i64 = 20;
+const BONUS_START: i64 = 25;
+const BONUS_EXACT: i64 = 50;
+/// Penalty per skipped character (between matches, and before the first one)
+const PENALTY_GAP: i64 = 1;
+/// Leading characters beyond this aren't penalized further
+const MAX_LEADING_PENALTY: i64 = 15;
+
+/// A successful fuzzy match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher is better
+    pub score: i64,
+    /// Matched character ranges (char indices, ascending, non-overlapping)
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// Fuzzy-match `pattern` against `text`. Returns None unless every word matches.
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<FuzzyMatch> {
+    let words: Vec<Vec<char>> = pattern
+        .split_whitespace()
+        .map(|w| w.chars().map(fold_case).collect())
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+
+    let original: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = original.iter().copied().map(fold_case).collect();
+
+    let mut score = 0;
+    let mut positions = Vec::new();
+    for word in &words {
+        let (word_score, word_positions) = match_word(word, &lower, &original)?;
+        score += word_score;
+        positions.extend(word_positions);
+    }
+    positions.sort_unstable();
+    positions.dedup();
+
+    Some(FuzzyMatch {
+        score,
+        ranges: to_ranges(&positions),
+    })
+}
+
+/// Lowercase a char while keeping a 1:1 mapping of char indices.
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn match_word(word: &[char], lower: &[char], original: &[char]) -> Option<(i64, Vec<usize>)> {
+    if word.is_empty() || word.len() > lower.len() {
+        return None;
+    }
+
+    // Exact substring: always the best placement for this word
+    if let Some(start) = lower.windows(word.len()).position(|w| w == word) {
+        let positions: Vec<usize> = (start..start + word.len()).collect();
+        return Some((
+            score_positions(&positions, original) + BONUS_EXACT,
+            positions,
+        ));
+    }
+
+    // Reject matches scattered across the whole text
+    let max_span = (word.len() * 3).max(word.len() + 8);
+    let mut best: Option<(i64, Vec<usize>)> = None;
+
+    for start in (0..lower.len()).filter(|&i| lower[i] == word[0]) {
+        let mut positions = vec![start];
+        let mut ti = start + 1;
+        for &c in &word[1..] {
+            while ti < lower.len() && lower[ti] != c {
+                ti += 1;
+            }
+            if ti == lower.len() {
+                break;
+            }
+            positions.push(ti);
+            ti += 1;
+        }
+        if positions.len() < word.len() {
+            // Later starts see a suffix of the text, so they can't match either
+            break;
+        }
+        if positions[positions.len() - 1] - start + 1 > max_span {
+            continue;
+        }
+        let score = score_positions(&positions, original);
+        if best.as_ref().is_none_or(|(b, _)| score > *b) {
+            best = Some((score, positions));
+        }
+    }
+
+    best
+}
+
+fn score_positions(positions: &[usize], text: &[char]) -> i64 {
+    let mut score = 0;
+    for (i, &pos) in positions.iter().enumerate() {
+        score += SCORE_MATCH;
+        if i > 0 {
+            let prev = positions[i - 1];
+            if pos == prev + 1 {
+                score += BONUS_CONSECUTIVE;
+            } else {
+                score -= PENALTY_GAP * (pos - prev - 1) as i64;
+            }
+        }
+        if is_word_start(text, pos) {
+            score += BONUS_BOUNDARY;
+        }
+    }
+    match positions.first() {
+        Some(0) => score + BONUS_START,
+        Some(&first) => score - (first as i64).min(MAX_LEADING_PENALTY) * PENALTY_GAP,
+        None => score,
+    }
+}
+
+/// Start of the text, after a separator, or a camelCase hump.
+fn is_word_start(text: &[char], pos: usize) -> bool {
+    if pos == 0 {
+        return true;
+    }
+    let (prev, cur) = (text[pos - 1], text[pos]);
+    !prev.is_alphanumeric() || (prev.is_lowercase() && cur.is_uppercase())
+}
+
+/// Merge sorted positions into contiguous ranges.
+fn to_ranges(positions: &[usize]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for &pos in positions {
+        match ranges.last_mut() {
+            Some(last) if last.end == pos => last.end = pos + 1,
+            _ => ranges.push(pos..pos + 1),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_substring_matches_with_single_range() {
+        let m = fuzzy_match("login", "Fix the Login bug").unwrap();
+        assert_eq!(m.ranges, vec![8..13]);
+    }
+
+    #[test]
+    fn subsequence_matches_and_reports_each_run() {
+        let m = fuzzy_match("fxlg", "fix login").unwrap();
+        assert_eq!(m.ranges, vec![0..1, 2..3, 4..5, 6..7]);
+    }
+
+    #[test]
+    fn every_word_must_match_in_any_order() {
+        assert!(fuzzy_match("bug login", "Fix the Login bug").is_some());
+        assert!(fuzzy_match("bug deploy", "Fix the Login bug").is_none());
+    }
+
+    #[test]
+    fn word_starts_and_contiguity_score_higher() {
+        let contiguous = fuzzy_match("auth", "auth module").unwrap();
+        let scattered = fuzzy_match("auth", "a util that helps").unwrap();
+        assert!(contiguous.score > scattered.score);
+
+        let boundary = fuzzy_match("ob", "two_bees").unwrap();
+        let inner = fuzzy_match("ob", "twoxbees").unwrap();
+        assert!(boundary.score > inner.score);
+    }
+
+    #[test]
+    fn widely_scattered_letters_do_not_match() {
+        assert!(fuzzy_match("login", "/Users/brandon/app/lib/big/io/internal").is_none());
+    }
+
+    #[test]
+    fn ranges_use_char_indices_for_utf8_text() {
+        let m = fuzzy_match("wörld", "héllo wörld").unwrap();
+        assert_eq!(m.ranges, vec![6..11]);
+    }
+}
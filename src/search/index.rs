@@ -0,0 +1,867 @@
+//! Persistent inverted index over conversation contents.
+//!
+//! Every user/assistant message in every session JSONL is tokenized and
+//! recorded as `token -> (file, message)` postings. The index lives in the
+//! cache dir and is refreshed incrementally: unchanged files (same mtime and
+//! size) are skipped, grown files only have their new tail indexed, and
+//! shrunk or deleted files are dropped and re-indexed. Reading the changes
+//! ([`IndexScan`]) is separate from applying them, so a refresh can run on a
+//! background thread.
+//!
+//! Alongside the postings, each file records the tools it invoked and the
+//! files its Edit/Write/MultiEdit/NotebookEdit/Read calls touched, so
+//! conversations can be looked up by tool or by file.
+
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::claude::data_dir::ClaudeDir;
+use crate::claude::sessions::discover_session_files;
+use crate::claude::transcript::{parse_line, TranscriptBlock};
+
+/// Bump when the on-disk format or tokenization changes (forces a rebuild).
+const INDEX_VERSION: u32 = 4;
+/// Shorter tokens are too common to be useful and make prefix scans huge.
+const MIN_TOKEN_LEN: usize = 2;
+/// Longer tokens are almost always hashes or base64 noise.
+const MAX_TOKEN_LEN: usize = 40;
+/// Tools whose file input modifies the file.
+const MODIFYING_TOOLS: &[&str] = &["Edit", "Write", "MultiEdit", "NotebookEdit"];
+/// Tools whose file input only reads the file.
+const READING_TOOLS: &[&str] = &["Read"];
+
+/// How a conversation touched a file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileTouch {
+    /// Edited or written, not only read
+    pub modified: bool,
+    /// Number of tool calls on the file
+    pub count: u32,
+    /// Time of the latest touch (ms, 0 if unknown); modifications take
+    /// precedence over later reads
+    pub last_at: i64,
+    /// Message index of the touch `last_at` refers to
+    pub message_index: u32,
+}
+
+impl FileTouch {
+    fn record(&mut self, modifies: bool, at: i64, message_index: u32) {
+        self.count += 1;
+        if modifies || !self.modified {
+            self.modified |= modifies;
+            self.last_at = at;
+            self.message_index = message_index;
+        }
+    }
+}
+
+/// A conversation that touched a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileToucher {
+    pub session_id: String,
+    pub touch: FileTouch,
+}
+
+/// Index bookkeeping for one JSONL file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedFile {
+    session_id: String,
+    path: PathBuf,
+    /// File mtime (ms) when last indexed
+    mtime: i64,
+    /// File size when last indexed
+    size: u64,
+    /// Bytes indexed so far (always ends at a line boundary)
+    indexed_bytes: u64,
+    /// Byte offset of each indexed message's line, by message index
+    offsets: Vec<u64>,
+    /// Names of tools invoked in the indexed messages
+    #[serde(default)]
+    tools: BTreeSet<String>,
+    /// Files touched by Edit/Write/MultiEdit/Read calls, by path
+    #[serde(default)]
+    files: BTreeMap<String, FileTouch>,
+}
+
+impl IndexedFile {
+    fn progress(&self) -> Progress {
+        Progress {
+            mtime: self.mtime,
+            size: self.size,
+            indexed_bytes: self.indexed_bytes,
+            messages: self.offsets.len(),
+        }
+    }
+}
+
+/// How far a file has been indexed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Progress {
+    mtime: i64,
+    size: u64,
+    indexed_bytes: u64,
+    messages: usize,
+}
+
+/// What was read from one session file since it was last indexed.
+#[derive(Debug)]
+struct FileUpdate {
+    session_id: String,
+    path: PathBuf,
+    mtime: i64,
+    size: u64,
+    /// Progress the update continues from (None = index from scratch)
+    from: Option<Progress>,
+    /// Bytes indexed once the update is applied
+    indexed_bytes: u64,
+    offsets: Vec<u64>,
+    tools: BTreeSet<String>,
+    /// (file path, modifies, time, message index) of each file tool call
+    touched: Vec<(String, bool, i64, u32)>,
+    /// (token, message index) postings of the new messages
+    postings: Vec<(String, u32)>,
+}
+
+/// Snapshot of the index's progress, from [`ContentIndex::scan`]. Reading the
+/// changes doesn't need the index, so it can run on a background thread.
+#[derive(Debug)]
+pub struct IndexScan {
+    progress: HashMap<PathBuf, Progress>,
+}
+
+impl IndexScan {
+    /// Read what changed in the session files under the `projects`
+    /// directories of `claude_dirs` since the snapshot.
+    pub fn run(self, claude_dirs: &[ClaudeDir]) -> IndexUpdate {
+        let mut found: Vec<(String, PathBuf)> = Vec::new();
+        for claude_dir in claude_dirs {
+            let Ok(entries) = fs::read_dir(claude_dir.projects_dir()) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let project_dir = entry.path();
+                if !project_dir.is_dir() {
+                    continue;
+                }
+                for (session_id, file) in discover_session_files(&project_dir) {
+                    found.push((session_id, file.path));
+                }
+            }
+        }
+
+        let present: HashSet<&PathBuf> = found.iter().map(|(_, path)| path).collect();
+        let removed = self
+            .progress
+            .keys()
+            .filter(|path| !present.contains(path))
+            .cloned()
+            .collect();
+        // A single unreadable file shouldn't block the rest of the index
+        let files = found
+            .iter()
+            .filter_map(|(session_id, path)| {
+                read_changes(session_id, path, self.progress.get(path))
+                    .ok()
+                    .flatten()
+            })
+            .collect();
+        IndexUpdate { removed, files }
+    }
+}
+
+/// Changes found by an [`IndexScan`], applied with [`ContentIndex::apply`].
+#[derive(Debug)]
+pub struct IndexUpdate {
+    /// Indexed files that no longer exist
+    removed: Vec<PathBuf>,
+    files: Vec<FileUpdate>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IndexData {
+    version: u32,
+    next_id: u32,
+    files: BTreeMap<u32, IndexedFile>,
+    /// Token -> (file id, message index) postings
+    postings: BTreeMap<String, Vec<(u32, u32)>>,
+}
+
+impl Default for IndexData {
+    fn default() -> Self {
+        Self {
+            version: INDEX_VERSION,
+            next_id: 0,
+            files: BTreeMap::new(),
+            postings: BTreeMap::new(),
+        }
+    }
+}
+
+/// A message matching a content query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentHit {
+    pub session_id: String,
+    /// Index of the message in the transcript (see `parse_transcript`)
+    pub message_index: usize,
+    path: PathBuf,
+    offset: u64,
+}
+
+/// Inverted index over all conversation contents.
+#[derive(Default)]
+pub struct ContentIndex {
+    /// Where the index is persisted (None = in-memory only)
+    path: Option<PathBuf>,
+    data: IndexData,
+    /// File id by session file path
+    by_path: HashMap<PathBuf, u32>,
+    /// Tokens with postings for each file id, so a file is dropped without
+    /// scanning every posting list
+    terms: HashMap<u32, HashSet<String>>,
+    dirty: bool,
+}
+
+impl ContentIndex {
+    /// Create an empty in-memory index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the index from `path`, starting empty if it is missing, corrupt or
+    /// from an older format.
+    pub fn load(path: &Path) -> Self {
+        let data = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<IndexData>(&content).ok())
+            .filter(|data| data.version == INDEX_VERSION)
+            .unwrap_or_default();
+
+        let by_path = data
+            .files
+            .iter()
+            .map(|(id, file)| (file.path.clone(), *id))
+            .collect();
+        let mut terms: HashMap<u32, HashSet<String>> = HashMap::new();
+        for (token, postings) in &data.postings {
+            for (id, _) in postings {
+                terms.entry(*id).or_default().insert(token.clone());
+            }
+        }
+
+        Self {
+            path: Some(path.to_path_buf()),
+            data,
+            by_path,
+            terms,
+            dirty: false,
+        }
+    }
+
+    /// Default index location: `<cache dir>/claudatui/content-index.json`.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("claudatui").join("content-index.json"))
+    }
+
+    /// Number of indexed files.
+    pub fn file_count(&self) -> usize {
+        self.data.files.len()
+    }
+
+    /// Bring the index up to date with all session files under the
+    /// `projects` directories of `claude_dirs`.
+    pub fn refresh(&mut self, claude_dirs: &[ClaudeDir]) {
+        let update = self.scan().run(claude_dirs);
+        self.apply(update);
+    }
+
+    /// Snapshot of how far each file has been indexed, to read the changes
+    /// with [`IndexScan::run`] without holding the index.
+    pub fn scan(&self) -> IndexScan {
+        IndexScan {
+            progress: self
+                .data
+                .files
+                .values()
+                .map(|file| (file.path.clone(), file.progress()))
+                .collect(),
+        }
+    }
+
+    /// Apply the changes found by a scan. Files indexed further since the
+    /// scan's snapshot are left alone; the next scan picks them up.
+    pub fn apply(&mut self, update: IndexUpdate) {
+        for path in &update.removed {
+            if let Some(&id) = self.by_path.get(path) {
+                self.remove_file(id);
+            }
+        }
+        for file in update.files {
+            self.apply_file(file);
+        }
+    }
+
+    /// Index what changed in a single session file.
+    pub fn refresh_file(&mut self, session_id: &str, path: &Path) -> Result<()> {
+        let progress = self
+            .by_path
+            .get(path)
+            .and_then(|id| self.data.files.get(id))
+            .map(IndexedFile::progress);
+        if let Some(update) = read_changes(session_id, path, progress.as_ref())? {
+            self.apply_file(update);
+        }
+        Ok(())
+    }
+
+    fn apply_file(&mut self, update: FileUpdate) {
+        let existing = self.by_path.get(&update.path).copied();
+        let id = match (existing, update.from) {
+            (Some(id), Some(from)) => {
+                if self.data.files.get(&id).map(IndexedFile::progress) != Some(from) {
+                    return;
+                }
+                id
+            }
+            // Removed since the scan
+            (None, Some(_)) => return,
+            (existing, None) => {
+                if let Some(id) = existing {
+                    self.remove_file(id);
+                }
+                let id = self.data.next_id;
+                self.data.next_id += 1;
+                self.data.files.insert(
+                    id,
+                    IndexedFile {
+                        session_id: update.session_id,
+                        path: update.path.clone(),
+                        mtime: update.mtime,
+                        size: update.size,
+                        indexed_bytes: 0,
+                        offsets: Vec::new(),
+                        tools: BTreeSet::new(),
+                        files: BTreeMap::new(),
+                    },
+                );
+                self.by_path.insert(update.path, id);
+                id
+            }
+        };
+
+        let terms = self.terms.entry(id).or_default();
+        for (token, message) in update.postings {
+            self.data
+                .postings
+                .entry(token.clone())
+                .or_default()
+                .push((id, message));
+            terms.insert(token);
+        }
+        if let Some(file) = self.data.files.get_mut(&id) {
+            file.offsets.extend(update.offsets);
+            file.tools.extend(update.tools);
+            for (path, modifies, at, message) in update.touched {
+                file.files
+                    .entry(path)
+                    .or_default()
+                    .record(modifies, at, message);
+            }
+            file.indexed_bytes = update.indexed_bytes;
+            file.mtime = update.mtime;
+            file.size = update.size;
+        }
+        self.dirty = true;
+    }
+
+    fn remove_file(&mut self, id: u32) {
+        let Some(file) = self.data.files.remove(&id) else {
+            return;
+        };
+        self.by_path.remove(&file.path);
+        for token in self.terms.remove(&id).unwrap_or_default() {
+            if let Some(postings) = self.data.postings.get_mut(&token) {
+                postings.retain(|(file, _)| *file != id);
+                if postings.is_empty() {
+                    self.data.postings.remove(&token);
+                }
+            }
+        }
+        self.dirty = true;
+    }
+
+    /// Find messages containing every query token (each token matches as a prefix).
+    ///
+    /// Hits are ordered by file, then by message index.
+    pub fn search(&self, query: &str) -> Vec<ContentHit> {
+        let tokens: BTreeSet<String> = tokenize(query).collect();
+        if tokens.is_empty() {
+            return Vec::new();
+        }
+
+        let mut matches: Option<BTreeSet<(u32, u32)>> = None;
+        for token in &tokens {
+            let postings: BTreeSet<(u32, u32)> = self
+                .data
+                .postings
+                .range(token.clone()..)
+                .take_while(|(key, _)| key.starts_with(token.as_str()))
+                .flat_map(|(_, postings)| postings.iter().copied())
+                .collect();
+            let next = match matches {
+                None => postings,
+                Some(prev) => prev.intersection(&postings).copied().collect(),
+            };
+            if next.is_empty() {
+                return Vec::new();
+            }
+            matches = Some(next);
+        }
+
+        matches
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(id, message)| {
+                let file = self.data.files.get(&id)?;
+                Some(ContentHit {
+                    session_id: file.session_id.clone(),
+                    message_index: message as usize,
+                    path: file.path.clone(),
+                    offset: *file.offsets.get(message as usize)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Session IDs of conversations that invoked `tool` (case-insensitive).
+    pub fn sessions_using_tool(&self, tool: &str) -> HashSet<String> {
+        self.data
+            .files
+            .values()
+            .filter(|file| file.tools.iter().any(|t| t.eq_ignore_ascii_case(tool)))
+            .map(|file| file.session_id.clone())
+            .collect()
+    }
+
+    /// Number of indexed messages of a session, if it is indexed.
+    pub fn message_count(&self, session_id: &str) -> Option<u32> {
+        self.data
+            .files
+            .values()
+            .filter(|file| file.session_id == session_id)
+            .map(|file| file.offsets.len() as u32)
+            .reduce(|a, b| a + b)
+    }
+
+    /// Number of indexed messages per session ID.
+    pub fn message_counts(&self) -> HashMap<String, u32> {
+        let mut counts = HashMap::new();
+        for file in self.data.files.values() {
+            *counts.entry(file.session_id.clone()).or_default() += file.offsets.len() as u32;
+        }
+        counts
+    }
+
+    /// Reverse index from file path to the conversations that touched it,
+    /// most recently touched first.
+    pub fn file_touches(&self) -> BTreeMap<String, Vec<FileToucher>> {
+        let mut touches: BTreeMap<String, Vec<FileToucher>> = BTreeMap::new();
+        for file in self.data.files.values() {
+            for (path, touch) in &file.files {
+                touches.entry(path.clone()).or_default().push(FileToucher {
+                    session_id: file.session_id.clone(),
+                    touch: *touch,
+                });
+            }
+        }
+        for touchers in touches.values_mut() {
+            touchers.sort_by_key(|t| std::cmp::Reverse(t.touch.last_at));
+        }
+        touches
+    }
+
+    /// Files a conversation touched, modified ones first, then most recent.
+    pub fn session_files(&self, session_id: &str) -> Vec<(String, FileTouch)> {
+        let mut files: Vec<(String, FileTouch)> = self
+            .data
+            .files
+            .values()
+            .filter(|file| file.session_id == session_id)
+            .flat_map(|file| {
+                file.files
+                    .iter()
+                    .map(|(path, touch)| (path.clone(), *touch))
+            })
+            .collect();
+        files.sort_by_key(|(_, t)| (std::cmp::Reverse(t.modified), std::cmp::Reverse(t.last_at)));
+        files
+    }
+
+    /// First hit per session, keyed by session ID.
+    pub fn first_hits(&self, query: &str) -> HashMap<String, ContentHit> {
+        let mut first = HashMap::new();
+        for hit in self.search(query) {
+            first.entry(hit.session_id.clone()).or_insert(hit);
+        }
+        first
+    }
+
+    /// Read the full text of a hit's message back from its JSONL file.
+    pub fn message_text(hit: &ContentHit) -> Option<String> {
+        let mut file = File::open(&hit.path).ok()?;
+        file.seek(SeekFrom::Start(hit.offset)).ok()?;
+        let mut line = String::new();
+        BufReader::new(file).read_line(&mut line).ok()?;
+        parse_line(line.trim_end()).map(|m| m.text())
+    }
+
+    /// Persist the index if it changed since it was loaded.
+    pub fn save(&mut self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        if !self.dirty {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create cache directory: {:?}", parent))?;
+        }
+
+        let json = serde_json::to_string(&self.data).context("Failed to serialize index")?;
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, json)
+            .with_context(|| format!("Failed to write temp index file: {:?}", temp_path))?;
+        fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to rename index file: {:?}", path))?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// Read the complete lines a session file gained since `progress`, or all of
+/// it when it's new or shrunk. `None` when the file is unchanged.
+fn read_changes(
+    session_id: &str,
+    path: &Path,
+    progress: Option<&Progress>,
+) -> Result<Option<FileUpdate>> {
+    let meta = fs::metadata(path)?;
+    let size = meta.len();
+    let mtime = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_millis() as i64);
+    if progress.is_some_and(|p| p.mtime == mtime && p.size == size) {
+        return Ok(None);
+    }
+    // Session files are append-only; a grown file only needs its tail indexed
+    let from = progress.copied().filter(|p| size >= p.indexed_bytes);
+    let (mut offset, mut message_index) = from.map_or((0, 0), |p| (p.indexed_bytes, p.messages));
+
+    let mut handle = File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
+    handle.seek(SeekFrom::Start(offset))?;
+    let mut reader = BufReader::new(handle);
+
+    let mut update = FileUpdate {
+        session_id: session_id.to_string(),
+        path: path.to_path_buf(),
+        mtime,
+        size,
+        from,
+        indexed_bytes: offset,
+        offsets: Vec::new(),
+        tools: BTreeSet::new(),
+        touched: Vec::new(),
+        postings: Vec::new(),
+    };
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        let n = reader.read_until(b'\n', &mut buf)?;
+        // Stop at EOF or a partially written last line
+        if n == 0 || buf.last() != Some(&b'\n') {
+            break;
+        }
+        let line_offset = offset;
+        offset += n as u64;
+
+        let line = String::from_utf8_lossy(&buf);
+        let Some(message) = parse_line(line.trim_end()) else {
+            continue;
+        };
+        let at = message
+            .timestamp
+            .as_deref()
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+            .map_or(0, |dt| dt.timestamp_millis());
+        for block in &message.blocks {
+            let TranscriptBlock::ToolUse { name, input, .. } = block else {
+                continue;
+            };
+            update.tools.insert(name.clone());
+            let modifies = MODIFYING_TOOLS.contains(&name.as_str());
+            if !modifies && !READING_TOOLS.contains(&name.as_str()) {
+                continue;
+            }
+            if let Some(touched) = touched_path(input) {
+                update
+                    .touched
+                    .push((touched, modifies, at, message_index as u32));
+            }
+        }
+        let tokens: HashSet<String> = tokenize(&message.text()).collect();
+        update
+            .postings
+            .extend(tokens.into_iter().map(|t| (t, message_index as u32)));
+        update.offsets.push(line_offset);
+        message_index += 1;
+    }
+    update.indexed_bytes = offset;
+    Ok(Some(update))
+}
+
+/// The file a file tool call works on: its `file_path` input, or
+/// `notebook_path` for notebook edits.
+fn touched_path(input: &str) -> Option<String> {
+    let input: Value = serde_json::from_str(input).ok()?;
+    ["file_path", "notebook_path"]
+        .into_iter()
+        .find_map(|key| input.get(key)?.as_str())
+        .filter(|path| !path.is_empty())
+        .map(str::to_string)
+}
+
+/// Split text into lowercase word tokens for indexing and querying.
+pub(crate) fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|t| (MIN_TOKEN_LEN..=MAX_TOKEN_LEN).contains(&t.chars().count()))
+        .map(str::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const SESSION: &str = "d90ed21d-ed03-4e94-87d7-dbc5de6cc828";
+
+    fn user_line(text: &str) -> String {
+        format!(
+            "{{\"type\":\"user\",\"message\":{{\"content\":{}}}}}\n",
+            serde_json::to_string(text).unwrap()
+        )
+    }
+
+    fn setup() -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("projects").join("-tmp-app");
+        fs::create_dir_all(&project).unwrap();
+        let file = project.join(format!("{}.jsonl", SESSION));
+        fs::write(
+            &file,
+            user_line("fix the login flow") + &user_line("now add Authentication tests"),
+        )
+        .unwrap();
+        (dir, file)
+    }
+
+    fn append(path: &Path, content: &str) {
+        let mut file = fs::OpenOptions::new().append(true).open(path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn tokenize_lowercases_and_drops_short_tokens() {
+        let tokens: Vec<String> = tokenize("Fix a Login_flow bug!").collect();
+        assert_eq!(tokens, vec!["fix", "login_flow", "bug"]);
+    }
+
+    #[test]
+    fn search_finds_message_by_prefix_and_requires_all_tokens() {
+        let (dir, _) = setup();
+        let mut index = ContentIndex::new();
+        index.refresh(&[ClaudeDir::new(dir.path())]);
+
+        let hits = index.search("authent");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].session_id, SESSION);
+        assert_eq!(hits[0].message_index, 1);
+        assert!(ContentIndex::message_text(&hits[0])
+            .unwrap()
+            .contains("Authentication"));
+
+        assert!(index.search("login tests").is_empty());
+        assert_eq!(index.search("login flow").len(), 1);
+    }
+
+    #[test]
+    fn sessions_using_tool_matches_tool_names_case_insensitively() {
+        let (dir, file) = setup();
+        append(
+            &file,
+            "{\"type\":\"assistant\",\"message\":{\"content\":[{\"type\":\"tool_use\",\"name\":\"Bash\",\"input\":{\"command\":\"ls\"}}]}}\n",
+        );
+        let mut index = ContentIndex::new();
+        index.refresh(&[ClaudeDir::new(dir.path())]);
+
+        assert!(index.sessions_using_tool("bash").contains(SESSION));
+        assert!(index.sessions_using_tool("Edit").is_empty());
+    }
+
+    #[test]
+    fn file_touches_map_files_to_conversations() {
+        let (dir, file) = setup();
+        let tool_use = |name: &str, path: &str, ts: &str| {
+            format!(
+                "{{\"type\":\"assistant\",\"timestamp\":\"{}\",\"message\":{{\"content\":[{{\"type\":\"tool_use\",\"name\":\"{}\",\"input\":{{\"file_path\":\"{}\"}}}}]}}}}\n",
+                ts, name, path
+            )
+        };
+        append(
+            &file,
+            &tool_use("Edit", "/tmp/app/src/main.rs", "2026-09-01T10:00:00Z"),
+        );
+        append(
+            &file,
+            &tool_use("Read", "/tmp/app/src/main.rs", "2026-09-01T11:00:00Z"),
+        );
+        append(
+            &file,
+            &tool_use("Read", "/tmp/app/README.md", "2026-09-01T12:00:00Z"),
+        );
+        append(
+            &file,
+            &tool_use("Bash", "/tmp/app/ignored", "2026-09-01T12:00:00Z"),
+        );
+        // Notebook edits name their file `notebook_path`
+        append(
+            &file,
+            "{\"type\":\"assistant\",\"timestamp\":\"2026-09-01T13:00:00Z\",\"message\":{\"content\":[{\"type\":\"tool_use\",\"name\":\"NotebookEdit\",\"input\":{\"notebook_path\":\"/tmp/app/plot.ipynb\",\"new_source\":\"x = 1\"}}]}}\n",
+        );
+        let mut index = ContentIndex::new();
+        index.refresh(&[ClaudeDir::new(dir.path())]);
+
+        let touches = index.file_touches();
+        assert_eq!(
+            touches.keys().collect::<Vec<_>>(),
+            vec![
+                "/tmp/app/README.md",
+                "/tmp/app/plot.ipynb",
+                "/tmp/app/src/main.rs"
+            ]
+        );
+        assert!(touches["/tmp/app/plot.ipynb"][0].touch.modified);
+        let main = &touches["/tmp/app/src/main.rs"][0];
+        assert_eq!(main.session_id, SESSION);
+        assert!(main.touch.modified);
+        assert_eq!(main.touch.count, 2);
+        // The edit, not the later read, is what a jump should land on
+        assert_eq!(main.touch.message_index, 2);
+        assert!(!touches["/tmp/app/README.md"][0].touch.modified);
+
+        // Per conversation, modified files come before later reads
+        let files: Vec<String> = index
+            .session_files(SESSION)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                "/tmp/app/plot.ipynb",
+                "/tmp/app/src/main.rs",
+                "/tmp/app/README.md"
+            ]
+        );
+        assert!(index.session_files("other").is_empty());
+    }
+
+    #[test]
+    fn refresh_indexes_only_appended_tail_and_skips_partial_lines() {
+        let (dir, file) = setup();
+        let mut index = ContentIndex::new();
+        index.refresh(&[ClaudeDir::new(dir.path())]);
+
+        append(&file, &user_line("deploy to staging"));
+        append(&file, "{\"type\":\"user\",\"message\":{\"content\":\"half");
+        index.refresh(&[ClaudeDir::new(dir.path())]);
+
+        let hits = index.search("staging");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].message_index, 2);
+        assert!(index.search("half").is_empty());
+
+        // Completing the partial line indexes it on the next refresh
+        append(&file, " written\"}}\n");
+        index.refresh(&[ClaudeDir::new(dir.path())]);
+        assert_eq!(index.search("written")[0].message_index, 3);
+    }
+
+    #[test]
+    fn refresh_reindexes_shrunk_files_and_drops_deleted_ones() {
+        let (dir, file) = setup();
+        let mut index = ContentIndex::new();
+        index.refresh(&[ClaudeDir::new(dir.path())]);
+
+        fs::write(&file, user_line("rewritten")).unwrap();
+        index.refresh(&[ClaudeDir::new(dir.path())]);
+        assert!(index.search("login").is_empty());
+        assert_eq!(index.search("rewritten")[0].message_index, 0);
+
+        fs::remove_file(&file).unwrap();
+        index.refresh(&[ClaudeDir::new(dir.path())]);
+        assert_eq!(index.file_count(), 0);
+        assert!(index.search("rewritten").is_empty());
+    }
+
+    #[test]
+    fn apply_skips_scans_taken_before_the_file_was_indexed_further() {
+        let (dir, file) = setup();
+        let dirs = [ClaudeDir::new(dir.path())];
+        let mut index = ContentIndex::new();
+        index.refresh(&dirs);
+
+        append(&file, &user_line("deploy to staging"));
+        let update = index.scan().run(&dirs);
+        // The file is indexed on its own while the scan is in flight
+        index.refresh_file(SESSION, &file).unwrap();
+        index.apply(update);
+        assert_eq!(index.search("staging").len(), 1);
+
+        fs::remove_file(&file).unwrap();
+        let update = index.scan().run(&dirs);
+        index.apply(update);
+        assert_eq!(index.file_count(), 0);
+        assert!(index.data.postings.is_empty());
+    }
+
+    #[test]
+    fn save_and_load_roundtrip() {
+        let (dir, file) = setup();
+        let index_path = dir.path().join("cache").join("index.json");
+        let mut index = ContentIndex::load(&index_path);
+        index.refresh(&[ClaudeDir::new(dir.path())]);
+        index.save().unwrap();
+
+        let mut loaded = ContentIndex::load(&index_path);
+        assert_eq!(loaded.file_count(), 1);
+        assert_eq!(loaded.search("login").len(), 1);
+
+        // The path and token lookups are rebuilt, so the file is updated in place
+        append(&file, &user_line("deploy to staging"));
+        loaded.refresh(&[ClaudeDir::new(dir.path())]);
+        assert_eq!(loaded.file_count(), 1);
+        assert_eq!(loaded.search("staging")[0].message_index, 2);
+        fs::remove_file(&file).unwrap();
+        loaded.refresh(&[ClaudeDir::new(dir.path())]);
+        assert!(loaded.data.postings.is_empty());
+    }
+}
@@ -1,7 +1,11 @@
 //! Search functionality for finding conversations.
 
 pub mod engine;
+pub mod fuzzy;
+pub mod index;
+pub mod query;
 pub mod types;
 
 pub use engine::SearchEngine;
+pub use index::ContentIndex;
 pub use types::{SearchFilterType, SearchQuery, SearchResult};
@@ -0,0 +1,425 @@
+//! Search query language: free text plus field filters.
+//!
+//! Tokens are whitespace-separated and may appear in any order:
+//!
+//! - `word`: fuzzy-matched against title, summary, tags, note, branch, path and contents
+//! - `"exact phrase"`: must appear verbatim
+//! - `-word`, `-"phrase"`, `-key:value`: negation
+//! - `branch:feat/x`, `project:api`: substring of the git branch / project path
+//! - `after:2026-09-01`, `before:2026-10-01`: last modified on/after, or before, a date
+//! - `msgs:>40` (also `<`, `>=`, `<=`, `=` or a bare number): message count
+//! - `is:archived`, `is:running`, `is:attention` (last turn interrupted or failed)
+//! - `tool:Bash`: conversations that invoked a tool
+//! - `tag:review` (or `tag:#review`): conversations carrying a tag
+//!
+//! Values may be quoted (`project:"my app"`). Malformed filters such as
+//! `after:someday` are treated as plain text.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Local, NaiveDate, TimeZone};
+
+use crate::claude::conversation::Conversation;
+
+/// A free-text word or quoted phrase (lowercased).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub text: String,
+    /// Quoted: must match verbatim rather than fuzzily
+    pub phrase: bool,
+    pub negated: bool,
+}
+
+/// Comparison operator for numeric filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Eq,
+    Ge,
+    Gt,
+}
+
+impl Comparison {
+    fn holds(self, value: u32, target: u32) -> bool {
+        match self {
+            Self::Lt => value < target,
+            Self::Le => value <= target,
+            Self::Eq => value == target,
+            Self::Ge => value >= target,
+            Self::Gt => value > target,
+        }
+    }
+}
+
+/// A field filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    /// Git branch contains the value (lowercased)
+    Branch(String),
+    /// Project path contains the value (lowercased)
+    Project(String),
+    /// Modified at or after this time (ms)
+    After(i64),
+    /// Modified before this time (ms)
+    Before(i64),
+    /// Message count comparison. Conversations the session index has no count
+    /// for use the content index's, filled in by `SearchQuery::resolve`
+    Messages {
+        cmp: Comparison,
+        target: u32,
+        counts: HashMap<String, u32>,
+    },
+    /// Carries this tag (lowercased, without `#`)
+    Tag(String),
+    Archived,
+    /// Has a live PTY; sessions are filled in by `SearchQuery::resolve`
+    Running(HashSet<String>),
+    /// Needs attention and isn't running; running sessions are filled in by
+    /// `SearchQuery::resolve`
+    Attention(HashSet<String>),
+    /// Invoked the named tool; sessions are filled in by `SearchQuery::resolve`
+    Tool {
+        name: String,
+        sessions: HashSet<String>,
+    },
+}
+
+/// A possibly negated field filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Predicate {
+    pub filter: Filter,
+    pub negated: bool,
+}
+
+impl Predicate {
+    /// Check the conversation against this filter.
+    pub fn matches(&self, conv: &Conversation) -> bool {
+        let hit = match &self.filter {
+            Filter::Branch(value) => conv
+                .git_branch
+                .as_ref()
+                .is_some_and(|b| b.to_lowercase().contains(value)),
+            Filter::Project(value) => conv
+                .project_path
+                .to_string_lossy()
+                .to_lowercase()
+                .contains(value),
+            Filter::After(ms) => conv.timestamp >= *ms,
+            Filter::Before(ms) => conv.timestamp < *ms,
+            Filter::Messages {
+                cmp,
+                target,
+                counts,
+            } => {
+                let count = conv
+                    .message_count
+                    .or_else(|| counts.get(&conv.session_id).copied());
+                // An unknown count matches neither the filter nor its negation
+                let Some(count) = count else {
+                    return false;
+                };
+                cmp.holds(count, *target)
+            }
+            Filter::Tag(value) => conv.tags.iter().any(|t| t.to_lowercase() == *value),
+            Filter::Archived => conv.is_archived,
+            Filter::Attention(running) => {
+                conv.health.is_some() && !running.contains(&conv.session_id)
+            }
+            Filter::Running(sessions) | Filter::Tool { sessions, .. } => {
+                sessions.contains(&conv.session_id)
+            }
+        };
+        hit != self.negated
+    }
+}
+
+/// A raw query token before interpretation.
+struct Token {
+    negated: bool,
+    key: Option<String>,
+    value: String,
+    quoted: bool,
+}
+
+/// Split query text into tokens, honouring quotes and a leading `-`.
+fn lex(text: &str) -> Vec<Token> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i].is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let negated = chars[i] == '-' && chars.get(i + 1).is_some_and(|c| !c.is_whitespace());
+        if negated {
+            i += 1;
+        }
+
+        let mut token = Token {
+            negated,
+            key: None,
+            value: String::new(),
+            quoted: false,
+        };
+        while i < chars.len() && !chars[i].is_whitespace() {
+            let c = chars[i];
+            if c == '"' {
+                // Quoted section runs to the closing quote (or end of input)
+                token.quoted = true;
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    token.value.push(chars[i]);
+                    i += 1;
+                }
+                i += 1;
+                continue;
+            }
+            if c == ':' && token.key.is_none() && !token.quoted && !token.value.is_empty() {
+                token.key = Some(std::mem::take(&mut token.value));
+            } else {
+                token.value.push(c);
+            }
+            i += 1;
+        }
+        tokens.push(token);
+    }
+
+    tokens
+}
+
+/// Parse query text into free-text terms and field predicates.
+pub(crate) fn parse(text: &str) -> (Vec<Term>, Vec<Predicate>) {
+    let mut terms = Vec::new();
+    let mut predicates = Vec::new();
+
+    for token in lex(text) {
+        if let Some(filter) = token
+            .key
+            .as_deref()
+            .and_then(|key| parse_filter(key, &token.value))
+        {
+            predicates.push(Predicate {
+                filter,
+                negated: token.negated,
+            });
+            continue;
+        }
+
+        // Unknown key or malformed value: keep the token as literal text
+        let text = match token.key {
+            Some(key) => format!("{}:{}", key, token.value),
+            None => token.value,
+        };
+        if !text.trim().is_empty() {
+            terms.push(Term {
+                text: text.to_lowercase(),
+                phrase: token.quoted,
+                negated: token.negated,
+            });
+        }
+    }
+
+    (terms, predicates)
+}
+
+fn parse_filter(key: &str, value: &str) -> Option<Filter> {
+    if value.is_empty() {
+        return None;
+    }
+    match key.to_lowercase().as_str() {
+        "branch" => Some(Filter::Branch(value.to_lowercase())),
+        "project" => Some(Filter::Project(value.to_lowercase())),
+        "after" => start_of_day_ms(value).map(Filter::After),
+        "before" => start_of_day_ms(value).map(Filter::Before),
+        "tag" => Some(value.trim_start_matches('#').to_lowercase())
+            .filter(|tag| !tag.is_empty())
+            .map(Filter::Tag),
+        "msgs" => parse_comparison(value).map(|(cmp, target)| Filter::Messages {
+            cmp,
+            target,
+            counts: HashMap::new(),
+        }),
+        "is" => match value.to_lowercase().as_str() {
+            "archived" => Some(Filter::Archived),
+            "running" => Some(Filter::Running(HashSet::new())),
+            "attention" => Some(Filter::Attention(HashSet::new())),
+            _ => None,
+        },
+        "tool" => Some(Filter::Tool {
+            name: value.to_string(),
+            sessions: HashSet::new(),
+        }),
+        _ => None,
+    }
+}
+
+/// Local midnight of a `YYYY-MM-DD` date, in ms.
+fn start_of_day_ms(value: &str) -> Option<i64> {
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(|dt| dt.timestamp_millis())
+}
+
+fn parse_comparison(value: &str) -> Option<(Comparison, u32)> {
+    let (cmp, number) = if let Some(rest) = value.strip_prefix(">=") {
+        (Comparison::Ge, rest)
+    } else if let Some(rest) = value.strip_prefix("<=") {
+        (Comparison::Le, rest)
+    } else if let Some(rest) = value.strip_prefix('>') {
+        (Comparison::Gt, rest)
+    } else if let Some(rest) = value.strip_prefix('<') {
+        (Comparison::Lt, rest)
+    } else if let Some(rest) = value.strip_prefix('=') {
+        (Comparison::Eq, rest)
+    } else {
+        (Comparison::Eq, value)
+    };
+    number.parse().ok().map(|n| (cmp, n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conversation() -> Conversation {
+        Conversation {
+            display: "Fix login".to_string(),
+            timestamp: start_of_day_ms("2026-09-15").unwrap(),
+            message_count: Some(42),
+            git_branch: Some("feat/oauth".to_string()),
+            tags: vec!["Review".to_string()],
+            ..Conversation::for_test("s1", "/Users/me/api-server")
+        }
+    }
+
+    fn predicates(text: &str) -> Vec<Predicate> {
+        parse(text).1
+    }
+
+    #[test]
+    fn parse_splits_terms_phrases_and_negations() {
+        let (terms, preds) = parse(r#"login "rate limit" -flaky -"old plan""#);
+        assert!(preds.is_empty());
+        let summary: Vec<_> = terms
+            .iter()
+            .map(|t| (t.text.as_str(), t.phrase, t.negated))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("login", false, false),
+                ("rate limit", true, false),
+                ("flaky", false, true),
+                ("old plan", true, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn field_filters_match_conversation_fields() {
+        let conv = conversation();
+        let all_match = |text: &str| predicates(text).iter().all(|p| p.matches(&conv));
+
+        assert!(all_match("branch:OAuth project:api"));
+        assert!(all_match("after:2026-09-01 before:2026-10-01"));
+        assert!(!all_match("after:2026-09-16"));
+        assert!(all_match("msgs:>40 msgs:<=42 msgs:42"));
+        assert!(!all_match("msgs:>42"));
+        assert!(all_match("-is:archived -branch:main"));
+        assert!(!all_match("is:archived"));
+        assert!(all_match("tag:review tag:#REVIEW -tag:bug"));
+        assert!(!all_match("tag:rev"));
+    }
+
+    #[test]
+    fn message_filters_use_resolved_counts_and_skip_unknown_ones() {
+        let conv = Conversation {
+            message_count: None,
+            ..conversation()
+        };
+        let all_match = |text: &str, counts: &HashMap<String, u32>| {
+            predicates(text).into_iter().all(|mut p| {
+                if let Filter::Messages {
+                    counts: ref mut c, ..
+                } = p.filter
+                {
+                    c.clone_from(counts);
+                }
+                p.matches(&conv)
+            })
+        };
+
+        let unknown = HashMap::new();
+        assert!(!all_match("msgs:>1", &unknown));
+        assert!(!all_match("-msgs:>1", &unknown));
+        assert!(!all_match("msgs:<100", &unknown));
+
+        let counts = HashMap::from([("s1".to_string(), 12)]);
+        assert!(all_match("msgs:>10 msgs:<100", &counts));
+        assert!(!all_match("msgs:>12", &counts));
+    }
+
+    #[test]
+    fn attention_filter_skips_healthy_and_running_conversations() {
+        use crate::claude::health::Health;
+
+        let mut conv = conversation();
+        let attention = |conv: &Conversation, running: &[&str]| {
+            Predicate {
+                filter: Filter::Attention(running.iter().map(ToString::to_string).collect()),
+                negated: false,
+            }
+            .matches(conv)
+        };
+
+        assert!(!attention(&conv, &[]));
+        conv.health = Some(Health::Interrupted);
+        assert!(attention(&conv, &[]));
+        // The agent is working on it again
+        assert!(!attention(&conv, &["s1"]));
+        assert_eq!(
+            predicates("is:attention")[0].filter,
+            Filter::Attention(HashSet::new())
+        );
+    }
+
+    #[test]
+    fn quoted_filter_values_may_contain_spaces() {
+        let preds = predicates(r#"project:"my app""#);
+        assert_eq!(preds[0].filter, Filter::Project("my app".to_string()));
+    }
+
+    #[test]
+    fn malformed_or_unknown_filters_fall_back_to_text() {
+        let (terms, preds) = parse("after:someday https://example.com msgs:lots");
+        assert!(preds.is_empty());
+        let texts: Vec<_> = terms.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec!["after:someday", "https://example.com", "msgs:lots"]
+        );
+    }
+
+    #[test]
+    fn session_filters_match_resolved_sessions() {
+        let conv = conversation();
+        let running = Predicate {
+            filter: Filter::Running(HashSet::from(["s1".to_string()])),
+            negated: false,
+        };
+        assert!(running.matches(&conv));
+        assert_eq!(
+            predicates("tool:Bash")[0].filter,
+            Filter::Tool {
+                name: "Bash".to_string(),
+                sessions: HashSet::new()
+            }
+        );
+    }
+}
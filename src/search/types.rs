@@ -1,14 +1,18 @@
 //! Types for the search feature.
 
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
 use crate::claude::conversation::Conversation;
+use crate::search::query::{self, Filter, Predicate, Term};
 
 /// Filter type for search queries
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SearchFilterType {
-    /// Search all fields (title/summary and project)
+    /// Search all fields (title/summary, message contents and project)
     #[default]
     All,
-    /// Search only content (title/summary)
+    /// Search only content (title/summary and message contents)
     Content,
     /// Search only project paths
     Project,
@@ -34,27 +38,105 @@ impl SearchFilterType {
     }
 }
 
-/// A search query with text and filter type
+/// A search query: the raw text plus the terms and filters parsed from it
+/// (see `search::query` for the syntax).
 #[derive(Debug, Clone)]
 pub struct SearchQuery {
     /// The search text
     pub text: String,
     /// The filter type
     pub filter_type: SearchFilterType,
+    /// Free-text words and phrases
+    pub terms: Vec<Term>,
+    /// Field filters (`branch:`, `after:`, `is:archived`, ...)
+    pub predicates: Vec<Predicate>,
 }
 
 impl SearchQuery {
-    /// Create a new search query
+    /// Create a new search query, parsing the text
     pub fn new(text: impl Into<String>, filter_type: SearchFilterType) -> Self {
+        let text = text.into();
+        let (terms, predicates) = query::parse(&text);
         Self {
-            text: text.into(),
+            text,
             filter_type,
+            terms,
+            predicates,
         }
     }
 
     /// Check if the query is empty
     pub fn is_empty(&self) -> bool {
-        self.text.trim().is_empty()
+        self.terms.is_empty() && self.predicates.is_empty()
+    }
+
+    /// Text to fuzzy-match: every non-negated word and phrase.
+    pub fn fuzzy_text(&self) -> String {
+        self.terms
+            .iter()
+            .filter(|t| !t.negated)
+            .map(|t| t.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Non-negated quoted phrases.
+    pub fn phrases(&self) -> impl Iterator<Item = &str> {
+        self.terms
+            .iter()
+            .filter(|t| t.phrase && !t.negated)
+            .map(|t| t.text.as_str())
+    }
+
+    /// Fill in the sessions for `is:running`, `is:attention` and `tool:`
+    /// filters, and the fallback message counts for `msgs:` filters.
+    pub fn resolve(
+        &mut self,
+        running_sessions: &HashSet<String>,
+        sessions_using_tool: impl Fn(&str) -> HashSet<String>,
+        message_counts: impl Fn() -> HashMap<String, u32>,
+    ) {
+        for predicate in &mut self.predicates {
+            match &mut predicate.filter {
+                Filter::Running(sessions) | Filter::Attention(sessions) => {
+                    sessions.clone_from(running_sessions);
+                }
+                Filter::Tool { name, sessions } => *sessions = sessions_using_tool(name),
+                Filter::Messages { counts, .. } => *counts = message_counts(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Whether the query filters on archive state. Archived conversations are
+    /// only searched when it does.
+    pub fn constrains_archived(&self) -> bool {
+        self.predicates
+            .iter()
+            .any(|p| matches!(p.filter, Filter::Archived))
+    }
+
+    /// Check every field filter.
+    pub fn matches_filters(&self, conv: &Conversation) -> bool {
+        self.predicates.iter().all(|p| p.matches(conv))
+    }
+
+    /// Whether a negated word or phrase appears in any of `fields`.
+    pub fn excludes(&self, fields: &[&str]) -> bool {
+        self.terms
+            .iter()
+            .filter(|t| t.negated)
+            .any(|t| fields.iter().any(|f| f.to_lowercase().contains(&t.text)))
+    }
+
+    /// Plain substring matching: every word and phrase appears in one of
+    /// `fields` and no negated term does.
+    pub fn matches_text(&self, fields: &[&str]) -> bool {
+        let lowered: Vec<String> = fields.iter().map(|f| f.to_lowercase()).collect();
+        self.terms.iter().all(|t| {
+            let found = lowered.iter().any(|f| f.contains(&t.text));
+            found != t.negated
+        })
     }
 }
 
@@ -65,6 +147,14 @@ pub struct SearchResult {
     pub conversation: Conversation,
     /// Preview snippet showing where the match occurred
     pub preview_snippet: String,
+    /// Transcript message the match is in (content index hits only)
+    pub message_index: Option<usize>,
+    /// Relevance score; results are sorted by this, highest first
+    pub score: i64,
+    /// Matched char ranges in `conversation.display`
+    pub title_ranges: Vec<Range<usize>>,
+    /// Matched char ranges in `preview_snippet`
+    pub snippet_ranges: Vec<Range<usize>>,
 }
 
 impl SearchResult {
@@ -73,6 +163,34 @@ impl SearchResult {
         Self {
             conversation,
             preview_snippet: preview_snippet.into(),
+            message_index: None,
+            score: 0,
+            title_ranges: Vec::new(),
+            snippet_ranges: Vec::new(),
         }
     }
+
+    /// Set the relevance score
+    pub fn with_score(mut self, score: i64) -> Self {
+        self.score = score;
+        self
+    }
+
+    /// Set the matched char ranges in the title
+    pub fn with_title_ranges(mut self, ranges: Vec<Range<usize>>) -> Self {
+        self.title_ranges = ranges;
+        self
+    }
+
+    /// Set the matched char ranges in the preview snippet
+    pub fn with_snippet_ranges(mut self, ranges: Vec<Range<usize>>) -> Self {
+        self.snippet_ranges = ranges;
+        self
+    }
+
+    /// Point the result at a specific transcript message
+    pub fn with_message(mut self, message_index: usize) -> Self {
+        self.message_index = Some(message_index);
+        self
+    }
 }
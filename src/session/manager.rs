@@ -14,10 +14,26 @@ use anyhow::{Context, Result};
 use portable_pty::{native_pty_system, CommandBuilder, PtyPair, PtySize};
 
 use super::types::{screen_state_from_vt100, SessionId, SessionState};
+use crate::claude::data_dir::CONFIG_DIR_ENV;
 
 /// Number of scrollback lines to retain in terminal history per session.
 pub const SCROLLBACK_LINES: usize = 10000;
 
+/// How claude is started in a new session.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpawnOptions<'a> {
+    /// `CLAUDE_CONFIG_DIR` to run claude with; `None` clears it so claude
+    /// uses `~/.claude`
+    pub config_dir: Option<&'a Path>,
+    /// Claude conversation to resume
+    pub claude_session_id: Option<&'a str>,
+    /// Assistant message to fork the resumed conversation at, into a new
+    /// Claude session
+    pub fork_at: Option<&'a str>,
+    /// Run with `--dangerously-skip-permissions`
+    pub dangerous_mode: bool,
+}
+
 /// A managed PTY session.
 pub struct ManagedSession {
     /// Unique session ID (internal to this process).
@@ -56,9 +72,14 @@ impl ManagedSession {
         working_dir: &Path,
         rows: u16,
         cols: u16,
-        claude_session_id: Option<&str>,
-        dangerous_mode: bool,
+        options: SpawnOptions,
     ) -> Result<Self> {
+        let SpawnOptions {
+            config_dir,
+            claude_session_id,
+            fork_at,
+            dangerous_mode,
+        } = options;
         let pty_system = native_pty_system();
 
         let pair = pty_system
@@ -82,12 +103,25 @@ impl ManagedSession {
         if let Some(sid) = claude_session_id {
             cmd.arg("--resume");
             cmd.arg(sid);
+            // Branch off at an earlier message into a new session, leaving
+            // the original conversation untouched
+            if let Some(uuid) = fork_at {
+                cmd.arg("--fork-session");
+                cmd.arg("--resume-session-at");
+                cmd.arg(uuid);
+            }
         }
 
         // Set environment variables for better terminal experience
         cmd.env("TERM", "xterm-256color");
         cmd.env("COLORTERM", "truecolor");
 
+        // Resume from the data directory (account) the conversation is in
+        match config_dir {
+            Some(dir) => cmd.env(CONFIG_DIR_ENV, dir),
+            None => cmd.env_remove(CONFIG_DIR_ENV),
+        }
+
         let _child = pair
             .slave
             .spawn_command(cmd)
@@ -257,29 +291,21 @@ impl SessionManager {
         }
     }
 
-    /// Create a new session.
+    /// Create a new session running claude as `options` describe.
     ///
     /// Returns the session ID on success.
     pub fn create_session(
         &mut self,
         working_dir: &Path,
-        claude_session_id: Option<&str>,
         rows: u16,
         cols: u16,
-        dangerous_mode: bool,
+        options: SpawnOptions,
     ) -> Result<SessionId> {
         // Generate a unique session ID
         let session_id = format!("session-{}", self.next_id);
         self.next_id += 1;
 
-        let session = ManagedSession::spawn(
-            session_id.clone(),
-            working_dir,
-            rows,
-            cols,
-            claude_session_id,
-            dangerous_mode,
-        )?;
+        let session = ManagedSession::spawn(session_id.clone(), working_dir, rows, cols, options)?;
 
         self.sessions.insert(session_id.clone(), session);
         Ok(session_id)
@@ -329,6 +355,15 @@ impl SessionManager {
         self.sessions.keys().cloned().collect()
     }
 
+    /// Resize a single session. No-op if it already has the given dimensions.
+    pub fn resize_session(&mut self, session_id: &str, rows: u16, cols: u16) {
+        if let Some(session) = self.sessions.get_mut(session_id) {
+            if session.rows != rows || session.cols != cols {
+                let _ = session.resize(rows, cols);
+            }
+        }
+    }
+
     /// Resize all sessions to the given dimensions.
     pub fn resize_all(&mut self, rows: u16, cols: u16) {
         for session in self.sessions.values_mut() {
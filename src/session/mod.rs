@@ -8,5 +8,5 @@
 pub mod manager;
 pub mod types;
 
-pub use manager::SessionManager;
+pub use manager::{SessionManager, SpawnOptions};
 pub use types::{CellAttrs, ScreenState, SessionState, TermColor};
@@ -0,0 +1,291 @@
+//! Detail panel: metadata of the selected conversation, shown next to the
+//! sidebar or in place of an empty terminal area.
+
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Local, Utc};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
+};
+
+use crate::claude::conversation::{parse_time, Conversation};
+use crate::claude::details::ConversationDetails;
+use crate::claude::log_stats::LogStats;
+use crate::claude::metadata::format_tags;
+use crate::claude::tool_stats::{format_duration, ToolStats};
+use crate::claude::usage::{format_cost, format_tokens, SessionUsage, TokenUsage};
+use crate::search::index::FileTouch;
+
+/// Files listed before the rest are summarized as a count.
+const MAX_FILES: usize = 8;
+/// Lines kept of the first prompt and the last reply.
+const MAX_TEXT_LINES: usize = 12;
+
+/// Snapshot of everything the panel shows about one conversation.
+pub struct DetailPanelState {
+    pub conversation: Conversation,
+    pub running: bool,
+    pub details: ConversationDetails,
+    /// Message count, from the content index when the session index has none
+    pub messages: Option<u32>,
+    /// Models that answered in the conversation
+    pub models: Vec<String>,
+    pub tokens: Option<TokenUsage>,
+    pub cost: Option<f64>,
+    pub tools: Option<ToolStats>,
+    /// Files touched, modified ones first
+    pub files: Vec<(String, FileTouch)>,
+}
+
+impl DetailPanelState {
+    /// Whether the snapshot no longer matches `conversation`.
+    pub fn is_stale(&self, conversation: &Conversation, running: bool) -> bool {
+        let shown = &self.conversation;
+        shown.session_id != conversation.session_id
+            || shown.timestamp != conversation.timestamp
+            || shown.display != conversation.display
+            || shown.is_archived != conversation.is_archived
+            || shown.pinned_at != conversation.pinned_at
+            || shown.health != conversation.health
+            || shown.tags != conversation.tags
+            || shown.note != conversation.note
+            || self.running != running
+    }
+
+    /// Fill in the models, tokens, cost and tool stats of the conversation
+    /// from the log stats.
+    pub fn set_log_stats(&mut self, stats: &LogStats, cost: Option<f64>) {
+        let usage = stats.usage(&self.conversation.session_id);
+        let models: BTreeSet<String> = usage
+            .map(|usage| {
+                usage
+                    .by_day_model
+                    .keys()
+                    .map(|(_, model)| model.clone())
+                    .filter(|model| model != "<synthetic>")
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.models = models.into_iter().collect();
+        self.tokens = usage.map(SessionUsage::total);
+        self.cost = cost;
+        self.tools = stats.tools(&self.conversation.session_id).cloned();
+    }
+}
+
+/// Widget rendering the detail panel.
+pub struct DetailPanel<'a> {
+    state: Option<&'a DetailPanelState>,
+}
+
+impl<'a> DetailPanel<'a> {
+    pub fn new(state: Option<&'a DetailPanelState>) -> Self {
+        Self { state }
+    }
+}
+
+impl Widget for DetailPanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let block = Block::default()
+            .title(" Details ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+
+        let lines = match self.state {
+            Some(state) => detail_lines(state),
+            None => vec![Line::styled(
+                "No conversation selected",
+                Style::default().fg(Color::DarkGray),
+            )],
+        };
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+}
+
+fn detail_lines(state: &DetailPanelState) -> Vec<Line<'static>> {
+    let conv = &state.conversation;
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines = vec![Line::styled(conv.display.clone(), bold)];
+
+    let mut status = Vec::new();
+    if state.running {
+        status.push(Span::styled("● running", Style::default().fg(Color::Green)));
+    }
+    if let Some(health) = conv.health {
+        status.push(Span::styled(
+            format!("⚠ {}", health.label()),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if conv.is_archived {
+        let since = conv
+            .archived_at
+            .map(|at| format!(" {}", at.with_timezone(&Local).format("%Y-%m-%d")))
+            .unwrap_or_default();
+        status.push(Span::styled(
+            format!("archived{}", since),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if conv.pinned_at.is_some() {
+        status.push(Span::styled(
+            "★ starred",
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if !status.is_empty() {
+        let mut spans = Vec::new();
+        for span in status {
+            if !spans.is_empty() {
+                spans.push(Span::raw("  "));
+            }
+            spans.push(span);
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if let Some(ref summary) = conv.summary {
+        if *summary != conv.display {
+            lines.push(Line::raw(summary.clone()));
+        }
+    }
+    lines.push(Line::raw(""));
+
+    lines.push(field("Project", conv.project_path.display().to_string()));
+    if let Some(ref branch) = conv.git_branch {
+        lines.push(field("Branch", branch.clone()));
+    }
+    // Sessions missing from the session index only have what their log says
+    if let Some(created) = parse_time(&conv.created).or(state.details.started) {
+        lines.push(field("Created", format_time(created)));
+    }
+    if let Some(modified) = parse_time(&conv.modified).or(state.details.last_active) {
+        lines.push(field("Modified", format_time(modified)));
+    }
+    if let Some(count) = state.messages {
+        lines.push(field("Messages", count.to_string()));
+    }
+    if !state.models.is_empty() {
+        lines.push(field("Models", state.models.join(", ")));
+    }
+    if let Some(ref tokens) = state.tokens {
+        let cost = state
+            .cost
+            .map(|cost| format!(" · {}", format_cost(cost)))
+            .unwrap_or_default();
+        lines.push(field(
+            "Tokens",
+            format!(
+                "{} ({} in, {} out){}",
+                format_tokens(tokens.total()),
+                format_tokens(tokens.input + tokens.cache_write + tokens.cache_read),
+                format_tokens(tokens.output),
+                cost
+            ),
+        ));
+    }
+    if let Some(ref tools) = state.tools {
+        let average = tools
+            .average_turn_ms()
+            .map(|ms| format!(" · avg turn {}", format_duration(ms)))
+            .unwrap_or_default();
+        lines.push(field(
+            "Tools",
+            format!(
+                "{} calls, {} failed, {} denied{}",
+                tools.calls(),
+                tools.errors(),
+                tools.denied(),
+                average
+            ),
+        ));
+    }
+    if !conv.tags.is_empty() {
+        lines.push(field("Tags", format_tags(&conv.tags)));
+    }
+    if let Some(ref note) = conv.note {
+        lines.push(field("Note", note.clone()));
+    }
+
+    if !state.files.is_empty() {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            format!("Files touched ({})", state.files.len()),
+            bold,
+        ));
+        for (path, touch) in state.files.iter().take(MAX_FILES) {
+            let (marker, color) = if touch.modified {
+                ("M", Color::Yellow)
+            } else {
+                ("R", Color::DarkGray)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {} ", marker), Style::default().fg(color)),
+                Span::raw(relative_path(path, conv)),
+            ]));
+        }
+        if state.files.len() > MAX_FILES {
+            lines.push(Line::styled(
+                format!("   … {} more", state.files.len() - MAX_FILES),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+    }
+
+    for (title, text) in [
+        ("First prompt", &state.details.first_prompt),
+        ("Last reply", &state.details.last_reply),
+    ] {
+        if let Some(text) = text {
+            lines.push(Line::raw(""));
+            lines.push(Line::styled(title, bold));
+            lines.extend(text_lines(text));
+        }
+    }
+    lines
+}
+
+/// A `label: value` line.
+fn field(label: &str, value: String) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            format!("{:<9}", format!("{}:", label)),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::raw(value),
+    ])
+}
+
+/// The first lines of a long text, marking what was cut off.
+fn text_lines(text: &str) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line> = text
+        .lines()
+        .take(MAX_TEXT_LINES)
+        .map(|line| Line::raw(line.to_string()))
+        .collect();
+    if text.lines().count() > MAX_TEXT_LINES {
+        lines.push(Line::styled("…", Style::default().fg(Color::DarkGray)));
+    }
+    lines
+}
+
+/// A file path relative to the conversation's project, when inside it.
+fn relative_path(path: &str, conv: &Conversation) -> String {
+    std::path::Path::new(path)
+        .strip_prefix(&conv.project_path)
+        .map_or_else(|_| path.to_string(), |p| p.display().to_string())
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
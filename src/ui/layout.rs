@@ -61,3 +61,22 @@ pub fn create_layout_with_help_config(area: Rect, config: &LayoutConfig) -> (Rec
         SidebarPosition::Right => (horizontal[1], horizontal[0], help_area),
     }
 }
+
+/// Split the terminal area to make room for the detail panel on the side
+/// facing the sidebar. Returns (detail panel, terminal).
+pub fn split_detail_panel(area: Rect, position: SidebarPosition) -> (Rect, Rect) {
+    let width = (area.width * 35 / 100).clamp(30, 60).min(area.width / 2);
+    let (detail, terminal) = match position {
+        SidebarPosition::Left => {
+            let [detail, terminal] =
+                Layout::horizontal([Constraint::Length(width), Constraint::Min(0)]).areas(area);
+            (detail, terminal)
+        }
+        SidebarPosition::Right => {
+            let [terminal, detail] =
+                Layout::horizontal([Constraint::Min(0), Constraint::Length(width)]).areas(area);
+            (detail, terminal)
+        }
+    };
+    (detail, terminal)
+}
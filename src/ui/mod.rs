@@ -1,13 +1,16 @@
 //! Terminal UI components: sidebar, terminal pane, modals, and widgets.
 
+pub mod detail_panel;
 pub mod help_menu;
 pub mod layout;
 pub mod modal;
 pub mod mosaic;
 pub mod sidebar;
 pub mod terminal_pane;
+pub mod tiling;
 pub mod toast;
 pub mod toast_widget;
+pub mod transcript;
 pub mod which_key;
 
 pub use help_menu::HelpMenuWidget;
@@ -0,0 +1,360 @@
+//! Archive preview modal: the conversations a bulk archive or the archive
+//! rules would archive, or a bulk delete would move to the trash, confirmed
+//! before anything changes.
+
+use chrono::{DateTime, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Widget},
+};
+
+use crate::claude::archive_rules::{parse_cutoff, ArchiveCandidate};
+
+/// Internal key-handling result (translated to `ModalKeyResult` via the trait).
+enum ArchiveModalKeyResult {
+    Continue,
+    Close,
+    Confirm,
+}
+
+/// What confirming the preview does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BulkAction {
+    /// Archive, automatically (by rule) or by hand
+    Archive { by_rule: bool },
+    /// Move to the trash
+    Trash,
+}
+
+/// State for the archive preview modal.
+pub struct ArchiveModalState {
+    /// What is being archived (e.g. the group name)
+    title: String,
+    /// Conversations that may be archived
+    candidates: Vec<ArchiveCandidate>,
+    /// Age cutoff being typed when archiving by age; only candidates idle
+    /// since before it are shown
+    cutoff: Option<String>,
+    /// Candidates shown, by index into `candidates`
+    shown: Vec<usize>,
+    action: BulkAction,
+    list_state: ListState,
+}
+
+impl ArchiveModalState {
+    /// Preview archiving exactly `candidates`.
+    pub fn new(title: impl Into<String>, candidates: Vec<ArchiveCandidate>, by_rule: bool) -> Self {
+        let mut state = Self {
+            title: title.into(),
+            candidates,
+            cutoff: None,
+            shown: Vec::new(),
+            action: BulkAction::Archive { by_rule },
+            list_state: ListState::default(),
+        };
+        state.update_shown();
+        state
+    }
+
+    /// Preview archiving the `candidates` idle since before a cutoff the
+    /// user types, starting at `cutoff`.
+    pub fn older_than(candidates: Vec<ArchiveCandidate>, cutoff: &str) -> Self {
+        let mut state = Self::new("Archive older than", candidates, false);
+        state.cutoff = Some(cutoff.to_string());
+        state.update_shown();
+        state
+    }
+
+    /// Preview moving `candidates` to the trash.
+    pub fn trash(title: impl Into<String>, candidates: Vec<ArchiveCandidate>) -> Self {
+        let mut state = Self::new(title, candidates, false);
+        state.action = BulkAction::Trash;
+        state
+    }
+
+    /// Session IDs that confirming would archive or delete.
+    fn session_ids(&self) -> Vec<String> {
+        self.shown
+            .iter()
+            .map(|&i| self.candidates[i].session_id.clone())
+            .collect()
+    }
+
+    /// The cutoff in ms, `None` when archiving by age and the input is invalid.
+    fn cutoff_ms(&self) -> Option<Option<i64>> {
+        match self.cutoff {
+            Some(ref text) => parse_cutoff(text, Utc::now()).map(Some),
+            None => Some(None),
+        }
+    }
+
+    fn update_shown(&mut self) {
+        self.shown = match self.cutoff_ms() {
+            Some(cutoff) => self
+                .candidates
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| cutoff.is_none_or(|cutoff| c.timestamp < cutoff))
+                .map(|(i, _)| i)
+                .collect(),
+            None => Vec::new(),
+        };
+        self.list_state
+            .select((!self.shown.is_empty()).then_some(0));
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.shown.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        self.list_state
+            .select(Some(current.saturating_add_signed(delta).min(len - 1)));
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ArchiveModalKeyResult {
+        match key.code {
+            KeyCode::Esc => return ArchiveModalKeyResult::Close,
+            KeyCode::Enter if !self.shown.is_empty() => return ArchiveModalKeyResult::Confirm,
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Backspace => {
+                if let Some(ref mut cutoff) = self.cutoff {
+                    cutoff.pop();
+                    self.update_shown();
+                }
+            }
+            KeyCode::Char(c) => match self.cutoff {
+                Some(ref mut cutoff) => {
+                    cutoff.push(c);
+                    self.update_shown();
+                }
+                None => match c {
+                    'q' => return ArchiveModalKeyResult::Close,
+                    'y' if !self.shown.is_empty() => return ArchiveModalKeyResult::Confirm,
+                    'j' => self.move_selection(1),
+                    'k' => self.move_selection(-1),
+                    _ => {}
+                },
+            },
+            _ => {}
+        }
+        ArchiveModalKeyResult::Continue
+    }
+}
+
+impl super::Modal for ArchiveModalState {
+    fn handle_key_modal(&mut self, key: KeyEvent) -> super::ModalKeyResult {
+        match self.handle_key(key) {
+            ArchiveModalKeyResult::Continue => super::ModalKeyResult::Continue,
+            ArchiveModalKeyResult::Close => super::ModalKeyResult::Close,
+            ArchiveModalKeyResult::Confirm => match self.action {
+                BulkAction::Archive { by_rule } => super::ModalKeyResult::ArchiveConfirmed {
+                    session_ids: self.session_ids(),
+                    by_rule,
+                },
+                BulkAction::Trash => super::ModalKeyResult::TrashConfirmed {
+                    session_ids: self.session_ids(),
+                },
+            },
+        }
+    }
+}
+
+/// Widget for rendering the archive preview modal.
+pub struct ArchiveModal<'a> {
+    state: &'a mut ArchiveModalState,
+}
+
+impl<'a> ArchiveModal<'a> {
+    pub fn new(state: &'a mut ArchiveModalState) -> Self {
+        Self { state }
+    }
+
+    /// Calculate the modal area (centered, 70% width, 70% height).
+    pub fn calculate_area(total: Rect) -> Rect {
+        let width = (total.width * 70 / 100)
+            .max(50)
+            .min(total.width.saturating_sub(4));
+        let height = (total.height * 70 / 100)
+            .max(12)
+            .min(total.height.saturating_sub(4));
+
+        let x = (total.width.saturating_sub(width)) / 2;
+        let y = (total.height.saturating_sub(height)) / 2;
+
+        Rect::new(x, y, width, height)
+    }
+}
+
+impl Widget for ArchiveModal<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 40 || area.height < 10 {
+            return;
+        }
+
+        Clear.render(area, buf);
+
+        let outer_block = Block::default()
+            .title(format!(" {} ", self.state.title))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = outer_block.inner(area);
+        outer_block.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Cutoff input or summary
+            Constraint::Length(1), // Spacer
+            Constraint::Min(3),    // List
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+        let dim = Style::default().fg(Color::DarkGray);
+        let count = self.state.shown.len();
+        let (outcome, confirm_label) = match self.state.action {
+            BulkAction::Archive { .. } => ("archived", "archive all"),
+            BulkAction::Trash => ("moved to the trash", "delete all"),
+        };
+        let summary = Span::styled(
+            format!(
+                " {} conversation{} will be {}",
+                count,
+                if count == 1 { "" } else { "s" },
+                outcome
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        );
+        let header = match self.state.cutoff {
+            Some(ref cutoff) => {
+                let mut spans = vec![
+                    Span::styled(" Older than: ", dim),
+                    Span::styled(format!("{}▏", cutoff), Style::default().fg(Color::Yellow)),
+                ];
+                if self.state.cutoff_ms().is_some() {
+                    spans.push(summary);
+                } else {
+                    spans.push(Span::styled(
+                        "  e.g. 30d, 2w or 2026-01-31",
+                        Style::default().fg(Color::Red),
+                    ));
+                }
+                Line::from(spans)
+            }
+            None => Line::from(summary),
+        };
+        Paragraph::new(header).render(chunks[0], buf);
+
+        if self.state.shown.is_empty() {
+            Paragraph::new("Nothing to archive")
+                .style(dim)
+                .alignment(Alignment::Center)
+                .render(chunks[2], buf);
+        } else {
+            let width = chunks[2].width.saturating_sub(2) as usize;
+            let now = Utc::now();
+            let items: Vec<ListItem> = self
+                .state
+                .shown
+                .iter()
+                .map(|&i| candidate_item(&self.state.candidates[i], width, now))
+                .collect();
+            let list = List::new(items)
+                .highlight_style(
+                    Style::default()
+                        .bg(Color::DarkGray)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .highlight_symbol("> ");
+            ratatui::widgets::StatefulWidget::render(
+                list,
+                chunks[2],
+                buf,
+                &mut self.state.list_state,
+            );
+        }
+
+        let keys: &[(&str, &str)] = if self.state.cutoff.is_some() {
+            &[
+                ("type", "cutoff"),
+                ("↑/↓", "nav"),
+                ("Enter", confirm_label),
+                ("Esc", "cancel"),
+            ]
+        } else {
+            &[
+                ("j/k", "nav"),
+                ("Enter/y", confirm_label),
+                ("Esc", "cancel"),
+            ]
+        };
+        let mut spans = Vec::new();
+        for (key, label) in keys {
+            spans.push(Span::styled(
+                format!(" {} ", key),
+                Style::default().fg(Color::Cyan),
+            ));
+            spans.push(Span::raw(format!("{} ", label)));
+        }
+        Paragraph::new(Line::from(spans))
+            .style(dim)
+            .alignment(Alignment::Center)
+            .render(chunks[3], buf);
+    }
+}
+
+/// Two lines per candidate: the title, then its project, how long it has
+/// been idle and the rule that matched.
+fn candidate_item(
+    candidate: &ArchiveCandidate,
+    width: usize,
+    now: DateTime<Utc>,
+) -> ListItem<'static> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut detail = format!(
+        "  {} · {}",
+        candidate.project_path.display(),
+        format_idle(candidate.timestamp, now)
+    );
+    if let Some(ref reason) = candidate.reason {
+        detail.push_str(" · ");
+        detail.push_str(reason);
+    }
+    ListItem::new(vec![
+        Line::raw(truncate(
+            candidate.title.lines().next().unwrap_or_default(),
+            width,
+        )),
+        Line::styled(truncate(&detail, width), dim),
+    ])
+}
+
+/// How long ago a conversation was last active, e.g. "idle 12d".
+fn format_idle(timestamp: i64, now: DateTime<Utc>) -> String {
+    let Some(at) = DateTime::from_timestamp_millis(timestamp) else {
+        return String::new();
+    };
+    let idle = now - at;
+    if idle.num_days() > 0 {
+        format!("idle {}d", idle.num_days())
+    } else if idle.num_hours() > 0 {
+        format!("idle {}h", idle.num_hours())
+    } else {
+        format!("idle {}m", idle.num_minutes().max(0))
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let mut out: String = text.chars().take(max.saturating_sub(1)).collect();
+        out.push('…');
+        out
+    }
+}
@@ -0,0 +1,263 @@
+//! Branches modal: a conversation's edit/rewind branches as a tree, with
+//! where each one diverged and its last message.
+
+use chrono::{DateTime, Local};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Widget},
+};
+
+use crate::claude::branches::{Branch, BranchTree};
+
+/// Internal key-handling result (translated to `ModalKeyResult` via the trait).
+enum BranchesModalKeyResult {
+    Continue,
+    Close,
+    Open(usize),
+    Resume(usize),
+}
+
+/// State for the branches modal.
+pub struct BranchesModalState {
+    /// Conversation the branches belong to
+    pub session_id: String,
+    pub title: String,
+    tree: BranchTree,
+    list_state: ListState,
+}
+
+impl BranchesModalState {
+    pub fn new(session_id: String, title: String, tree: BranchTree) -> Self {
+        let mut list_state = ListState::default();
+        list_state.select((!tree.branches.is_empty()).then_some(0));
+        Self {
+            session_id,
+            title,
+            tree,
+            list_state,
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.tree.branches.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        self.list_state
+            .select(Some(current.saturating_add_signed(delta).min(len - 1)));
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> BranchesModalKeyResult {
+        let selected = self.list_state.selected();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return BranchesModalKeyResult::Close,
+            KeyCode::Char('j') | KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_selection(-1),
+            KeyCode::Char('g') | KeyCode::Home => self.list_state.select_first(),
+            KeyCode::Char('G') | KeyCode::End => {
+                self.move_selection(self.tree.branches.len() as isize);
+            }
+            KeyCode::Enter => {
+                if let Some(idx) = selected {
+                    return BranchesModalKeyResult::Open(idx);
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Some(idx) = selected {
+                    return BranchesModalKeyResult::Resume(idx);
+                }
+            }
+            _ => {}
+        }
+        BranchesModalKeyResult::Continue
+    }
+}
+
+impl super::Modal for BranchesModalState {
+    fn handle_key_modal(&mut self, key: KeyEvent) -> super::ModalKeyResult {
+        match self.handle_key(key) {
+            BranchesModalKeyResult::Continue => super::ModalKeyResult::Continue,
+            BranchesModalKeyResult::Close => super::ModalKeyResult::Close,
+            BranchesModalKeyResult::Open(idx) => super::ModalKeyResult::BranchOpened {
+                session_id: self.session_id.clone(),
+                tip: self.tree.branches[idx].tip.clone(),
+            },
+            BranchesModalKeyResult::Resume(idx) => super::ModalKeyResult::BranchResumed {
+                session_id: self.session_id.clone(),
+                // The main branch is what a plain resume continues
+                fork_at: self.tree.branches[idx]
+                    .resume_at
+                    .clone()
+                    .filter(|_| idx > 0),
+            },
+        }
+    }
+}
+
+/// Widget for rendering the branches modal.
+pub struct BranchesModal<'a> {
+    state: &'a mut BranchesModalState,
+}
+
+impl<'a> BranchesModal<'a> {
+    pub fn new(state: &'a mut BranchesModalState) -> Self {
+        Self { state }
+    }
+
+    /// Calculate the modal area (centered, 70% width, 70% height).
+    pub fn calculate_area(total: Rect) -> Rect {
+        let width = (total.width * 70 / 100)
+            .max(50)
+            .min(total.width.saturating_sub(4));
+        let height = (total.height * 70 / 100)
+            .max(12)
+            .min(total.height.saturating_sub(4));
+
+        let x = (total.width.saturating_sub(width)) / 2;
+        let y = (total.height.saturating_sub(height)) / 2;
+
+        Rect::new(x, y, width, height)
+    }
+}
+
+impl Widget for BranchesModal<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 40 || area.height < 10 {
+            return;
+        }
+
+        Clear.render(area, buf);
+
+        let outer_block = Block::default()
+            .title(" Branches ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = outer_block.inner(area);
+        outer_block.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Title
+            Constraint::Length(1), // Spacer
+            Constraint::Min(3),    // List
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+        let count = self.state.tree.branches.len();
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!(" {}", self.state.title),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  {} branch{}", count, if count == 1 { "" } else { "es" }),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]))
+        .render(chunks[0], buf);
+
+        let mut list_area = chunks[2];
+        if self.state.tree.is_linear() {
+            // Just the one branch, with a note under it
+            list_area.height = list_area.height.min(2);
+            let note = Rect {
+                y: list_area.y + 3,
+                height: 1,
+                ..chunks[2]
+            };
+            Paragraph::new("This conversation has never been edited or rewound")
+                .style(Style::default().fg(Color::DarkGray))
+                .alignment(Alignment::Center)
+                .render(note.intersection(chunks[2]), buf);
+        }
+        let width = list_area.width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = self
+            .state
+            .tree
+            .branches
+            .iter()
+            .enumerate()
+            .map(|(i, branch)| branch_item(i, branch, width))
+            .collect();
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("> ");
+        ratatui::widgets::StatefulWidget::render(list, list_area, buf, &mut self.state.list_state);
+
+        let mut spans = Vec::new();
+        for (key, label) in [
+            ("j/k", "nav"),
+            ("Enter", "show tip"),
+            ("r", "resume here"),
+            ("Esc", "close"),
+        ] {
+            spans.push(Span::styled(
+                format!(" {} ", key),
+                Style::default().fg(Color::Cyan),
+            ));
+            spans.push(Span::raw(format!("{} ", label)));
+        }
+        Paragraph::new(Line::from(spans))
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .render(chunks[3], buf);
+    }
+}
+
+/// Two lines per branch: its tip, then where it split off.
+fn branch_item(index: usize, branch: &Branch, width: usize) -> ListItem<'static> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let indent = "  ".repeat(branch.depth);
+    let (label, color) = if index == 0 {
+        ("main".to_string(), Color::Green)
+    } else {
+        (format!("└ #{}", index), Color::Yellow)
+    };
+    let time = format_time(branch.last_timestamp.as_deref());
+    let used = indent.chars().count() + label.chars().count() + time.chars().count() + 3;
+    let tip = Line::from(vec![
+        Span::raw(indent.clone()),
+        Span::styled(label, Style::default().fg(color)),
+        Span::raw(" "),
+        Span::raw(truncate(&branch.last_text, width.saturating_sub(used))),
+        Span::styled(format!(" {}", time), dim),
+    ]);
+
+    let detail = match &branch.fork_text {
+        Some(fork) => format!(
+            "{}  {} msgs · diverged {} after “{}”",
+            indent,
+            branch.message_count,
+            format_time(branch.forked_at.as_deref()),
+            fork
+        ),
+        None => format!("{}  {} msgs · latest", indent, branch.message_count),
+    };
+    ListItem::new(vec![tip, Line::styled(truncate(&detail, width), dim)])
+}
+
+fn format_time(timestamp: Option<&str>) -> String {
+    timestamp
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map(|dt| dt.with_timezone(&Local).format("%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let mut out: String = text.chars().take(max.saturating_sub(1)).collect();
+        out.push('…');
+        out
+    }
+}
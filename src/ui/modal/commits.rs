@@ -0,0 +1,237 @@
+//! Commits modal: the git commits made in a conversation's project while it
+//! was active, with their diffstat.
+
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Widget},
+};
+
+use crate::claude::git_log::Commit;
+
+/// State for the commits modal.
+pub struct CommitsModalState {
+    pub title: String,
+    /// When the conversation was active
+    span: (DateTime<Utc>, DateTime<Utc>),
+    commits: Vec<Commit>,
+    list_state: ListState,
+}
+
+impl CommitsModalState {
+    pub fn new(title: String, span: (DateTime<Utc>, DateTime<Utc>), commits: Vec<Commit>) -> Self {
+        let mut list_state = ListState::default();
+        list_state.select((!commits.is_empty()).then_some(0));
+        Self {
+            title,
+            span,
+            commits,
+            list_state,
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.commits.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        self.list_state
+            .select(Some(current.saturating_add_signed(delta).min(len - 1)));
+    }
+}
+
+impl super::Modal for CommitsModalState {
+    fn handle_key_modal(&mut self, key: KeyEvent) -> super::ModalKeyResult {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return super::ModalKeyResult::Close,
+            KeyCode::Char('j') | KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_selection(-1),
+            KeyCode::Char('g') | KeyCode::Home => self.list_state.select_first(),
+            KeyCode::Char('G') | KeyCode::End => {
+                self.move_selection(self.commits.len() as isize);
+            }
+            KeyCode::Char('y') | KeyCode::Enter => {
+                if let Some(commit) = self.list_state.selected().and_then(|i| self.commits.get(i)) {
+                    return super::ModalKeyResult::CommitYanked(commit.hash.clone());
+                }
+            }
+            _ => {}
+        }
+        super::ModalKeyResult::Continue
+    }
+}
+
+/// Widget for rendering the commits modal.
+pub struct CommitsModal<'a> {
+    state: &'a mut CommitsModalState,
+}
+
+impl<'a> CommitsModal<'a> {
+    pub fn new(state: &'a mut CommitsModalState) -> Self {
+        Self { state }
+    }
+
+    /// Calculate the modal area (centered, 70% width, 70% height).
+    pub fn calculate_area(total: Rect) -> Rect {
+        let width = (total.width * 70 / 100)
+            .max(50)
+            .min(total.width.saturating_sub(4));
+        let height = (total.height * 70 / 100)
+            .max(12)
+            .min(total.height.saturating_sub(4));
+
+        let x = (total.width.saturating_sub(width)) / 2;
+        let y = (total.height.saturating_sub(height)) / 2;
+
+        Rect::new(x, y, width, height)
+    }
+}
+
+impl Widget for CommitsModal<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 40 || area.height < 10 {
+            return;
+        }
+
+        Clear.render(area, buf);
+
+        let outer_block = Block::default()
+            .title(" Commits ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = outer_block.inner(area);
+        outer_block.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Title
+            Constraint::Length(1), // Time span and totals
+            Constraint::Length(1), // Spacer
+            Constraint::Min(3),    // List
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+        let dim = Style::default().fg(Color::DarkGray);
+        Paragraph::new(Line::styled(
+            format!(" {}", self.state.title),
+            Style::default().add_modifier(Modifier::BOLD),
+        ))
+        .render(chunks[0], buf);
+
+        let commits = &self.state.commits;
+        let (start, end) = self.state.span;
+        let total = commits.iter().fold((0, 0), |(ins, del), c| {
+            (ins + c.stat.insertions, del + c.stat.deletions)
+        });
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!(" {} → {}", format_time(start), format_time(end)),
+                dim,
+            ),
+            Span::styled(
+                format!(
+                    "  {} commit{}",
+                    commits.len(),
+                    if commits.len() == 1 { "" } else { "s" }
+                ),
+                dim,
+            ),
+            Span::styled(format!("  +{}", total.0), Style::default().fg(Color::Green)),
+            Span::styled(format!(" -{}", total.1), Style::default().fg(Color::Red)),
+        ]))
+        .render(chunks[1], buf);
+
+        if commits.is_empty() {
+            Paragraph::new("No commits were made while this conversation was active")
+                .style(dim)
+                .alignment(Alignment::Center)
+                .render(chunks[3], buf);
+        } else {
+            let width = chunks[3].width.saturating_sub(2) as usize;
+            let items: Vec<ListItem> = commits.iter().map(|c| commit_item(c, width)).collect();
+            let list = List::new(items)
+                .highlight_style(
+                    Style::default()
+                        .bg(Color::DarkGray)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .highlight_symbol("> ");
+            ratatui::widgets::StatefulWidget::render(
+                list,
+                chunks[3],
+                buf,
+                &mut self.state.list_state,
+            );
+        }
+
+        let mut spans = Vec::new();
+        for (key, label) in [("j/k", "nav"), ("y", "copy hash"), ("Esc", "close")] {
+            spans.push(Span::styled(
+                format!(" {} ", key),
+                Style::default().fg(Color::Cyan),
+            ));
+            spans.push(Span::raw(format!("{} ", label)));
+        }
+        Paragraph::new(Line::from(spans))
+            .style(dim)
+            .alignment(Alignment::Center)
+            .render(chunks[4], buf);
+    }
+}
+
+/// Two lines per commit: hash and subject, then author, time and diffstat.
+fn commit_item(commit: &Commit, width: usize) -> ListItem<'static> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let header = Line::from(vec![
+        Span::styled(
+            commit.short_hash().to_string(),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::raw(" "),
+        Span::raw(truncate(
+            &commit.subject,
+            width.saturating_sub(commit.short_hash().len() + 1),
+        )),
+    ]);
+    let stat = commit.stat;
+    let detail = Line::from(vec![
+        Span::styled(
+            format!(
+                "        {} · {} · {} file{} ",
+                commit.author,
+                format_time(commit.time),
+                stat.files_changed,
+                if stat.files_changed == 1 { "" } else { "s" }
+            ),
+            dim,
+        ),
+        Span::styled(
+            format!("+{}", stat.insertions),
+            Style::default().fg(Color::Green),
+        ),
+        Span::styled(
+            format!(" -{}", stat.deletions),
+            Style::default().fg(Color::Red),
+        ),
+    ]);
+    ListItem::new(vec![header, detail])
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format("%m-%d %H:%M").to_string()
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let mut out: String = text.chars().take(max.saturating_sub(1)).collect();
+        out.push('…');
+        out
+    }
+}
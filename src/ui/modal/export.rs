@@ -0,0 +1,198 @@
+//! Export modal: pick a format and which block kinds to include, then save
+//! the conversation to a file or copy it to the clipboard.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::claude::export::{ExportFormat, ExportOptions};
+
+/// Where an export goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportDestination {
+    File,
+    Clipboard,
+}
+
+/// Internal key-handling result (translated to `ModalKeyResult` via the trait).
+enum ExportModalKeyResult {
+    Continue,
+    Close,
+    Confirmed(ExportDestination),
+}
+
+/// State for the export modal.
+pub struct ExportModalState {
+    /// Conversation being exported
+    pub session_id: String,
+    pub title: String,
+    pub format: ExportFormat,
+    pub options: ExportOptions,
+}
+
+impl ExportModalState {
+    pub fn new(session_id: String, title: String) -> Self {
+        Self {
+            session_id,
+            title,
+            format: ExportFormat::default(),
+            options: ExportOptions::default(),
+        }
+    }
+
+    fn cycle_format(&mut self, forward: bool) {
+        let all = ExportFormat::ALL;
+        let idx = all.iter().position(|f| *f == self.format).unwrap_or(0);
+        let next = if forward {
+            (idx + 1) % all.len()
+        } else {
+            (idx + all.len() - 1) % all.len()
+        };
+        self.format = all[next];
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> ExportModalKeyResult {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return ExportModalKeyResult::Close,
+            KeyCode::Tab | KeyCode::Right => self.cycle_format(true),
+            KeyCode::BackTab | KeyCode::Left => self.cycle_format(false),
+            KeyCode::Char('t') => self.options.tool_calls = !self.options.tool_calls,
+            KeyCode::Char('r') => self.options.tool_results = !self.options.tool_results,
+            KeyCode::Char('k') => self.options.thinking = !self.options.thinking,
+            KeyCode::Enter => return ExportModalKeyResult::Confirmed(ExportDestination::File),
+            KeyCode::Char('y') => {
+                return ExportModalKeyResult::Confirmed(ExportDestination::Clipboard)
+            }
+            _ => {}
+        }
+        ExportModalKeyResult::Continue
+    }
+}
+
+impl super::Modal for ExportModalState {
+    fn handle_key_modal(&mut self, key: KeyEvent) -> super::ModalKeyResult {
+        match self.handle_key(key) {
+            ExportModalKeyResult::Continue => super::ModalKeyResult::Continue,
+            ExportModalKeyResult::Close => super::ModalKeyResult::Close,
+            ExportModalKeyResult::Confirmed(destination) => {
+                super::ModalKeyResult::ExportConfirmed {
+                    session_id: self.session_id.clone(),
+                    format: self.format,
+                    options: self.options,
+                    destination,
+                }
+            }
+        }
+    }
+}
+
+/// Widget for rendering the export modal.
+pub struct ExportModal<'a> {
+    state: &'a ExportModalState,
+}
+
+impl<'a> ExportModal<'a> {
+    pub fn new(state: &'a ExportModalState) -> Self {
+        Self { state }
+    }
+
+    /// Calculate the modal area (centered, fixed size).
+    pub fn calculate_area(total: Rect) -> Rect {
+        let width = 56.min(total.width.saturating_sub(4));
+        let height = 12.min(total.height.saturating_sub(2));
+        let x = (total.width.saturating_sub(width)) / 2;
+        let y = (total.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+}
+
+impl Widget for ExportModal<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 30 || area.height < 10 {
+            return;
+        }
+
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .title(" Export ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Title
+            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Format
+            Constraint::Length(1), // Spacer
+            Constraint::Length(3), // Options
+            Constraint::Min(0),    // Spacer
+            Constraint::Length(1), // Help
+        ])
+        .split(inner);
+
+        Paragraph::new(Line::from(Span::styled(
+            format!(" {}", self.state.title),
+            Style::default().add_modifier(Modifier::BOLD),
+        )))
+        .render(chunks[0], buf);
+
+        let mut format_spans = vec![Span::raw(" Format: ")];
+        for format in ExportFormat::ALL {
+            let style = if format == self.state.format {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            format_spans.push(Span::styled(format!(" {} ", format.name()), style));
+            format_spans.push(Span::raw(" "));
+        }
+        Paragraph::new(Line::from(format_spans)).render(chunks[2], buf);
+
+        let options = &self.state.options;
+        let option_lines: Vec<Line> = [
+            ('t', "tool calls", options.tool_calls),
+            ('r', "tool results", options.tool_results),
+            ('k', "thinking", options.thinking),
+        ]
+        .into_iter()
+        .map(|(key, label, on)| {
+            let (mark, style) = if on {
+                ("[x]", Style::default().fg(Color::Green))
+            } else {
+                ("[ ]", Style::default().fg(Color::DarkGray))
+            };
+            Line::from(vec![
+                Span::styled(format!("  {} ", key), Style::default().fg(Color::Cyan)),
+                Span::styled(mark, style),
+                Span::raw(format!(" {}", label)),
+            ])
+        })
+        .collect();
+        Paragraph::new(option_lines).render(chunks[4], buf);
+
+        let help = Line::from(vec![
+            Span::styled(" Tab ", Style::default().fg(Color::Cyan)),
+            Span::raw("format "),
+            Span::styled(" Enter ", Style::default().fg(Color::Cyan)),
+            Span::raw("save "),
+            Span::styled(" y ", Style::default().fg(Color::Cyan)),
+            Span::raw("copy "),
+            Span::styled(" Esc ", Style::default().fg(Color::Cyan)),
+            Span::raw("cancel"),
+        ]);
+        Paragraph::new(help)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .render(chunks[6], buf);
+    }
+}
@@ -0,0 +1,454 @@
+//! Files picker modal: find a file touched by Edit/Write/MultiEdit/Read tool
+//! calls and list the conversations that touched it.
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, TimeZone};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Widget},
+};
+
+use crate::search::fuzzy::fuzzy_match;
+use crate::search::index::FileTouch;
+
+use super::search::highlight_spans;
+
+/// A conversation that touched a file, with its title resolved.
+#[derive(Debug, Clone)]
+pub struct FileTouchEntry {
+    pub session_id: String,
+    pub title: String,
+    pub touch: FileTouch,
+}
+
+/// A touched file and every conversation that touched it.
+#[derive(Debug, Clone)]
+pub struct TouchedFile {
+    /// Path as recorded in the tool call
+    pub path: String,
+    /// Most recently touching conversation first
+    pub touchers: Vec<FileTouchEntry>,
+}
+
+impl TouchedFile {
+    fn modified(&self) -> bool {
+        self.touchers.iter().any(|t| t.touch.modified)
+    }
+
+    fn last_at(&self) -> i64 {
+        self.touchers
+            .iter()
+            .map(|t| t.touch.last_at)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Internal key-handling result (translated to `ModalKeyResult` via the trait).
+enum FilesModalKeyResult {
+    Continue,
+    Close,
+    /// Jump to the message where the conversation touched the file
+    Selected {
+        session_id: String,
+        message_index: usize,
+    },
+}
+
+/// State for the files picker modal.
+pub struct FilesModalState {
+    /// All touched files, most recently touched first
+    files: Vec<TouchedFile>,
+    /// Project of the group selected when the modal opened
+    project: Option<PathBuf>,
+    /// Show files from every project instead of just `project`
+    all_projects: bool,
+    query: String,
+    /// Indices into `files` matching the query, with match ranges in the display path
+    visible: Vec<(usize, Vec<Range<usize>>)>,
+    list_state: ListState,
+    /// File whose conversations are shown (index into `files`)
+    opened: Option<usize>,
+    conversation_state: ListState,
+}
+
+impl FilesModalState {
+    pub fn new(mut files: Vec<TouchedFile>, project: Option<PathBuf>) -> Self {
+        files.sort_by_key(|f| std::cmp::Reverse(f.last_at()));
+        let mut state = Self {
+            files,
+            all_projects: project.is_none(),
+            project,
+            query: String::new(),
+            visible: Vec::new(),
+            list_state: ListState::default(),
+            opened: None,
+            conversation_state: ListState::default(),
+        };
+        state.refilter();
+        state
+    }
+
+    /// Path shown for a file: relative to the scoped project when inside it.
+    fn display_path<'a>(&self, path: &'a str) -> &'a str {
+        self.scope()
+            .and_then(|project| Path::new(path).strip_prefix(project).ok())
+            .and_then(Path::to_str)
+            .unwrap_or(path)
+    }
+
+    fn scope(&self) -> Option<&Path> {
+        self.project.as_deref().filter(|_| !self.all_projects)
+    }
+
+    fn refilter(&mut self) {
+        let scope = self.scope();
+        let mut visible: Vec<(usize, i64, Vec<Range<usize>>)> = self
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| scope.is_none_or(|p| Path::new(&f.path).starts_with(p)))
+            .filter_map(|(i, f)| {
+                if self.query.is_empty() {
+                    return Some((i, 0, Vec::new()));
+                }
+                fuzzy_match(&self.query, self.display_path(&f.path)).map(|m| (i, m.score, m.ranges))
+            })
+            .collect();
+        // Stable sort keeps recency order among equal scores
+        visible.sort_by_key(|(_, score, _)| std::cmp::Reverse(*score));
+        self.visible = visible
+            .into_iter()
+            .map(|(i, _, ranges)| (i, ranges))
+            .collect();
+        self.list_state
+            .select((!self.visible.is_empty()).then_some(0));
+    }
+
+    fn move_selection(state: &mut ListState, len: usize, delta: isize) {
+        if len == 0 {
+            return;
+        }
+        let current = state.selected().unwrap_or(0);
+        state.select(Some(current.saturating_add_signed(delta).min(len - 1)));
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> FilesModalKeyResult {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        if let Some(file) = self.opened.and_then(|i| self.files.get(i)) {
+            let len = file.touchers.len();
+            match key.code {
+                KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('h') | KeyCode::Left => {
+                    self.opened = None;
+                }
+                KeyCode::Char('q') => return FilesModalKeyResult::Close,
+                KeyCode::Char('j') | KeyCode::Down => {
+                    Self::move_selection(&mut self.conversation_state, len, 1);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    Self::move_selection(&mut self.conversation_state, len, -1);
+                }
+                KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => {
+                    let selected = self.conversation_state.selected().unwrap_or(0);
+                    if let Some(entry) = file.touchers.get(selected) {
+                        return FilesModalKeyResult::Selected {
+                            session_id: entry.session_id.clone(),
+                            message_index: entry.touch.message_index as usize,
+                        };
+                    }
+                }
+                _ => {}
+            }
+            return FilesModalKeyResult::Continue;
+        }
+
+        let len = self.visible.len();
+        match key.code {
+            KeyCode::Esc => return FilesModalKeyResult::Close,
+            KeyCode::Tab if self.project.is_some() => {
+                self.all_projects = !self.all_projects;
+                self.refilter();
+            }
+            KeyCode::Down => Self::move_selection(&mut self.list_state, len, 1),
+            KeyCode::Up => Self::move_selection(&mut self.list_state, len, -1),
+            KeyCode::Char('j') if ctrl => Self::move_selection(&mut self.list_state, len, 1),
+            KeyCode::Char('k') if ctrl => Self::move_selection(&mut self.list_state, len, -1),
+            KeyCode::PageDown => Self::move_selection(&mut self.list_state, len, 10),
+            KeyCode::PageUp => Self::move_selection(&mut self.list_state, len, -10),
+            KeyCode::Enter => {
+                let selected = self.list_state.selected().unwrap_or(0);
+                if let Some((idx, _)) = self.visible.get(selected) {
+                    self.opened = Some(*idx);
+                    self.conversation_state.select(Some(0));
+                }
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.query.push(c);
+                self.refilter();
+            }
+            KeyCode::Backspace if self.query.pop().is_some() => self.refilter(),
+            _ => {}
+        }
+        FilesModalKeyResult::Continue
+    }
+}
+
+impl super::Modal for FilesModalState {
+    fn handle_key_modal(&mut self, key: KeyEvent) -> super::ModalKeyResult {
+        match self.handle_key(key) {
+            FilesModalKeyResult::Continue => super::ModalKeyResult::Continue,
+            FilesModalKeyResult::Close => super::ModalKeyResult::Close,
+            FilesModalKeyResult::Selected {
+                session_id,
+                message_index,
+            } => super::ModalKeyResult::SearchSelected {
+                session_id,
+                message_index: Some(message_index),
+            },
+        }
+    }
+}
+
+/// Widget for rendering the files picker modal.
+pub struct FilesModal<'a> {
+    state: &'a mut FilesModalState,
+}
+
+impl<'a> FilesModal<'a> {
+    pub fn new(state: &'a mut FilesModalState) -> Self {
+        Self { state }
+    }
+
+    /// Calculate the modal area (centered, 70% width, 70% height).
+    pub fn calculate_area(total: Rect) -> Rect {
+        let width = (total.width * 70 / 100)
+            .max(50)
+            .min(total.width.saturating_sub(4));
+        let height = (total.height * 70 / 100)
+            .max(12)
+            .min(total.height.saturating_sub(4));
+
+        let x = (total.width.saturating_sub(width)) / 2;
+        let y = (total.height.saturating_sub(height)) / 2;
+
+        Rect::new(x, y, width, height)
+    }
+}
+
+impl Widget for FilesModal<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 40 || area.height < 10 {
+            return;
+        }
+
+        Clear.render(area, buf);
+
+        let outer_block = Block::default()
+            .title(" Files ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+
+        let inner = outer_block.inner(area);
+        outer_block.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Scope / file header
+            Constraint::Length(3), // Query input
+            Constraint::Min(3),    // List
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+        if self.state.opened.is_some() {
+            self.render_conversations(&chunks, buf);
+        } else {
+            self.render_files(&chunks, buf);
+        }
+    }
+}
+
+impl FilesModal<'_> {
+    fn render_files(self, chunks: &[Rect], buf: &mut Buffer) {
+        let scope = match (self.state.scope(), &self.state.project) {
+            (Some(project), _) => format!(" In {}", project.display()),
+            (None, Some(_)) => " In all projects".to_string(),
+            (None, None) => " All projects".to_string(),
+        };
+        let mut header = vec![Span::styled(scope, Style::default().fg(Color::Yellow))];
+        if self.state.project.is_some() {
+            header.push(Span::styled(
+                " (Tab to toggle)",
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        Paragraph::new(Line::from(header)).render(chunks[0], buf);
+
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(" File ");
+        let input_inner = input_block.inner(chunks[1]);
+        input_block.render(chunks[1], buf);
+        Paragraph::new(Line::from(vec![
+            Span::raw(self.state.query.clone()),
+            Span::styled(" ", Style::default().bg(Color::White)),
+        ]))
+        .render(input_inner, buf);
+
+        if self.state.visible.is_empty() {
+            let message = if self.state.files.is_empty() {
+                "No file edits found in conversation logs"
+            } else {
+                "No matching files"
+            };
+            Paragraph::new(message)
+                .style(Style::default().fg(Color::DarkGray))
+                .alignment(Alignment::Center)
+                .render(chunks[2], buf);
+        } else {
+            let dim = Style::default().fg(Color::DarkGray);
+            let items: Vec<ListItem> = self
+                .state
+                .visible
+                .iter()
+                .filter_map(|(idx, ranges)| {
+                    let file = self.state.files.get(*idx)?;
+                    let marker = if file.modified() {
+                        Span::styled("M ", Style::default().fg(Color::Yellow))
+                    } else {
+                        Span::styled("R ", dim)
+                    };
+                    let mut spans = vec![marker];
+                    spans.extend(highlight_spans(
+                        self.state.display_path(&file.path),
+                        ranges,
+                        Style::default(),
+                    ));
+                    spans.push(Span::styled(
+                        format!(
+                            "  {} conv · {}",
+                            file.touchers.len(),
+                            format_time(file.last_at())
+                        ),
+                        dim,
+                    ));
+                    Some(ListItem::new(Line::from(spans)))
+                })
+                .collect();
+            let list = List::new(items)
+                .highlight_style(
+                    Style::default()
+                        .bg(Color::DarkGray)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .highlight_symbol("> ");
+            ratatui::widgets::StatefulWidget::render(
+                list,
+                chunks[2],
+                buf,
+                &mut self.state.list_state,
+            );
+        }
+
+        render_help(
+            chunks[3],
+            buf,
+            &[
+                ("C-j/k", "nav"),
+                ("Enter", "conversations"),
+                ("Esc", "close"),
+            ],
+        );
+    }
+
+    fn render_conversations(self, chunks: &[Rect], buf: &mut Buffer) {
+        let FilesModalState {
+            files,
+            opened,
+            conversation_state,
+            ..
+        } = self.state;
+        let Some(file) = opened.and_then(|i| files.get(i)) else {
+            return;
+        };
+
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                " Conversations that touched ",
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(
+                file.path.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ]))
+        .render(chunks[0], buf);
+
+        let list_area = chunks[1].union(chunks[2]);
+        let dim = Style::default().fg(Color::DarkGray);
+        let items: Vec<ListItem> = file
+            .touchers
+            .iter()
+            .map(|entry| {
+                let (verb, style) = if entry.touch.modified {
+                    ("edited", Style::default().fg(Color::Yellow))
+                } else {
+                    ("read  ", dim)
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{} ", format_time(entry.touch.last_at)), dim),
+                    Span::styled(format!("{} ", verb), style),
+                    Span::styled(format!("{:>3}× ", entry.touch.count), dim),
+                    Span::raw(entry.title.clone()),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("> ");
+        ratatui::widgets::StatefulWidget::render(list, list_area, buf, conversation_state);
+
+        render_help(
+            chunks[3],
+            buf,
+            &[("j/k", "nav"), ("Enter", "open at edit"), ("Esc", "back")],
+        );
+    }
+}
+
+fn render_help(area: Rect, buf: &mut Buffer, keys: &[(&str, &str)]) {
+    let mut spans = Vec::new();
+    for (key, label) in keys {
+        spans.push(Span::styled(
+            format!(" {} ", key),
+            Style::default().fg(Color::Cyan),
+        ));
+        spans.push(Span::raw(format!("{} ", label)));
+    }
+    Paragraph::new(Line::from(spans))
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center)
+        .render(area, buf);
+}
+
+/// Local time of a touch, or a placeholder when the log had no timestamp.
+fn format_time(ms: i64) -> String {
+    if ms == 0 {
+        return "—               ".to_string();
+    }
+    Local
+        .timestamp_millis_opt(ms)
+        .single()
+        .map_or_else(String::new, |dt| dt.format("%Y-%m-%d %H:%M").to_string())
+}
@@ -0,0 +1,309 @@
+//! Named layout modal: save the current tiling layout, apply or delete saved ones.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Widget},
+};
+
+// ---------------------------------------------------------------------------
+// Data types
+// ---------------------------------------------------------------------------
+
+/// Sub-mode within the layout modal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutModalMode {
+    /// Navigating the saved layout list.
+    List,
+    /// Text input for the name of a layout being saved.
+    Input,
+}
+
+/// Internal key-handling result (translated to `ModalKeyResult` via the trait).
+enum LayoutModalKeyResult {
+    Continue,
+    Close,
+    Saved(String),
+    Applied(usize),
+    Deleted(usize),
+}
+
+// ---------------------------------------------------------------------------
+// State
+// ---------------------------------------------------------------------------
+
+/// State for the named layout modal dialog.
+pub struct LayoutModalState {
+    /// Snapshot of saved layout names (kept in sync by the `App` action methods).
+    pub layouts: Vec<String>,
+    /// Currently highlighted index in the list.
+    pub selected: usize,
+    /// Ratatui list widget state.
+    pub list_state: ListState,
+    /// Current sub-mode (list navigation vs text input).
+    pub mode: LayoutModalMode,
+    /// Text buffer for name input.
+    pub input_buffer: String,
+    /// Cursor position within `input_buffer`.
+    pub cursor_pos: usize,
+    /// Inline error message (displayed in red).
+    pub error_message: Option<String>,
+}
+
+impl LayoutModalState {
+    pub fn new(layouts: Vec<String>) -> Self {
+        let mut list_state = ListState::default();
+        if !layouts.is_empty() {
+            list_state.select(Some(0));
+        }
+        Self {
+            layouts,
+            selected: 0,
+            list_state,
+            mode: LayoutModalMode::List,
+            input_buffer: String::new(),
+            cursor_pos: 0,
+            error_message: None,
+        }
+    }
+
+    /// Replace the layout list after a save or delete, returning to list mode.
+    pub fn set_layouts(&mut self, layouts: Vec<String>) {
+        self.layouts = layouts;
+        self.mode = LayoutModalMode::List;
+        self.input_buffer.clear();
+        self.cursor_pos = 0;
+        self.error_message = None;
+        if self.layouts.is_empty() {
+            self.selected = 0;
+            self.list_state.select(None);
+        } else {
+            self.selected = self.selected.min(self.layouts.len() - 1);
+            self.list_state.select(Some(self.selected));
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> LayoutModalKeyResult {
+        match self.mode {
+            LayoutModalMode::List => self.handle_list_key(key),
+            LayoutModalMode::Input => self.handle_input_key(key),
+        }
+    }
+
+    // -- List mode -----------------------------------------------------------
+
+    fn handle_list_key(&mut self, key: KeyEvent) -> LayoutModalKeyResult {
+        match key.code {
+            KeyCode::Esc => LayoutModalKeyResult::Close,
+
+            // Navigation
+            KeyCode::Char('j') | KeyCode::Down if !self.layouts.is_empty() => {
+                self.selected = (self.selected + 1).min(self.layouts.len().saturating_sub(1));
+                self.list_state.select(Some(self.selected));
+                LayoutModalKeyResult::Continue
+            }
+            KeyCode::Char('k') | KeyCode::Up if !self.layouts.is_empty() => {
+                self.selected = self.selected.saturating_sub(1);
+                self.list_state.select(Some(self.selected));
+                LayoutModalKeyResult::Continue
+            }
+
+            // Save current layout under a new name
+            KeyCode::Char('n' | 's') => {
+                self.mode = LayoutModalMode::Input;
+                self.input_buffer.clear();
+                self.cursor_pos = 0;
+                self.error_message = None;
+                LayoutModalKeyResult::Continue
+            }
+
+            // Delete
+            KeyCode::Char('d' | 'x') if !self.layouts.is_empty() => {
+                LayoutModalKeyResult::Deleted(self.selected)
+            }
+
+            // Apply
+            KeyCode::Enter if !self.layouts.is_empty() => {
+                LayoutModalKeyResult::Applied(self.selected)
+            }
+
+            _ => LayoutModalKeyResult::Continue,
+        }
+    }
+
+    // -- Input mode ----------------------------------------------------------
+
+    fn handle_input_key(&mut self, key: KeyEvent) -> LayoutModalKeyResult {
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = LayoutModalMode::List;
+                self.input_buffer.clear();
+                self.cursor_pos = 0;
+                self.error_message = None;
+                LayoutModalKeyResult::Continue
+            }
+            KeyCode::Enter => {
+                let name = self.input_buffer.trim().to_string();
+                if name.is_empty() {
+                    self.error_message = Some("Name cannot be empty".to_string());
+                    return LayoutModalKeyResult::Continue;
+                }
+                LayoutModalKeyResult::Saved(name)
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.insert(self.cursor_pos, c);
+                self.cursor_pos += 1;
+                self.error_message = None;
+                LayoutModalKeyResult::Continue
+            }
+            KeyCode::Backspace => {
+                if self.cursor_pos > 0 {
+                    self.cursor_pos -= 1;
+                    self.input_buffer.remove(self.cursor_pos);
+                }
+                LayoutModalKeyResult::Continue
+            }
+            KeyCode::Left => {
+                self.cursor_pos = self.cursor_pos.saturating_sub(1);
+                LayoutModalKeyResult::Continue
+            }
+            KeyCode::Right => {
+                if self.cursor_pos < self.input_buffer.len() {
+                    self.cursor_pos += 1;
+                }
+                LayoutModalKeyResult::Continue
+            }
+            _ => LayoutModalKeyResult::Continue,
+        }
+    }
+}
+
+impl super::Modal for LayoutModalState {
+    fn handle_key_modal(&mut self, key: KeyEvent) -> super::ModalKeyResult {
+        match self.handle_key(key) {
+            LayoutModalKeyResult::Continue => super::ModalKeyResult::Continue,
+            LayoutModalKeyResult::Close => super::ModalKeyResult::Close,
+            LayoutModalKeyResult::Saved(name) => super::ModalKeyResult::LayoutSaved(name),
+            LayoutModalKeyResult::Applied(idx) => super::ModalKeyResult::LayoutApplied(idx),
+            LayoutModalKeyResult::Deleted(idx) => super::ModalKeyResult::LayoutDeleted(idx),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Widget
+// ---------------------------------------------------------------------------
+
+/// Widget for rendering the named layout modal.
+pub struct LayoutModal<'a> {
+    state: &'a LayoutModalState,
+}
+
+impl<'a> LayoutModal<'a> {
+    pub fn new(state: &'a LayoutModalState) -> Self {
+        Self { state }
+    }
+
+    /// Calculate the modal area (centered, ~40% width, ~40% height).
+    pub fn calculate_area(total: Rect) -> Rect {
+        let width = (total.width * 40 / 100)
+            .max(40)
+            .min(total.width.saturating_sub(4));
+        let height = (total.height * 40 / 100)
+            .max(12)
+            .min(total.height.saturating_sub(4));
+
+        let x = (total.width.saturating_sub(width)) / 2;
+        let y = (total.height.saturating_sub(height)) / 2;
+
+        Rect::new(x, y, width, height)
+    }
+}
+
+impl Widget for LayoutModal<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 30 || area.height < 8 {
+            return;
+        }
+
+        Clear.render(area, buf);
+
+        let outer_block = Block::default()
+            .title(" Layouts ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+
+        let inner = outer_block.inner(area);
+        outer_block.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Min(3),    // Layout list
+            Constraint::Length(1), // Input line or error message
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+        render_layout_list(self.state, chunks[0], buf);
+
+        if self.state.mode == LayoutModalMode::Input {
+            let spans = vec![
+                Span::styled("Save as: ", Style::default().fg(Color::White)),
+                Span::raw(self.state.input_buffer.clone()),
+                Span::styled(" ", Style::default().bg(Color::White)),
+            ];
+            Paragraph::new(Line::from(spans)).render(chunks[1], buf);
+        } else if let Some(ref error) = self.state.error_message {
+            Paragraph::new(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(Color::Red),
+            )))
+            .render(chunks[1], buf);
+        }
+
+        let help = if self.state.mode == LayoutModalMode::Input {
+            " Enter save  Esc cancel"
+        } else if self.state.layouts.is_empty() {
+            " n save current  Esc close"
+        } else {
+            " Enter apply  n save current  d delete  Esc close"
+        };
+        Paragraph::new(help)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .render(chunks[2], buf);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Render helpers
+// ---------------------------------------------------------------------------
+
+fn render_layout_list(state: &LayoutModalState, area: Rect, buf: &mut Buffer) {
+    if state.layouts.is_empty() {
+        Paragraph::new("No saved layouts. Press n to save the current one.")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .render(area, buf);
+        return;
+    }
+
+    let items: Vec<ListItem> = state
+        .layouts
+        .iter()
+        .map(|name| ListItem::new(Line::from(name.clone())))
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+
+    let mut list_state = state.list_state.clone();
+    ratatui::widgets::StatefulWidget::render(list, area, buf, &mut list_state);
+}
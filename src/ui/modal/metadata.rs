@@ -0,0 +1,298 @@
+//! Conversation metadata modal: edit a custom title, tags and a note.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
+};
+
+use crate::claude::metadata::ConversationMeta;
+
+/// Which input has focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Title,
+    Tags,
+    Note,
+}
+
+impl Field {
+    fn next(self) -> Self {
+        match self {
+            Self::Title => Self::Tags,
+            Self::Tags => Self::Note,
+            Self::Note => Self::Title,
+        }
+    }
+
+    fn prev(self) -> Self {
+        match self {
+            Self::Title => Self::Note,
+            Self::Tags => Self::Title,
+            Self::Note => Self::Tags,
+        }
+    }
+}
+
+/// A text input with a cursor (byte offset, always on a char boundary).
+#[derive(Default)]
+struct Input {
+    text: String,
+    cursor: usize,
+}
+
+impl Input {
+    fn new(text: String) -> Self {
+        Self {
+            cursor: text.len(),
+            text,
+        }
+    }
+
+    fn insert(&mut self, c: char) {
+        self.text.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    fn backspace(&mut self) {
+        if let Some(c) = self.text[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+            self.text.remove(self.cursor);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.text.len() {
+            self.text.remove(self.cursor);
+        }
+    }
+
+    fn left(&mut self) {
+        if let Some(c) = self.text[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+        }
+    }
+
+    fn right(&mut self) {
+        if let Some(c) = self.text[self.cursor..].chars().next() {
+            self.cursor += c.len_utf8();
+        }
+    }
+
+    /// Lines of the text, with the cursor drawn when `focused`.
+    fn lines(&self, focused: bool) -> Vec<Line<'static>> {
+        let cursor_style = Style::default().bg(Color::White).fg(Color::Black);
+        let mut lines = Vec::new();
+        let mut spans = Vec::new();
+        let mut current = String::new();
+        for (i, c) in self.text.char_indices() {
+            if focused && i == self.cursor {
+                spans.push(Span::raw(std::mem::take(&mut current)));
+                let shown = if c == '\n' {
+                    " ".to_string()
+                } else {
+                    c.to_string()
+                };
+                spans.push(Span::styled(shown, cursor_style));
+                if c != '\n' {
+                    continue;
+                }
+            }
+            if c == '\n' {
+                spans.push(Span::raw(std::mem::take(&mut current)));
+                lines.push(Line::from(std::mem::take(&mut spans)));
+            } else {
+                current.push(c);
+            }
+        }
+        spans.push(Span::raw(current));
+        if focused && self.cursor == self.text.len() {
+            spans.push(Span::styled(" ", cursor_style));
+        }
+        lines.push(Line::from(spans));
+        lines
+    }
+}
+
+/// State for the conversation metadata modal.
+pub struct MetadataModalState {
+    session_id: String,
+    /// Shown in the modal title
+    conversation_title: String,
+    title: Input,
+    tags: Input,
+    note: Input,
+    focus: Field,
+}
+
+impl MetadataModalState {
+    pub fn new(
+        session_id: String,
+        conversation_title: String,
+        meta: Option<&ConversationMeta>,
+    ) -> Self {
+        let meta = meta.cloned().unwrap_or_default();
+        Self {
+            session_id,
+            conversation_title,
+            tags: Input::new(meta.tags_text()),
+            title: Input::new(meta.title.unwrap_or_default()),
+            note: Input::new(meta.note.unwrap_or_default()),
+            focus: Field::Title,
+        }
+    }
+
+    fn input_mut(&mut self) -> &mut Input {
+        match self.focus {
+            Field::Title => &mut self.title,
+            Field::Tags => &mut self.tags,
+            Field::Note => &mut self.note,
+        }
+    }
+
+    fn meta(&self) -> ConversationMeta {
+        ConversationMeta::new(&self.title.text, &self.tags.text, &self.note.text)
+    }
+
+    /// Handle key input. Returns the edited metadata when saved.
+    fn handle_key(&mut self, key: KeyEvent) -> Option<ConversationMeta> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return (key.code == KeyCode::Char('s')).then(|| self.meta());
+        }
+        match key.code {
+            KeyCode::Tab => self.focus = self.focus.next(),
+            KeyCode::BackTab => self.focus = self.focus.prev(),
+            // The note is multi-line; Enter saves from the single-line fields
+            KeyCode::Enter if self.focus == Field::Note => self.note.insert('\n'),
+            KeyCode::Enter => return Some(self.meta()),
+            KeyCode::Char(c) => self.input_mut().insert(c),
+            KeyCode::Backspace => self.input_mut().backspace(),
+            KeyCode::Delete => self.input_mut().delete(),
+            KeyCode::Left => self.input_mut().left(),
+            KeyCode::Right => self.input_mut().right(),
+            KeyCode::Home => self.input_mut().cursor = 0,
+            KeyCode::End => {
+                let input = self.input_mut();
+                input.cursor = input.text.len();
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+impl super::Modal for MetadataModalState {
+    fn handle_key_modal(&mut self, key: KeyEvent) -> super::ModalKeyResult {
+        match self.handle_key(key) {
+            Some(meta) => super::ModalKeyResult::MetadataSaved {
+                session_id: self.session_id.clone(),
+                meta,
+            },
+            None => super::ModalKeyResult::Continue,
+        }
+    }
+}
+
+/// Widget for rendering the conversation metadata modal.
+pub struct MetadataModal<'a> {
+    state: &'a MetadataModalState,
+}
+
+impl<'a> MetadataModal<'a> {
+    pub fn new(state: &'a MetadataModalState) -> Self {
+        Self { state }
+    }
+
+    /// Calculate the modal area (centered, 60% width, ~20 lines).
+    pub fn calculate_area(total: Rect) -> Rect {
+        let width = (total.width * 60 / 100)
+            .max(50)
+            .min(total.width.saturating_sub(4));
+        let height = 20u16.min(total.height.saturating_sub(4));
+
+        let x = (total.width.saturating_sub(width)) / 2;
+        let y = (total.height.saturating_sub(height)) / 2;
+
+        Rect::new(x, y, width, height)
+    }
+}
+
+impl Widget for MetadataModal<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 30 || area.height < 14 {
+            return;
+        }
+
+        Clear.render(area, buf);
+
+        let title = format!(
+            " Edit — {} ",
+            self.state
+                .conversation_title
+                .lines()
+                .next()
+                .unwrap_or_default()
+        );
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(3), // Title
+            Constraint::Length(3), // Tags
+            Constraint::Min(4),    // Note
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+        let state = self.state;
+        let fields = [
+            (
+                Field::Title,
+                &state.title,
+                " Title (blank for the summary) ",
+            ),
+            (Field::Tags, &state.tags, " Tags (#a #b) "),
+            (Field::Note, &state.note, " Note "),
+        ];
+        for ((field, input, label), chunk) in fields.into_iter().zip(chunks.iter()) {
+            let focused = state.focus == field;
+            let border = if focused {
+                Color::Yellow
+            } else {
+                Color::DarkGray
+            };
+            let input_block = Block::default()
+                .title(label)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border));
+            Paragraph::new(input.lines(focused))
+                .wrap(Wrap { trim: false })
+                .block(input_block)
+                .render(*chunk, buf);
+        }
+
+        let key_style = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+        let mut spans = Vec::new();
+        for (key, label) in [
+            ("Tab", "next field"),
+            ("Enter/^S", "save"),
+            ("Esc", "cancel"),
+        ] {
+            spans.push(Span::styled(format!(" {} ", key), key_style));
+            spans.push(Span::raw(format!("{} ", label)));
+        }
+        Paragraph::new(Line::from(spans))
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .render(chunks[3], buf);
+    }
+}
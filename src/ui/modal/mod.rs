@@ -4,16 +4,39 @@ use std::path::PathBuf;
 
 use crossterm::event::KeyEvent;
 
+use crate::claude::export::{ExportFormat, ExportOptions};
+use crate::claude::metadata::ConversationMeta;
+
+pub mod archive;
+pub mod branches;
+pub mod commits;
+pub mod export;
+pub mod files;
+pub mod layout;
+pub mod metadata;
 pub mod new_project;
 pub mod profile;
 pub mod search;
+pub mod stats;
+pub mod tools;
+pub mod trash;
 pub mod workspace;
 pub mod worktree;
 pub mod worktree_search;
 
+pub use archive::{ArchiveModal, ArchiveModalState};
+pub use branches::{BranchesModal, BranchesModalState};
+pub use commits::{CommitsModal, CommitsModalState};
+pub use export::{ExportDestination, ExportModal, ExportModalState};
+pub use files::{FileTouchEntry, FilesModal, FilesModalState, TouchedFile};
+pub use layout::{LayoutModal, LayoutModalState};
+pub use metadata::{MetadataModal, MetadataModalState};
 pub use new_project::{NewProjectModal, NewProjectModalState, NewProjectTab};
 pub use profile::{ProfileModal, ProfileModalState};
 pub use search::{SearchKeyResult, SearchModal, SearchModalState};
+pub use stats::{StatsModal, StatsModalState};
+pub use tools::{ToolsModal, ToolsModalState};
+pub use trash::{TrashModal, TrashModalState};
 pub use workspace::{WorkspaceModal, WorkspaceModalState};
 pub use worktree::{WorktreeModal, WorktreeModalState};
 pub use worktree_search::{
@@ -28,8 +51,12 @@ pub enum ModalKeyResult {
     Close,
     /// A path was selected (NewProject modal).
     PathSelected(PathBuf),
-    /// A search result was selected (Search modal).
-    SearchSelected(String),
+    /// A search result was selected (Search modal). `message_index` is set
+    /// for content hits so the transcript can open at the match.
+    SearchSelected {
+        session_id: String,
+        message_index: Option<usize>,
+    },
     /// Search query changed (Search modal).
     SearchQueryChanged,
     /// A branch name was entered (Worktree modal).
@@ -53,6 +80,45 @@ pub enum ModalKeyResult {
     ProfileDeleted(usize),
     /// A profile was activated (by index).
     ProfileActivated(usize),
+    /// The current layout was saved under a name.
+    LayoutSaved(String),
+    /// A saved layout was applied (by index).
+    LayoutApplied(usize),
+    /// A saved layout was deleted (by index).
+    LayoutDeleted(usize),
+    /// Export the conversation with the chosen settings.
+    ExportConfirmed {
+        session_id: String,
+        format: ExportFormat,
+        options: ExportOptions,
+        destination: ExportDestination,
+    },
+    /// Show a conversation's transcript at a branch tip (Branches modal).
+    BranchOpened { session_id: String, tip: String },
+    /// Resume a conversation; `fork_at` forks it at an earlier assistant
+    /// message instead of continuing the latest branch (Branches modal).
+    BranchResumed {
+        session_id: String,
+        fork_at: Option<String>,
+    },
+    /// A commit hash should be copied to the clipboard (Commits modal).
+    CommitYanked(String),
+    /// A trash entry, by ID, should be restored (Trash modal).
+    TrashRestored(String),
+    /// A trash entry, by ID, should be deleted permanently (Trash modal).
+    TrashPurged(String),
+    /// The previewed conversations should be archived (Archive modal).
+    ArchiveConfirmed {
+        session_ids: Vec<String>,
+        by_rule: bool,
+    },
+    /// The previewed conversations should be moved to the trash (Archive modal).
+    TrashConfirmed { session_ids: Vec<String> },
+    /// A conversation's title, tags and note were edited (Metadata modal).
+    MetadataSaved {
+        session_id: String,
+        meta: ConversationMeta,
+    },
 }
 
 /// Trait for unified modal key dispatch.
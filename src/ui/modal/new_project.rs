@@ -186,28 +186,20 @@ impl NewProjectModalState {
                 self.cursor_pos += 1;
                 self.error_message = None;
             }
-            KeyCode::Backspace => {
-                if self.cursor_pos > 0 {
-                    self.cursor_pos -= 1;
-                    self.path_input.remove(self.cursor_pos);
-                    self.error_message = None;
-                }
+            KeyCode::Backspace if self.cursor_pos > 0 => {
+                self.cursor_pos -= 1;
+                self.path_input.remove(self.cursor_pos);
+                self.error_message = None;
             }
-            KeyCode::Delete => {
-                if self.cursor_pos < self.path_input.len() {
-                    self.path_input.remove(self.cursor_pos);
-                    self.error_message = None;
-                }
+            KeyCode::Delete if self.cursor_pos < self.path_input.len() => {
+                self.path_input.remove(self.cursor_pos);
+                self.error_message = None;
             }
-            KeyCode::Left => {
-                if self.cursor_pos > 0 {
-                    self.cursor_pos -= 1;
-                }
+            KeyCode::Left if self.cursor_pos > 0 => {
+                self.cursor_pos -= 1;
             }
-            KeyCode::Right => {
-                if self.cursor_pos < self.path_input.len() {
-                    self.cursor_pos += 1;
-                }
+            KeyCode::Right if self.cursor_pos < self.path_input.len() => {
+                self.cursor_pos += 1;
             }
             KeyCode::Home => {
                 self.cursor_pos = 0;
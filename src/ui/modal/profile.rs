@@ -101,8 +101,7 @@ impl ProfileModalState {
 
             // Navigation
             KeyCode::Char('j') | KeyCode::Down if !self.profiles.is_empty() => {
-                self.selected =
-                    (self.selected + 1).min(self.profiles.len().saturating_sub(1));
+                self.selected = (self.selected + 1).min(self.profiles.len().saturating_sub(1));
                 self.list_state.select(Some(self.selected));
                 self.error_message = None;
                 ProfileModalKeyResult::Continue
@@ -390,7 +389,10 @@ fn render_profile_list(state: &ProfileModalState, area: Rect, buf: &mut Buffer)
         .enumerate()
         .map(|(i, name)| {
             let is_active = state.active_profile == Some(i);
-            let mut spans = vec![Span::styled(name.clone(), Style::default().fg(Color::White))];
+            let mut spans = vec![Span::styled(
+                name.clone(),
+                Style::default().fg(Color::White),
+            )];
             if is_active {
                 spans.push(Span::styled(
                     " (active)",
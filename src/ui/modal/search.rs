@@ -1,5 +1,7 @@
 //! Search modal dialog for finding conversations.
 
+use std::ops::Range;
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     buffer::Buffer,
@@ -109,7 +111,10 @@ impl SearchModalState {
             // Select result
             KeyCode::Enter => {
                 if let Some(result) = self.results.get(self.selected) {
-                    SearchKeyResult::Selected(result.conversation.session_id.clone())
+                    SearchKeyResult::Selected(
+                        result.conversation.session_id.clone(),
+                        result.message_index,
+                    )
                 } else {
                     SearchKeyResult::Continue
                 }
@@ -179,8 +184,11 @@ impl super::Modal for SearchModalState {
         match self.handle_key(key) {
             SearchKeyResult::Continue => super::ModalKeyResult::Continue,
             SearchKeyResult::QueryChanged => super::ModalKeyResult::SearchQueryChanged,
-            SearchKeyResult::Selected(session_id) => {
-                super::ModalKeyResult::SearchSelected(session_id)
+            SearchKeyResult::Selected(session_id, message_index) => {
+                super::ModalKeyResult::SearchSelected {
+                    session_id,
+                    message_index,
+                }
             }
         }
     }
@@ -192,8 +200,8 @@ pub enum SearchKeyResult {
     Continue,
     /// Query changed, need to re-search
     QueryChanged,
-    /// User selected a result (session_id)
-    Selected(String),
+    /// User selected a result (session_id, matching message for content hits)
+    Selected(String, Option<usize>),
 }
 
 /// Widget for rendering the search modal
@@ -344,7 +352,7 @@ impl SearchModal<'_> {
     fn render_results(&mut self, area: Rect, buf: &mut Buffer) {
         if self.state.results.is_empty() {
             let message = if self.state.query.is_empty() {
-                "Type to search..."
+                "Type to search...  (branch: project: after: before: msgs:>N is:archived is:running is:attention tool: \"phrase\" -word)"
             } else {
                 "No results found"
             };
@@ -368,21 +376,32 @@ impl SearchModal<'_> {
                     .map(|n| n.to_string_lossy().into_owned())
                     .unwrap_or_else(|| "Unknown".to_string());
 
-                let display = result
-                    .conversation
-                    .summary
-                    .as_ref()
-                    .unwrap_or(&result.conversation.display);
-
-                let truncated = truncate_str(display, 57);
-
-                ListItem::new(Line::from(vec![
-                    Span::styled(
-                        format!("[{}] ", project_name),
-                        Style::default().fg(Color::Yellow),
-                    ),
-                    Span::raw(truncated),
-                ]))
+                let truncated = truncate_str(&result.conversation.display, 57);
+
+                let mut spans = vec![Span::styled(
+                    format!("[{}] ", project_name),
+                    Style::default().fg(Color::Yellow),
+                )];
+                spans.extend(highlight_spans(
+                    &truncated,
+                    &result.title_ranges,
+                    Style::default(),
+                ));
+                // Matches outside the title show the matching text after it;
+                // content hits also show where in the conversation the match is
+                if result.message_index.is_some() || !result.snippet_ranges.is_empty() {
+                    let prefix = result
+                        .message_index
+                        .map_or_else(|| "  ".to_string(), |m| format!("  #{} ", m + 1));
+                    let dim = Style::default().fg(Color::DarkGray);
+                    spans.push(Span::styled(prefix, dim));
+                    spans.extend(highlight_spans(
+                        &result.preview_snippet,
+                        &result.snippet_ranges,
+                        dim,
+                    ));
+                }
+                ListItem::new(Line::from(spans))
             })
             .collect();
 
@@ -416,6 +435,33 @@ impl SearchModal<'_> {
     }
 }
 
+/// Split `text` into spans, highlighting the given char ranges.
+pub(crate) fn highlight_spans(
+    text: &str,
+    ranges: &[Range<usize>],
+    base: Style,
+) -> Vec<Span<'static>> {
+    let highlight = base.fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_highlighted = false;
+
+    for (i, c) in text.chars().enumerate() {
+        let highlighted = ranges.iter().any(|r| r.contains(&i));
+        if highlighted != current_highlighted && !current.is_empty() {
+            let style = if current_highlighted { highlight } else { base };
+            spans.push(Span::styled(std::mem::take(&mut current), style));
+        }
+        current_highlighted = highlighted;
+        current.push(c);
+    }
+    if !current.is_empty() {
+        let style = if current_highlighted { highlight } else { base };
+        spans.push(Span::styled(current, style));
+    }
+    spans
+}
+
 /// Safely truncate a string to a maximum number of characters (not bytes).
 /// Appends "..." if truncated.
 fn truncate_str(s: &str, max_chars: usize) -> String {
@@ -0,0 +1,275 @@
+//! Usage stats modal: token usage and cost rolled up by project, group,
+//! profile, day, model or conversation.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Widget},
+};
+
+use crate::claude::usage::{format_cost, format_tokens, UsageDimension, UsageReport, UsageRow};
+
+/// Internal key-handling result (translated to `ModalKeyResult` via the trait).
+enum StatsModalKeyResult {
+    Continue,
+    Close,
+}
+
+/// State for the usage stats modal.
+pub struct StatsModalState {
+    /// One report per dimension, in `UsageDimension::ALL` order
+    reports: Vec<(UsageDimension, UsageReport)>,
+    /// Index into `reports` of the dimension being shown
+    current: usize,
+    /// Highlighted row
+    selected: usize,
+    list_state: ListState,
+}
+
+impl StatsModalState {
+    pub fn new(reports: Vec<(UsageDimension, UsageReport)>) -> Self {
+        let mut state = Self {
+            reports,
+            current: 0,
+            selected: 0,
+            list_state: ListState::default(),
+        };
+        state.reset_selection();
+        state
+    }
+
+    /// Replace the reports with fresh ones, keeping the dimension shown and
+    /// the selection in range.
+    pub fn set_reports(&mut self, reports: Vec<(UsageDimension, UsageReport)>) {
+        self.reports = reports;
+        self.current = self.current.min(self.reports.len().saturating_sub(1));
+        let count = self.row_count();
+        self.selected = self.selected.min(count.saturating_sub(1));
+        self.list_state.select((count > 0).then_some(self.selected));
+    }
+
+    fn report(&self) -> Option<&(UsageDimension, UsageReport)> {
+        self.reports.get(self.current)
+    }
+
+    fn row_count(&self) -> usize {
+        self.report().map_or(0, |(_, r)| r.rows.len())
+    }
+
+    fn reset_selection(&mut self) {
+        self.selected = 0;
+        self.list_state.select((self.row_count() > 0).then_some(0));
+    }
+
+    fn cycle(&mut self, forward: bool) {
+        if self.reports.is_empty() {
+            return;
+        }
+        let len = self.reports.len();
+        self.current = if forward {
+            (self.current + 1) % len
+        } else {
+            (self.current + len - 1) % len
+        };
+        self.reset_selection();
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let count = self.row_count();
+        if count == 0 {
+            return;
+        }
+        self.selected = self.selected.saturating_add_signed(delta).min(count - 1);
+        self.list_state.select(Some(self.selected));
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> StatsModalKeyResult {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return StatsModalKeyResult::Close,
+            KeyCode::Tab | KeyCode::Char('l') | KeyCode::Right => self.cycle(true),
+            KeyCode::BackTab | KeyCode::Char('h') | KeyCode::Left => self.cycle(false),
+            KeyCode::Char('j') | KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_selection(-1),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::Char('g') | KeyCode::Home => self.move_selection(isize::MIN),
+            KeyCode::Char('G') | KeyCode::End => self.move_selection(isize::MAX),
+            _ => {}
+        }
+        StatsModalKeyResult::Continue
+    }
+}
+
+impl super::Modal for StatsModalState {
+    fn handle_key_modal(&mut self, key: KeyEvent) -> super::ModalKeyResult {
+        match self.handle_key(key) {
+            StatsModalKeyResult::Continue => super::ModalKeyResult::Continue,
+            StatsModalKeyResult::Close => super::ModalKeyResult::Close,
+        }
+    }
+}
+
+/// Widget for rendering the usage stats modal.
+pub struct StatsModal<'a> {
+    state: &'a mut StatsModalState,
+}
+
+impl<'a> StatsModal<'a> {
+    pub fn new(state: &'a mut StatsModalState) -> Self {
+        Self { state }
+    }
+
+    /// Calculate the modal area (centered, ~70% width, ~70% height).
+    pub fn calculate_area(total: Rect) -> Rect {
+        let width = (total.width * 70 / 100)
+            .max(60)
+            .min(total.width.saturating_sub(4));
+        let height = (total.height * 70 / 100)
+            .max(12)
+            .min(total.height.saturating_sub(4));
+
+        let x = (total.width.saturating_sub(width)) / 2;
+        let y = (total.height.saturating_sub(height)) / 2;
+
+        Rect::new(x, y, width, height)
+    }
+}
+
+/// Width of each numeric column.
+const NUM_WIDTH: usize = 9;
+/// Numeric columns: input, output, cache write, cache read, cost.
+const NUM_COLUMNS: usize = 5;
+
+impl Widget for StatsModal<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 40 || area.height < 8 {
+            return;
+        }
+
+        Clear.render(area, buf);
+
+        let outer_block = Block::default()
+            .title(" Usage ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+
+        let inner = outer_block.inner(area);
+        outer_block.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Dimension tabs
+            Constraint::Length(1), // Column header
+            Constraint::Min(1),    // Rows
+            Constraint::Length(1), // Total
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+        let label_width = (inner.width as usize).saturating_sub(NUM_WIDTH * NUM_COLUMNS + 2);
+        let StatsModalState {
+            reports,
+            current,
+            list_state,
+            ..
+        } = self.state;
+        let Some((dimension, report)) = reports.get(*current) else {
+            return;
+        };
+        let dimension = *dimension;
+
+        // Dimension tabs
+        let mut tabs = Vec::new();
+        for (dim, _) in reports.iter() {
+            let style = if *dim == dimension {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            tabs.push(Span::styled(format!(" {} ", dim.name()), style));
+            tabs.push(Span::raw(" "));
+        }
+        Paragraph::new(Line::from(tabs)).render(chunks[0], buf);
+
+        let header = format!(
+            "  {:<label_width$}{:>w$}{:>w$}{:>w$}{:>w$}{:>w$}",
+            dimension.name(),
+            "input",
+            "output",
+            "cache w",
+            "cache r",
+            "cost",
+            w = NUM_WIDTH,
+        );
+        Paragraph::new(header)
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .render(chunks[1], buf);
+
+        if report.rows.is_empty() {
+            let message = if dimension == UsageDimension::Profile {
+                "No profiles configured"
+            } else {
+                "No usage recorded"
+            };
+            Paragraph::new(message)
+                .style(Style::default().fg(Color::DarkGray))
+                .alignment(Alignment::Center)
+                .render(chunks[2], buf);
+        } else {
+            let items: Vec<ListItem> = report
+                .rows
+                .iter()
+                .map(|row| ListItem::new(format_row(row, label_width)))
+                .collect();
+            let list = List::new(items)
+                .highlight_style(
+                    Style::default()
+                        .bg(Color::DarkGray)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .highlight_symbol("> ");
+            ratatui::widgets::StatefulWidget::render(list, chunks[2], buf, list_state);
+        }
+
+        Paragraph::new(format!("  {}", format_row(&report.total, label_width)))
+            .style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .render(chunks[3], buf);
+
+        Paragraph::new(" Tab/h/l breakdown  j/k scroll  Esc close")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .render(chunks[4], buf);
+    }
+}
+
+/// One table row: label padded/truncated to `label_width`, then the numbers.
+fn format_row(row: &UsageRow, label_width: usize) -> String {
+    let label: String = if row.label.chars().count() > label_width {
+        row.label
+            .chars()
+            .take(label_width.saturating_sub(1))
+            .chain(std::iter::once('…'))
+            .collect()
+    } else {
+        row.label.clone()
+    };
+    format!(
+        "{:<label_width$}{:>w$}{:>w$}{:>w$}{:>w$}{:>w$}",
+        label,
+        format_tokens(row.tokens.input),
+        format_tokens(row.tokens.output),
+        format_tokens(row.tokens.cache_write),
+        format_tokens(row.tokens.cache_read),
+        format_cost(row.cost),
+        w = NUM_WIDTH,
+    )
+}
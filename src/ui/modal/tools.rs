@@ -0,0 +1,457 @@
+//! Tool stats modal: tool calls, failures, permission denials and turn
+//! durations of the selected conversation, and the same rolled up by project.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Widget},
+};
+
+use crate::claude::tool_stats::{
+    format_duration, format_percent, ToolCount, ToolProjectRow, ToolStats, TOOL_COLUMNS,
+};
+
+/// Consecutive failures of one tool from which a streak is highlighted.
+const STREAK_WARNING: u32 = 3;
+
+/// Which table the modal shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ToolsTab {
+    Conversation,
+    Projects,
+}
+
+/// State for the tool stats modal.
+pub struct ToolsModalState {
+    /// Title and stats of the selected conversation
+    conversation: Option<(String, ToolStats)>,
+    /// Per-tool rows of the conversation, most called first
+    tool_rows: Vec<(String, ToolCount)>,
+    projects: Vec<ToolProjectRow>,
+    /// All projects together
+    total: ToolStats,
+    tab: ToolsTab,
+    list_state: ListState,
+}
+
+impl ToolsModalState {
+    pub fn new(conversation: Option<(String, ToolStats)>, projects: Vec<ToolProjectRow>) -> Self {
+        let tab = if conversation.is_some() {
+            ToolsTab::Conversation
+        } else {
+            ToolsTab::Projects
+        };
+        let mut state = Self {
+            conversation: None,
+            tool_rows: Vec::new(),
+            projects: Vec::new(),
+            total: ToolStats::default(),
+            tab,
+            list_state: ListState::default(),
+        };
+        state.set_stats(conversation, projects);
+        state.reset_selection();
+        state
+    }
+
+    /// Replace the stats with fresh ones, keeping the tab shown and the
+    /// selection in range.
+    pub fn set_stats(
+        &mut self,
+        conversation: Option<(String, ToolStats)>,
+        projects: Vec<ToolProjectRow>,
+    ) {
+        let mut tool_rows: Vec<(String, ToolCount)> = conversation
+            .as_ref()
+            .map(|(_, stats)| {
+                stats
+                    .tools
+                    .iter()
+                    .map(|(name, count)| (name.clone(), *count))
+                    .collect()
+            })
+            .unwrap_or_default();
+        tool_rows.sort_by_key(|(_, count)| std::cmp::Reverse(count.calls));
+
+        let mut total = ToolStats::default();
+        for row in &projects {
+            total.merge(&row.stats);
+        }
+        self.conversation = conversation;
+        self.tool_rows = tool_rows;
+        self.projects = projects;
+        self.total = total;
+
+        let count = self.row_count();
+        let selected = self.list_state.selected().unwrap_or(0);
+        self.list_state
+            .select(count.checked_sub(1).map(|last| selected.min(last)));
+    }
+
+    fn row_count(&self) -> usize {
+        match self.tab {
+            ToolsTab::Conversation => self.tool_rows.len(),
+            ToolsTab::Projects => self.projects.len(),
+        }
+    }
+
+    fn reset_selection(&mut self) {
+        self.list_state.select((self.row_count() > 0).then_some(0));
+    }
+
+    fn switch_tab(&mut self) {
+        self.tab = match self.tab {
+            ToolsTab::Conversation => ToolsTab::Projects,
+            ToolsTab::Projects => ToolsTab::Conversation,
+        };
+        self.reset_selection();
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let count = self.row_count();
+        if count == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        self.list_state
+            .select(Some(current.saturating_add_signed(delta).min(count - 1)));
+    }
+}
+
+impl super::Modal for ToolsModalState {
+    fn handle_key_modal(&mut self, key: KeyEvent) -> super::ModalKeyResult {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return super::ModalKeyResult::Close,
+            KeyCode::Tab
+            | KeyCode::BackTab
+            | KeyCode::Char('h' | 'l')
+            | KeyCode::Left
+            | KeyCode::Right => {
+                self.switch_tab();
+            }
+            KeyCode::Char('j') | KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_selection(-1),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::Char('g') | KeyCode::Home => self.move_selection(isize::MIN),
+            KeyCode::Char('G') | KeyCode::End => self.move_selection(isize::MAX),
+            _ => {}
+        }
+        super::ModalKeyResult::Continue
+    }
+}
+
+/// Widget for rendering the tool stats modal.
+pub struct ToolsModal<'a> {
+    state: &'a mut ToolsModalState,
+}
+
+impl<'a> ToolsModal<'a> {
+    pub fn new(state: &'a mut ToolsModalState) -> Self {
+        Self { state }
+    }
+
+    /// Calculate the modal area (centered, ~80% width, ~70% height).
+    pub fn calculate_area(total: Rect) -> Rect {
+        let width = (total.width * 80 / 100)
+            .max(60)
+            .min(total.width.saturating_sub(4));
+        let height = (total.height * 70 / 100)
+            .max(14)
+            .min(total.height.saturating_sub(4));
+
+        let x = (total.width.saturating_sub(width)) / 2;
+        let y = (total.height.saturating_sub(height)) / 2;
+
+        Rect::new(x, y, width, height)
+    }
+}
+
+/// Width of each numeric column.
+const NUM_WIDTH: usize = 7;
+
+impl Widget for ToolsModal<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 40 || area.height < 10 {
+            return;
+        }
+
+        Clear.render(area, buf);
+
+        let outer_block = Block::default()
+            .title(" Tools ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = outer_block.inner(area);
+        outer_block.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Tabs
+            Constraint::Length(1), // Title
+            Constraint::Length(1), // Summary
+            Constraint::Length(1), // Failure streak
+            Constraint::Length(1), // Column header
+            Constraint::Min(1),    // Rows
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+        let mut tabs = Vec::new();
+        for (tab, name) in [
+            (ToolsTab::Conversation, "conversation"),
+            (ToolsTab::Projects, "projects"),
+        ] {
+            let style = if tab == self.state.tab {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            tabs.push(Span::styled(format!(" {} ", name), style));
+            tabs.push(Span::raw(" "));
+        }
+        Paragraph::new(Line::from(tabs)).render(chunks[0], buf);
+
+        match self.state.tab {
+            ToolsTab::Conversation => render_conversation(self.state, &chunks, buf),
+            ToolsTab::Projects => render_projects(self.state, &chunks, buf),
+        }
+
+        Paragraph::new(" Tab/h/l conversation/projects  j/k scroll  Esc close")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .render(chunks[6], buf);
+    }
+}
+
+fn render_conversation(state: &mut ToolsModalState, chunks: &[Rect], buf: &mut Buffer) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let Some((title, stats)) = &state.conversation else {
+        Paragraph::new("No conversation selected")
+            .style(dim)
+            .alignment(Alignment::Center)
+            .render(chunks[5], buf);
+        return;
+    };
+
+    Paragraph::new(Line::styled(
+        format!(" {}", title),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))
+    .render(chunks[1], buf);
+    Paragraph::new(summary_line(stats)).render(chunks[2], buf);
+    Paragraph::new(streak_line(stats)).render(chunks[3], buf);
+
+    let label_width = (chunks[4].width as usize).saturating_sub(NUM_WIDTH * 4 + 2);
+    Paragraph::new(format!(
+        "  {:<label_width$}{:>w$}{:>w$}{:>w$}{:>w$}",
+        "tool",
+        "calls",
+        "errors",
+        "err%",
+        "denied",
+        w = NUM_WIDTH,
+    ))
+    .style(Style::default().add_modifier(Modifier::BOLD))
+    .render(chunks[4], buf);
+
+    if state.tool_rows.is_empty() {
+        Paragraph::new("No tool calls")
+            .style(dim)
+            .alignment(Alignment::Center)
+            .render(chunks[5], buf);
+        return;
+    }
+    let items: Vec<ListItem> = state
+        .tool_rows
+        .iter()
+        .map(|(name, count)| {
+            let share = if count.calls == 0 {
+                0.0
+            } else {
+                count.errors as f64 / count.calls as f64
+            };
+            ListItem::new(format!(
+                "{}{:>w$}{:>w$}{:>w$}{:>w$}",
+                pad(name, label_width),
+                count.calls,
+                count.errors,
+                format_percent(share),
+                count.denied,
+                w = NUM_WIDTH,
+            ))
+        })
+        .collect();
+    render_list(items, chunks[5], buf, &mut state.list_state);
+}
+
+fn render_projects(state: &mut ToolsModalState, chunks: &[Rect], buf: &mut Buffer) {
+    let dim = Style::default().fg(Color::DarkGray);
+    Paragraph::new(Line::styled(
+        " All projects",
+        Style::default().add_modifier(Modifier::BOLD),
+    ))
+    .render(chunks[1], buf);
+    Paragraph::new(summary_line(&state.total)).render(chunks[2], buf);
+
+    // Streak of the highlighted project
+    let selected = state
+        .list_state
+        .selected()
+        .and_then(|i| state.projects.get(i));
+    if let Some(row) = selected {
+        Paragraph::new(streak_line(&row.stats)).render(chunks[3], buf);
+    }
+
+    // turns, avg turn, calls, one per tool column, MCP, err%, deny%
+    let columns = TOOL_COLUMNS.len() + 6;
+    let label_width = (chunks[4].width as usize).saturating_sub(NUM_WIDTH * columns + 2);
+    let mut header = format!(
+        "  {:<label_width$}{:>w$}{:>w$}{:>w$}",
+        "project",
+        "turns",
+        "avg",
+        "calls",
+        w = NUM_WIDTH
+    );
+    for name in TOOL_COLUMNS.iter().chain(["MCP"].iter()) {
+        header.push_str(&format!("{:>w$}", name, w = NUM_WIDTH));
+    }
+    header.push_str(&format!("{:>w$}{:>w$}", "err%", "deny%", w = NUM_WIDTH));
+    Paragraph::new(header)
+        .style(Style::default().add_modifier(Modifier::BOLD))
+        .render(chunks[4], buf);
+
+    if state.projects.is_empty() {
+        Paragraph::new("No tool calls recorded")
+            .style(dim)
+            .alignment(Alignment::Center)
+            .render(chunks[5], buf);
+        return;
+    }
+    let items: Vec<ListItem> = state
+        .projects
+        .iter()
+        .map(|row| ListItem::new(project_row(row, label_width)))
+        .collect();
+    render_list(items, chunks[5], buf, &mut state.list_state);
+}
+
+fn render_list(items: Vec<ListItem>, area: Rect, buf: &mut Buffer, list_state: &mut ListState) {
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ");
+    ratatui::widgets::StatefulWidget::render(list, area, buf, list_state);
+}
+
+/// Turns, turn durations, calls, failures and denials on one line.
+fn summary_line(stats: &ToolStats) -> Line<'static> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let duration = |ms: Option<i64>| ms.map_or_else(|| "-".to_string(), format_duration);
+    let error_style = if stats.errors() > 0 {
+        Style::default().fg(Color::Red)
+    } else {
+        dim
+    };
+    let denial_style = if stats.denied() > 0 {
+        Style::default().fg(Color::Yellow)
+    } else {
+        dim
+    };
+    Line::from(vec![
+        Span::styled(
+            format!(
+                " {} turns · avg {} · longest {} · {} calls · ",
+                stats.turns(),
+                duration(stats.average_turn_ms()),
+                duration(stats.longest_turn_ms()),
+                stats.calls()
+            ),
+            dim,
+        ),
+        Span::styled(
+            format!(
+                "{} failed ({})",
+                stats.errors(),
+                format_percent(stats.error_rate())
+            ),
+            error_style,
+        ),
+        Span::styled(" · ", dim),
+        Span::styled(
+            format!(
+                "{} denied ({})",
+                stats.denied(),
+                format_percent(stats.denial_rate())
+            ),
+            denial_style,
+        ),
+    ])
+}
+
+/// The longest failure streak, highlighted when it looks like a loop.
+fn streak_line(stats: &ToolStats) -> Line<'static> {
+    match &stats.failure_streak {
+        Some(streak) if streak.count >= STREAK_WARNING => Line::styled(
+            format!(
+                " ⚠ {} consecutive failed {} calls",
+                streak.count, streak.tool
+            ),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        Some(streak) => Line::styled(
+            format!(
+                " Longest failure streak: {} × {}",
+                streak.count, streak.tool
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+        None => Line::default(),
+    }
+}
+
+/// One row of the per-project table.
+fn project_row(row: &ToolProjectRow, label_width: usize) -> String {
+    let stats = &row.stats;
+    let mut line = format!(
+        "{}{:>w$}{:>w$}{:>w$}",
+        pad(&row.label, label_width),
+        stats.turns(),
+        stats
+            .average_turn_ms()
+            .map_or_else(|| "-".to_string(), format_duration),
+        stats.calls(),
+        w = NUM_WIDTH,
+    );
+    for name in TOOL_COLUMNS.iter().chain(["MCP"].iter()) {
+        line.push_str(&format!("{:>w$}", stats.calls_of(name), w = NUM_WIDTH));
+    }
+    line.push_str(&format!(
+        "{:>w$}{:>w$}",
+        format_percent(stats.error_rate()),
+        format_percent(stats.denial_rate()),
+        w = NUM_WIDTH
+    ));
+    line
+}
+
+/// `text` padded or truncated to exactly `width` characters.
+fn pad(text: &str, width: usize) -> String {
+    if text.chars().count() > width {
+        text.chars()
+            .take(width.saturating_sub(1))
+            .chain(std::iter::once('…'))
+            .collect()
+    } else {
+        format!("{:<width$}", text)
+    }
+}
@@ -0,0 +1,247 @@
+//! Trash modal: deleted conversations, with restore and permanent purge.
+
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Widget},
+};
+
+use crate::claude::trash::TrashEntry;
+
+/// Internal key-handling result (translated to `ModalKeyResult` via the trait).
+enum TrashModalKeyResult {
+    Continue,
+    Close,
+    Restore(usize),
+    Purge(usize),
+}
+
+/// State for the trash modal.
+pub struct TrashModalState {
+    entries: Vec<TrashEntry>,
+    /// Days until trashed conversations are purged (None = kept forever)
+    purge_after_days: Option<u32>,
+    list_state: ListState,
+}
+
+impl TrashModalState {
+    pub fn new(entries: Vec<TrashEntry>, purge_after_days: Option<u32>) -> Self {
+        let mut state = Self {
+            entries: Vec::new(),
+            purge_after_days,
+            list_state: ListState::default(),
+        };
+        state.set_entries(entries);
+        state
+    }
+
+    /// Replace the entries after a restore or purge, keeping the selection in range.
+    pub fn set_entries(&mut self, entries: Vec<TrashEntry>) {
+        let selected = self.list_state.selected().unwrap_or(0);
+        self.list_state
+            .select(entries.len().checked_sub(1).map(|last| selected.min(last)));
+        self.entries = entries;
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.entries.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0);
+        self.list_state
+            .select(Some(current.saturating_add_signed(delta).min(len - 1)));
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> TrashModalKeyResult {
+        let selected = self.list_state.selected();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return TrashModalKeyResult::Close,
+            KeyCode::Char('j') | KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_selection(-1),
+            KeyCode::Char('g') | KeyCode::Home => self.list_state.select_first(),
+            KeyCode::Char('G') | KeyCode::End => {
+                self.move_selection(self.entries.len() as isize);
+            }
+            KeyCode::Enter | KeyCode::Char('r') => {
+                if let Some(idx) = selected {
+                    return TrashModalKeyResult::Restore(idx);
+                }
+            }
+            KeyCode::Char('D') => {
+                if let Some(idx) = selected {
+                    return TrashModalKeyResult::Purge(idx);
+                }
+            }
+            _ => {}
+        }
+        TrashModalKeyResult::Continue
+    }
+}
+
+impl super::Modal for TrashModalState {
+    fn handle_key_modal(&mut self, key: KeyEvent) -> super::ModalKeyResult {
+        match self.handle_key(key) {
+            TrashModalKeyResult::Continue => super::ModalKeyResult::Continue,
+            TrashModalKeyResult::Close => super::ModalKeyResult::Close,
+            TrashModalKeyResult::Restore(idx) => {
+                super::ModalKeyResult::TrashRestored(self.entries[idx].id.clone())
+            }
+            TrashModalKeyResult::Purge(idx) => {
+                super::ModalKeyResult::TrashPurged(self.entries[idx].id.clone())
+            }
+        }
+    }
+}
+
+/// Widget for rendering the trash modal.
+pub struct TrashModal<'a> {
+    state: &'a mut TrashModalState,
+}
+
+impl<'a> TrashModal<'a> {
+    pub fn new(state: &'a mut TrashModalState) -> Self {
+        Self { state }
+    }
+
+    /// Calculate the modal area (centered, 70% width, 70% height).
+    pub fn calculate_area(total: Rect) -> Rect {
+        let width = (total.width * 70 / 100)
+            .max(50)
+            .min(total.width.saturating_sub(4));
+        let height = (total.height * 70 / 100)
+            .max(12)
+            .min(total.height.saturating_sub(4));
+
+        let x = (total.width.saturating_sub(width)) / 2;
+        let y = (total.height.saturating_sub(height)) / 2;
+
+        Rect::new(x, y, width, height)
+    }
+}
+
+impl Widget for TrashModal<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 40 || area.height < 10 {
+            return;
+        }
+
+        Clear.render(area, buf);
+
+        let outer_block = Block::default()
+            .title(" Trash ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = outer_block.inner(area);
+        outer_block.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Summary
+            Constraint::Length(1), // Spacer
+            Constraint::Min(3),    // List
+            Constraint::Length(1), // Help bar
+        ])
+        .split(inner);
+
+        let count = self.state.entries.len();
+        let retention = match self.state.purge_after_days {
+            Some(days) => format!("  purged after {} days", days),
+            None => "  kept until purged".to_string(),
+        };
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!(
+                    " {} conversation{}",
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(retention, Style::default().fg(Color::DarkGray)),
+        ]))
+        .render(chunks[0], buf);
+
+        if self.state.entries.is_empty() {
+            Paragraph::new("The trash is empty")
+                .style(Style::default().fg(Color::DarkGray))
+                .alignment(Alignment::Center)
+                .render(chunks[2], buf);
+        } else {
+            let width = chunks[2].width.saturating_sub(2) as usize;
+            let items: Vec<ListItem> = self
+                .state
+                .entries
+                .iter()
+                .map(|entry| entry_item(entry, width))
+                .collect();
+            let list = List::new(items)
+                .highlight_style(
+                    Style::default()
+                        .bg(Color::DarkGray)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .highlight_symbol("> ");
+            ratatui::widgets::StatefulWidget::render(
+                list,
+                chunks[2],
+                buf,
+                &mut self.state.list_state,
+            );
+        }
+
+        let mut spans = Vec::new();
+        for (key, label) in [
+            ("j/k", "nav"),
+            ("Enter", "restore"),
+            ("D", "delete forever"),
+            ("Esc", "close"),
+        ] {
+            spans.push(Span::styled(
+                format!(" {} ", key),
+                Style::default().fg(Color::Cyan),
+            ));
+            spans.push(Span::raw(format!("{} ", label)));
+        }
+        Paragraph::new(Line::from(spans))
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .render(chunks[3], buf);
+    }
+}
+
+/// Two lines per entry: the title, then its project and when it was deleted.
+fn entry_item(entry: &TrashEntry, width: usize) -> ListItem<'static> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let detail = format!(
+        "  {} · deleted {}",
+        entry.project_path.display(),
+        format_time(entry.trashed_at)
+    );
+    ListItem::new(vec![
+        Line::raw(truncate(
+            entry.title.lines().next().unwrap_or_default(),
+            width,
+        )),
+        Line::styled(truncate(&detail, width), dim),
+    ])
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let mut out: String = text.chars().take(max.saturating_sub(1)).collect();
+        out.push('…');
+        out
+    }
+}
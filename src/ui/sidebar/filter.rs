@@ -32,6 +32,13 @@ impl super::SidebarState {
         self.filter_active = false;
     }
 
+    /// Replace the filter text, leaving the input inactive
+    pub fn set_filter(&mut self, query: &str) {
+        self.filter_query = query.to_string();
+        self.filter_cursor_pos = self.filter_query.len();
+        self.filter_active = false;
+    }
+
     /// Whether there is a non-empty filter query
     pub fn has_filter(&self) -> bool {
         !self.filter_query.is_empty()
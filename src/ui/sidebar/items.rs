@@ -3,7 +3,10 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::app::EphemeralSession;
+use crate::claude::conversation::Conversation;
 use crate::claude::grouping::ConversationGroup;
+use crate::claude::metadata::format_tags;
+use crate::search::SearchQuery;
 
 use super::{
     ArchiveFilter, ControlAction, SectionKind, SidebarContext, SidebarItem, SidebarState, PAGE_SIZE,
@@ -20,8 +23,8 @@ pub fn build_sidebar_items(
 ) -> Vec<SidebarItem> {
     let mut items = Vec::new();
 
-    let filter_lower = ctx.filter_query.to_lowercase();
-    let has_text_filter = !filter_lower.is_empty();
+    let filter = ctx.filter;
+    let has_text_filter = !filter.is_empty();
 
     let has_workspaces = !ctx.workspaces.is_empty();
 
@@ -36,7 +39,26 @@ pub fn build_sidebar_items(
         }
     };
 
-    // Always show WorkspaceSectionHeader at top
+    // Starred conversations come first, in their own section
+    let pinned = pinned_conversations(ctx);
+    if !pinned.is_empty() {
+        items.push(SidebarItem::PinnedHeader);
+        for (group_key, index, conv) in pinned {
+            items.push(SidebarItem::Conversation {
+                group_key: group_key.clone(),
+                index,
+            });
+            for child in 0..ctx.subagents.get(&conv.session_id).map_or(0, Vec::len) {
+                items.push(SidebarItem::Subagent {
+                    group_key: group_key.clone(),
+                    index,
+                    child,
+                });
+            }
+        }
+    }
+
+    // Always show WorkspaceSectionHeader above the groups
     items.push(SidebarItem::WorkspaceSectionHeader);
 
     if has_workspaces {
@@ -75,7 +97,7 @@ pub fn build_sidebar_items(
                     collapsed,
                     visible_conversations,
                     visible_groups,
-                    &filter_lower,
+                    filter,
                     has_text_filter,
                 );
             }
@@ -139,7 +161,7 @@ pub fn build_sidebar_items(
                                 collapsed,
                                 visible_conversations,
                                 visible_groups,
-                                &filter_lower,
+                                filter,
                                 has_text_filter,
                             );
                         }
@@ -185,7 +207,7 @@ pub fn build_sidebar_items(
                     collapsed,
                     visible_conversations,
                     visible_groups,
-                    &filter_lower,
+                    filter,
                     has_text_filter,
                 );
             }
@@ -281,7 +303,7 @@ fn render_groups_with_limit(
     collapsed: &HashSet<String>,
     visible_conversations: &HashMap<String, usize>,
     visible_groups: &HashMap<String, usize>,
-    filter_lower: &str,
+    filter: &SearchQuery,
     has_text_filter: bool,
 ) {
     let filtered_groups: Vec<&ConversationGroup> = if ctx.hide_inactive {
@@ -308,7 +330,7 @@ fn render_groups_with_limit(
             ctx,
             collapsed,
             visible_conversations,
-            filter_lower,
+            filter,
             has_text_filter,
         );
     }
@@ -358,7 +380,7 @@ fn render_group_items(
     ctx: &SidebarContext,
     collapsed: &HashSet<String>,
     visible_conversations: &HashMap<String, usize>,
-    filter_lower: &str,
+    filter: &SearchQuery,
     has_text_filter: bool,
 ) {
     // Check if a non-plan-impl conversation in this group has a running PTY (parent)
@@ -377,19 +399,19 @@ fn render_group_items(
     let group_key = group.key();
 
     // When text filter is active, check if group name matches
-    let group_name_matches =
-        has_text_filter && group.display_name().to_lowercase().contains(filter_lower);
+    let group_name_matches = has_text_filter && filter.matches_text(&[&group.display_name()]);
 
     // Check if group has any conversations visible with current archive + text filter
     let has_visible_convs = group.conversations().iter().any(|conv| {
-        !is_hidden_plan_implementation(conv, ctx.running_sessions, group_has_running_parent)
+        conv.pinned_at.is_none()
+            && !is_hidden_plan_implementation(conv, ctx.running_sessions, group_has_running_parent)
             && should_show_conversation(
                 conv,
                 ctx.archive_filter,
                 ctx.running_sessions,
                 ctx.hide_inactive,
             )
-            && (!has_text_filter || group_name_matches || conv_matches_filter(conv, filter_lower))
+            && (!has_text_filter || conv_matches_filter(conv, filter, group_name_matches))
     });
 
     // Skip groups with no visible conversations
@@ -429,6 +451,8 @@ fn render_group_items(
         let filtered_indices: Vec<usize> = conversations
             .iter()
             .enumerate()
+            // Starred conversations are listed in the Pinned section instead
+            .filter(|(_, conv)| conv.pinned_at.is_none())
             .filter(|(_, conv)| {
                 !is_hidden_plan_implementation(conv, ctx.running_sessions, group_has_running_parent)
             })
@@ -441,7 +465,7 @@ fn render_group_items(
                 )
             })
             .filter(|(_, conv)| {
-                !has_text_filter || group_name_matches || conv_matches_filter(conv, filter_lower)
+                !has_text_filter || conv_matches_filter(conv, filter, group_name_matches)
             })
             .map(|(idx, _)| idx)
             .collect();
@@ -454,12 +478,23 @@ fn render_group_items(
             SidebarState::visible_count(visible_conversations, &group_key).min(total)
         };
 
-        // Add visible conversations
-        for &index in filtered_indices.iter().take(vis) {
+        // Add visible conversations, plan implementations under their planner
+        for (index, _) in nest_plan_implementations(conversations, &filtered_indices)
+            .into_iter()
+            .take(vis)
+        {
             items.push(SidebarItem::Conversation {
                 group_key: group_key.clone(),
                 index,
             });
+            let subagents = ctx.subagents.get(&conversations[index].session_id);
+            for child in 0..subagents.map_or(0, Vec::len) {
+                items.push(SidebarItem::Subagent {
+                    group_key: group_key.clone(),
+                    index,
+                    child,
+                });
+            }
         }
 
         // Emit section controls for conversations (only when not filtering)
@@ -499,9 +534,43 @@ fn render_group_items(
     }
 }
 
+/// Starred conversations passing the current filters, in the order they were
+/// pinned, with their group key and index within the group.
+pub(super) fn pinned_conversations<'a>(
+    ctx: &SidebarContext<'a>,
+) -> Vec<(String, usize, &'a Conversation)> {
+    let mut pinned: Vec<(String, usize, &Conversation)> = ctx
+        .groups
+        .iter()
+        .flat_map(|group| {
+            let group_key = group.key();
+            group
+                .conversations()
+                .iter()
+                .enumerate()
+                .filter(|(_, conv)| conv.pinned_at.is_some())
+                .map(move |(index, conv)| (group_key.clone(), index, conv))
+        })
+        .filter(|(_, _, conv)| {
+            should_show_conversation(
+                conv,
+                ctx.archive_filter,
+                ctx.running_sessions,
+                ctx.hide_inactive,
+            ) && (ctx.filter.is_empty() || conv_matches_filter(conv, ctx.filter, false))
+        })
+        .collect();
+    pinned.sort_by_key(|(_, _, conv)| conv.pinned_at);
+    pinned
+}
+
 /// Check if a project has any conversations visible under the current filters
 /// (archive filter, hide_inactive, running sessions, plan implementation hiding).
-fn project_has_visible_conversations(groups: &[&ConversationGroup], ctx: &SidebarContext) -> bool {
+/// Starred conversations don't count: they are shown in the Pinned section.
+pub(super) fn project_has_visible_conversations(
+    groups: &[&ConversationGroup],
+    ctx: &SidebarContext,
+) -> bool {
     groups.iter().any(|group| {
         let group_has_running_parent = group
             .conversations()
@@ -509,7 +578,12 @@ fn project_has_visible_conversations(groups: &[&ConversationGroup], ctx: &Sideba
             .any(|c| !c.is_plan_implementation && ctx.running_sessions.contains(&c.session_id));
 
         group.conversations().iter().any(|conv| {
-            !is_hidden_plan_implementation(conv, ctx.running_sessions, group_has_running_parent)
+            conv.pinned_at.is_none()
+                && !is_hidden_plan_implementation(
+                    conv,
+                    ctx.running_sessions,
+                    group_has_running_parent,
+                )
                 && should_show_conversation(
                     conv,
                     ctx.archive_filter,
@@ -576,16 +650,66 @@ pub fn group_has_active_content(
     false
 }
 
-/// Check if a conversation matches the text filter (case-insensitive)
+/// Check if a conversation matches the filter query: field filters must pass,
+/// and text terms must match its title/summary (or the group name).
 pub(super) fn conv_matches_filter(
-    conv: &crate::claude::conversation::Conversation,
-    filter_lower: &str,
+    conv: &Conversation,
+    filter: &SearchQuery,
+    group_name_matches: bool,
 ) -> bool {
-    conv.display.to_lowercase().contains(filter_lower)
-        || conv
-            .summary
-            .as_ref()
-            .is_some_and(|s| s.to_lowercase().contains(filter_lower))
+    let tags = format_tags(&conv.tags);
+    let fields = [
+        conv.display.as_str(),
+        conv.summary.as_deref().unwrap_or_default(),
+        tags.as_str(),
+        conv.note.as_deref().unwrap_or_default(),
+    ];
+    filter.matches_filters(conv)
+        && !filter.excludes(&fields)
+        && (group_name_matches || filter.matches_text(&fields))
+}
+
+/// Order a group's listed conversations (`indices`) so that plan
+/// implementations follow the conversation that planned them, when it is
+/// listed too. Returns each index with its nesting depth.
+pub(super) fn nest_plan_implementations(
+    conversations: &[Conversation],
+    indices: &[usize],
+) -> Vec<(usize, usize)> {
+    let listed: HashMap<&str, usize> = indices
+        .iter()
+        .map(|&i| (conversations[i].session_id.as_str(), i))
+        .collect();
+    let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut roots = Vec::new();
+    for &i in indices {
+        let parent = conversations[i]
+            .plan_parent
+            .as_deref()
+            .and_then(|p| listed.get(p).copied())
+            .filter(|&p| p != i);
+        match parent {
+            Some(p) => children.entry(p).or_default().push(i),
+            None => roots.push(i),
+        }
+    }
+
+    let mut nested = Vec::with_capacity(indices.len());
+    let mut visited = HashSet::new();
+    // Conversations only reachable through a cycle of links become roots
+    for root in roots.into_iter().chain(indices.iter().copied()) {
+        let mut stack = vec![(root, 0)];
+        while let Some((i, depth)) = stack.pop() {
+            if !visited.insert(i) {
+                continue;
+            }
+            nested.push((i, depth));
+            if let Some(kids) = children.get(&i) {
+                stack.extend(kids.iter().rev().map(|&k| (k, depth + 1)));
+            }
+        }
+    }
+    nested
 }
 
 /// Check if a plan implementation conversation should be hidden.
@@ -596,7 +720,7 @@ pub(super) fn conv_matches_filter(
 /// Once the parent dies or the user directly activates the plan impl (giving it its own
 /// PTY), it becomes visible.
 pub(super) fn is_hidden_plan_implementation(
-    conv: &crate::claude::conversation::Conversation,
+    conv: &Conversation,
     running_sessions: &HashSet<String>,
     group_has_running_parent: bool,
 ) -> bool {
@@ -607,7 +731,7 @@ pub(super) fn is_hidden_plan_implementation(
 
 /// Check if a conversation should be shown based on archive filter and other criteria
 pub(super) fn should_show_conversation(
-    conv: &crate::claude::conversation::Conversation,
+    conv: &Conversation,
     archive_filter: ArchiveFilter,
     running_sessions: &HashSet<String>,
     hide_inactive: bool,
@@ -10,6 +10,9 @@ use ratatui::widgets::ListState;
 
 use crate::app::EphemeralSession;
 use crate::claude::grouping::ConversationGroup;
+use crate::claude::subagents::Subagent;
+use crate::claude::ClaudeDir;
+use crate::search::SearchQuery;
 
 // Re-export public API
 pub use filter::FilterKeyResult;
@@ -33,6 +36,8 @@ pub struct SidebarContext<'a> {
     pub archive_filter: ArchiveFilter,
     /// Current filter query text (empty = no filter)
     pub filter_query: &'a str,
+    /// `filter_query` parsed with the search query language
+    pub filter: &'a SearchQuery,
     /// Whether the filter input is actively accepting keystrokes
     pub filter_active: bool,
     /// Cursor position within the filter input (only used when filter_active)
@@ -41,6 +46,13 @@ pub struct SidebarContext<'a> {
     pub workspaces: &'a [String],
     /// Name of the active profile (None = "All" mode or no profiles configured)
     pub active_profile_name: Option<&'a str>,
+    /// Cost of each session in USD (for the totals shown next to items)
+    pub session_costs: &'a HashMap<String, f64>,
+    /// Subagents listed under expanded conversations, by session ID
+    pub subagents: &'a HashMap<String, Vec<Subagent>>,
+    /// Claude data directories; with more than one, conversations are
+    /// tagged with the one they came from
+    pub claude_dirs: &'a [ClaudeDir],
 }
 
 /// Archive filter modes for the sidebar
@@ -215,8 +227,10 @@ pub enum ControlAction {
 }
 
 /// Represents an item in the flattened sidebar list
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SidebarItem {
+    /// Header of the starred conversations section (only shown when there are some)
+    PinnedHeader,
     /// Always-visible section header for workspaces
     WorkspaceSectionHeader,
     /// Collapsible project header grouping worktrees/directories
@@ -244,6 +258,15 @@ pub enum SidebarItem {
         /// Index into the group's conversation list
         index: usize,
     },
+    /// A subagent listed under its expanded parent conversation
+    Subagent {
+        /// Key of the parent conversation's group
+        group_key: String,
+        /// Index of the parent in the group's conversation list
+        index: usize,
+        /// Index into the parent's subagents
+        child: usize,
+    },
     /// A running session that hasn't been saved yet (temp session)
     EphemeralSession {
         /// Session identifier
@@ -271,6 +294,9 @@ impl SidebarItem {
     /// Whether this item can be selected/highlighted by the cursor.
     /// Non-interactive decorative items (section headers) return false.
     pub fn is_selectable(&self) -> bool {
-        !matches!(self, SidebarItem::WorkspaceSectionHeader)
+        !matches!(
+            self,
+            SidebarItem::PinnedHeader | SidebarItem::WorkspaceSectionHeader
+        )
     }
 }
@@ -10,11 +10,18 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph, StatefulWidget, Widget},
 };
 
+use crate::claude::conversation::Conversation;
+use crate::claude::data_dir::source_of;
 use crate::claude::grouping::ConversationGroup;
+use crate::claude::health::Health;
+use crate::claude::metadata::format_tags;
+use crate::claude::usage::{format_cost, format_tokens};
+use crate::search::SearchQuery;
 
 use super::items::{
     conv_matches_filter, group_has_active_content, is_hidden_plan_implementation,
-    project_has_active_content, should_show_conversation, visible_group_count,
+    nest_plan_implementations, pinned_conversations, project_has_active_content,
+    project_has_visible_conversations, should_show_conversation, visible_group_count,
 };
 use super::{ArchiveFilter, ControlAction, SectionKind, SidebarContext, SidebarState, PAGE_SIZE};
 
@@ -177,8 +184,8 @@ fn build_list_items(
     let mut current_index: usize = 0;
 
     // Prepare case-insensitive filter query
-    let filter_lower = ctx.filter_query.to_lowercase();
-    let has_text_filter = !filter_lower.is_empty();
+    let filter = ctx.filter;
+    let has_text_filter = !filter.is_empty();
 
     let has_workspaces = !ctx.workspaces.is_empty();
 
@@ -193,7 +200,34 @@ fn build_list_items(
         }
     };
 
-    // WorkspaceSectionHeader always at top — show profile name if active
+    // Starred conversations first, numbered for quick access
+    let pinned = pinned_conversations(ctx);
+    if !pinned.is_empty() {
+        let line_num = format_relative_line_number(current_index, selected_index);
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(line_num, Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                "\u{2605} Pinned",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ])));
+        current_index += 1;
+        for (number, (_, _, conv)) in pinned.into_iter().enumerate() {
+            render_conversation_items(
+                &mut items,
+                &mut current_index,
+                conv,
+                ctx,
+                selected_index,
+                " ",
+                (number < 9).then_some(number + 1),
+            );
+        }
+    }
+
+    // WorkspaceSectionHeader above the groups — show profile name if active
     let header_text = match ctx.active_profile_name {
         Some(name) => name.to_string(),
         None => "Workspaces".to_string(),
@@ -221,11 +255,17 @@ fn build_list_items(
                 continue;
             }
 
+            // Skip projects where all conversations are hidden by current filters
+            if !project_has_visible_conversations(groups, ctx) {
+                continue;
+            }
+
             let is_project_collapsed = collapsed_projects.contains(project_key);
             render_project_header(
                 &mut items,
                 &mut current_index,
                 project_name,
+                groups_cost(groups, ctx),
                 is_project_collapsed,
                 selected_index,
                 " ",
@@ -242,7 +282,7 @@ fn build_list_items(
                     visible_conversations,
                     visible_groups,
                     selected_index,
-                    &filter_lower,
+                    filter,
                     has_text_filter,
                     2,
                 );
@@ -262,12 +302,11 @@ fn build_list_items(
                 .map(|(_, _, g)| visible_group_count(g, ctx))
                 .sum();
 
-            // When hide_inactive is on, only show OtherHeader if at least one
-            // "other" group has active content.
-            let show_other = !ctx.hide_inactive
-                || all_other_groups.iter().any(|g| {
-                    group_has_active_content(g, ctx.running_sessions, ctx.ephemeral_sessions)
-                });
+            // Only show OtherHeader if at least one "other" project has visible
+            // conversations (accounts for archive filter and hide_inactive).
+            let show_other = other_projects
+                .iter()
+                .any(|(_, _, groups)| project_has_visible_conversations(groups, ctx));
 
             if show_other {
                 let arrow = if other_collapsed {
@@ -301,11 +340,17 @@ fn build_list_items(
                             continue;
                         }
 
+                        // Skip projects where all conversations are hidden by current filters
+                        if !project_has_visible_conversations(groups, ctx) {
+                            continue;
+                        }
+
                         let is_project_collapsed = collapsed_projects.contains(project_key);
                         render_project_header(
                             &mut items,
                             &mut current_index,
                             project_name,
+                            groups_cost(groups, ctx),
                             is_project_collapsed,
                             selected_index,
                             " ",
@@ -322,7 +367,7 @@ fn build_list_items(
                                 visible_conversations,
                                 visible_groups,
                                 selected_index,
-                                &filter_lower,
+                                filter,
                                 has_text_filter,
                                 2,
                             );
@@ -349,11 +394,17 @@ fn build_list_items(
                 continue;
             }
 
+            // Skip projects where all conversations are hidden by current filters
+            if !project_has_visible_conversations(groups, ctx) {
+                continue;
+            }
+
             let is_project_collapsed = collapsed_projects.contains(project_key);
             render_project_header(
                 &mut items,
                 &mut current_index,
                 project_name,
+                groups_cost(groups, ctx),
                 is_project_collapsed,
                 selected_index,
                 "",
@@ -370,7 +421,7 @@ fn build_list_items(
                     visible_conversations,
                     visible_groups,
                     selected_index,
-                    &filter_lower,
+                    filter,
                     has_text_filter,
                     1,
                 );
@@ -386,6 +437,7 @@ fn render_project_header(
     items: &mut Vec<ListItem<'static>>,
     current_index: &mut usize,
     name: &str,
+    cost: f64,
     is_collapsed: bool,
     selected_index: Option<usize>,
     indent: &str,
@@ -393,7 +445,7 @@ fn render_project_header(
     let arrow = if is_collapsed { "\u{25b8}" } else { "\u{25be}" };
     let header = format!("{}{} {}", indent, arrow, name);
     let line_num = format_relative_line_number(*current_index, selected_index);
-    items.push(ListItem::new(Line::from(vec![
+    let mut spans = vec![
         Span::styled(line_num, Style::default().fg(Color::DarkGray)),
         Span::styled(
             header,
@@ -401,10 +453,31 @@ fn render_project_header(
                 .add_modifier(Modifier::BOLD)
                 .fg(Color::Cyan),
         ),
-    ])));
+    ];
+    spans.extend(cost_span(cost));
+    items.push(ListItem::new(Line::from(spans)));
     *current_index += 1;
 }
 
+/// Dim cost suffix, omitted when nothing was spent.
+fn cost_span(cost: f64) -> Option<Span<'static>> {
+    (cost >= 0.005).then(|| {
+        Span::styled(
+            format!(" {}", format_cost(cost)),
+            Style::default().fg(Color::DarkGray),
+        )
+    })
+}
+
+/// Total cost of all conversations in the given groups.
+fn groups_cost(groups: &[&ConversationGroup], ctx: &SidebarContext) -> f64 {
+    groups
+        .iter()
+        .flat_map(|g| g.conversations())
+        .filter_map(|c| ctx.session_costs.get(&c.session_id))
+        .sum()
+}
+
 /// Render an AddWorkspace item (dim "+ Add workspace").
 fn render_add_workspace(
     items: &mut Vec<ListItem<'static>>,
@@ -455,7 +528,7 @@ fn render_groups_list_with_limit(
     visible_conversations: &HashMap<String, usize>,
     visible_groups: &HashMap<String, usize>,
     selected_index: Option<usize>,
-    filter_lower: &str,
+    filter: &SearchQuery,
     has_text_filter: bool,
     indent_offset: usize,
 ) {
@@ -485,7 +558,7 @@ fn render_groups_list_with_limit(
             collapsed,
             visible_conversations,
             selected_index,
-            filter_lower,
+            filter,
             has_text_filter,
             indent_offset,
         );
@@ -551,7 +624,7 @@ fn render_group_list_items(
     collapsed: &HashSet<String>,
     visible_conversations: &HashMap<String, usize>,
     selected_index: Option<usize>,
-    filter_lower: &str,
+    filter: &SearchQuery,
     has_text_filter: bool,
     indent_offset: usize,
 ) {
@@ -569,19 +642,19 @@ fn render_group_list_items(
     }
 
     // When text filter is active, check if group name matches or any conversation matches
-    let group_name_matches =
-        has_text_filter && group.display_name().to_lowercase().contains(filter_lower);
+    let group_name_matches = has_text_filter && filter.matches_text(&[&group.display_name()]);
 
     // Check if group has any conversations visible with current archive + text filter
     let has_visible_convs = group.conversations().iter().any(|conv| {
-        !is_hidden_plan_implementation(conv, ctx.running_sessions, group_has_running_parent)
+        conv.pinned_at.is_none()
+            && !is_hidden_plan_implementation(conv, ctx.running_sessions, group_has_running_parent)
             && should_show_conversation(
                 conv,
                 ctx.archive_filter,
                 ctx.running_sessions,
                 ctx.hide_inactive,
             )
-            && (!has_text_filter || group_name_matches || conv_matches_filter(conv, filter_lower))
+            && (!has_text_filter || conv_matches_filter(conv, filter, group_name_matches))
     });
 
     // Skip groups with no visible conversations (unless showing all and no text filter)
@@ -602,11 +675,13 @@ fn render_group_list_items(
     let arrow = if is_collapsed { "\u{25b8}" } else { "\u{25be}" };
     let header = format!("{}{} {}", group_indent, arrow, name);
     let line_num = format_relative_line_number(*current_index, selected_index);
-    items.push(ListItem::new(Line::from(vec![
+    let mut spans = vec![
         Span::styled(line_num, Style::default().fg(Color::DarkGray)),
         Span::styled(header, Style::default().add_modifier(Modifier::BOLD)),
         Span::styled(" +", Style::default().fg(Color::Green)),
-    ])));
+    ];
+    spans.extend(cost_span(groups_cost(&[group], ctx)));
+    items.push(ListItem::new(Line::from(spans)));
     *current_index += 1;
 
     // Conversations and ephemeral sessions (if not collapsed)
@@ -638,12 +713,15 @@ fn render_group_list_items(
         // Get all conversations, hiding plan impls only when parent is orchestrating
         // Also filter by archive status, inactive, and text filter
         let conversations = group.conversations();
-        let filtered_convos: Vec<_> = conversations
+        let filtered_indices: Vec<usize> = conversations
             .iter()
-            .filter(|conv| {
+            .enumerate()
+            // Starred conversations are listed in the Pinned section instead
+            .filter(|(_, conv)| conv.pinned_at.is_none())
+            .filter(|(_, conv)| {
                 !is_hidden_plan_implementation(conv, ctx.running_sessions, group_has_running_parent)
             })
-            .filter(|conv| {
+            .filter(|(_, conv)| {
                 should_show_conversation(
                     conv,
                     ctx.archive_filter,
@@ -651,61 +729,35 @@ fn render_group_list_items(
                     ctx.hide_inactive,
                 )
             })
-            .filter(|conv| {
-                !has_text_filter || group_name_matches || conv_matches_filter(conv, filter_lower)
+            .filter(|(_, conv)| {
+                !has_text_filter || conv_matches_filter(conv, filter, group_name_matches)
             })
+            .map(|(idx, _)| idx)
             .collect();
 
         // Determine how many conversations to show (from filtered list)
-        let total = filtered_convos.len();
+        let total = filtered_indices.len();
         let vis = if has_text_filter {
             total
         } else {
             SidebarState::visible_count(visible_conversations, &group_key).min(total)
         };
 
-        // Then show saved conversations (limited or all)
-        for conv in filtered_convos.iter().take(vis) {
-            // If session is running in background, show it as Active
-            // regardless of the file-based status
-            let is_running = ctx.running_sessions.contains(&conv.session_id);
-            let (status_indicator, archive_indicator) = if is_running {
-                (
-                    Span::styled("\u{25cf} ", Style::default().fg(Color::Green)),
-                    None,
-                )
-            } else {
-                // Not running -- always show as idle regardless of JSONL state
-                let status = Span::styled("\u{25cb} ", Style::default().fg(Color::DarkGray));
-                // Show archive indicator when in "All" view
-                let archive = if ctx.archive_filter == ArchiveFilter::All && conv.is_archived {
-                    Some(Span::styled(
-                        "\u{1f4e6} ",
-                        Style::default().fg(Color::DarkGray),
-                    ))
-                } else {
-                    None
-                };
-                (status, archive)
-            };
-
-            let display = truncate_string(&conv.display, 30);
-            let line_num = format_relative_line_number(*current_index, selected_index);
-            let mut line_parts = vec![
-                Span::styled(line_num, Style::default().fg(Color::DarkGray)),
-                Span::raw(conv_indent.clone()),
-            ];
-
-            // Add archive indicator if present (only in All view)
-            if let Some(indicator) = archive_indicator {
-                line_parts.push(indicator);
-            }
-
-            line_parts.push(status_indicator);
-            line_parts.push(Span::raw(display));
-
-            items.push(ListItem::new(Line::from(line_parts)));
-            *current_index += 1;
+        // Then show saved conversations (limited or all), plan
+        // implementations under their planner
+        for (index, depth) in nest_plan_implementations(conversations, &filtered_indices)
+            .into_iter()
+            .take(vis)
+        {
+            render_conversation_items(
+                items,
+                current_index,
+                &conversations[index],
+                ctx,
+                selected_index,
+                &format!("{}{}", conv_indent, "  ".repeat(depth)),
+                None,
+            );
         }
 
         // Emit section controls for conversations (only when not filtering)
@@ -757,6 +809,134 @@ fn render_group_list_items(
     }
 }
 
+/// Render a conversation row, then its expanded subagents. `pin_number` is the
+/// quick-open number shown on starred conversations.
+fn render_conversation_items(
+    items: &mut Vec<ListItem<'static>>,
+    current_index: &mut usize,
+    conv: &Conversation,
+    ctx: &SidebarContext,
+    selected_index: Option<usize>,
+    conv_indent: &str,
+    pin_number: Option<usize>,
+) {
+    // If session is running in background, show it as Active
+    // regardless of the file-based status
+    let is_running = ctx.running_sessions.contains(&conv.session_id);
+    let (status_indicator, archive_indicator) = if is_running {
+        (
+            Span::styled("\u{25cf} ", Style::default().fg(Color::Green)),
+            None,
+        )
+    } else {
+        // Not running -- idle, or a warning when the last turn went wrong
+        let status = match conv.health {
+            Some(Health::ApiError | Health::ToolFailed) => {
+                Span::styled("\u{26a0} ", Style::default().fg(Color::Red))
+            }
+            Some(_) => Span::styled("\u{26a0} ", Style::default().fg(Color::Yellow)),
+            None => Span::styled("\u{25cb} ", Style::default().fg(Color::DarkGray)),
+        };
+        // Show archive indicator when in "All" view
+        let archive = if ctx.archive_filter == ArchiveFilter::All && conv.is_archived {
+            Some(Span::styled(
+                "\u{1f4e6} ",
+                Style::default().fg(Color::DarkGray),
+            ))
+        } else {
+            None
+        };
+        (status, archive)
+    };
+
+    let display = truncate_string(&conv.display, 30);
+    let line_num = format_relative_line_number(*current_index, selected_index);
+    let mut line_parts = vec![
+        Span::styled(line_num, Style::default().fg(Color::DarkGray)),
+        Span::raw(conv_indent.to_string()),
+    ];
+
+    // Add archive indicator if present (only in All view)
+    if let Some(indicator) = archive_indicator {
+        line_parts.push(indicator);
+    }
+
+    // Implements a plan written in another conversation
+    if conv.plan_parent.is_some() {
+        line_parts.push(Span::styled(
+            "\u{21b3} ",
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+
+    if let Some(number) = pin_number {
+        line_parts.push(Span::styled(
+            format!("{} ", number),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    line_parts.push(status_indicator);
+    line_parts.push(Span::raw(display));
+    if !conv.tags.is_empty() {
+        line_parts.push(Span::styled(
+            format!(" {}", truncate_string(&format_tags(&conv.tags), 24)),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if conv.note.is_some() {
+        line_parts.push(Span::styled(
+            " \u{270e}",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if ctx.claude_dirs.len() > 1 {
+        if let Some(source) = source_of(ctx.claude_dirs, &conv.file_path) {
+            line_parts.push(Span::styled(
+                format!(" [{}]", source.label),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+    }
+    let subagents = ctx.subagents.get(&conv.session_id);
+    if !conv.subagent_files.is_empty() {
+        let marker = if subagents.is_some() { "▾" } else { "▸" };
+        line_parts.push(Span::styled(
+            format!(" {}{}", marker, conv.subagent_files.len()),
+            Style::default().fg(Color::Blue),
+        ));
+    }
+    line_parts.extend(cost_span(
+        ctx.session_costs
+            .get(&conv.session_id)
+            .copied()
+            .unwrap_or_default(),
+    ));
+
+    items.push(ListItem::new(Line::from(line_parts)));
+    *current_index += 1;
+
+    for subagent in subagents.into_iter().flatten() {
+        let line_num = format_relative_line_number(*current_index, selected_index);
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(line_num, Style::default().fg(Color::DarkGray)),
+            Span::raw(conv_indent.to_string()),
+            Span::styled("  \u{21b3} ", Style::default().fg(Color::Blue)),
+            Span::raw(truncate_string(&subagent.description, 24)),
+            Span::styled(
+                format!(
+                    " {} msgs · {}",
+                    subagent.message_count,
+                    format_tokens(subagent.usage.total().total())
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ])));
+        *current_index += 1;
+    }
+}
+
 /// Render a single section control item (Show more/all/fewer/Collapse).
 fn render_section_control(
     items: &mut Vec<ListItem<'static>>,
@@ -0,0 +1,636 @@
+//! Tiling layout tree for the terminal area.
+//!
+//! The terminal area is described by a binary tree of splits. Leaves are panes
+//! identified by a [`PaneId`]; interior nodes split their area between two
+//! children by a percentage ratio (the tree itself lives in `crate::layout`,
+//! as it is saved in the config). Any pane can hold any session — the mapping
+//! from pane to session lives in `App`, this module only deals with geometry.
+
+use ratatui::layout::Rect;
+
+pub use crate::layout::{LayoutNode, PaneId, SplitDirection};
+
+/// Default share (percent) given to the first child of a new split.
+pub const DEFAULT_SPLIT_RATIO: u16 = 50;
+
+/// Smallest share (percent) either side of a split may shrink to.
+pub const MIN_SPLIT_RATIO: u16 = 10;
+
+/// Largest share (percent) the first side of a split may grow to.
+pub const MAX_SPLIT_RATIO: u16 = 90;
+
+/// Virtual area used for directional navigation, independent of the real render size.
+const NAV_AREA: Rect = Rect {
+    x: 0,
+    y: 0,
+    width: 1000,
+    height: 1000,
+};
+
+/// A direction for moving focus or swapping panes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// A draggable boundary between the two children of a split.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitHandle {
+    /// Path from the root to the split (`false` = first child, `true` = second)
+    pub path: Vec<bool>,
+    /// Orientation of the split
+    pub direction: SplitDirection,
+    /// Area covered by the whole split
+    pub area: Rect,
+    /// Column (horizontal split) or row (vertical split) where the second child starts
+    pub boundary: u16,
+}
+
+impl SplitHandle {
+    /// Whether a screen cell lies on this boundary.
+    ///
+    /// Both border cells adjacent to the boundary count, so either pane's
+    /// edge can be grabbed.
+    pub fn hit(&self, col: u16, row: u16) -> bool {
+        let a = self.area;
+        match self.direction {
+            SplitDirection::Horizontal => {
+                (col == self.boundary || col + 1 == self.boundary)
+                    && row >= a.y
+                    && row < a.y + a.height
+            }
+            SplitDirection::Vertical => {
+                (row == self.boundary || row + 1 == self.boundary)
+                    && col >= a.x
+                    && col < a.x + a.width
+            }
+        }
+    }
+
+    /// Ratio that puts the boundary at the given screen position.
+    pub fn ratio_at(&self, col: u16, row: u16) -> u16 {
+        let (offset, len) = match self.direction {
+            SplitDirection::Horizontal => (col.saturating_sub(self.area.x), self.area.width),
+            SplitDirection::Vertical => (row.saturating_sub(self.area.y), self.area.height),
+        };
+        if len == 0 {
+            return DEFAULT_SPLIT_RATIO;
+        }
+        ((offset as u32 * 100 / len as u32) as u16).clamp(MIN_SPLIT_RATIO, MAX_SPLIT_RATIO)
+    }
+}
+
+impl LayoutNode {
+    /// Compute the screen rect of every pane within `area`.
+    pub fn compute_rects(&self, area: Rect) -> Vec<(PaneId, Rect)> {
+        let mut rects = Vec::new();
+        self.collect_rects(area, &mut rects);
+        rects
+    }
+
+    fn collect_rects(&self, area: Rect, out: &mut Vec<(PaneId, Rect)>) {
+        match self {
+            LayoutNode::Pane { id } => out.push((*id, area)),
+            LayoutNode::Split {
+                direction,
+                ratio,
+                first,
+                second,
+            } => {
+                let (a, b) = split_rect(area, *direction, *ratio);
+                first.collect_rects(a, out);
+                second.collect_rects(b, out);
+            }
+        }
+    }
+
+    fn collect_handles(&self, area: Rect, path: &mut Vec<bool>, out: &mut Vec<SplitHandle>) {
+        if let LayoutNode::Split {
+            direction,
+            ratio,
+            first,
+            second,
+        } = self
+        {
+            let (a, b) = split_rect(area, *direction, *ratio);
+            out.push(SplitHandle {
+                path: path.clone(),
+                direction: *direction,
+                area,
+                boundary: match direction {
+                    SplitDirection::Horizontal => b.x,
+                    SplitDirection::Vertical => b.y,
+                },
+            });
+            path.push(false);
+            first.collect_handles(a, path, out);
+            path.pop();
+            path.push(true);
+            second.collect_handles(b, path, out);
+            path.pop();
+        }
+    }
+}
+
+/// Split `area` into two rects according to direction and ratio.
+pub fn split_rect(area: Rect, direction: SplitDirection, ratio: u16) -> (Rect, Rect) {
+    let ratio = ratio.clamp(MIN_SPLIT_RATIO, MAX_SPLIT_RATIO);
+    match direction {
+        SplitDirection::Horizontal => {
+            let first_w = (area.width as u32 * ratio as u32 / 100) as u16;
+            let first = Rect {
+                width: first_w,
+                ..area
+            };
+            let second = Rect {
+                x: area.x + first_w,
+                width: area.width - first_w,
+                ..area
+            };
+            (first, second)
+        }
+        SplitDirection::Vertical => {
+            let first_h = (area.height as u32 * ratio as u32 / 100) as u16;
+            let first = Rect {
+                height: first_h,
+                ..area
+            };
+            let second = Rect {
+                y: area.y + first_h,
+                height: area.height - first_h,
+                ..area
+            };
+            (first, second)
+        }
+    }
+}
+
+/// Find the nearest pane from `from` in the given direction.
+///
+/// Candidates must lie entirely on that side and overlap on the perpendicular
+/// axis. Ties are broken by the distance between centers on that axis.
+pub fn neighbor_in_direction(
+    rects: &[(PaneId, Rect)],
+    from: PaneId,
+    direction: Direction,
+) -> Option<PaneId> {
+    let (_, cur) = rects.iter().find(|(id, _)| *id == from)?;
+    let cur = *cur;
+
+    let overlaps = |a0: u16, a1: u16, b0: u16, b1: u16| a0 < b1 && b0 < a1;
+    let center = |start: u16, len: u16| start as i32 * 2 + len as i32;
+
+    rects
+        .iter()
+        .filter(|(id, _)| *id != from)
+        .filter_map(|(id, r)| {
+            let (gap, perp) = match direction {
+                Direction::Left if r.x + r.width <= cur.x => (
+                    cur.x - (r.x + r.width),
+                    overlaps(r.y, r.y + r.height, cur.y, cur.y + cur.height),
+                ),
+                Direction::Right if r.x >= cur.x + cur.width => (
+                    r.x - (cur.x + cur.width),
+                    overlaps(r.y, r.y + r.height, cur.y, cur.y + cur.height),
+                ),
+                Direction::Up if r.y + r.height <= cur.y => (
+                    cur.y - (r.y + r.height),
+                    overlaps(r.x, r.x + r.width, cur.x, cur.x + cur.width),
+                ),
+                Direction::Down if r.y >= cur.y + cur.height => (
+                    r.y - (cur.y + cur.height),
+                    overlaps(r.x, r.x + r.width, cur.x, cur.x + cur.width),
+                ),
+                _ => return None,
+            };
+            if !perp {
+                return None;
+            }
+            let drift = match direction {
+                Direction::Left | Direction::Right => {
+                    (center(r.y, r.height) - center(cur.y, cur.height)).abs()
+                }
+                Direction::Up | Direction::Down => {
+                    (center(r.x, r.width) - center(cur.x, cur.width)).abs()
+                }
+            };
+            Some((gap, drift, *id))
+        })
+        .min()
+        .map(|(_, _, id)| id)
+}
+
+/// A tiling layout: the split tree plus which pane is focused.
+#[derive(Debug, Clone)]
+pub struct TilingLayout {
+    root: LayoutNode,
+    focused: PaneId,
+    next_id: u32,
+}
+
+impl Default for TilingLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TilingLayout {
+    /// A layout with a single pane.
+    pub fn new() -> Self {
+        Self {
+            root: LayoutNode::Pane { id: PaneId(0) },
+            focused: PaneId(0),
+            next_id: 1,
+        }
+    }
+
+    /// Build a layout from a saved tree. Focus starts on the first pane.
+    /// Panes sharing an ID (a hand-edited config) get fresh IDs.
+    pub fn from_root(mut root: LayoutNode) -> Self {
+        root.dedup_pane_ids();
+        let ids = root.pane_ids();
+        let focused = ids.first().copied().unwrap_or(PaneId(0));
+        let next_id = ids.iter().map(|id| id.0 + 1).max().unwrap_or(1);
+        Self {
+            root,
+            focused,
+            next_id,
+        }
+    }
+
+    /// The layout tree.
+    pub fn root(&self) -> &LayoutNode {
+        &self.root
+    }
+
+    /// The focused pane.
+    pub fn focused(&self) -> PaneId {
+        self.focused
+    }
+
+    /// All pane IDs in reading order.
+    pub fn pane_ids(&self) -> Vec<PaneId> {
+        self.root.pane_ids()
+    }
+
+    /// Number of panes in the layout.
+    pub fn pane_count(&self) -> usize {
+        self.pane_ids().len()
+    }
+
+    /// Whether `id` is a pane in this layout.
+    pub fn contains(&self, id: PaneId) -> bool {
+        self.pane_ids().contains(&id)
+    }
+
+    /// Focus a specific pane. Returns false if the pane does not exist.
+    pub fn focus(&mut self, id: PaneId) -> bool {
+        if self.contains(id) {
+            self.focused = id;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Compute pane rects within `area`.
+    pub fn compute_rects(&self, area: Rect) -> Vec<(PaneId, Rect)> {
+        self.root.compute_rects(area)
+    }
+
+    /// Draggable split boundaries within `area`.
+    pub fn split_handles(&self, area: Rect) -> Vec<SplitHandle> {
+        let mut handles = Vec::new();
+        self.root
+            .collect_handles(area, &mut Vec::new(), &mut handles);
+        handles
+    }
+
+    /// The split boundary under a screen cell, innermost first.
+    pub fn handle_at(&self, area: Rect, col: u16, row: u16) -> Option<SplitHandle> {
+        self.split_handles(area)
+            .into_iter()
+            .rev()
+            .find(|h| h.hit(col, row))
+    }
+
+    /// Set the ratio of the split at `path`. Returns false if there is no split there.
+    pub fn set_ratio(&mut self, path: &[bool], new_ratio: u16) -> bool {
+        match self.root.node_at_mut(path) {
+            Some(LayoutNode::Split { ratio, .. }) => {
+                *ratio = new_ratio.clamp(MIN_SPLIT_RATIO, MAX_SPLIT_RATIO);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Move the nearest split boundary around the focused pane by `step` percent.
+    ///
+    /// Uses the innermost enclosing split on the matching axis; Right/Down move
+    /// the boundary right/down, Left/Up move it left/up. Returns the new ratio,
+    /// or `None` if no split runs along that axis.
+    pub fn resize_focused(&mut self, direction: Direction, step: u16) -> Option<u16> {
+        let axis = match direction {
+            Direction::Left | Direction::Right => SplitDirection::Horizontal,
+            Direction::Up | Direction::Down => SplitDirection::Vertical,
+        };
+        let path = self.root.path_to(self.focused)?;
+        let split_path = (0..path.len()).rev().map(|len| &path[..len]).find(|p| {
+            matches!(
+                self.root.node_at(p),
+                Some(LayoutNode::Split { direction, .. }) if *direction == axis
+            )
+        })?;
+        let split_path = split_path.to_vec();
+
+        let Some(LayoutNode::Split { ratio, .. }) = self.root.node_at_mut(&split_path) else {
+            return None;
+        };
+        let new_ratio = match direction {
+            Direction::Right | Direction::Down => ratio.saturating_add(step),
+            Direction::Left | Direction::Up => ratio.saturating_sub(step),
+        }
+        .clamp(MIN_SPLIT_RATIO, MAX_SPLIT_RATIO);
+        *ratio = new_ratio;
+        Some(new_ratio)
+    }
+
+    /// Split the focused pane in two. The new pane takes the second half and
+    /// receives focus. Returns the new pane's ID, or `None` if the focused
+    /// pane isn't in the layout.
+    pub fn split_focused(&mut self, direction: SplitDirection) -> Option<PaneId> {
+        let focused = self.focused;
+        let new_id = PaneId(self.next_id);
+        let leaf = self.root.find_pane_mut(focused)?;
+        *leaf = LayoutNode::Split {
+            direction,
+            ratio: DEFAULT_SPLIT_RATIO,
+            first: Box::new(LayoutNode::Pane { id: focused }),
+            second: Box::new(LayoutNode::Pane { id: new_id }),
+        };
+        self.next_id += 1;
+        self.focused = new_id;
+        Some(new_id)
+    }
+
+    /// Close the focused pane. Focus moves to the pane that took over its space.
+    /// Returns the closed pane's ID, or `None` if it was the last pane.
+    pub fn close_focused(&mut self) -> Option<PaneId> {
+        if self.pane_count() <= 1 {
+            return None;
+        }
+        let closed = self.focused;
+        let before = self.compute_rects(NAV_AREA);
+        let closed_rect = before.iter().find(|(id, _)| *id == closed).map(|(_, r)| *r);
+
+        let root = std::mem::replace(&mut self.root, LayoutNode::Pane { id: closed });
+        self.root = root
+            .without_pane(closed)
+            .unwrap_or(LayoutNode::Pane { id: closed });
+
+        // Focus whichever remaining pane now covers the closed pane's top-left corner
+        let after = self.compute_rects(NAV_AREA);
+        self.focused = closed_rect
+            .and_then(|cr| {
+                after
+                    .iter()
+                    .find(|(_, r)| r.contains(ratatui::layout::Position::new(cr.x, cr.y)))
+                    .map(|(id, _)| *id)
+            })
+            .or_else(|| after.first().map(|(id, _)| *id))
+            .unwrap_or(closed);
+        Some(closed)
+    }
+
+    /// The nearest pane to the focused one in `direction`, if any.
+    pub fn neighbor(&self, direction: Direction) -> Option<PaneId> {
+        let rects = self.compute_rects(NAV_AREA);
+        neighbor_in_direction(&rects, self.focused, direction)
+    }
+
+    /// Move focus to the nearest pane in `direction`. Returns false if there is none.
+    pub fn focus_direction(&mut self, direction: Direction) -> bool {
+        match self.neighbor(direction) {
+            Some(id) => {
+                self.focused = id;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Swap the focused pane with its neighbor in `direction`. Focus follows
+    /// the moved pane. Returns the neighbor's ID if a swap happened.
+    pub fn swap_direction(&mut self, direction: Direction) -> Option<PaneId> {
+        let other = self.neighbor(direction)?;
+        self.root.swap_ids(self.focused, other);
+        Some(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area() -> Rect {
+        Rect::new(0, 0, 100, 40)
+    }
+
+    /// Three agents on the left column, a test runner on the right.
+    fn three_plus_one() -> TilingLayout {
+        let mut layout = TilingLayout::new();
+        let right = layout.split_focused(SplitDirection::Horizontal).unwrap();
+        assert!(layout.focus(PaneId(0)));
+        layout.split_focused(SplitDirection::Vertical);
+        layout.split_focused(SplitDirection::Vertical);
+        layout.focus(right);
+        layout
+    }
+
+    #[test]
+    fn new_layout_has_single_pane_covering_area() {
+        let layout = TilingLayout::new();
+        let rects = layout.compute_rects(area());
+        assert_eq!(rects, vec![(PaneId(0), area())]);
+    }
+
+    #[test]
+    fn split_focused_creates_new_focused_pane() {
+        let mut layout = TilingLayout::new();
+        let new_id = layout.split_focused(SplitDirection::Horizontal).unwrap();
+        assert_eq!(new_id, PaneId(1));
+        assert_eq!(layout.focused(), new_id);
+        let rects = layout.compute_rects(area());
+        assert_eq!(rects[0].1, Rect::new(0, 0, 50, 40));
+        assert_eq!(rects[1].1, Rect::new(50, 0, 50, 40));
+    }
+
+    #[test]
+    fn rects_tile_area_without_gaps() {
+        let layout = three_plus_one();
+        let rects = layout.compute_rects(area());
+        assert_eq!(rects.len(), 4);
+        let total: u32 = rects
+            .iter()
+            .map(|(_, r)| r.width as u32 * r.height as u32)
+            .sum();
+        assert_eq!(total, 100 * 40);
+    }
+
+    #[test]
+    fn focus_direction_moves_between_columns_and_rows() {
+        let mut layout = three_plus_one();
+        let right = layout.focused();
+        assert!(layout.focus_direction(Direction::Left));
+        let left = layout.focused();
+        assert_ne!(left, right);
+        assert!(layout.focus_direction(Direction::Right));
+        assert_eq!(layout.focused(), right);
+        assert!(!layout.focus_direction(Direction::Right));
+        assert!(!layout.focus_direction(Direction::Up));
+    }
+
+    #[test]
+    fn focus_direction_walks_down_a_stack() {
+        let mut layout = three_plus_one();
+        layout.focus(PaneId(0));
+        assert!(layout.focus_direction(Direction::Down));
+        assert!(layout.focus_direction(Direction::Down));
+        assert!(!layout.focus_direction(Direction::Down));
+        assert!(layout.focus_direction(Direction::Up));
+    }
+
+    #[test]
+    fn close_focused_collapses_into_sibling() {
+        let mut layout = TilingLayout::new();
+        layout.split_focused(SplitDirection::Vertical);
+        assert_eq!(layout.close_focused(), Some(PaneId(1)));
+        assert_eq!(layout.pane_ids(), vec![PaneId(0)]);
+        assert_eq!(layout.focused(), PaneId(0));
+        assert_eq!(layout.compute_rects(area()), vec![(PaneId(0), area())]);
+    }
+
+    #[test]
+    fn close_last_pane_is_refused() {
+        let mut layout = TilingLayout::new();
+        assert_eq!(layout.close_focused(), None);
+        assert_eq!(layout.pane_count(), 1);
+    }
+
+    #[test]
+    fn swap_direction_exchanges_positions_and_keeps_focus() {
+        let mut layout = TilingLayout::new();
+        layout.split_focused(SplitDirection::Horizontal);
+        assert_eq!(layout.swap_direction(Direction::Left), Some(PaneId(0)));
+        assert_eq!(layout.focused(), PaneId(1));
+        assert_eq!(layout.pane_ids(), vec![PaneId(1), PaneId(0)]);
+    }
+
+    #[test]
+    fn from_root_continues_id_sequence() {
+        let layout = three_plus_one();
+        let restored = TilingLayout::from_root(layout.root().clone());
+        let mut restored_clone = restored.clone();
+        let new_id = restored_clone
+            .split_focused(SplitDirection::Vertical)
+            .unwrap();
+        assert!(!layout.pane_ids().contains(&new_id));
+        assert_eq!(restored.focused(), restored.pane_ids()[0]);
+    }
+
+    #[test]
+    fn from_root_renumbers_duplicate_pane_ids() {
+        let pane = |id| Box::new(LayoutNode::Pane { id: PaneId(id) });
+        let root = LayoutNode::Split {
+            direction: SplitDirection::Horizontal,
+            ratio: DEFAULT_SPLIT_RATIO,
+            first: pane(3),
+            second: Box::new(LayoutNode::Split {
+                direction: SplitDirection::Vertical,
+                ratio: DEFAULT_SPLIT_RATIO,
+                first: pane(3),
+                second: pane(1),
+            }),
+        };
+        let mut layout = TilingLayout::from_root(root);
+        assert_eq!(layout.pane_ids(), vec![PaneId(3), PaneId(4), PaneId(1)]);
+        assert_eq!(
+            layout.split_focused(SplitDirection::Vertical),
+            Some(PaneId(5))
+        );
+    }
+
+    #[test]
+    fn split_focused_without_focused_pane_changes_nothing() {
+        let mut layout = TilingLayout {
+            focused: PaneId(7),
+            ..TilingLayout::new()
+        };
+        assert_eq!(layout.split_focused(SplitDirection::Horizontal), None);
+        assert_eq!(layout.pane_ids(), vec![PaneId(0)]);
+        assert_eq!(layout.focused(), PaneId(7));
+        assert_eq!(layout.next_id, 1);
+    }
+
+    #[test]
+    fn layout_node_round_trips_through_json() {
+        let layout = three_plus_one();
+        let json = serde_json::to_string(layout.root()).unwrap();
+        let parsed: LayoutNode = serde_json::from_str(&json).unwrap();
+        assert_eq!(&parsed, layout.root());
+    }
+
+    #[test]
+    fn resize_focused_moves_matching_axis_boundary() {
+        let mut layout = TilingLayout::new();
+        layout.split_focused(SplitDirection::Horizontal);
+        assert_eq!(layout.resize_focused(Direction::Left, 10), Some(40));
+        let rects = layout.compute_rects(area());
+        assert_eq!(rects[0].1.width, 40);
+        assert_eq!(layout.resize_focused(Direction::Up, 10), None);
+    }
+
+    #[test]
+    fn resize_focused_clamps_ratio() {
+        let mut layout = TilingLayout::new();
+        layout.split_focused(SplitDirection::Vertical);
+        assert_eq!(
+            layout.resize_focused(Direction::Down, 80),
+            Some(MAX_SPLIT_RATIO)
+        );
+        assert_eq!(
+            layout.resize_focused(Direction::Up, 200),
+            Some(MIN_SPLIT_RATIO)
+        );
+    }
+
+    #[test]
+    fn resize_focused_uses_innermost_split_on_axis() {
+        let mut layout = three_plus_one();
+        layout.focus(PaneId(0));
+        // Pane 0 sits in the vertical stack inside the left column
+        assert!(layout.resize_focused(Direction::Down, 10).is_some());
+        assert!(layout.resize_focused(Direction::Right, 10).is_some());
+        let rects = layout.compute_rects(area());
+        let (_, left_top) = rects.iter().find(|(id, _)| *id == PaneId(0)).unwrap();
+        assert_eq!(left_top.width, 60);
+    }
+
+    #[test]
+    fn handle_at_finds_boundary_and_drag_sets_ratio() {
+        let mut layout = TilingLayout::new();
+        layout.split_focused(SplitDirection::Horizontal);
+        let handle = layout.handle_at(area(), 50, 10).unwrap();
+        assert_eq!(handle.direction, SplitDirection::Horizontal);
+        assert!(layout.handle_at(area(), 20, 10).is_none());
+
+        let ratio = handle.ratio_at(30, 10);
+        assert_eq!(ratio, 30);
+        assert!(layout.set_ratio(&handle.path, ratio));
+        assert_eq!(layout.compute_rects(area())[0].1.width, 30);
+    }
+}
@@ -62,6 +62,21 @@ impl ToastManager {
 
     pub fn push(&mut self, message: impl Into<String>, toast_type: ToastType) {
         let toast = Toast::new(self.next_id, message, toast_type);
+        self.push_toast(toast);
+    }
+
+    /// Push a toast that stays up longer (or shorter) than the default.
+    pub fn push_with_duration(
+        &mut self,
+        message: impl Into<String>,
+        toast_type: ToastType,
+        duration: Duration,
+    ) {
+        let toast = Toast::new(self.next_id, message, toast_type).with_duration(duration);
+        self.push_toast(toast);
+    }
+
+    fn push_toast(&mut self, toast: Toast) {
         self.next_id = self.next_id.wrapping_add(1);
         self.queue.push_back(toast);
         self.trim_queue();
@@ -0,0 +1,988 @@
+//! Read-only transcript view: renders a parsed conversation JSONL as
+//! formatted text with collapsible thinking/tool blocks, scrolling and search.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Local};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use crate::claude::conversation::Conversation;
+use crate::claude::subagents::Subagent;
+use crate::claude::transcript::{Role, Transcript, TranscriptBlock};
+use crate::claude::usage::format_tokens;
+
+/// Indent for block content under a message header.
+const INDENT: &str = "  ";
+
+/// Result of a key press in the transcript view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptKeyResult {
+    /// Key handled, stay in the view
+    Continue,
+    /// Return focus to the sidebar (the transcript stays visible)
+    Back,
+    /// Resume the conversation in a real session
+    Resume,
+    /// Show the conversation's branch tree
+    Branches,
+    /// Show the git commits made while the conversation was active
+    Commits,
+    /// Switch to the planning conversation, or to the implementation of its plan
+    PlanLink,
+    /// Show the transcript of the subagent at this index
+    OpenSubagent(usize),
+}
+
+/// One rendered (already wrapped) line of the transcript.
+#[derive(Debug, Clone)]
+struct TranscriptLine {
+    text: String,
+    style: Style,
+    /// Index of the message this line belongs to
+    message: usize,
+    /// Index of the block within the message (None for headers/spacers)
+    block: Option<usize>,
+    /// Row of the subagent list shown above the messages
+    subagent: Option<SubagentRow>,
+}
+
+/// A row of the subagent list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SubagentRow {
+    /// The collapsible "N subagents" header
+    Summary,
+    /// One subagent, by index
+    Agent(usize),
+}
+
+/// Incremental search state.
+#[derive(Debug, Clone, Default)]
+struct TranscriptSearch {
+    /// Typing a query (`/` pressed, Enter not yet)
+    input_active: bool,
+    query: String,
+    /// Line indices containing the committed query
+    matches: Vec<usize>,
+}
+
+/// State for the transcript view.
+pub struct TranscriptViewState {
+    /// Conversation being shown (used to resume it)
+    pub conversation: Conversation,
+    transcript: Transcript,
+    /// Collapsible blocks that are currently expanded, keyed by (message, block)
+    expanded: HashSet<(usize, usize)>,
+    lines: Vec<TranscriptLine>,
+    /// Content width `lines` was wrapped for (0 = needs rebuild)
+    width: u16,
+    /// Visible content height from the last layout
+    height: usize,
+    /// Highlighted line
+    cursor: usize,
+    /// First visible line
+    scroll: usize,
+    search: TranscriptSearch,
+    /// Message to jump to once the view has been laid out
+    pending_message: Option<usize>,
+    /// Subagents launched from this conversation
+    subagents: Vec<Subagent>,
+    subagents_expanded: bool,
+    /// View to return to when this one shows a subagent
+    parent: Option<Box<TranscriptViewState>>,
+}
+
+impl TranscriptViewState {
+    pub fn new(conversation: Conversation, transcript: Transcript) -> Self {
+        Self {
+            conversation,
+            transcript,
+            expanded: HashSet::new(),
+            lines: Vec::new(),
+            width: 0,
+            height: 0,
+            cursor: 0,
+            scroll: 0,
+            search: TranscriptSearch::default(),
+            pending_message: None,
+            subagents: Vec::new(),
+            subagents_expanded: false,
+            parent: None,
+        }
+    }
+
+    /// List the conversation's subagents above its messages.
+    pub fn with_subagents(mut self, subagents: Vec<Subagent>) -> Self {
+        self.subagents = subagents;
+        self.width = 0;
+        self
+    }
+
+    /// Return to `parent` when leaving this (subagent) view.
+    pub fn with_parent(mut self, parent: TranscriptViewState) -> Self {
+        self.parent = Some(Box::new(parent));
+        self
+    }
+
+    /// Detach the view this one was opened from, if any.
+    pub fn take_parent(&mut self) -> Option<TranscriptViewState> {
+        self.parent.take().map(|parent| *parent)
+    }
+
+    /// The top-level conversation: the one subagent views were opened from.
+    pub fn root_conversation(&self) -> &Conversation {
+        self.parent
+            .as_ref()
+            .map_or(&self.conversation, |parent| parent.root_conversation())
+    }
+
+    /// Subagents of the conversation.
+    pub fn subagents(&self) -> &[Subagent] {
+        &self.subagents
+    }
+
+    /// Number of messages in the transcript.
+    pub fn message_count(&self) -> usize {
+        self.transcript.messages.len()
+    }
+
+    /// Index of the message with the given UUID.
+    pub fn message_index(&self, uuid: &str) -> Option<usize> {
+        self.transcript.message_index(uuid)
+    }
+
+    /// Index of the message under the cursor.
+    pub fn current_message(&self) -> usize {
+        self.lines.get(self.cursor).map_or(0, |l| l.message)
+    }
+
+    /// Whether a search query is being typed (keys are text, not commands).
+    pub fn is_searching(&self) -> bool {
+        self.search.input_active
+    }
+
+    /// Position the cursor on a message's header once laid out.
+    pub fn jump_to_message(&mut self, index: usize) {
+        self.pending_message = Some(index);
+        if self.width > 0 {
+            self.apply_pending_jump();
+        }
+    }
+
+    /// Wrap lines for the given content size. Call before rendering.
+    pub fn layout(&mut self, width: u16, height: u16) {
+        self.height = height as usize;
+        if width != self.width {
+            self.width = width;
+            self.rebuild();
+        }
+        self.apply_pending_jump();
+        self.scroll_to_cursor();
+    }
+
+    fn apply_pending_jump(&mut self) {
+        if let Some(message) = self.pending_message.take() {
+            if let Some(line) = self.lines.iter().position(|l| l.message >= message) {
+                self.cursor = line;
+                // Put the message at the top of the view
+                self.scroll = line;
+            }
+        }
+    }
+
+    /// Rebuild the wrapped lines, keeping the cursor on the same block.
+    fn rebuild(&mut self) {
+        let anchor = self
+            .lines
+            .get(self.cursor)
+            .map(|l| (l.message, l.block, l.subagent));
+        self.lines = build_subagent_lines(&self.subagents, self.subagents_expanded);
+        self.lines.extend(build_lines(
+            &self.transcript,
+            &self.expanded,
+            self.width as usize,
+        ));
+
+        if let Some((message, block, subagent)) = anchor {
+            self.cursor = self
+                .lines
+                .iter()
+                .position(|l| l.message == message && l.block == block && l.subagent == subagent)
+                .or_else(|| self.lines.iter().position(|l| l.message == message))
+                .unwrap_or(0);
+        }
+        self.cursor = self.cursor.min(self.lines.len().saturating_sub(1));
+        self.refresh_matches();
+    }
+
+    // -- Navigation ----------------------------------------------------------
+
+    fn move_cursor(&mut self, delta: isize) {
+        let max = self.lines.len().saturating_sub(1);
+        self.cursor = self.cursor.saturating_add_signed(delta).min(max);
+        self.scroll_to_cursor();
+    }
+
+    fn scroll_to_cursor(&mut self) {
+        let height = self.height.max(1);
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + height {
+            self.scroll = self.cursor + 1 - height;
+        }
+        let max_scroll = self.lines.len().saturating_sub(height);
+        self.scroll = self.scroll.min(max_scroll);
+    }
+
+    /// Scroll the viewport without moving past the content (mouse wheel).
+    pub fn scroll_by(&mut self, delta: isize) {
+        let height = self.height.max(1);
+        let max_scroll = self.lines.len().saturating_sub(height);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max_scroll);
+        self.cursor = self
+            .cursor
+            .clamp(self.scroll, (self.scroll + height).saturating_sub(1));
+        self.cursor = self.cursor.min(self.lines.len().saturating_sub(1));
+    }
+
+    fn jump_message(&mut self, forward: bool) {
+        let current = self.current_message();
+        let target = if forward {
+            self.lines
+                .iter()
+                .position(|l| l.message > current && l.block.is_none())
+        } else {
+            // Header of the current message if we're inside it, else the previous one
+            let header = self
+                .lines
+                .iter()
+                .position(|l| l.message == current)
+                .unwrap_or(0);
+            if header < self.cursor {
+                Some(header)
+            } else {
+                self.lines
+                    .iter()
+                    .position(|l| current > 0 && l.message == current - 1)
+            }
+        };
+        if let Some(line) = target {
+            self.cursor = line;
+            self.scroll_to_cursor();
+        }
+    }
+
+    // -- Collapsing ----------------------------------------------------------
+
+    /// Toggle the collapsible block under the cursor.
+    fn toggle_at_cursor(&mut self) {
+        let Some(line) = self.lines.get(self.cursor) else {
+            return;
+        };
+        let Some(block) = line.block else {
+            return;
+        };
+        let key = (line.message, block);
+        if !self.is_collapsible(key) {
+            return;
+        }
+        if !self.expanded.remove(&key) {
+            self.expanded.insert(key);
+        }
+        self.rebuild();
+        // Keep the cursor on the block header
+        if let Some(pos) = self
+            .lines
+            .iter()
+            .position(|l| l.message == key.0 && l.block == Some(key.1))
+        {
+            self.cursor = pos;
+        }
+        self.scroll_to_cursor();
+    }
+
+    /// Expand every collapsible block, or collapse all if any is expanded.
+    fn toggle_all(&mut self) {
+        if self.expanded.is_empty() {
+            for (m, message) in self.transcript.messages.iter().enumerate() {
+                for (b, block) in message.blocks.iter().enumerate() {
+                    if !matches!(block, TranscriptBlock::Text(_)) {
+                        self.expanded.insert((m, b));
+                    }
+                }
+            }
+        } else {
+            self.expanded.clear();
+        }
+        self.rebuild();
+        self.scroll_to_cursor();
+    }
+
+    fn is_collapsible(&self, (message, block): (usize, usize)) -> bool {
+        self.transcript
+            .messages
+            .get(message)
+            .and_then(|m| m.blocks.get(block))
+            .is_some_and(|b| !matches!(b, TranscriptBlock::Text(_)))
+    }
+
+    // -- Search --------------------------------------------------------------
+
+    /// Commit the typed query: expand blocks containing it and jump to the first hit.
+    fn commit_search(&mut self) {
+        self.search.input_active = false;
+        let query = self.search.query.clone();
+        if query.is_empty() {
+            self.search.matches.clear();
+            return;
+        }
+
+        for (m, message) in self.transcript.messages.iter().enumerate() {
+            for (b, block) in message.blocks.iter().enumerate() {
+                if !matches!(block, TranscriptBlock::Text(_))
+                    && !find_ci(block_text(block), &query).is_empty()
+                {
+                    self.expanded.insert((m, b));
+                }
+            }
+        }
+        self.rebuild();
+        self.next_match(true);
+    }
+
+    fn refresh_matches(&mut self) {
+        if self.search.query.is_empty() {
+            self.search.matches.clear();
+            return;
+        }
+        let query = &self.search.query;
+        self.search.matches = self
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| !find_ci(&l.text, query).is_empty())
+            .map(|(i, _)| i)
+            .collect();
+    }
+
+    fn next_match(&mut self, forward: bool) {
+        let target = if forward {
+            self.search
+                .matches
+                .iter()
+                .find(|&&i| i > self.cursor)
+                .or_else(|| self.search.matches.first())
+        } else {
+            self.search
+                .matches
+                .iter()
+                .rev()
+                .find(|&&i| i < self.cursor)
+                .or_else(|| self.search.matches.last())
+        };
+        if let Some(&line) = target {
+            self.cursor = line;
+            self.scroll_to_cursor();
+        }
+    }
+
+    // -- Keys ----------------------------------------------------------------
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> TranscriptKeyResult {
+        if self.search.input_active {
+            match key.code {
+                KeyCode::Esc => {
+                    self.search = TranscriptSearch::default();
+                }
+                KeyCode::Enter => self.commit_search(),
+                KeyCode::Backspace => {
+                    self.search.query.pop();
+                }
+                KeyCode::Char(c) => self.search.query.push(c),
+                _ => {}
+            }
+            return TranscriptKeyResult::Continue;
+        }
+
+        let half_page = (self.height / 2).max(1) as isize;
+        match (key.code, key.modifiers) {
+            (KeyCode::Char('d'), KeyModifiers::CONTROL) | (KeyCode::PageDown, _) => {
+                self.move_cursor(half_page);
+            }
+            (KeyCode::Char('u'), KeyModifiers::CONTROL) | (KeyCode::PageUp, _) => {
+                self.move_cursor(-half_page);
+            }
+            (KeyCode::Char('j') | KeyCode::Down, _) => self.move_cursor(1),
+            (KeyCode::Char('k') | KeyCode::Up, _) => self.move_cursor(-1),
+            (KeyCode::Char('g') | KeyCode::Home, _) => {
+                self.cursor = 0;
+                self.scroll_to_cursor();
+            }
+            (KeyCode::Char('G') | KeyCode::End, _) => {
+                self.cursor = self.lines.len().saturating_sub(1);
+                self.scroll_to_cursor();
+            }
+            (KeyCode::Char(']' | '}'), _) => self.jump_message(true),
+            (KeyCode::Char('[' | '{'), _) => self.jump_message(false),
+            (KeyCode::Enter | KeyCode::Tab | KeyCode::Char('o'), _) => {
+                match self.lines.get(self.cursor).and_then(|l| l.subagent) {
+                    Some(SubagentRow::Agent(index)) => {
+                        return TranscriptKeyResult::OpenSubagent(index);
+                    }
+                    Some(SubagentRow::Summary) => {
+                        self.subagents_expanded = !self.subagents_expanded;
+                        self.rebuild();
+                        self.scroll_to_cursor();
+                    }
+                    None => self.toggle_at_cursor(),
+                }
+            }
+            (KeyCode::Char('t'), _) => self.toggle_all(),
+            (KeyCode::Char('/'), _) => {
+                self.search = TranscriptSearch {
+                    input_active: true,
+                    ..TranscriptSearch::default()
+                };
+            }
+            (KeyCode::Char('n'), _) => self.next_match(true),
+            (KeyCode::Char('N'), _) => self.next_match(false),
+            (KeyCode::Char('r'), _) => return TranscriptKeyResult::Resume,
+            (KeyCode::Char('b'), _) => return TranscriptKeyResult::Branches,
+            (KeyCode::Char('c'), _) => return TranscriptKeyResult::Commits,
+            (KeyCode::Char('p'), _) => return TranscriptKeyResult::PlanLink,
+            (KeyCode::Esc, _) if !self.search.query.is_empty() => {
+                self.search = TranscriptSearch::default();
+            }
+            (KeyCode::Esc | KeyCode::Char('q' | 'h'), _) => return TranscriptKeyResult::Back,
+            _ => {}
+        }
+        TranscriptKeyResult::Continue
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Line building
+// ---------------------------------------------------------------------------
+
+/// Searchable text of a block.
+fn block_text(block: &TranscriptBlock) -> &str {
+    match block {
+        TranscriptBlock::Text(text) | TranscriptBlock::Thinking(text) => text,
+        TranscriptBlock::ToolUse { input, .. } => input,
+        TranscriptBlock::ToolResult { content, .. } => content,
+    }
+}
+
+/// Lines for the subagent list: a summary header, then one line per subagent
+/// when expanded.
+fn build_subagent_lines(subagents: &[Subagent], expanded: bool) -> Vec<TranscriptLine> {
+    if subagents.is_empty() {
+        return Vec::new();
+    }
+    let line = |text: String, style: Style, row: SubagentRow| TranscriptLine {
+        text,
+        style,
+        message: 0,
+        block: None,
+        subagent: Some(row),
+    };
+
+    let messages: usize = subagents.iter().map(|s| s.message_count).sum();
+    let tokens: u64 = subagents.iter().map(|s| s.usage.total().total()).sum();
+    let marker = if expanded { "▾" } else { "▸" };
+    let mut lines = vec![line(
+        format!(
+            "{} {} subagent{} · {} msgs · {} tokens",
+            marker,
+            subagents.len(),
+            if subagents.len() == 1 { "" } else { "s" },
+            messages,
+            format_tokens(tokens)
+        ),
+        Style::default()
+            .fg(Color::Blue)
+            .add_modifier(Modifier::BOLD),
+        SubagentRow::Summary,
+    )];
+
+    if expanded {
+        for (i, subagent) in subagents.iter().enumerate() {
+            let kind = subagent
+                .subagent_type
+                .as_deref()
+                .map(|t| format!("[{}] ", t))
+                .unwrap_or_default();
+            lines.push(line(
+                format!(
+                    "{}↳ {}{} · {} msgs · {} tokens",
+                    INDENT,
+                    kind,
+                    subagent.description,
+                    subagent.message_count,
+                    format_tokens(subagent.usage.total().total())
+                ),
+                Style::default().fg(Color::Blue),
+                SubagentRow::Agent(i),
+            ));
+        }
+    }
+
+    lines.push(line(String::new(), Style::default(), SubagentRow::Summary));
+    lines
+}
+
+fn build_lines(
+    transcript: &Transcript,
+    expanded: &HashSet<(usize, usize)>,
+    width: usize,
+) -> Vec<TranscriptLine> {
+    let content_width = width.saturating_sub(INDENT.len() * 2).max(10);
+    let mut lines = Vec::new();
+
+    for (m, message) in transcript.messages.iter().enumerate() {
+        let (who, color) = match message.role {
+            Role::User => ("You", Color::Cyan),
+            Role::Assistant => ("Claude", Color::Magenta),
+        };
+        let time = message
+            .timestamp
+            .as_deref()
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+            .map(|dt| format!("  {}", dt.with_timezone(&Local).format("%Y-%m-%d %H:%M")))
+            .unwrap_or_default();
+        lines.push(TranscriptLine {
+            text: format!("● {}{}", who, time),
+            style: Style::default().fg(color).add_modifier(Modifier::BOLD),
+            message: m,
+            block: None,
+            subagent: None,
+        });
+
+        for (b, block) in message.blocks.iter().enumerate() {
+            let is_expanded = expanded.contains(&(m, b));
+            let marker = if is_expanded { "▾" } else { "▸" };
+            let mut push = |text: String, style: Style| {
+                lines.push(TranscriptLine {
+                    text,
+                    style,
+                    message: m,
+                    block: Some(b),
+                    subagent: None,
+                });
+            };
+
+            match block {
+                TranscriptBlock::Text(text) => {
+                    for row in wrap(text, content_width) {
+                        push(format!("{}{}", INDENT, row), Style::default());
+                    }
+                }
+                TranscriptBlock::Thinking(text) => {
+                    let style = Style::default()
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::ITALIC);
+                    push(
+                        format!(
+                            "{}{} thinking ({} lines)",
+                            INDENT,
+                            marker,
+                            text.lines().count()
+                        ),
+                        style,
+                    );
+                    if is_expanded {
+                        for row in wrap(text, content_width) {
+                            push(format!("{}{}{}", INDENT, INDENT, row), style);
+                        }
+                    }
+                }
+                TranscriptBlock::ToolUse {
+                    name,
+                    summary,
+                    input,
+                } => {
+                    let header = if summary.is_empty() {
+                        format!("{}{} {}", INDENT, marker, name)
+                    } else {
+                        format!("{}{} {}: {}", INDENT, marker, name, summary)
+                    };
+                    push(
+                        truncate_chars(&header, width),
+                        Style::default().fg(Color::Yellow),
+                    );
+                    if is_expanded {
+                        for row in wrap(input, content_width) {
+                            push(
+                                format!("{}{}{}", INDENT, INDENT, row),
+                                Style::default().fg(Color::Gray),
+                            );
+                        }
+                    }
+                }
+                TranscriptBlock::ToolResult { content, is_error } => {
+                    let (label, color) = if *is_error {
+                        ("error", Color::Red)
+                    } else {
+                        ("result", Color::Green)
+                    };
+                    push(
+                        format!(
+                            "{}{} {} ({} lines)",
+                            INDENT,
+                            marker,
+                            label,
+                            content.lines().count()
+                        ),
+                        Style::default().fg(color),
+                    );
+                    if is_expanded {
+                        for row in wrap(content, content_width) {
+                            push(
+                                format!("{}{}{}", INDENT, INDENT, row),
+                                Style::default().fg(Color::Gray),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        lines.push(TranscriptLine {
+            text: String::new(),
+            style: Style::default(),
+            message: m,
+            block: None,
+            subagent: None,
+        });
+    }
+
+    lines
+}
+
+/// Wrap text to `width` characters, breaking at spaces where possible.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+
+    for line in text.lines() {
+        let line = line.replace('\t', "    ");
+        let mut rest = line.as_str();
+        loop {
+            if rest.chars().count() <= width {
+                rows.push(rest.to_string());
+                break;
+            }
+            // Byte offset of the char at `width`
+            let limit = rest
+                .char_indices()
+                .nth(width)
+                .map_or(rest.len(), |(i, _)| i);
+            let split = if rest[limit..].starts_with(' ') {
+                limit
+            } else {
+                rest[..limit].rfind(' ').filter(|&i| i > 0).unwrap_or(limit)
+            };
+            rows.push(rest[..split].to_string());
+            rest = rest[split..].strip_prefix(' ').unwrap_or(&rest[split..]);
+        }
+    }
+
+    if rows.is_empty() {
+        rows.push(String::new());
+    }
+    rows
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(max.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+/// ASCII case-insensitive substring search returning byte ranges.
+fn find_ci(haystack: &str, needle: &str) -> Vec<(usize, usize)> {
+    let (hay, pat) = (haystack.as_bytes(), needle.as_bytes());
+    if pat.is_empty() || pat.len() > hay.len() {
+        return Vec::new();
+    }
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i + pat.len() <= hay.len() {
+        if hay[i..i + pat.len()].eq_ignore_ascii_case(pat) {
+            ranges.push((i, i + pat.len()));
+            i += pat.len();
+        } else {
+            i += 1;
+        }
+    }
+    ranges
+}
+
+// ---------------------------------------------------------------------------
+// Widget
+// ---------------------------------------------------------------------------
+
+/// Widget for rendering the transcript view into a pane.
+pub struct TranscriptView<'a> {
+    state: &'a TranscriptViewState,
+    focused: bool,
+}
+
+impl<'a> TranscriptView<'a> {
+    pub fn new(state: &'a TranscriptViewState, focused: bool) -> Self {
+        Self { state, focused }
+    }
+
+    /// Content size (width, height) for a pane area: borders and the status row removed.
+    pub fn content_size(area: Rect) -> (u16, u16) {
+        (area.width.saturating_sub(2), area.height.saturating_sub(3))
+    }
+}
+
+impl Widget for TranscriptView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let state = self.state;
+        let border_style = if self.focused {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let block = Block::default()
+            .title(if state.parent.is_some() {
+                format!(" {} [SUBAGENT] ", state.conversation.display)
+            } else {
+                format!(" {} [TRANSCRIPT] ", state.conversation.display)
+            })
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.height < 2 {
+            return;
+        }
+
+        let (_, height) = Self::content_size(area);
+        let query = &state.search.query;
+        let match_style = Style::default().fg(Color::Black).bg(Color::Yellow);
+
+        for (row, line) in state
+            .lines
+            .iter()
+            .enumerate()
+            .skip(state.scroll)
+            .take(height as usize)
+        {
+            let y = inner.y + (row - state.scroll) as u16;
+            let mut style = line.style;
+            if self.focused && row == state.cursor {
+                style = style.bg(Color::DarkGray);
+            }
+
+            let ranges = if state.search.input_active {
+                Vec::new()
+            } else {
+                find_ci(&line.text, query)
+            };
+            let mut spans = Vec::new();
+            let mut pos = 0;
+            for (start, end) in ranges {
+                spans.push(Span::styled(&line.text[pos..start], style));
+                spans.push(Span::styled(&line.text[start..end], match_style));
+                pos = end;
+            }
+            spans.push(Span::styled(&line.text[pos..], style));
+            buf.set_line(inner.x, y, &Line::from(spans), inner.width);
+        }
+
+        // Status row
+        let status_area = Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1);
+        let status = if state.search.input_active {
+            Line::from(vec![
+                Span::styled("/", Style::default().fg(Color::Yellow)),
+                Span::raw(query.clone()),
+                Span::styled(" ", Style::default().bg(Color::White)),
+            ])
+        } else {
+            let mut spans = vec![Span::styled(
+                format!(
+                    " msg {}/{} ",
+                    (state.current_message() + 1).min(state.message_count()),
+                    state.message_count()
+                ),
+                Style::default().fg(Color::Cyan),
+            )];
+            if !query.is_empty() {
+                spans.push(Span::styled(
+                    format!(" /{} ({} lines) ", query, state.search.matches.len()),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            if self.focused {
+                spans.push(Span::styled(
+                    " Enter fold  t fold all  / search  [ ] msg  r resume  q back",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            } else {
+                spans.push(Span::styled(
+                    " l focus  Esc close",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            Line::from(spans)
+        };
+        Paragraph::new(status).render(status_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::transcript::TranscriptMessage;
+    use std::path::PathBuf;
+
+    fn conversation() -> Conversation {
+        Conversation {
+            display: "test".to_string(),
+            file_path: PathBuf::from("/tmp/s.jsonl"),
+            ..Conversation::for_test("s", "/tmp")
+        }
+    }
+
+    fn message(role: Role, blocks: Vec<TranscriptBlock>) -> TranscriptMessage {
+        TranscriptMessage {
+            uuid: None,
+            role,
+            timestamp: None,
+            blocks,
+        }
+    }
+
+    fn sample_state() -> TranscriptViewState {
+        let transcript = Transcript {
+            messages: vec![
+                message(
+                    Role::User,
+                    vec![TranscriptBlock::Text("run the tests".to_string())],
+                ),
+                message(
+                    Role::Assistant,
+                    vec![
+                        TranscriptBlock::Text("Running".to_string()),
+                        TranscriptBlock::ToolUse {
+                            name: "Bash".to_string(),
+                            summary: "cargo test".to_string(),
+                            input: "{\n  \"command\": \"cargo test\"\n}".to_string(),
+                        },
+                    ],
+                ),
+                message(
+                    Role::User,
+                    vec![TranscriptBlock::ToolResult {
+                        content: "ok\nneedle found".to_string(),
+                        is_error: false,
+                    }],
+                ),
+            ],
+        };
+        let mut state = TranscriptViewState::new(conversation(), transcript);
+        state.layout(80, 20);
+        state
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn wrap_breaks_at_spaces_and_keeps_blank_lines() {
+        assert_eq!(wrap("aaa bbb ccc", 7), vec!["aaa bbb", "ccc"]);
+        assert_eq!(wrap("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("a\n\nb", 10), vec!["a", "", "b"]);
+    }
+
+    #[test]
+    fn tool_blocks_start_collapsed_and_toggle_open() {
+        let mut state = sample_state();
+        assert!(!state.lines.iter().any(|l| l.text.contains("\"command\"")));
+
+        let header = state
+            .lines
+            .iter()
+            .position(|l| l.text.contains("Bash: cargo test"))
+            .unwrap();
+        state.cursor = header;
+        state.handle_key(key(KeyCode::Enter));
+        assert!(state.lines.iter().any(|l| l.text.contains("\"command\"")));
+        assert_eq!(state.cursor, header);
+
+        state.handle_key(key(KeyCode::Enter));
+        assert!(!state.lines.iter().any(|l| l.text.contains("\"command\"")));
+    }
+
+    #[test]
+    fn search_expands_collapsed_block_and_jumps_to_match() {
+        let mut state = sample_state();
+        state.handle_key(key(KeyCode::Char('/')));
+        for c in "NEEDLE".chars() {
+            state.handle_key(key(KeyCode::Char(c)));
+        }
+        state.handle_key(key(KeyCode::Enter));
+
+        assert_eq!(state.search.matches.len(), 1);
+        assert!(state.lines[state.cursor].text.contains("needle found"));
+        assert_eq!(state.current_message(), 2);
+    }
+
+    #[test]
+    fn message_jumps_and_pending_jump_land_on_headers() {
+        let mut state = sample_state();
+        state.handle_key(key(KeyCode::Char(']')));
+        assert_eq!(state.current_message(), 1);
+        assert!(state.lines[state.cursor].block.is_none());
+
+        state.jump_to_message(2);
+        assert_eq!(state.current_message(), 2);
+    }
+
+    #[test]
+    fn subagent_list_expands_and_opens_subagents() {
+        let subagent = Subagent {
+            agent_id: "a1".to_string(),
+            file_path: PathBuf::from("/tmp/agent-a1.jsonl"),
+            description: "Locate config".to_string(),
+            subagent_type: Some("Explore".to_string()),
+            message_count: 4,
+            usage: Default::default(),
+            timestamp: 0,
+        };
+        let mut state = sample_state().with_subagents(vec![subagent]);
+        state.layout(80, 20);
+        assert!(state.lines[0].text.contains("1 subagent · 4 msgs"));
+        assert!(!state.lines.iter().any(|l| l.text.contains("Locate config")));
+
+        state.cursor = 0;
+        state.handle_key(key(KeyCode::Enter));
+        assert!(state.lines[1].text.contains("[Explore] Locate config"));
+
+        state.cursor = 1;
+        assert_eq!(
+            state.handle_key(key(KeyCode::Enter)),
+            TranscriptKeyResult::OpenSubagent(0)
+        );
+    }
+
+    #[test]
+    fn find_ci_returns_byte_ranges() {
+        assert_eq!(find_ci("Foo foo", "FOO"), vec![(0, 3), (4, 7)]);
+        assert!(find_ci("abc", "").is_empty());
+    }
+}